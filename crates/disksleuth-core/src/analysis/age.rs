@@ -1,16 +1,152 @@
 /// File age analysis — find old/stale files that haven't been modified recently.
-use crate::model::{FileTree, NodeIndex};
+///
+/// [`analyse_age`] makes a single pass that produces the stale-file list, the
+/// totals across *all* stale files (not just the listed ones), and a byte
+/// histogram over fixed age buckets.  [`start_age_analysis`] runs it on a
+/// background thread so the GUI never walks the arena on the render thread.
+///
+/// Ages are measured from the modification time, or with
+/// [`AgeBasis::Accessed`] from the last access where the scan recorded one
+/// (see [`ScanOptions`](crate::scanner::ScanOptions)).
+use crate::analysis::worker::{spawn_oneshot, WorkerHandle};
+use crate::model::{FileNode, FileTree, NodeIndex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+/// Number of nodes visited between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
+/// Seconds per day, for age arithmetic.
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Which timestamp a file's age is measured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgeBasis {
+    /// Last modification.
+    #[default]
+    Modified,
+    /// Last access, or modification if that is later (or if no access time
+    /// was recorded).
+    Accessed,
+}
+
+impl AgeBasis {
+    /// Every basis, in display order.
+    pub const ALL: [AgeBasis; 2] = [Self::Modified, Self::Accessed];
+
+    /// Human-readable label for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Modified => "Modified",
+            Self::Accessed => "Accessed",
+        }
+    }
+
+    /// The timestamp of `node` this basis measures from.
+    pub fn timestamp(self, node: &FileNode) -> Option<SystemTime> {
+        match self {
+            Self::Modified => node.modified,
+            Self::Accessed => {
+                let accessed = node.extended.as_ref().and_then(|e| e.accessed);
+                node.modified.max(accessed)
+            }
+        }
+    }
+}
+
 /// A file identified as old/stale.
+#[derive(Debug, Clone)]
 pub struct StaleFile {
     pub index: NodeIndex,
     pub path: String,
     pub size: u64,
-    pub last_modified: SystemTime,
+    /// The timestamp its age is measured from (see [`AgeBasis`]).
+    pub last_used: SystemTime,
     pub age_days: u64,
 }
 
+/// Fixed age ranges for the age histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeBucket {
+    /// Modified in the last 30 days.
+    Under30Days,
+    /// 30–90 days.
+    Days30To90,
+    /// 90–365 days.
+    Days90To365,
+    /// 1–3 years.
+    Years1To3,
+    /// More than 3 years.
+    Over3Years,
+}
+
+impl AgeBucket {
+    /// All buckets, youngest first.
+    pub const ALL: [AgeBucket; 5] = [
+        Self::Under30Days,
+        Self::Days30To90,
+        Self::Days90To365,
+        Self::Years1To3,
+        Self::Over3Years,
+    ];
+
+    /// The bucket a file of `age_days` falls into.
+    pub fn from_age_days(age_days: u64) -> Self {
+        match age_days {
+            0..30 => Self::Under30Days,
+            30..90 => Self::Days30To90,
+            90..365 => Self::Days90To365,
+            365..1095 => Self::Years1To3,
+            _ => Self::Over3Years,
+        }
+    }
+
+    /// Short label for charts.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Under30Days => "< 30 days",
+            Self::Days30To90 => "30–90 days",
+            Self::Days90To365 => "90–365 days",
+            Self::Years1To3 => "1–3 years",
+            Self::Over3Years => "> 3 years",
+        }
+    }
+
+    /// Position in [`AgeBucket::ALL`].
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Bytes and file counts per [`AgeBucket`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgeHistogram {
+    /// Bytes per bucket, indexed by [`AgeBucket::index`].
+    pub bytes: [u64; 5],
+    /// Files per bucket, indexed by [`AgeBucket::index`].
+    pub files: [u64; 5],
+    /// Bytes in files with no usable timestamp (missing or in the future).
+    pub unknown_bytes: u64,
+    /// Files with no usable timestamp.
+    pub unknown_files: u64,
+}
+
+/// Everything the stale-files view needs from one pass over the tree.
+#[derive(Debug, Clone, Default)]
+pub struct AgeReport {
+    /// The largest stale files, sorted by size descending and capped.
+    pub stale: Vec<StaleFile>,
+    /// Number of stale files, including those beyond the cap.
+    pub stale_count: u64,
+    /// Bytes in all stale files, including those beyond the cap.
+    pub stale_bytes: u64,
+    /// Age distribution of every file in the tree.
+    pub histogram: AgeHistogram,
+    /// The timestamp ages were measured from.
+    pub basis: AgeBasis,
+}
+
 /// Find files not modified in the last `min_age_days` days,
 /// sorted by size descending.
 ///
@@ -18,46 +154,122 @@ pub struct StaleFile {
 /// avoids an integer underflow (`max_results - 1` wrapping to `usize::MAX`)
 /// that would panic inside `select_nth_unstable_by`.
 pub fn find_stale_files(tree: &FileTree, min_age_days: u64, max_results: usize) -> Vec<StaleFile> {
-    // Guard: requesting zero results is always satisfiable trivially, and
-    // prevents the `max_results - 1` subtraction below from underflowing.
     if max_results == 0 {
         return Vec::new();
     }
+    analyse_age(
+        tree,
+        min_age_days,
+        max_results,
+        AgeBasis::Modified,
+        &AtomicBool::new(false),
+    )
+    .map(|report| report.stale)
+    .unwrap_or_default()
+}
 
+/// Classify every file by age, measured from `basis`, and collect the
+/// largest stale ones.
+///
+/// Returns `None` if `cancel` was set before the pass finished.  Paths are
+/// only reconstructed for the files that make the cut.
+pub fn analyse_age(
+    tree: &FileTree,
+    min_age_days: u64,
+    max_results: usize,
+    basis: AgeBasis,
+    cancel: &AtomicBool,
+) -> Option<AgeReport> {
     let now = SystemTime::now();
-    let threshold = Duration::from_secs(min_age_days * 24 * 3600);
-
-    let mut stale: Vec<StaleFile> = tree
-        .nodes
-        .iter()
-        .enumerate()
-        .filter_map(|(i, node)| {
-            if node.is_dir {
-                return None;
-            }
-            let modified = node.modified?;
-            let age = now.duration_since(modified).ok()?;
-            if age >= threshold {
-                Some(StaleFile {
-                    index: NodeIndex::new(i),
-                    path: tree.full_path(NodeIndex::new(i)),
-                    size: node.size,
-                    last_modified: modified,
-                    age_days: age.as_secs() / 86400,
-                })
-            } else {
-                None
-            }
+    let threshold = Duration::from_secs(min_age_days.saturating_mul(SECS_PER_DAY));
+    let mut report = AgeReport {
+        basis,
+        ..Default::default()
+    };
+    // (index, timestamp, age in days) for every stale file.
+    let mut candidates: Vec<(NodeIndex, SystemTime, u64)> = Vec::new();
+
+    for (i, node) in tree.nodes.iter().enumerate() {
+        if i.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if node.is_dir {
+            continue;
+        }
+        let Some((used, age)) = basis
+            .timestamp(node)
+            .and_then(|t| now.duration_since(t).ok().map(|age| (t, age)))
+        else {
+            report.histogram.unknown_bytes += node.size;
+            report.histogram.unknown_files += 1;
+            continue;
+        };
+
+        let age_days = age.as_secs() / SECS_PER_DAY;
+        let bucket = AgeBucket::from_age_days(age_days).index();
+        report.histogram.bytes[bucket] += node.size;
+        report.histogram.files[bucket] += 1;
+
+        if age >= threshold {
+            report.stale_count += 1;
+            report.stale_bytes += node.size;
+            candidates.push((NodeIndex::new(i), used, age_days));
+        }
+    }
+
+    // Guard: requesting zero results is always satisfiable trivially, and
+    // prevents the `max_results - 1` subtraction below from underflowing.
+    let by_size_desc = |a: &(NodeIndex, SystemTime, u64), b: &(NodeIndex, SystemTime, u64)| {
+        tree.node(b.0).size.cmp(&tree.node(a.0).size)
+    };
+    if max_results == 0 {
+        candidates.clear();
+    } else if candidates.len() > max_results {
+        // Partial sort: O(n) select + O(k log k) sort of top-k, vs O(n log n) full sort.
+        candidates.select_nth_unstable_by(max_results - 1, by_size_desc);
+        candidates.truncate(max_results);
+    }
+    candidates.sort_unstable_by(by_size_desc);
+
+    report.stale = candidates
+        .into_iter()
+        .map(|(index, last_used, age_days)| StaleFile {
+            index,
+            path: tree.full_path(index),
+            size: tree.node(index).size,
+            last_used,
+            age_days,
         })
         .collect();
+    Some(report)
+}
 
-    // Partial sort: O(n) select + O(k log k) sort of top-k, vs O(n log n) full sort.
-    if stale.len() > max_results {
-        stale.select_nth_unstable_by(max_results - 1, |a, b| b.size.cmp(&a.size));
-        stale.truncate(max_results);
-    }
-    stale.sort_unstable_by(|a, b| b.size.cmp(&a.size));
-    stale
+/// Messages sent from the age-analysis thread to the UI.
+#[derive(Debug)]
+pub enum AgeMessage {
+    /// The analysis finished.
+    Complete(AgeReport),
+    /// The analysis was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed age analysis; receives exactly one
+/// [`AgeMessage`] when it ends.
+pub type AgeHandle = WorkerHandle<AgeMessage>;
+
+/// Run [`analyse_age`] on a background thread.
+pub fn start_age_analysis(
+    tree: Arc<FileTree>,
+    min_age_days: u64,
+    max_results: usize,
+    basis: AgeBasis,
+) -> AgeHandle {
+    spawn_oneshot("disksleuth-age", move |cancel| {
+        match analyse_age(&tree, min_age_days, max_results, basis, cancel) {
+            Some(report) => AgeMessage::Complete(report),
+            None => AgeMessage::Cancelled,
+        }
+    })
 }
 
 #[cfg(test)]
@@ -208,4 +420,91 @@ mod tests {
         let result = find_stale_files(&tree, 0, 100);
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn age_bucket_boundaries() {
+        assert_eq!(AgeBucket::from_age_days(29), AgeBucket::Under30Days);
+        assert_eq!(AgeBucket::from_age_days(30), AgeBucket::Days30To90);
+        assert_eq!(AgeBucket::from_age_days(364), AgeBucket::Days90To365);
+        assert_eq!(AgeBucket::from_age_days(365), AgeBucket::Years1To3);
+        assert_eq!(AgeBucket::from_age_days(1095), AgeBucket::Over3Years);
+    }
+
+    /// The histogram covers every file; stale totals include files beyond
+    /// the result cap.
+    #[test]
+    fn report_histogram_and_totals_cover_all_files() {
+        let mut tree = FileTree::with_capacity(6);
+        let root = tree.add_root(CompactString::new("C:"));
+        for (name, size, days) in [
+            ("a", 10, 5),
+            ("b", 20, 100),
+            ("c", 40, 500),
+            ("d", 80, 2000),
+        ] {
+            let idx = tree.add_node(file_with_age(name, size, root, days));
+            tree.add_child(root, idx);
+        }
+        let unknown = tree.add_node(FileNode::new_file(CompactString::new("u"), 7, Some(root)));
+        tree.add_child(root, unknown);
+        tree.aggregate_sizes();
+
+        let report =
+            analyse_age(&tree, 90, 1, AgeBasis::Modified, &AtomicBool::new(false)).unwrap();
+        assert_eq!(report.histogram.bytes, [10, 0, 20, 40, 80]);
+        assert_eq!(report.histogram.files, [1, 0, 1, 1, 1]);
+        assert_eq!(
+            (
+                report.histogram.unknown_bytes,
+                report.histogram.unknown_files
+            ),
+            (7, 1)
+        );
+        assert_eq!((report.stale_count, report.stale_bytes), (3, 140));
+        assert_eq!(report.stale.len(), 1);
+        assert_eq!(report.stale[0].size, 80);
+    }
+
+    #[test]
+    fn cancelled_analysis_returns_none() {
+        let mut tree = FileTree::with_capacity(1);
+        tree.add_root(CompactString::new("C:"));
+        assert!(analyse_age(&tree, 0, 10, AgeBasis::Modified, &AtomicBool::new(true)).is_none());
+    }
+
+    /// By access time, a recently read old file is not stale; files without
+    /// a recorded access time fall back to their modification time.
+    #[test]
+    fn access_basis_uses_later_of_access_and_modification() {
+        use crate::model::ExtendedMetadata;
+
+        let mut tree = FileTree::with_capacity(4);
+        let root = tree.add_root(CompactString::new("C:"));
+        let mut read = file_with_age("read.db", 100, root, 800);
+        read.extended = Some(Box::new(ExtendedMetadata {
+            accessed: Some(SystemTime::now() - Duration::from_secs(5 * SECS_PER_DAY)),
+            ..Default::default()
+        }));
+        let mut touched = file_with_age("touched.log", 200, root, 10);
+        // An access time older than the modification time is ignored.
+        touched.extended = Some(Box::new(ExtendedMetadata {
+            accessed: Some(SystemTime::now() - Duration::from_secs(900 * SECS_PER_DAY)),
+            ..Default::default()
+        }));
+        let untracked = file_with_age("untracked.iso", 400, root, 800);
+        for node in [read, touched, untracked] {
+            let idx = tree.add_node(node);
+            tree.add_child(root, idx);
+        }
+        tree.aggregate_sizes();
+
+        let cancel = AtomicBool::new(false);
+        let modified = analyse_age(&tree, 365, 10, AgeBasis::Modified, &cancel).unwrap();
+        assert_eq!((modified.stale_count, modified.stale_bytes), (2, 500));
+
+        let accessed = analyse_age(&tree, 365, 10, AgeBasis::Accessed, &cancel).unwrap();
+        assert_eq!(accessed.basis, AgeBasis::Accessed);
+        assert_eq!((accessed.stale_count, accessed.stale_bytes), (1, 400));
+        assert_eq!(accessed.histogram.files[AgeBucket::Under30Days.index()], 2);
+    }
 }
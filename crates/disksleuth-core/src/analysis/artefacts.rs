@@ -0,0 +1,422 @@
+/// Developer artefacts — regenerable build outputs and dependency caches.
+///
+/// A directory is an artefact when its name matches an [`ArtefactRule`] and
+/// the rule's marker files are present: beside it (a `target/` next to
+/// `Cargo.toml`) or inside it (a CMake build tree holding `CMakeCache.txt`).
+/// Artefacts are never nested: once a directory matches, nothing below it is
+/// considered, so a `node_modules` inside a `node_modules` is not reported
+/// twice.  [`start_artefact_analysis`] runs [`find_artefacts`] on a
+/// background thread.
+use crate::analysis::worker::{spawn_oneshot, WorkerHandle};
+use crate::model::{FileTree, NodeIndex, NodeRemap};
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Number of nodes visited between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
+/// Most artefacts listed, largest first; totals still cover all of them.
+pub const MAX_ARTEFACTS: usize = 10_000;
+
+/// How to recognise one kind of artefact directory.
+///
+/// Names and markers are compared ASCII case-insensitively.  A marker
+/// starting with `*` matches by suffix (`*.csproj`).  A rule with no markers
+/// matches by name alone; `"*"` as a name matches any directory and must be
+/// paired with `contains` markers.
+#[derive(Debug, Clone, Copy)]
+pub struct ArtefactRule {
+    /// What the directory holds, for display.
+    pub label: &'static str,
+    /// Directory names this rule applies to.
+    pub names: &'static [&'static str],
+    /// Files or folders, any of which must sit beside the directory.
+    pub siblings: &'static [&'static str],
+    /// Files or folders, any of which must sit inside the directory.
+    pub contains: &'static [&'static str],
+}
+
+/// Built-in rules, tried in order; the first match wins.
+pub const ARTEFACT_RULES: &[ArtefactRule] = &[
+    ArtefactRule {
+        label: "Cargo build output",
+        names: &["target"],
+        siblings: &["Cargo.toml"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Maven build output",
+        names: &["target"],
+        siblings: &["pom.xml"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "npm packages",
+        names: &["node_modules"],
+        siblings: &["package.json"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Bundler cache",
+        names: &[".next", ".nuxt", ".parcel-cache", ".turbo", ".angular"],
+        siblings: &["package.json"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Gradle cache",
+        names: &[".gradle"],
+        siblings: &[
+            "build.gradle",
+            "build.gradle.kts",
+            "settings.gradle",
+            "settings.gradle.kts",
+        ],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Gradle build output",
+        names: &["build"],
+        siblings: &["build.gradle", "build.gradle.kts"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: ".NET build output",
+        names: &["bin", "obj"],
+        siblings: &["*.csproj", "*.fsproj", "*.vbproj"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "CMake build tree",
+        names: &["*"],
+        siblings: &[],
+        contains: &["CMakeCache.txt"],
+    },
+    ArtefactRule {
+        label: "Python bytecode",
+        names: &["__pycache__"],
+        siblings: &[],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Python virtualenv",
+        names: &[".venv", "venv", "env"],
+        siblings: &[],
+        contains: &["pyvenv.cfg"],
+    },
+    ArtefactRule {
+        label: "tox environments",
+        names: &[".tox", ".nox"],
+        siblings: &[],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Python tool cache",
+        names: &[".pytest_cache", ".mypy_cache", ".ruff_cache"],
+        siblings: &[],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Dart tool cache",
+        names: &[".dart_tool"],
+        siblings: &["pubspec.yaml"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Haskell Stack build",
+        names: &[".stack-work"],
+        siblings: &["stack.yaml"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Zig cache",
+        names: &["zig-cache", ".zig-cache", "zig-out"],
+        siblings: &["build.zig"],
+        contains: &[],
+    },
+    ArtefactRule {
+        label: "Terraform providers",
+        names: &[".terraform"],
+        siblings: &["*.tf"],
+        contains: &[],
+    },
+];
+
+/// One reclaimable directory.
+#[derive(Debug, Clone)]
+pub struct Artefact {
+    pub index: NodeIndex,
+    /// The matching rule's label.
+    pub kind: &'static str,
+    pub path: String,
+    pub size: u64,
+    /// Files inside.
+    pub files: u64,
+    /// Day of the newest modification inside, if any file had one (see
+    /// [`DayStamp::to_time`](crate::model::DayStamp::to_time)).
+    pub last_activity: Option<SystemTime>,
+}
+
+/// Everything the reclaimable view needs from one pass over the tree.
+#[derive(Debug, Clone, Default)]
+pub struct ArtefactReport {
+    /// The largest artefacts, sorted by size descending and capped.
+    pub artefacts: Vec<Artefact>,
+    /// Number of artefacts, including those beyond the cap.
+    pub total_count: u64,
+    /// Bytes in all artefacts, including those beyond the cap.
+    pub total_bytes: u64,
+}
+
+impl ArtefactReport {
+    /// Translate the report to `tree`, the result of
+    /// [`FileTree::without_subtrees`]: removed artefacts are dropped, the
+    /// rest are re-measured, and the totals adjusted by the difference so
+    /// artefacts beyond the cap stay counted.
+    pub fn remap(&mut self, tree: &FileTree, remap: &NodeRemap) {
+        let (mut count, mut bytes) = (self.total_count, self.total_bytes);
+        self.artefacts.retain_mut(|a| {
+            bytes = bytes.saturating_sub(a.size);
+            match remap.get(a.index) {
+                Some(n) => {
+                    let node = tree.node(n);
+                    a.index = n;
+                    a.size = node.size;
+                    a.files = node.descendant_count;
+                    bytes += a.size;
+                    true
+                }
+                None => {
+                    count = count.saturating_sub(1);
+                    false
+                }
+            }
+        });
+        self.total_count = count;
+        self.total_bytes = bytes;
+    }
+}
+
+/// Whether `name` matches `pattern` (`*suffix` or an exact name).
+fn name_matches(name: &str, pattern: &str) -> bool {
+    match pattern.strip_prefix('*') {
+        Some(suffix) => {
+            name.len() > suffix.len()
+                && name.is_char_boundary(name.len() - suffix.len())
+                && name[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+        }
+        None => name.eq_ignore_ascii_case(pattern),
+    }
+}
+
+/// Whether any child of `parent` (other than `skip`) matches a marker.
+fn has_marker(tree: &FileTree, parent: NodeIndex, skip: NodeIndex, markers: &[&str]) -> bool {
+    let mut child = tree.node(parent).first_child;
+    while let Some(c) = child {
+        let node = tree.node(c);
+        if c != skip && markers.iter().any(|m| name_matches(&node.name, m)) {
+            return true;
+        }
+        child = node.next_sibling;
+    }
+    false
+}
+
+/// The first rule directory `index` satisfies, if any.
+fn match_rule(tree: &FileTree, index: NodeIndex) -> Option<&'static ArtefactRule> {
+    let node = tree.node(index);
+    ARTEFACT_RULES.iter().find(|rule| {
+        let named = rule
+            .names
+            .iter()
+            .any(|n| *n == "*" || name_matches(&node.name, n));
+        if !named {
+            return false;
+        }
+        if rule.siblings.is_empty() && rule.contains.is_empty() {
+            return true;
+        }
+        let beside = !rule.siblings.is_empty()
+            && node
+                .parent
+                .is_some_and(|p| has_marker(tree, p, index, rule.siblings));
+        beside || (!rule.contains.is_empty() && has_marker(tree, index, index, rule.contains))
+    })
+}
+
+/// Find every artefact directory in `tree`.
+///
+/// Returns `None` if `cancel` was set before the pass finished.  Paths are
+/// only reconstructed for the artefacts that make the cut.
+pub fn find_artefacts(tree: &FileTree, cancel: &AtomicBool) -> Option<ArtefactReport> {
+    let mut report = ArtefactReport::default();
+    // claimed[i]: node i is an artefact or lies inside one.  Parents come
+    // before children in the arena, so one forward pass suffices.
+    let mut claimed = vec![false; tree.nodes.len()];
+    let mut found: Vec<(NodeIndex, &'static str)> = Vec::new();
+
+    for (i, node) in tree.nodes.iter().enumerate() {
+        if i.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let Some(parent) = node.parent else {
+            continue;
+        };
+        if claimed[parent.idx()] {
+            claimed[i] = true;
+            continue;
+        }
+        if !node.is_dir || node.is_error {
+            continue;
+        }
+        if let Some(rule) = match_rule(tree, NodeIndex::new(i)) {
+            claimed[i] = true;
+            report.total_count += 1;
+            report.total_bytes += node.size;
+            found.push((NodeIndex::new(i), rule.label));
+        }
+    }
+
+    found.sort_unstable_by_key(|&(i, _)| Reverse(tree.node(i).size));
+    found.truncate(MAX_ARTEFACTS);
+    report.artefacts = found
+        .into_iter()
+        .map(|(index, kind)| {
+            let node = tree.node(index);
+            Artefact {
+                index,
+                kind,
+                path: tree.full_path(index),
+                size: node.size,
+                files: node.descendant_count,
+                last_activity: node.newest_day.to_time(),
+            }
+        })
+        .collect();
+    Some(report)
+}
+
+/// Messages sent from the artefact-analysis thread to the UI.
+#[derive(Debug)]
+pub enum ArtefactMessage {
+    /// The analysis finished.
+    Complete(ArtefactReport),
+    /// The analysis was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed artefact analysis; receives exactly one
+/// [`ArtefactMessage`] when it ends.
+pub type ArtefactHandle = WorkerHandle<ArtefactMessage>;
+
+/// Run [`find_artefacts`] on a background thread.
+pub fn start_artefact_analysis(tree: Arc<FileTree>) -> ArtefactHandle {
+    spawn_oneshot("disksleuth-artefacts", move |cancel| {
+        match find_artefacts(&tree, cancel) {
+            Some(report) => ArtefactMessage::Complete(report),
+            None => ArtefactMessage::Cancelled,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+
+    fn run(tree: &FileTree) -> ArtefactReport {
+        find_artefacts(tree, &AtomicBool::new(false)).unwrap()
+    }
+
+    /// `target/` counts only beside `Cargo.toml`; nested matches are not
+    /// reported separately.
+    #[test]
+    fn sibling_marker_is_required() {
+        let mut tree = FileTree::with_capacity(10);
+        let root = tree.add_root(CompactString::new("C:"));
+        let project = tree.add_test_dir(root, "app");
+        tree.add_test_file(project, "Cargo.toml", 1);
+        let target = tree.add_test_dir(project, "target");
+        let nested = tree.add_test_dir(target, "node_modules");
+        tree.add_test_file(target, "package.json", 1);
+        tree.add_test_file(nested, "big.o", 500);
+        let other = tree.add_test_dir(root, "docs");
+        let not_target = tree.add_test_dir(other, "target");
+        tree.add_test_file(not_target, "keep.txt", 50);
+        tree.aggregate_sizes();
+
+        let report = run(&tree);
+        assert_eq!(report.total_count, 1);
+        assert_eq!(report.artefacts[0].index, target);
+        assert_eq!(report.artefacts[0].kind, "Cargo build output");
+        assert_eq!(
+            (report.artefacts[0].size, report.artefacts[0].files),
+            (501, 2)
+        );
+    }
+
+    /// Suffix markers, inner markers and name-only rules.
+    #[test]
+    fn marker_kinds_and_case() {
+        let mut tree = FileTree::with_capacity(12);
+        let root = tree.add_root(CompactString::new("C:"));
+        let dotnet = tree.add_test_dir(root, "Service");
+        tree.add_test_file(dotnet, "Service.CSPROJ", 1);
+        let obj = tree.add_test_dir(dotnet, "obj");
+        tree.add_test_file(obj, "a.dll", 10);
+        let build = tree.add_test_dir(root, "out-release");
+        tree.add_test_file(build, "CMakeCache.txt", 20);
+        let cache = tree.add_test_dir(root, "__pycache__");
+        tree.add_test_file(cache, "m.pyc", 30);
+        let fake_venv = tree.add_test_dir(root, "venv");
+        tree.add_test_file(fake_venv, "notes.txt", 40);
+        tree.aggregate_sizes();
+
+        let report = run(&tree);
+        let kinds: Vec<(&str, NodeIndex)> =
+            report.artefacts.iter().map(|a| (a.kind, a.index)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("Python bytecode", cache),
+                ("CMake build tree", build),
+                (".NET build output", obj),
+            ]
+        );
+        assert_eq!(report.total_bytes, 60);
+    }
+
+    /// Removing a listed artefact keeps the ones beyond the cap counted.
+    #[test]
+    fn remap_subtracts_removed_artefacts() {
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("C:"));
+        let cache = tree.add_test_dir(root, "__pycache__");
+        tree.add_test_file(cache, "m.pyc", 30);
+        let build = tree.add_test_dir(root, "out-release");
+        tree.add_test_file(build, "CMakeCache.txt", 20);
+        let other = tree.add_test_dir(root, "lib");
+        let nested = tree.add_test_dir(other, "__pycache__");
+        tree.add_test_file(nested, "n.pyc", 10);
+        tree.aggregate_sizes();
+
+        let mut report = run(&tree);
+        assert_eq!((report.total_count, report.total_bytes), (3, 60));
+        // As if the smallest had fallen beyond the cap.
+        report.artefacts.truncate(2);
+
+        let (pruned, remap) = tree.without_subtrees(&[cache]);
+        report.remap(&pruned, &remap);
+        assert_eq!(report.artefacts.len(), 1);
+        assert_eq!(report.artefacts[0].kind, "CMake build tree");
+        assert_eq!((report.total_count, report.total_bytes), (2, 30));
+    }
+
+    #[test]
+    fn cancelled_analysis_returns_none() {
+        let mut tree = FileTree::with_capacity(1);
+        tree.add_root(CompactString::new("C:"));
+        assert!(find_artefacts(&tree, &AtomicBool::new(true)).is_none());
+    }
+}
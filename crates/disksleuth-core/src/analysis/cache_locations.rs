@@ -0,0 +1,638 @@
+/// Well-known cache and temp locations — where tool caches quietly grow.
+///
+/// [`CACHE_RULES`] lists package-manager caches, build caches, browser
+/// caches, temp folders and system download caches per platform, each
+/// relative to a [`Base`] resolved from the environment (`%LOCALAPPDATA%`,
+/// `$HOME`, `$XDG_CACHE_HOME`, …).  [`find_cache_locations`] resolves the
+/// rules for one platform and looks each path up in a scanned tree; only
+/// locations inside a scanned root are reported.  The environment is passed
+/// in so the lookup is testable; [`find_cache_locations_here`] uses the real
+/// one.
+use crate::cleanup::outermost_nodes;
+use crate::model::{FileTree, NodeIndex};
+use std::cmp::Reverse;
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+
+/// Operating system a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Windows,
+    Linux,
+    MacOs,
+}
+
+impl Platform {
+    /// The platform this build runs on.
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Where a rule's relative path starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    /// `%USERPROFILE%` on Windows, `$HOME` elsewhere.
+    Home,
+    /// `%LOCALAPPDATA%` (Windows only).
+    LocalAppData,
+    /// `%APPDATA%` (Windows only).
+    AppData,
+    /// `%ProgramData%` (Windows only).
+    ProgramData,
+    /// `%SystemRoot%` (Windows only).
+    SystemRoot,
+    /// `%TEMP%` / `%TMP%` on Windows, `$TMPDIR` or `/tmp` elsewhere.
+    Temp,
+    /// `$XDG_CACHE_HOME`, else `~/.cache` (Linux only).
+    XdgCache,
+    /// The filesystem root `/` (Linux and macOS only).
+    Root,
+}
+
+/// Whether a location can be cleared by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Safety {
+    /// Pure cache; the tool re-downloads or rebuilds what it needs.
+    Safe,
+    /// Safe once the owning program is closed.
+    WhenClosed,
+    /// Holds state as well as cache; use the tool's own cleanup command.
+    Caution,
+}
+
+impl Safety {
+    /// Short description for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Safe => "Safe to clear",
+            Self::WhenClosed => "Safe when closed",
+            Self::Caution => "Use the tool's cleanup",
+        }
+    }
+}
+
+/// One well-known location.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheRule {
+    /// Tool or component that owns the location.
+    pub tool: &'static str,
+    pub platforms: &'static [Platform],
+    pub base: Base,
+    /// Path below `base`, `/`-separated.
+    pub path: &'static str,
+    pub safety: Safety,
+    /// The tool's own command for clearing it, if it has one.
+    pub cleanup: Option<&'static str>,
+    /// What `cleanup` needs beyond the tool itself, if anything.
+    pub cleanup_note: Option<&'static str>,
+}
+
+const WIN: &[Platform] = &[Platform::Windows];
+const LINUX: &[Platform] = &[Platform::Linux];
+const MAC: &[Platform] = &[Platform::MacOs];
+const UNIX: &[Platform] = &[Platform::Linux, Platform::MacOs];
+const ALL: &[Platform] = &[Platform::Windows, Platform::Linux, Platform::MacOs];
+
+/// Built-in rules.  Where two rules overlap (a cache inside a temp folder),
+/// both are listed but the bytes count once in [`CacheReport::total_bytes`].
+pub const CACHE_RULES: &[CacheRule] = &[
+    // ── Language package managers ──
+    CacheRule {
+        tool: "Cargo registry",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".cargo/registry",
+        safety: Safety::Safe,
+        cleanup: Some("cargo cache --autoclean"),
+        cleanup_note: Some("needs the cargo-cache plugin (cargo install cargo-cache)"),
+    },
+    CacheRule {
+        tool: "Cargo git checkouts",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".cargo/git",
+        safety: Safety::Safe,
+        cleanup: Some("cargo cache --autoclean"),
+        cleanup_note: Some("needs the cargo-cache plugin (cargo install cargo-cache)"),
+    },
+    CacheRule {
+        tool: "npm",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "npm-cache",
+        safety: Safety::Safe,
+        cleanup: Some("npm cache clean --force"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "npm",
+        platforms: UNIX,
+        base: Base::Home,
+        path: ".npm",
+        safety: Safety::Safe,
+        cleanup: Some("npm cache clean --force"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Yarn",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "Yarn/Cache",
+        safety: Safety::Safe,
+        cleanup: Some("yarn cache clean"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Yarn",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "yarn",
+        safety: Safety::Safe,
+        cleanup: Some("yarn cache clean"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "pnpm store",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "pnpm/store",
+        safety: Safety::Caution,
+        cleanup: Some("pnpm store prune"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "pnpm store",
+        platforms: LINUX,
+        base: Base::Home,
+        path: ".local/share/pnpm/store",
+        safety: Safety::Caution,
+        cleanup: Some("pnpm store prune"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "pip",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "pip/Cache",
+        safety: Safety::Safe,
+        cleanup: Some("pip cache purge"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "pip",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "pip",
+        safety: Safety::Safe,
+        cleanup: Some("pip cache purge"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "pip",
+        platforms: MAC,
+        base: Base::Home,
+        path: "Library/Caches/pip",
+        safety: Safety::Safe,
+        cleanup: Some("pip cache purge"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Conda packages",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".conda/pkgs",
+        safety: Safety::Caution,
+        cleanup: Some("conda clean --all"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Gradle",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".gradle/caches",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Maven repository",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".m2/repository",
+        safety: Safety::Safe,
+        cleanup: Some("mvn dependency:purge-local-repository"),
+        cleanup_note: Some("run inside a Maven project"),
+    },
+    CacheRule {
+        tool: "NuGet packages",
+        platforms: ALL,
+        base: Base::Home,
+        path: ".nuget/packages",
+        safety: Safety::Safe,
+        cleanup: Some("dotnet nuget locals all --clear"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Go modules",
+        platforms: ALL,
+        base: Base::Home,
+        path: "go/pkg/mod",
+        safety: Safety::Caution,
+        cleanup: Some("go clean -modcache"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Go build cache",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "go-build",
+        safety: Safety::Safe,
+        cleanup: Some("go clean -cache"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Go build cache",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "go-build",
+        safety: Safety::Safe,
+        cleanup: Some("go clean -cache"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Xcode DerivedData",
+        platforms: MAC,
+        base: Base::Home,
+        path: "Library/Developer/Xcode/DerivedData",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    // ── Browsers ──
+    CacheRule {
+        tool: "Chrome cache",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "Google/Chrome/User Data/Default/Cache",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Edge cache",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "Microsoft/Edge/User Data/Default/Cache",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Chrome cache",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "google-chrome",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Firefox cache",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "mozilla",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Application caches",
+        platforms: MAC,
+        base: Base::Home,
+        path: "Library/Caches",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    // ── Temp folders ──
+    CacheRule {
+        tool: "User temp folder",
+        platforms: ALL,
+        base: Base::Temp,
+        path: "",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Windows temp folder",
+        platforms: WIN,
+        base: Base::SystemRoot,
+        path: "Temp",
+        safety: Safety::WhenClosed,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Thumbnail cache",
+        platforms: LINUX,
+        base: Base::XdgCache,
+        path: "thumbnails",
+        safety: Safety::Safe,
+        cleanup: None,
+        cleanup_note: None,
+    },
+    // ── Containers and system ──
+    CacheRule {
+        tool: "Docker Desktop data",
+        platforms: WIN,
+        base: Base::LocalAppData,
+        path: "Docker/wsl",
+        safety: Safety::Caution,
+        cleanup: Some("docker system prune"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Docker data root",
+        platforms: WIN,
+        base: Base::ProgramData,
+        path: "Docker",
+        safety: Safety::Caution,
+        cleanup: Some("docker system prune"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Docker data root",
+        platforms: LINUX,
+        base: Base::Root,
+        path: "var/lib/docker",
+        safety: Safety::Caution,
+        cleanup: Some("docker system prune"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "Windows Update downloads",
+        platforms: WIN,
+        base: Base::SystemRoot,
+        path: "SoftwareDistribution/Download",
+        safety: Safety::Caution,
+        cleanup: Some("cleanmgr"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "APT package cache",
+        platforms: LINUX,
+        base: Base::Root,
+        path: "var/cache/apt/archives",
+        safety: Safety::Safe,
+        cleanup: Some("sudo apt-get clean"),
+        cleanup_note: None,
+    },
+    CacheRule {
+        tool: "systemd journal",
+        platforms: LINUX,
+        base: Base::Root,
+        path: "var/log/journal",
+        safety: Safety::Caution,
+        cleanup: Some("sudo journalctl --vacuum-size=200M"),
+        cleanup_note: None,
+    },
+];
+
+/// A rule that matched a directory in the tree.
+#[derive(Debug, Clone)]
+pub struct CacheLocation {
+    pub rule: &'static CacheRule,
+    pub index: NodeIndex,
+    /// The resolved on-disk path.
+    pub path: PathBuf,
+    pub size: u64,
+    /// Files inside.
+    pub files: u64,
+}
+
+/// Every well-known location found in one tree.
+#[derive(Debug, Clone, Default)]
+pub struct CacheReport {
+    /// Matches sorted by size descending.
+    pub locations: Vec<CacheLocation>,
+    /// Bytes in all matches, counting overlapping locations once.
+    pub total_bytes: u64,
+}
+
+/// Resolve `base` for `platform` from `env`.
+fn resolve_base(
+    base: Base,
+    platform: Platform,
+    env: &impl Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    let var = |name: &str| env(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let windows = platform == Platform::Windows;
+    match base {
+        Base::Home if windows => var("USERPROFILE"),
+        Base::Home => var("HOME"),
+        Base::LocalAppData if windows => var("LOCALAPPDATA"),
+        Base::AppData if windows => var("APPDATA"),
+        Base::ProgramData if windows => var("ProgramData"),
+        Base::SystemRoot if windows => var("SystemRoot"),
+        Base::Temp if windows => var("TEMP").or_else(|| var("TMP")),
+        Base::Temp => var("TMPDIR").or_else(|| Some(PathBuf::from("/tmp"))),
+        Base::XdgCache if platform == Platform::Linux => {
+            var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|h| h.join(".cache")))
+        }
+        Base::Root if !windows => Some(PathBuf::from("/")),
+        _ => None,
+    }
+}
+
+/// Find the node at `path`, starting from whichever scanned root contains
+/// it.  Names compare case-insensitively when `ignore_case` is set.
+fn locate(tree: &FileTree, path: &Path, ignore_case: bool) -> Option<NodeIndex> {
+    let same = |a: &str, b: &str| {
+        if ignore_case {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    };
+    let components: Vec<Component> = path.components().collect();
+    tree.root_paths.iter().find_map(|(root, root_path)| {
+        let prefix: Vec<Component> = root_path.components().collect();
+        if prefix.len() > components.len()
+            || !prefix.iter().zip(&components).all(|(a, b)| {
+                same(
+                    &a.as_os_str().to_string_lossy(),
+                    &b.as_os_str().to_string_lossy(),
+                )
+            })
+        {
+            return None;
+        }
+        let mut node = *root;
+        for component in &components[prefix.len()..] {
+            let Component::Normal(name) = component else {
+                return None;
+            };
+            let name = name.to_string_lossy();
+            let mut child = tree.node(node).first_child;
+            node = loop {
+                let c = child?;
+                if same(&tree.node(c).name, &name) {
+                    break c;
+                }
+                child = tree.node(c).next_sibling;
+            };
+        }
+        Some(node)
+    })
+}
+
+/// Match the rules for `platform` against `tree`, resolving bases with
+/// `env` (an environment-variable lookup).
+pub fn find_cache_locations(
+    tree: &FileTree,
+    platform: Platform,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> CacheReport {
+    let mut locations: Vec<CacheLocation> = Vec::new();
+    for rule in CACHE_RULES
+        .iter()
+        .filter(|r| r.platforms.contains(&platform))
+    {
+        let Some(base) = resolve_base(rule.base, platform, &env) else {
+            continue;
+        };
+        let path = rule
+            .path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .fold(base, |p, s| p.join(s));
+        let Some(index) = locate(tree, &path, platform == Platform::Windows) else {
+            continue;
+        };
+        let node = tree.node(index);
+        // A root, a file, or a location another rule already claimed.
+        if node.parent.is_none() || !node.is_dir || locations.iter().any(|l| l.index == index) {
+            continue;
+        }
+        locations.push(CacheLocation {
+            rule,
+            index,
+            path,
+            size: node.size,
+            files: node.descendant_count,
+        });
+    }
+    locations.sort_by_key(|l| Reverse(l.size));
+
+    let nodes: Vec<NodeIndex> = locations.iter().map(|l| l.index).collect();
+    let total_bytes = outermost_nodes(tree, &nodes)
+        .into_iter()
+        .map(|n| tree.node(n).size)
+        .sum();
+    CacheReport {
+        locations,
+        total_bytes,
+    }
+}
+
+/// [`find_cache_locations`] for this platform and process environment.
+pub fn find_cache_locations_here(tree: &FileTree) -> CacheReport {
+    find_cache_locations(tree, Platform::current(), |name| std::env::var_os(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: HashMap<String, OsString> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    /// A Linux home with an npm cache, a pip cache under `$XDG_CACHE_HOME`
+    /// and `/tmp`; overlapping matches count once.
+    #[test]
+    fn linux_rules_match_scanned_paths() {
+        let mut tree = FileTree::with_capacity(16);
+        let root = tree.add_root(CompactString::new("/"));
+        tree.set_root_path(root, PathBuf::from("/"));
+        let home = tree.add_test_dir(root, "home");
+        let user = tree.add_test_dir(home, "ann");
+        let npm = tree.add_test_dir(user, ".npm");
+        tree.add_test_file(npm, "index", 100);
+        let cache = tree.add_test_dir(user, ".cache");
+        let pip = tree.add_test_dir(cache, "pip");
+        tree.add_test_file(pip, "wheel", 40);
+        let tmp = tree.add_test_dir(root, "tmp");
+        tree.add_test_file(tmp, "x", 7);
+        // Nested in .npm: matched by the Yarn rule via XDG_CACHE_HOME below.
+        let yarn = tree.add_test_dir(npm, "yarn");
+        tree.add_test_file(yarn, "pkg", 5);
+        tree.aggregate_sizes();
+
+        let report = find_cache_locations(
+            &tree,
+            Platform::Linux,
+            env(&[("HOME", "/home/ann"), ("XDG_CACHE_HOME", "/home/ann/.npm")]),
+        );
+        let found: Vec<(&str, u64)> = report
+            .locations
+            .iter()
+            .map(|l| (l.rule.tool, l.size))
+            .collect();
+        assert_eq!(found, [("npm", 105), ("User temp folder", 7), ("Yarn", 5)]);
+        assert_eq!(report.total_bytes, 112, "yarn lies inside .npm");
+    }
+
+    /// Windows lookups ignore case and resolve `%LOCALAPPDATA%`.
+    #[test]
+    fn windows_rules_ignore_case() {
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("scan"));
+        tree.set_root_path(root, PathBuf::from("/scan"));
+        let local = tree.add_test_dir(root, "appdata");
+        let pip = tree.add_test_dir(local, "PIP");
+        let pip_cache = tree.add_test_dir(pip, "cache");
+        tree.add_test_file(pip_cache, "a", 9);
+        tree.aggregate_sizes();
+
+        let report = find_cache_locations(
+            &tree,
+            Platform::Windows,
+            env(&[("LOCALAPPDATA", "/scan/AppData")]),
+        );
+        assert_eq!(report.locations.len(), 1);
+        assert_eq!(report.locations[0].index, pip_cache);
+        assert_eq!(report.locations[0].rule.safety, Safety::Safe);
+
+        // Outside every scanned root: nothing.
+        let report =
+            find_cache_locations(&tree, Platform::Windows, env(&[("LOCALAPPDATA", "/other")]));
+        assert!(report.locations.is_empty());
+    }
+
+    /// A cleanup note only ever qualifies a command.
+    #[test]
+    fn cleanup_notes_have_commands() {
+        for rule in CACHE_RULES {
+            assert!(
+                rule.cleanup_note.is_none() || rule.cleanup.is_some(),
+                "{} has a note but no command",
+                rule.tool
+            );
+        }
+    }
+}
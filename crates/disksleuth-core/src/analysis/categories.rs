@@ -0,0 +1,444 @@
+/// Data-driven file categories.
+///
+/// Which category an extension belongs to, and each category's name and
+/// colour, come from a [`CategoryTable`].  The built-in table is the
+/// default; a JSON file can move extensions to another category, recolour a
+/// built-in category, and add custom categories:
+///
+/// ```json
+/// {
+///   "categories": [
+///     { "name": "Disk images", "color": "#7aa2f7", "extensions": ["vhdx", "qcow2", "vmdk"] },
+///     { "name": "Documents", "extensions": ["pst"] },
+///     { "name": "Code", "color": "#9ece6a" }
+///   ]
+/// }
+/// ```
+///
+/// A name matching a built-in category (case-insensitively) refers to it;
+/// any other name creates a custom category, which needs a colour.  Listed
+/// extensions (with or without the dot, any case) override the built-in
+/// table.
+///
+/// The table is loaded once at startup and installed process-wide with
+/// [`install_categories`]; lookups go through [`categories`].
+use crate::analysis::file_types::FileCategory;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Longest extension (bytes) the table can hold; longer ones are `Other`.
+pub const MAX_EXTENSION_LEN: usize = 16;
+
+/// Most custom categories a config file may define.
+pub const MAX_CUSTOM_CATEGORIES: usize = 32;
+
+/// File name of the category config inside the config directory.
+pub const CATEGORIES_FILE_NAME: &str = "categories.json";
+
+/// An extension lowercased and zero-padded into a fixed-size key.
+type ExtensionKey = [u8; MAX_EXTENSION_LEN];
+
+/// Colour of `Other`, also used for a category the table does not have.
+const OTHER_COLOR: [u8; 3] = [0x6c, 0x70, 0x86];
+
+/// Built-in categories in display order, with their colours.
+const BUILTIN_CATEGORIES: [(FileCategory, [u8; 3]); 9] = [
+    (FileCategory::Documents, [0x89, 0xb4, 0xfa]),
+    (FileCategory::Images, [0xf9, 0xe2, 0xaf]),
+    (FileCategory::Video, [0xf3, 0x8b, 0xa8]),
+    (FileCategory::Audio, [0xcb, 0xa6, 0xf7]),
+    (FileCategory::Archives, [0xfa, 0xb3, 0x87]),
+    (FileCategory::Code, [0xa6, 0xe3, 0xa1]),
+    (FileCategory::Executables, [0xf3, 0x8b, 0xa8]),
+    (FileCategory::System, [0x94, 0xe2, 0xd5]),
+    (FileCategory::Other, OTHER_COLOR),
+];
+
+/// The built-in extension table.  Anything not listed is `Other`.
+#[rustfmt::skip]
+const BUILTIN_EXTENSIONS: &[(FileCategory, &[&str])] = &[
+    (FileCategory::Documents, &[
+        "doc", "docx", "pdf", "txt", "rtf", "odt", "xls", "xlsx", "ppt", "pptx", "csv", "md",
+        "epub",
+    ]),
+    (FileCategory::Images, &[
+        "jpg", "jpeg", "png", "gif", "bmp", "svg", "webp", "ico", "tiff", "tif", "psd", "raw",
+        "cr2", "nef", "heic", "heif",
+    ]),
+    (FileCategory::Video, &[
+        "mp4", "mkv", "avi", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg", "3gp",
+    ]),
+    (FileCategory::Audio, &["mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus"]),
+    (FileCategory::Archives, &[
+        "zip", "rar", "7z", "tar", "gz", "bz2", "xz", "zst", "cab", "iso", "dmg",
+    ]),
+    (FileCategory::Code, &[
+        "rs", "py", "js", "ts", "jsx", "tsx", "c", "cpp", "h", "hpp", "cs", "java", "go", "rb",
+        "php", "swift", "kt", "scala", "html", "css", "scss", "json", "xml", "yaml", "yml",
+        "toml", "sql", "sh", "bat", "ps1",
+    ]),
+    (FileCategory::Executables, &["exe", "msi", "dll", "so", "dylib", "app", "com", "scr"]),
+    (FileCategory::System, &[
+        "sys", "drv", "inf", "cat", "log", "etl", "dat", "reg", "tmp", "bak",
+    ]),
+];
+
+/// A category's display name and colour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryInfo {
+    pub category: FileCategory,
+    pub name: String,
+    /// sRGB colour used by the chart and the treemap.
+    pub color: [u8; 3],
+}
+
+/// Error returned when a category config cannot be used.
+#[derive(Debug, Error)]
+pub enum CategoryConfigError {
+    /// The file exists but could not be read.
+    #[error("cannot read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file is not valid JSON for [`CategoryConfig`].
+    #[error("invalid category file: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// A category entry has an empty name.
+    #[error("a category has an empty name")]
+    EmptyName,
+    /// A new category was declared without a colour.
+    #[error("category {0:?} needs a colour")]
+    MissingColor(String),
+    /// A colour is not `#rrggbb`.
+    #[error("invalid colour {0:?}; expected \"#rrggbb\"")]
+    InvalidColor(String),
+    /// An extension is empty or longer than [`MAX_EXTENSION_LEN`] bytes.
+    #[error("extension {0:?} must be 1 to 16 bytes long")]
+    InvalidExtension(String),
+    /// More than [`MAX_CUSTOM_CATEGORIES`] custom categories.
+    #[error("at most {MAX_CUSTOM_CATEGORIES} custom categories are supported")]
+    TooManyCategories,
+}
+
+/// The category config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryConfig {
+    #[serde(default)]
+    pub categories: Vec<CategoryEntry>,
+}
+
+/// One entry of the config: a built-in or custom category.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryEntry {
+    pub name: String,
+    /// `#rrggbb`; required for a new category, optional for a built-in one.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Extensions moved into this category.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+/// Extension → category lookup plus every category's name and colour.
+#[derive(Debug, Clone)]
+pub struct CategoryTable {
+    /// Built-in categories first, in display order, then custom ones in
+    /// config order; `FileCategory::Custom(i)` is entry `9 + i`.
+    categories: Vec<CategoryInfo>,
+    /// Sorted by key for binary search.
+    extensions: Vec<(ExtensionKey, FileCategory)>,
+}
+
+impl Default for CategoryTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl CategoryTable {
+    /// The built-in categories and extensions.
+    pub fn builtin() -> Self {
+        let mut map = BTreeMap::new();
+        for &(category, extensions) in BUILTIN_EXTENSIONS {
+            for ext in extensions {
+                if let Some(key) = extension_key(ext) {
+                    map.insert(key, category);
+                }
+            }
+        }
+        Self {
+            categories: BUILTIN_CATEGORIES
+                .iter()
+                .map(|&(category, color)| CategoryInfo {
+                    category,
+                    name: category.builtin_label().to_string(),
+                    color,
+                })
+                .collect(),
+            extensions: map.into_iter().collect(),
+        }
+    }
+
+    /// The built-in table with `config` applied on top.
+    pub fn from_config(config: &CategoryConfig) -> Result<Self, CategoryConfigError> {
+        let mut table = Self::builtin();
+        let mut map: BTreeMap<ExtensionKey, FileCategory> = table.extensions.drain(..).collect();
+
+        for entry in &config.categories {
+            let name = entry.name.trim();
+            if name.is_empty() {
+                return Err(CategoryConfigError::EmptyName);
+            }
+            let color = entry.color.as_deref().map(parse_color).transpose()?;
+            let existing = table
+                .categories
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(name));
+            let category = match existing {
+                Some(info) => {
+                    if let Some(color) = color {
+                        info.color = color;
+                    }
+                    info.category
+                }
+                None => {
+                    let index = table.categories.len() - BUILTIN_CATEGORIES.len();
+                    if index >= MAX_CUSTOM_CATEGORIES {
+                        return Err(CategoryConfigError::TooManyCategories);
+                    }
+                    let color =
+                        color.ok_or_else(|| CategoryConfigError::MissingColor(name.to_string()))?;
+                    let category = FileCategory::Custom(index as u8);
+                    table.categories.push(CategoryInfo {
+                        category,
+                        name: name.to_string(),
+                        color,
+                    });
+                    category
+                }
+            };
+            for ext in &entry.extensions {
+                let trimmed = ext.trim().trim_start_matches('.');
+                let key = extension_key(trimmed)
+                    .filter(|_| !trimmed.is_empty())
+                    .ok_or_else(|| CategoryConfigError::InvalidExtension(ext.clone()))?;
+                map.insert(key, category);
+            }
+        }
+
+        table.extensions = map.into_iter().collect();
+        Ok(table)
+    }
+
+    /// Parse a JSON config and apply it to the built-in table.
+    pub fn from_json(text: &str) -> Result<Self, CategoryConfigError> {
+        Self::from_config(&serde_json::from_str(text)?)
+    }
+
+    /// Load a JSON config file.
+    pub fn load(path: &Path) -> Result<Self, CategoryConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| CategoryConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_json(&text)
+    }
+
+    /// Category of an extension (without the dot, any case).
+    ///
+    /// Zero-heap-allocation hot path: the extension is lowercased into a
+    /// fixed-size stack key and binary-searched in the sorted table.
+    pub fn categorise(&self, ext: &str) -> FileCategory {
+        let Some(key) = extension_key(ext) else {
+            return FileCategory::Other;
+        };
+        match self.extensions.binary_search_by(|(k, _)| k.cmp(&key)) {
+            Ok(i) => self.extensions[i].1,
+            Err(_) => FileCategory::Other,
+        }
+    }
+
+    /// Every category: built-ins in display order, then custom ones.
+    pub fn categories(&self) -> &[CategoryInfo] {
+        &self.categories
+    }
+
+    /// Name and colour of `category`, if the table has it.
+    pub fn info(&self, category: FileCategory) -> Option<&CategoryInfo> {
+        let index = match category {
+            FileCategory::Custom(i) => BUILTIN_CATEGORIES.len() + i as usize,
+            builtin => BUILTIN_CATEGORIES.iter().position(|&(c, _)| c == builtin)?,
+        };
+        self.categories.get(index)
+    }
+
+    /// Colour of `category`; `Other`'s colour for one the table lacks.
+    pub fn color(&self, category: FileCategory) -> [u8; 3] {
+        self.info(category).map_or(OTHER_COLOR, |info| info.color)
+    }
+}
+
+/// Lowercase `ext` into a zero-padded key, or `None` if it is too long.
+fn extension_key(ext: &str) -> Option<ExtensionKey> {
+    let bytes = ext.as_bytes();
+    if bytes.len() > MAX_EXTENSION_LEN {
+        return None;
+    }
+    let mut key = [0u8; MAX_EXTENSION_LEN];
+    for (dest, &src) in key.iter_mut().zip(bytes) {
+        *dest = src.to_ascii_lowercase();
+    }
+    Some(key)
+}
+
+/// Parse `#rrggbb`.
+fn parse_color(text: &str) -> Result<[u8; 3], CategoryConfigError> {
+    let invalid = || CategoryConfigError::InvalidColor(text.to_string());
+    let hex = text.trim().strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut rgb = [0u8; 3];
+    for (i, channel) in rgb.iter_mut().enumerate() {
+        *channel = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(rgb)
+}
+
+// ── Process-wide table ───────────────────────────────────────────────────────
+
+static TABLE: OnceLock<CategoryTable> = OnceLock::new();
+
+/// The table in use: the one passed to [`install_categories`], or the
+/// built-in table if none was installed before the first lookup.
+pub fn categories() -> &'static CategoryTable {
+    TABLE.get_or_init(CategoryTable::builtin)
+}
+
+/// Make `table` the process-wide table.
+///
+/// Must run before anything is categorised (i.e. before the first scan);
+/// returns `false`, leaving the current table in place, if one is already
+/// in use.
+pub fn install_categories(table: CategoryTable) -> bool {
+    TABLE.set(table).is_ok()
+}
+
+/// Where the category config lives: `DISKSLEUTH_CATEGORIES` if set, else
+/// `%APPDATA%\DiskSleuth\categories.json` on Windows and
+/// `$XDG_CONFIG_HOME/disksleuth/categories.json` (default `~/.config`)
+/// elsewhere.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("DISKSLEUTH_CATEGORIES") {
+        return Some(PathBuf::from(path));
+    }
+    #[cfg(windows)]
+    {
+        std::env::var_os("APPDATA").map(|dir| {
+            PathBuf::from(dir)
+                .join("DiskSleuth")
+                .join(CATEGORIES_FILE_NAME)
+        })
+    }
+    #[cfg(not(windows))]
+    {
+        let config = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+        Some(config.join("disksleuth").join(CATEGORIES_FILE_NAME))
+    }
+}
+
+/// Load the config at [`config_path`].  A missing file is not an error:
+/// it yields `Ok(None)` and the built-in table stays in use.
+pub fn load_config() -> Result<Option<(PathBuf, CategoryTable)>, CategoryConfigError> {
+    let Some(path) = config_path() else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let table = CategoryTable::load(&path)?;
+    Ok(Some((path, table)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The built-in table must be sorted with no duplicate keys, or the
+    /// binary search would miss entries.
+    #[test]
+    fn builtin_table_is_sorted_and_unique() {
+        let table = CategoryTable::builtin();
+        assert!(table.extensions.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(table.categorise("Mp4"), FileCategory::Video);
+        assert_eq!(table.categorise("vhdx"), FileCategory::Other);
+        assert_eq!(table.categorise("abcdefghijklmnopq"), FileCategory::Other);
+        assert_eq!(table.categories().len(), 9);
+    }
+
+    /// Config entries move extensions, recolour built-ins and add custom
+    /// categories.
+    #[test]
+    fn config_overrides_and_extends() {
+        let table = CategoryTable::from_json(
+            r##"{ "categories": [
+                { "name": "Disk images", "color": "#102030", "extensions": [".VHDX", "qcow2"] },
+                { "name": "documents", "color": "#ffffff", "extensions": ["log", "pst"] }
+            ] }"##,
+        )
+        .unwrap();
+
+        let images = FileCategory::Custom(0);
+        assert_eq!(table.categorise("vhdx"), images);
+        assert_eq!(table.categorise("QCOW2"), images);
+        assert_eq!(table.info(images).unwrap().name, "Disk images");
+        assert_eq!(table.color(images), [0x10, 0x20, 0x30]);
+
+        assert_eq!(table.categorise("log"), FileCategory::Documents);
+        assert_eq!(table.categorise("pst"), FileCategory::Documents);
+        assert_eq!(table.color(FileCategory::Documents), [0xff, 0xff, 0xff]);
+        // Untouched built-ins keep working.
+        assert_eq!(table.categorise("rs"), FileCategory::Code);
+        assert_eq!(table.categories().len(), 10);
+    }
+
+    #[test]
+    fn config_errors() {
+        let err = |json: &str| CategoryTable::from_json(json).unwrap_err();
+        assert!(matches!(
+            err(r#"{ "categories": [{ "name": "New" }] }"#),
+            CategoryConfigError::MissingColor(_)
+        ));
+        assert!(matches!(
+            err(r#"{ "categories": [{ "name": "Code", "color": "green" }] }"#),
+            CategoryConfigError::InvalidColor(_)
+        ));
+        assert!(matches!(
+            err(r#"{ "categories": [{ "name": "Code", "extensions": ["."] }] }"#),
+            CategoryConfigError::InvalidExtension(_)
+        ));
+        assert!(matches!(
+            err(r#"{ "categories": [{ "name": " " }] }"#),
+            CategoryConfigError::EmptyName
+        ));
+        assert!(matches!(
+            err(r#"{ "groups": [] }"#),
+            CategoryConfigError::Parse(_)
+        ));
+
+        let many: Vec<String> = (0..=MAX_CUSTOM_CATEGORIES)
+            .map(|i| format!(r##"{{ "name": "c{i}", "color": "#000000" }}"##))
+            .collect();
+        let json = format!(r#"{{ "categories": [{}] }}"#, many.join(","));
+        assert!(matches!(err(&json), CategoryConfigError::TooManyCategories));
+    }
+}
@@ -0,0 +1,584 @@
+/// Compressibility estimation — how much a subtree would shrink under
+/// filesystem compression, without reading all of it.
+///
+/// [`estimate_compression`] splits the files below a directory into strata
+/// by category and size band, draws up to [`SAMPLES_PER_STRATUM`] files from
+/// each, and compresses up to [`SAMPLE_CHUNKS`] chunks of
+/// [`SAMPLE_CHUNK_LEN`] bytes spread through every drawn file with a fast
+/// deflate level.  Each stratum's ratio — compressed over read bytes of its
+/// samples — is applied to all of its bytes, with a 95% confidence interval
+/// from the spread of the samples around it.
+///
+/// Estimates are reported for the whole subtree, for each folder directly
+/// inside it, and per extension.  An extension with enough samples of its
+/// own uses them; rarer ones fall back to their strata.  Deflate is a
+/// stand-in for the filesystem's codec (LZNT1 / XPRESS on NTFS), so treat
+/// the figures as a guide rather than a promise.
+///
+/// The pass runs on a background thread via [`start_compression_estimate`].
+use crate::analysis::file_types::{categorise_extension, file_extension, FileCategory};
+use crate::analysis::worker::{spawn_worker, WorkerHandle};
+use crate::model::{FileTree, NodeIndex};
+use compact_str::CompactString;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Bytes compressed per chunk.
+pub const SAMPLE_CHUNK_LEN: usize = 32 * 1024;
+
+/// Chunks read from each sampled file: its start, middle and end.
+pub const SAMPLE_CHUNKS: u64 = 3;
+
+/// Most files sampled from one category / size-band stratum.
+pub const SAMPLES_PER_STRATUM: usize = 32;
+
+/// Fewest samples of an extension for its own ratio to be used.
+pub const MIN_EXTENSION_SAMPLES: usize = 4;
+
+/// Most folders and extensions listed in a [`CompressionReport`].
+pub const MAX_COMPRESSION_ROWS: usize = 200;
+
+/// Upper bounds of the size bands; larger files form the last band.
+const SIZE_BANDS: [u64; 4] = [64 * 1024, 1024 * 1024, 16 * 1024 * 1024, 256 * 1024 * 1024];
+
+/// Deflate level: the fastest, closest to what filesystems use.
+const DEFLATE_LEVEL: u8 = 1;
+
+/// Files sampled between progress messages and cancellation checks.
+const SAMPLE_BATCH: usize = 16;
+
+/// Number of nodes visited between cancellation checks while stratifying.
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
+/// Two-sided 95% normal quantile.
+const Z_95: f64 = 1.96;
+
+/// Variance assumed for a ratio when fewer than two samples were read —
+/// the most a value in `0..=1` can have.
+const UNKNOWN_VARIANCE: f64 = 0.25;
+
+/// Estimated compression of a set of files.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Estimate {
+    pub files: u64,
+    /// Logical bytes of the files.
+    pub bytes: u64,
+    /// Estimated bytes once compressed.
+    pub compressed: u64,
+    /// Half-width of the 95% confidence interval of [`Estimate::ratio`].
+    pub margin: f64,
+    /// Sampled files the estimate rests on: those of every stratum it
+    /// draws on, or the extension's own.
+    pub samples: u64,
+}
+
+impl Estimate {
+    /// Compressed size as a share of the original; 1.0 for no files.
+    pub fn ratio(&self) -> f64 {
+        match self.bytes {
+            0 => 1.0,
+            bytes => self.compressed as f64 / bytes as f64,
+        }
+    }
+
+    /// Estimated bytes saved.
+    pub fn savings(&self) -> u64 {
+        self.bytes - self.compressed
+    }
+
+    /// 95% confidence interval of the bytes saved, low to high.
+    pub fn savings_range(&self) -> (u64, u64) {
+        let ratio = self.ratio();
+        let saved = |r: f64| (self.bytes as f64 * (1.0 - r.clamp(0.0, 1.0))) as u64;
+        (saved(ratio + self.margin), saved(ratio - self.margin))
+    }
+}
+
+/// Estimate for one folder directly inside the analysed directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirEstimate {
+    pub index: NodeIndex,
+    pub estimate: Estimate,
+}
+
+/// Estimate for one extension (lowercase, without the dot).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionEstimate {
+    pub extension: CompactString,
+    pub category: FileCategory,
+    pub estimate: Estimate,
+    /// Whether the ratio comes from this extension's own samples rather
+    /// than from its strata.
+    pub sampled: bool,
+}
+
+/// Result of [`estimate_compression`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionReport {
+    /// Directory the estimate covers.
+    pub root: NodeIndex,
+    pub total: Estimate,
+    /// Folders directly inside `root`, most savings first.
+    pub dirs: Vec<DirEstimate>,
+    /// Extensions below `root`, most savings first.
+    pub extensions: Vec<ExtensionEstimate>,
+    pub files_sampled: u64,
+    /// Bytes read and compressed.
+    pub bytes_read: u64,
+    /// Sampled files that could not be read.
+    pub unreadable: u64,
+}
+
+/// Index of the size band `size` falls into.
+fn size_band(size: u64) -> u8 {
+    SIZE_BANDS
+        .iter()
+        .take_while(|&&bound| size >= bound)
+        .count() as u8
+}
+
+/// Small deterministic generator for reservoir sampling, so that the same
+/// tree always yields the same sample.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Bytes read and compressed from one sampled file.
+type Sample = (u64, u64);
+
+/// Files of one category and size band.
+#[derive(Default)]
+struct Stratum {
+    files: u64,
+    bytes: u64,
+    picked: Vec<NodeIndex>,
+    samples: Vec<Sample>,
+}
+
+impl Stratum {
+    /// Ratio of the readable samples; a stratum none of whose samples
+    /// could be read counts as incompressible.
+    fn ratio(&self) -> f64 {
+        ratio(&self.samples).unwrap_or(1.0)
+    }
+
+    /// Variance of [`Stratum::ratio`], with the finite population correction.
+    fn ratio_variance(&self) -> f64 {
+        ratio_variance(&self.samples, self.files)
+    }
+}
+
+/// Byte-weighted ratio of `samples`: compressed bytes over bytes read.
+fn ratio(samples: &[Sample]) -> Option<f64> {
+    let (read, compressed) = samples
+        .iter()
+        .fold((0u64, 0u64), |(r, c), &(read, compressed)| {
+            (r + read, c + compressed)
+        });
+    (read > 0).then(|| compressed as f64 / read as f64)
+}
+
+/// Variance of [`ratio`] over `samples` drawn from `population` files: the
+/// spread of each sample's compressed bytes around the ratio, scaled by the
+/// mean bytes read.
+fn ratio_variance(samples: &[Sample], population: u64) -> f64 {
+    let n = samples.len();
+    if n == 0 {
+        return UNKNOWN_VARIANCE;
+    }
+    let variance = match ratio(samples) {
+        Some(r) if n >= 2 => {
+            let mean_read = samples.iter().map(|&(read, _)| read as f64).sum::<f64>() / n as f64;
+            let residuals = samples
+                .iter()
+                .map(|&(read, compressed)| (compressed as f64 - r * read as f64).powi(2))
+                .sum::<f64>();
+            residuals / (n - 1) as f64 / mean_read.powi(2)
+        }
+        _ => UNKNOWN_VARIANCE,
+    };
+    let fpc = 1.0 - n as f64 / population.max(n as u64) as f64;
+    variance / n as f64 * fpc
+}
+
+/// Files and bytes of a folder or extension, split by stratum.
+#[derive(Default)]
+struct Group {
+    files: u64,
+    bytes: u64,
+    strata: HashMap<usize, u64>,
+}
+
+impl Group {
+    fn add(&mut self, stratum: usize, size: u64) {
+        self.files += 1;
+        self.bytes += size;
+        *self.strata.entry(stratum).or_default() += size;
+    }
+
+    /// Apply each stratum's ratio to this group's bytes in it.
+    fn estimate(&self, strata: &[Stratum]) -> Estimate {
+        let mut compressed = 0.0;
+        let mut variance = 0.0;
+        let mut samples = 0;
+        for (&s, &bytes) in &self.strata {
+            let stratum = &strata[s];
+            compressed += bytes as f64 * stratum.ratio();
+            variance += (bytes as f64).powi(2) * stratum.ratio_variance();
+            samples += stratum.samples.len() as u64;
+        }
+        Estimate {
+            files: self.files,
+            bytes: self.bytes,
+            compressed: (compressed as u64).min(self.bytes),
+            margin: match self.bytes {
+                0 => 0.0,
+                b => Z_95 * variance.sqrt() / b as f64,
+            },
+            samples,
+        }
+    }
+}
+
+/// Read up to [`SAMPLE_CHUNKS`] chunks spread through the file at `path` of
+/// `size` bytes and deflate each, returning bytes read and compressed.
+pub fn sample_file(path: &Path, size: u64) -> io::Result<(u64, u64)> {
+    let mut file = File::open(path)?;
+    let chunk = SAMPLE_CHUNK_LEN as u64;
+    let offsets: Vec<u64> = if size <= chunk * SAMPLE_CHUNKS {
+        (0..SAMPLE_CHUNKS).map(|i| i * chunk).collect()
+    } else {
+        let last = size - chunk;
+        (0..SAMPLE_CHUNKS)
+            .map(|i| last / (SAMPLE_CHUNKS - 1) * i)
+            .collect()
+    };
+
+    let mut buf = Vec::with_capacity(SAMPLE_CHUNK_LEN);
+    let (mut read, mut compressed) = (0u64, 0u64);
+    for offset in offsets {
+        file.seek(SeekFrom::Start(offset))?;
+        buf.clear();
+        (&mut file).take(chunk).read_to_end(&mut buf)?;
+        if buf.is_empty() {
+            break;
+        }
+        let packed = miniz_oxide::deflate::compress_to_vec(&buf, DEFLATE_LEVEL);
+        // Filesystems store a chunk uncompressed when deflating grows it.
+        read += buf.len() as u64;
+        compressed += packed.len().min(buf.len()) as u64;
+    }
+    Ok((read, compressed))
+}
+
+/// Estimate how well the files below `root` would compress.
+///
+/// `progress` receives files sampled, files to sample and bytes read after
+/// each batch.  Returns `None` if `cancel` is set before the pass finishes.
+pub fn estimate_compression(
+    tree: &FileTree,
+    root: NodeIndex,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(u64, u64, u64),
+) -> Option<CompressionReport> {
+    let mut strata: Vec<Stratum> = Vec::new();
+    let mut stratum_ids: HashMap<(FileCategory, u8), usize> = HashMap::new();
+    let mut total = Group::default();
+    let mut dirs: Vec<(NodeIndex, Group)> = Vec::new();
+    let mut extensions: HashMap<CompactString, Group> = HashMap::new();
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+    // ── Stratify ────────────────────────────────────────────────
+    // Depth-first below `root`, remembering which of its folders each file
+    // is under.
+    let mut stack: Vec<(NodeIndex, Option<usize>)> = vec![(root, None)];
+    let mut visited = 0usize;
+    while let Some((idx, dir)) = stack.pop() {
+        if visited.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        visited += 1;
+        let node = tree.node(idx);
+        if node.is_dir {
+            let mut child = node.first_child;
+            while let Some(c) = child {
+                let child_dir = match dir {
+                    None if tree.node(c).is_dir => {
+                        dirs.push((c, Group::default()));
+                        Some(dirs.len() - 1)
+                    }
+                    _ => dir,
+                };
+                stack.push((c, child_dir));
+                child = tree.node(c).next_sibling;
+            }
+            continue;
+        }
+        if node.is_error || node.size == 0 {
+            continue;
+        }
+
+        let mut ext = CompactString::new(file_extension(&node.name));
+        ext.make_ascii_lowercase();
+        let key = (categorise_extension(&ext), size_band(node.size));
+        let s = *stratum_ids.entry(key).or_insert_with(|| {
+            strata.push(Stratum::default());
+            strata.len() - 1
+        });
+        let stratum = &mut strata[s];
+        stratum.files += 1;
+        stratum.bytes += node.size;
+        if stratum.picked.len() < SAMPLES_PER_STRATUM {
+            stratum.picked.push(idx);
+        } else {
+            let slot = (rng.next() % stratum.files) as usize;
+            if slot < SAMPLES_PER_STRATUM {
+                stratum.picked[slot] = idx;
+            }
+        }
+
+        total.add(s, node.size);
+        if let Some(d) = dir {
+            dirs[d].1.add(s, node.size);
+        }
+        extensions.entry(ext).or_default().add(s, node.size);
+    }
+
+    // ── Sample ──────────────────────────────────────────────────
+    let plan: Vec<(usize, NodeIndex)> = strata
+        .iter()
+        .enumerate()
+        .flat_map(|(s, stratum)| stratum.picked.iter().map(move |&idx| (s, idx)))
+        .collect();
+    let planned = plan.len() as u64;
+    let mut ext_samples: HashMap<CompactString, Vec<Sample>> = HashMap::new();
+    let (mut sampled, mut bytes_read, mut unreadable) = (0u64, 0u64, 0u64);
+    for batch in plan.chunks(SAMPLE_BATCH) {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let results: Vec<io::Result<(u64, u64)>> = batch
+            .par_iter()
+            .map(|&(_, idx)| match tree.fs_path(idx) {
+                Some(path) => sample_file(&path, tree.node(idx).size),
+                None => Err(io::ErrorKind::NotFound.into()),
+            })
+            .collect();
+        for (&(s, idx), result) in batch.iter().zip(results) {
+            match result {
+                Ok((read, compressed)) if read > 0 => {
+                    strata[s].samples.push((read, compressed));
+                    let mut ext = CompactString::new(file_extension(&tree.node(idx).name));
+                    ext.make_ascii_lowercase();
+                    ext_samples.entry(ext).or_default().push((read, compressed));
+                    bytes_read += read;
+                }
+                Ok(_) => {}
+                Err(_) => unreadable += 1,
+            }
+        }
+        sampled += batch.len() as u64;
+        progress(sampled, planned, bytes_read);
+    }
+
+    // ── Extrapolate ─────────────────────────────────────────────
+    let mut dirs: Vec<DirEstimate> = dirs
+        .into_iter()
+        .filter(|(_, group)| group.bytes > 0)
+        .map(|(index, group)| DirEstimate {
+            index,
+            estimate: group.estimate(&strata),
+        })
+        .collect();
+    dirs.sort_unstable_by(|a, b| {
+        b.estimate
+            .savings()
+            .cmp(&a.estimate.savings())
+            .then(a.index.cmp(&b.index))
+    });
+    dirs.truncate(MAX_COMPRESSION_ROWS);
+
+    let mut extensions: Vec<ExtensionEstimate> = extensions
+        .into_iter()
+        .map(|(extension, group)| {
+            let mut estimate = group.estimate(&strata);
+            let own = ext_samples.get(&extension).map_or(&[][..], Vec::as_slice);
+            let sampled = own.len() >= MIN_EXTENSION_SAMPLES;
+            if let (true, Some(ratio)) = (sampled, ratio(own)) {
+                estimate.compressed = ((group.bytes as f64 * ratio) as u64).min(group.bytes);
+                estimate.margin = Z_95 * ratio_variance(own, group.files).sqrt();
+                estimate.samples = own.len() as u64;
+            }
+            ExtensionEstimate {
+                category: categorise_extension(&extension),
+                extension,
+                estimate,
+                sampled,
+            }
+        })
+        .collect();
+    extensions.sort_unstable_by(|a, b| {
+        b.estimate
+            .savings()
+            .cmp(&a.estimate.savings())
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+    extensions.truncate(MAX_COMPRESSION_ROWS);
+
+    Some(CompressionReport {
+        root,
+        total: total.estimate(&strata),
+        dirs,
+        extensions,
+        files_sampled: sampled - unreadable,
+        bytes_read,
+        unreadable,
+    })
+}
+
+/// Messages sent from the compression thread to the UI.
+#[derive(Debug)]
+pub enum CompressionMessage {
+    /// Files sampled so far, of the total to sample, and bytes read.
+    Progress {
+        sampled: u64,
+        total: u64,
+        bytes_read: u64,
+    },
+    /// The pass finished.
+    Complete(CompressionReport),
+    /// The pass was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed compressibility pass; receives
+/// progress, then exactly one `Complete` or `Cancelled`.
+pub type CompressionHandle = WorkerHandle<CompressionMessage>;
+
+/// Run [`estimate_compression`] on a background thread.
+pub fn start_compression_estimate(tree: Arc<FileTree>, root: NodeIndex) -> CompressionHandle {
+    spawn_worker("disksleuth-compression", 16, move |tx, cancel| {
+        let report = estimate_compression(&tree, root, cancel, |sampled, total, bytes_read| {
+            let _ = tx.try_send(CompressionMessage::Progress {
+                sampled,
+                total,
+                bytes_read,
+            });
+        });
+        let msg = match report {
+            Some(report) => CompressionMessage::Complete(report),
+            None => CompressionMessage::Cancelled,
+        };
+        let _ = tx.send(msg);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::file_node::FileNode;
+
+    #[test]
+    fn size_bands_split_at_bounds() {
+        assert_eq!(size_band(0), 0);
+        assert_eq!(size_band(64 * 1024 - 1), 0);
+        assert_eq!(size_band(64 * 1024), 1);
+        assert_eq!(size_band(20 * 1024 * 1024), 3);
+        assert_eq!(size_band(u64::MAX), 4);
+    }
+
+    /// Large samples weigh more than small ones, and samples that all
+    /// compress alike leave no doubt about the ratio.
+    #[test]
+    fn ratio_is_weighted_by_bytes_read() {
+        let samples = [(100, 10), (9_900, 9_900)];
+        assert_eq!(ratio(&samples), Some(0.991));
+        assert_eq!(ratio(&[]), None);
+
+        let alike = [(1_000, 250), (4_000, 1_000), (2_000, 500)];
+        assert_eq!(ratio(&alike), Some(0.25));
+        assert_eq!(ratio_variance(&alike, 100), 0.0);
+        assert!(ratio_variance(&samples, 100) > 0.0);
+        // Every file sampled: nothing left to estimate.
+        assert_eq!(ratio_variance(&samples, 2), 0.0);
+        assert_eq!(ratio_variance(&[(10, 5)], 100), UNKNOWN_VARIANCE * 0.99);
+    }
+
+    /// Text compresses, random bytes do not, and both are attributed to
+    /// their folder and extension.
+    #[test]
+    fn estimates_per_folder_and_extension() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut tree = FileTree::with_capacity(32);
+        let root = tree.add_root(CompactString::new("root"));
+        tree.set_root_path(root, temp.path().to_path_buf());
+
+        let add = |tree: &mut FileTree, dir: &str, name: &str, data: &[u8]| {
+            let dir_path = temp.path().join(dir);
+            std::fs::create_dir_all(&dir_path).unwrap();
+            std::fs::write(dir_path.join(name), data).unwrap();
+            let parent = match tree
+                .children(root)
+                .into_iter()
+                .find(|&c| tree.node(c).name == dir)
+            {
+                Some(d) => d,
+                None => {
+                    let d = tree.add_node(FileNode::new_dir(CompactString::new(dir), Some(root)));
+                    tree.add_child(root, d);
+                    d
+                }
+            };
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new(name),
+                data.len() as u64,
+                Some(parent),
+            ));
+            tree.add_child(parent, f);
+        };
+
+        let text = b"the quick brown fox jumps over the lazy dog\n".repeat(2_000);
+        let mut rng = XorShift(42);
+        for i in 0..5 {
+            add(&mut tree, "logs", &format!("{i}.log"), &text);
+            let noise: Vec<u8> = (0..text.len()).map(|_| rng.next() as u8).collect();
+            add(&mut tree, "media", &format!("{i}.bin"), &noise);
+        }
+        tree.aggregate_sizes();
+
+        let cancel = AtomicBool::new(false);
+        let mut calls = 0;
+        let report = estimate_compression(&tree, root, &cancel, |_, _, _| calls += 1).unwrap();
+        assert!(calls > 0);
+        assert_eq!(report.files_sampled, 10);
+        assert_eq!(report.unreadable, 0);
+        assert_eq!(report.total.files, 10);
+
+        assert_eq!(report.dirs.len(), 2);
+        let logs = &report.dirs[0];
+        assert_eq!(tree.node(logs.index).name, "logs");
+        assert!(logs.estimate.ratio() < 0.1, "{}", logs.estimate.ratio());
+        assert!(report.dirs[1].estimate.ratio() > 0.99);
+
+        let log = &report.extensions[0];
+        assert_eq!((log.extension.as_str(), log.sampled), ("log", true));
+        assert_eq!(log.estimate.samples, 5);
+        let (low, high) = log.estimate.savings_range();
+        assert!(low <= log.estimate.savings() && log.estimate.savings() <= high);
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(estimate_compression(&tree, root, &cancel, |_, _, _| {}).is_none());
+    }
+}
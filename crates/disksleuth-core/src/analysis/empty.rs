@@ -0,0 +1,200 @@
+/// Empty folders and zero-byte files — leftovers after cleanups and installs.
+///
+/// A folder is empty when no file lies anywhere below it
+/// (`descendant_count == 0` after aggregation) and nothing below it was
+/// unreadable.  Only the outermost empty folder of a skeleton is listed;
+/// removing it removes the rest.  Results are grouped by the top-level
+/// folder (the child of the scan root) they sit in.
+use crate::model::{FileTree, NodeIndex};
+use std::collections::HashMap;
+
+/// Most items (folders plus files) listed; counts still cover all of them.
+pub const MAX_EMPTY_ITEMS: usize = 100_000;
+
+/// Empty folders and zero-byte files under one top-level folder.
+#[derive(Debug, Clone)]
+pub struct EmptyGroup {
+    /// The top-level folder, or the scan root for items directly under it.
+    pub top: NodeIndex,
+    /// Outermost empty folders.
+    pub empty_dirs: Vec<NodeIndex>,
+    pub zero_byte_files: Vec<NodeIndex>,
+}
+
+impl EmptyGroup {
+    /// Folders plus files in the group.
+    pub fn len(&self) -> usize {
+        self.empty_dirs.len() + self.zero_byte_files.len()
+    }
+
+    /// Whether the group lists nothing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Result of [`find_empty`].
+#[derive(Debug, Clone, Default)]
+pub struct EmptyReport {
+    /// Groups with the most items first.
+    pub groups: Vec<EmptyGroup>,
+    /// Outermost empty folders, including any beyond the cap.
+    pub empty_dir_count: u64,
+    /// Empty folders including nested ones.
+    pub empty_dir_total: u64,
+    /// Zero-byte files, including any beyond the cap.
+    pub zero_byte_count: u64,
+}
+
+impl EmptyReport {
+    /// Every listed outermost empty folder, group by group.
+    pub fn all_empty_dirs(&self) -> Vec<NodeIndex> {
+        self.groups
+            .iter()
+            .flat_map(|g| g.empty_dirs.iter().copied())
+            .collect()
+    }
+
+    /// Every listed zero-byte file, group by group.
+    pub fn all_zero_byte_files(&self) -> Vec<NodeIndex> {
+        self.groups
+            .iter()
+            .flat_map(|g| g.zero_byte_files.iter().copied())
+            .collect()
+    }
+}
+
+/// The child of the scan root that `index` lies under, or the root itself
+/// for items directly under it.
+fn top_level_of(tree: &FileTree, index: NodeIndex) -> NodeIndex {
+    let Some(mut current) = tree.node(index).parent else {
+        return index;
+    };
+    while let Some(parent) = tree.node(current).parent {
+        if tree.node(parent).parent.is_none() {
+            break;
+        }
+        current = parent;
+    }
+    current
+}
+
+/// Find empty folders and zero-byte files in `tree`.
+pub fn find_empty(tree: &FileTree) -> EmptyReport {
+    let len = tree.nodes.len();
+    // Children follow their parent in the arena, so a reverse pass carries
+    // "contains something unreadable" up to every ancestor.
+    let mut unreadable = vec![false; len];
+    for i in (0..len).rev() {
+        let node = &tree.nodes[i];
+        if node.is_error || unreadable[i] {
+            unreadable[i] = true;
+            if let Some(p) = node.parent {
+                unreadable[p.idx()] = true;
+            }
+        }
+    }
+    let is_empty_dir = |i: usize| {
+        let node = &tree.nodes[i];
+        node.is_dir && node.parent.is_some() && node.descendant_count == 0 && !unreadable[i]
+    };
+
+    let mut report = EmptyReport::default();
+    let mut groups: HashMap<NodeIndex, EmptyGroup> = HashMap::new();
+    let mut listed = 0usize;
+    for (i, node) in tree.nodes.iter().enumerate() {
+        let index = NodeIndex::new(i);
+        let is_dir = if is_empty_dir(i) {
+            report.empty_dir_total += 1;
+            // Nested in another empty folder: removed with it.
+            if node.parent.is_some_and(|p| is_empty_dir(p.idx())) {
+                continue;
+            }
+            report.empty_dir_count += 1;
+            true
+        } else if !node.is_dir && !node.is_error && node.size == 0 {
+            report.zero_byte_count += 1;
+            false
+        } else {
+            continue;
+        };
+        if listed >= MAX_EMPTY_ITEMS {
+            continue;
+        }
+        listed += 1;
+        let top = top_level_of(tree, index);
+        let group = groups.entry(top).or_insert_with(|| EmptyGroup {
+            top,
+            empty_dirs: Vec::new(),
+            zero_byte_files: Vec::new(),
+        });
+        if is_dir {
+            group.empty_dirs.push(index);
+        } else {
+            group.zero_byte_files.push(index);
+        }
+    }
+
+    report.groups = groups.into_values().collect();
+    report
+        .groups
+        .sort_by(|a, b| b.len().cmp(&a.len()).then(a.top.cmp(&b.top)));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::file_node::FileNode;
+    use compact_str::CompactString;
+
+    /// Outermost empty folders and zero-byte files, grouped by top level.
+    #[test]
+    fn finds_outermost_empty_dirs_and_zero_byte_files() {
+        let mut tree = FileTree::with_capacity(16);
+        let root = tree.add_root(CompactString::new("C:"));
+        let app = tree.add_test_dir(root, "app");
+        let skeleton = tree.add_test_dir(app, "old");
+        tree.add_test_dir(skeleton, "a");
+        tree.add_test_dir(skeleton, "b");
+        let lock = tree.add_test_file(app, "app.lock", 0);
+        tree.add_test_file(app, "data.bin", 10);
+        let docs = tree.add_test_dir(root, "docs");
+        let stub = tree.add_test_file(docs, "todo.txt", 0);
+        let top_empty = tree.add_test_dir(root, "empty");
+        tree.aggregate_sizes();
+
+        let report = find_empty(&tree);
+        assert_eq!(report.empty_dir_count, 2);
+        assert_eq!(report.empty_dir_total, 4);
+        assert_eq!(report.zero_byte_count, 2);
+
+        let app_group = report.groups.iter().find(|g| g.top == app).unwrap();
+        assert_eq!(app_group.empty_dirs, [skeleton]);
+        assert_eq!(app_group.zero_byte_files, [lock]);
+        assert_eq!(report.groups[0].top, app, "largest group first");
+        let docs_group = report.groups.iter().find(|g| g.top == docs).unwrap();
+        assert_eq!(docs_group.zero_byte_files, [stub]);
+        let root_group = report.groups.iter().find(|g| g.top == root).unwrap();
+        assert_eq!(root_group.empty_dirs, [top_empty]);
+    }
+
+    /// A folder with an unreadable subfolder is not known to be empty.
+    #[test]
+    fn unreadable_contents_are_not_empty() {
+        let mut tree = FileTree::with_capacity(4);
+        let root = tree.add_root(CompactString::new("C:"));
+        let outer = tree.add_test_dir(root, "outer");
+        let denied = tree.add_node(FileNode::new_error(
+            CompactString::new("denied"),
+            true,
+            Some(outer),
+        ));
+        tree.add_child(outer, denied);
+        tree.aggregate_sizes();
+
+        let report = find_empty(&tree);
+        assert_eq!(report.empty_dir_total, 0);
+        assert!(report.groups.is_empty());
+    }
+}
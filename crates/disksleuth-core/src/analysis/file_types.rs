@@ -1,10 +1,21 @@
 /// File type categorisation based on file extensions.
 ///
 /// Groups files into broad categories (Documents, Media, Code, Archives,
-/// System, Other) and computes size/count totals per category.
-use crate::model::FileTree;
+/// System, Other, plus any custom ones from the category config) and
+/// computes size/count totals per category.  The extension table itself
+/// lives in [`crate::analysis::categories`].  Files identified by the
+/// signature pass ([`crate::analysis::signature`]) are categorised by their
+/// content instead.
+use crate::analysis::categories::categories;
+use crate::analysis::signature::DetectedTypes;
+use crate::model::{FileTree, NodeIndex};
+use compact_str::CompactString;
 use std::collections::HashMap;
 
+/// Most distinct extensions kept by [`analyse_extensions`]; beyond this the
+/// smallest by total size are dropped.
+pub const MAX_EXTENSION_STATS: usize = 10_000;
+
 /// Broad file type categories for visual grouping.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FileCategory {
@@ -17,11 +28,25 @@ pub enum FileCategory {
     Executables,
     System,
     Other,
+    /// A category defined in the category config, by position there.
+    Custom(u8),
 }
 
 impl FileCategory {
     /// Human-readable label for display.
+    ///
+    /// Custom categories are named by the process-wide category table.
     pub fn label(self) -> &'static str {
+        match self {
+            Self::Custom(_) => categories()
+                .info(self)
+                .map_or("Other", |info| info.name.as_str()),
+            builtin => builtin.builtin_label(),
+        }
+    }
+
+    /// Label of a built-in category; `Other` for custom ones.
+    pub(crate) fn builtin_label(self) -> &'static str {
         match self {
             Self::Documents => "Documents",
             Self::Images => "Images",
@@ -31,7 +56,7 @@ impl FileCategory {
             Self::Code => "Code",
             Self::Executables => "Executables",
             Self::System => "System",
-            Self::Other => "Other",
+            Self::Other | Self::Custom(_) => "Other",
         }
     }
 }
@@ -44,71 +69,104 @@ pub struct CategoryStats {
     pub file_count: u64,
 }
 
+/// Totals for one extension, case-folded.
+#[derive(Debug, Clone)]
+pub struct ExtensionStats {
+    /// Lowercase extension without the dot; empty for files without one.
+    pub extension: CompactString,
+    pub category: FileCategory,
+    pub total_size: u64,
+    pub file_count: u64,
+    /// The largest file with this extension.
+    pub largest: NodeIndex,
+    pub largest_size: u64,
+}
+
+impl ExtensionStats {
+    /// Mean file size, rounded down.
+    pub fn average_size(&self) -> u64 {
+        self.total_size / self.file_count.max(1)
+    }
+}
+
 /// Categorise a file extension into a broad category.
 ///
-/// Zero-heap-allocation hot path: extensions are lowercased into a fixed-size
-/// stack buffer (`[u8; 16]`) rather than allocating a `String`.  File
-/// extensions longer than 16 bytes are treated as `Other`.
+/// Looks the extension up in the process-wide [`categories`] table without
+/// allocating (see [`CategoryTable::categorise`]).  File extensions longer
+/// than 16 bytes are treated as `Other`.
+///
+/// [`CategoryTable::categorise`]: crate::analysis::categories::CategoryTable::categorise
 pub fn categorise_extension(ext: &str) -> FileCategory {
-    // Fast rejection: any extension longer than 16 bytes is definitely `Other`.
-    let bytes = ext.as_bytes();
-    if bytes.len() > 16 {
-        return FileCategory::Other;
+    categories().categorise(ext)
+}
+
+/// Extension of a file name without the dot, or `""` if it has none.
+///
+/// A leading dot alone does not start an extension, so `.bashrc` has none.
+pub fn file_extension(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext,
+        _ => "",
     }
+}
 
-    // Lowercase into a stack buffer — zero heap allocation.
-    let mut lower = [0u8; 16];
-    for (dest, &src) in lower.iter_mut().zip(bytes.iter()) {
-        *dest = src.to_ascii_lowercase();
+/// Categorise a file by its name's extension.
+pub fn categorise_file_name(name: &str) -> FileCategory {
+    categorise_extension(file_extension(name))
+}
+
+/// Categorise file `index`: by its detected content type if the signature
+/// pass identified it, otherwise by its extension.
+pub fn node_category(tree: &FileTree, index: NodeIndex, detected: &DetectedTypes) -> FileCategory {
+    match detected.get(&index) {
+        Some(kind) => kind.category(),
+        None => categorise_file_name(&tree.node(index).name),
     }
-    let lower_str = match std::str::from_utf8(&lower[..bytes.len()]) {
-        Ok(s) => s,
-        Err(_) => return FileCategory::Other,
-    };
-
-    match lower_str {
-        // Documents
-        "doc" | "docx" | "pdf" | "txt" | "rtf" | "odt" | "xls" | "xlsx" | "ppt" | "pptx"
-        | "csv" | "md" | "epub" => FileCategory::Documents,
-        // Images
-        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "svg" | "webp" | "ico" | "tiff" | "tif"
-        | "psd" | "raw" | "cr2" | "nef" | "heic" | "heif" => FileCategory::Images,
-        // Video
-        "mp4" | "mkv" | "avi" | "mov" | "wmv" | "flv" | "webm" | "m4v" | "mpg" | "mpeg" | "3gp" => {
-            FileCategory::Video
-        }
-        // Audio
-        "mp3" | "wav" | "flac" | "aac" | "ogg" | "wma" | "m4a" | "opus" => FileCategory::Audio,
-        // Archives
-        "zip" | "rar" | "7z" | "tar" | "gz" | "bz2" | "xz" | "zst" | "cab" | "iso" | "dmg" => {
-            FileCategory::Archives
-        }
-        // Code
-        "rs" | "py" | "js" | "ts" | "jsx" | "tsx" | "c" | "cpp" | "h" | "hpp" | "cs" | "java"
-        | "go" | "rb" | "php" | "swift" | "kt" | "scala" | "html" | "css" | "scss" | "json"
-        | "xml" | "yaml" | "yml" | "toml" | "sql" | "sh" | "bat" | "ps1" => FileCategory::Code,
-        // Executables
-        "exe" | "msi" | "dll" | "so" | "dylib" | "app" | "com" | "scr" => FileCategory::Executables,
-        // System
-        "sys" | "drv" | "inf" | "cat" | "log" | "etl" | "dat" | "reg" | "tmp" | "bak" => {
-            FileCategory::System
+}
+
+/// Which nodes remain when the tree is filtered to one category.
+///
+/// `mask[i]` is true for every file in `category` and for every directory
+/// that contains at least one of them, so the filtered tree stays navigable
+/// from the roots down.  Files are categorised by [`node_category`].
+pub fn category_mask(
+    tree: &FileTree,
+    category: FileCategory,
+    detected: &DetectedTypes,
+) -> Vec<bool> {
+    let mut mask: Vec<bool> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| !n.is_dir && node_category(tree, NodeIndex::new(i), detected) == category)
+        .collect();
+    // Children always follow their parent in the arena, so one reverse
+    // pass carries every match up to all of its ancestors.
+    for i in (0..tree.nodes.len()).rev() {
+        if mask[i] {
+            if let Some(parent) = tree.nodes[i].parent {
+                mask[parent.idx()] = true;
+            }
         }
-        _ => FileCategory::Other,
     }
+    mask
 }
 
 /// Compute per-category size and count stats for the entire tree.
-pub fn analyse_file_types(tree: &FileTree) -> Vec<CategoryStats> {
-    // There are exactly 9 categories — pre-size to avoid rehashing.
-    let mut map: HashMap<FileCategory, CategoryStats> = HashMap::with_capacity(9);
-
-    for node in &tree.nodes {
+///
+/// Files are categorised by [`node_category`], so content detected by the
+/// signature pass overrides the extension.
+pub fn analyse_file_types(tree: &FileTree, detected: &DetectedTypes) -> Vec<CategoryStats> {
+    // Pre-size to the number of categories to avoid rehashing.
+    let mut map: HashMap<FileCategory, CategoryStats> =
+        HashMap::with_capacity(categories().categories().len());
+
+    for (i, node) in tree.nodes.iter().enumerate() {
         if node.is_dir {
             continue;
         }
 
-        let ext = node.name.rsplit('.').next().unwrap_or("");
-        let cat = categorise_extension(ext);
+        let cat = node_category(tree, NodeIndex::new(i), detected);
 
         let entry = map.entry(cat).or_insert_with(|| CategoryStats {
             category: Some(cat),
@@ -124,9 +182,59 @@ pub fn analyse_file_types(tree: &FileTree) -> Vec<CategoryStats> {
     results
 }
 
+/// Compute per-extension stats for the entire tree, largest total first.
+///
+/// Each extension is lowercased into one reused buffer and looked up by
+/// `&str`, so the pass allocates per distinct extension at most — not per
+/// file, however long the extension.  At most [`MAX_EXTENSION_STATS`]
+/// entries are returned.
+pub fn analyse_extensions(tree: &FileTree) -> Vec<ExtensionStats> {
+    let mut map: HashMap<CompactString, ExtensionStats> = HashMap::new();
+    let mut key = String::new();
+
+    for (i, node) in tree.nodes.iter().enumerate() {
+        if node.is_dir {
+            continue;
+        }
+
+        key.clear();
+        key.push_str(file_extension(&node.name));
+        key.make_ascii_lowercase();
+        if !map.contains_key(key.as_str()) {
+            let ext = CompactString::new(&key);
+            let stats = ExtensionStats {
+                extension: ext.clone(),
+                category: categorise_extension(&ext),
+                total_size: 0,
+                file_count: 0,
+                largest: NodeIndex::new(i),
+                largest_size: 0,
+            };
+            map.insert(ext, stats);
+        }
+        let entry = map.get_mut(key.as_str()).expect("inserted above");
+        if entry.file_count == 0 || node.size > entry.largest_size {
+            entry.largest = NodeIndex::new(i);
+            entry.largest_size = node.size;
+        }
+        entry.total_size += node.size;
+        entry.file_count += 1;
+    }
+
+    let mut results: Vec<ExtensionStats> = map.into_values().collect();
+    results.sort_by(|a, b| {
+        b.total_size
+            .cmp(&a.total_size)
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+    results.truncate(MAX_EXTENSION_STATS);
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::signature::DetectedType;
     use crate::model::{file_node::FileNode, FileTree};
     use compact_str::CompactString;
 
@@ -179,6 +287,101 @@ mod tests {
         assert_eq!(categorise_extension("ZIP"), FileCategory::Archives);
     }
 
+    // ── categorise_file_name ─────────────────────────────────────────────
+
+    #[test]
+    fn file_extension_ignores_leading_dot() {
+        assert_eq!(file_extension("main.rs"), "rs");
+        assert_eq!(file_extension("archive.tar.gz"), "gz");
+        assert_eq!(file_extension(".bashrc"), "");
+        assert_eq!(file_extension("Makefile"), "");
+        assert_eq!(categorise_file_name("Photo.JPG"), FileCategory::Images);
+        assert_eq!(categorise_file_name("rs"), FileCategory::Other);
+    }
+
+    // ── category_mask ────────────────────────────────────────────────────
+
+    /// Matching files and their ancestors are kept; everything else is not.
+    #[test]
+    fn category_mask_keeps_matches_and_ancestors() {
+        let mut tree = FileTree::with_capacity(6);
+        let root = tree.add_root(CompactString::new("C:"));
+        let src = tree.add_node(FileNode::new_dir(CompactString::new("src"), Some(root)));
+        tree.add_child(root, src);
+        let docs = tree.add_node(FileNode::new_dir(CompactString::new("docs"), Some(root)));
+        tree.add_child(root, docs);
+        let rs = tree.add_node(FileNode::new_file(
+            CompactString::new("main.rs"),
+            10,
+            Some(src),
+        ));
+        tree.add_child(src, rs);
+        let pdf = tree.add_node(FileNode::new_file(
+            CompactString::new("guide.pdf"),
+            10,
+            Some(docs),
+        ));
+        tree.add_child(docs, pdf);
+        tree.aggregate_sizes();
+
+        let mask = category_mask(&tree, FileCategory::Code, &DetectedTypes::new());
+        assert!(mask[root.idx()] && mask[src.idx()] && mask[rs.idx()]);
+        assert!(!mask[docs.idx()] && !mask[pdf.idx()]);
+
+        let none = category_mask(&tree, FileCategory::Video, &DetectedTypes::new());
+        assert!(none.iter().all(|&keep| !keep));
+
+        // Detected content wins over the extension.
+        let detected = DetectedTypes::from([(pdf, DetectedType::Zip)]);
+        let archives = category_mask(&tree, FileCategory::Archives, &detected);
+        assert!(archives[docs.idx()] && archives[pdf.idx()] && !archives[src.idx()]);
+    }
+
+    // ── analyse_extensions ───────────────────────────────────────────────
+
+    /// Extensions are case-folded; average and largest are per extension.
+    #[test]
+    fn analyse_extensions_folds_case() {
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("C:"));
+        let mut largest = None;
+        let files = [
+            ("a.JPG", 300),
+            ("b.jpg", 100),
+            ("notes", 50),
+            ("c.Backup-Before-Migration-2024", 20),
+            ("d.backup-before-migration-2024", 10),
+        ];
+        for (name, size) in files {
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new(name),
+                size,
+                Some(root),
+            ));
+            tree.add_child(root, f);
+            if size == 300 {
+                largest = Some(f);
+            }
+        }
+        tree.aggregate_sizes();
+
+        let stats = analyse_extensions(&tree);
+        assert_eq!(stats.len(), 3);
+        let jpg = &stats[0];
+        assert_eq!(jpg.extension, "jpg");
+        assert_eq!(jpg.category, FileCategory::Images);
+        assert_eq!(jpg.file_count, 2);
+        assert_eq!(jpg.total_size, 400);
+        assert_eq!(jpg.average_size(), 200);
+        assert_eq!(Some(jpg.largest), largest);
+        assert_eq!(jpg.largest_size, 300);
+        assert_eq!(stats[1].extension, "");
+        assert_eq!(stats[1].category, FileCategory::Other);
+        // Longer than the inline buffer, still folded into one entry.
+        assert_eq!(stats[2].extension, "backup-before-migration-2024");
+        assert_eq!((stats[2].file_count, stats[2].total_size), (2, 30));
+    }
+
     // ── analyse_file_types ───────────────────────────────────────────────
 
     /// A tree with two .rs files and one .png file should produce two
@@ -211,7 +414,7 @@ mod tests {
 
         tree.aggregate_sizes();
 
-        let stats = analyse_file_types(&tree);
+        let stats = analyse_file_types(&tree, &DetectedTypes::new());
 
         // Find Code and Images entries.
         let code = stats
@@ -238,7 +441,7 @@ mod tests {
         tree.add_child(root, dir);
         tree.aggregate_sizes();
 
-        let stats = analyse_file_types(&tree);
+        let stats = analyse_file_types(&tree, &DetectedTypes::new());
         // The tree has only a root dir and one child dir — no files.
         assert!(
             stats.is_empty(),
@@ -250,7 +453,7 @@ mod tests {
     #[test]
     fn analyse_empty_tree() {
         let tree = FileTree::with_capacity(0);
-        let stats = analyse_file_types(&tree);
+        let stats = analyse_file_types(&tree, &DetectedTypes::new());
         assert!(stats.is_empty());
     }
 
@@ -279,7 +482,7 @@ mod tests {
 
         tree.aggregate_sizes();
 
-        let stats = analyse_file_types(&tree);
+        let stats = analyse_file_types(&tree, &DetectedTypes::new());
         assert!(stats.len() >= 2);
         assert!(
             stats[0].total_size >= stats[1].total_size,
@@ -0,0 +1,220 @@
+/// File-count hotspots — directories holding huge numbers of small files.
+///
+/// Size-based views hide folders that burn MFT records / inodes and make
+/// backups crawl.  [`analyse_hotspots`] makes one pass over the arena,
+/// totalling for every directory its own files, all files below it, and the
+/// allocation slack those files waste (cluster size minus the tail of the
+/// last cluster), then ranks the directories by the chosen [`HotspotSort`].
+use crate::model::{FileNode, FileTree, NodeIndex};
+
+/// Cluster size assumed for slack estimates: the NTFS / ext4 default.
+pub const DEFAULT_CLUSTER_SIZE: u64 = 4096;
+
+/// Most directories returned by [`analyse_hotspots`].
+pub const MAX_HOTSPOTS: usize = 200;
+
+/// Fewest files below a directory for it to be ranked by average size;
+/// otherwise a folder holding one tiny file would top the list.
+pub const HOTSPOT_MIN_FILES: u64 = 100;
+
+/// How [`analyse_hotspots`] ranks directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HotspotSort {
+    /// Files directly inside, most first.
+    DirectFiles,
+    /// Files anywhere below, most first.
+    #[default]
+    Files,
+    /// Average size of the files below, smallest first.
+    AverageSize,
+    /// Estimated slack of the files below, most first.
+    Slack,
+}
+
+impl HotspotSort {
+    /// Every ranking, in table column order.
+    pub const ALL: [HotspotSort; 4] = [
+        Self::DirectFiles,
+        Self::Files,
+        Self::AverageSize,
+        Self::Slack,
+    ];
+
+    /// Column header for the ranking.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DirectFiles => "Files here",
+            Self::Files => "Files below",
+            Self::AverageSize => "Avg size",
+            Self::Slack => "Slack",
+        }
+    }
+}
+
+/// File counts and slack for one directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotspot {
+    pub index: NodeIndex,
+    /// Files directly inside.
+    pub direct_files: u64,
+    /// Files anywhere below.
+    pub files: u64,
+    /// Bytes of the files below.
+    pub bytes: u64,
+    /// Estimated bytes lost to cluster rounding by the files below.
+    pub slack: u64,
+}
+
+impl Hotspot {
+    /// Average size of the files below; 0 for a folder without files.
+    pub fn average_size(&self) -> u64 {
+        self.bytes.checked_div(self.files).unwrap_or(0)
+    }
+}
+
+/// Bytes lost to rounding a file of `size` up to whole clusters.
+pub fn cluster_slack(size: u64, cluster_size: u64) -> u64 {
+    if cluster_size == 0 {
+        return 0;
+    }
+    match size % cluster_size {
+        0 => 0,
+        tail => cluster_size - tail,
+    }
+}
+
+/// Bytes the filesystem keeps for `node` before cluster rounding: the
+/// logical size, or less for compressed and sparse files.
+pub(crate) fn stored_bytes(node: &FileNode) -> u64 {
+    node.size.min(node.allocated_size)
+}
+
+/// Rank the directories of `tree` by `sort`, returning at most
+/// [`MAX_HOTSPOTS`].
+///
+/// Slack is estimated from each file's [`stored_bytes`] with `cluster_size`,
+/// as the Slack window does; tiny files
+/// stored inside the MFT record on NTFS are counted as if they took a
+/// cluster, so the estimate is an upper bound there.
+pub fn analyse_hotspots(tree: &FileTree, sort: HotspotSort, cluster_size: u64) -> Vec<Hotspot> {
+    let len = tree.nodes.len();
+    let mut direct = vec![0u64; len];
+    let mut slack = vec![0u64; len];
+    // Children follow their parent in the arena, so a reverse pass sees
+    // every child before its parent.
+    for i in (0..len).rev() {
+        let node = &tree.nodes[i];
+        let Some(parent) = node.parent else {
+            continue;
+        };
+        if node.is_dir {
+            slack[parent.idx()] += slack[i];
+        } else if !node.is_error {
+            direct[parent.idx()] += 1;
+            slack[parent.idx()] += cluster_slack(stored_bytes(node), cluster_size);
+        }
+    }
+
+    let mut hotspots: Vec<Hotspot> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.is_dir && node.descendant_count > 0)
+        .filter(|(_, node)| {
+            sort != HotspotSort::AverageSize || node.descendant_count >= HOTSPOT_MIN_FILES
+        })
+        .map(|(i, node)| Hotspot {
+            index: NodeIndex::new(i),
+            direct_files: direct[i],
+            files: node.descendant_count,
+            bytes: node.size,
+            slack: slack[i],
+        })
+        .collect();
+
+    let by_key = |a: &Hotspot, b: &Hotspot| {
+        match sort {
+            HotspotSort::DirectFiles => b.direct_files.cmp(&a.direct_files),
+            HotspotSort::Files => b.files.cmp(&a.files),
+            HotspotSort::AverageSize => a.average_size().cmp(&b.average_size()),
+            HotspotSort::Slack => b.slack.cmp(&a.slack),
+        }
+        .then(a.index.cmp(&b.index))
+    };
+    if hotspots.len() > MAX_HOTSPOTS {
+        hotspots.select_nth_unstable_by(MAX_HOTSPOTS - 1, by_key);
+        hotspots.truncate(MAX_HOTSPOTS);
+    }
+    hotspots.sort_unstable_by(by_key);
+    hotspots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+
+    fn files(tree: &mut FileTree, parent: NodeIndex, count: usize, size: u64) {
+        for i in 0..count {
+            tree.add_test_file(parent, &format!("f{i}"), size);
+        }
+    }
+
+    #[test]
+    fn slack_rounds_up_to_whole_clusters() {
+        assert_eq!(cluster_slack(0, 4096), 0);
+        assert_eq!(cluster_slack(1, 4096), 4095);
+        assert_eq!(cluster_slack(4096, 4096), 0);
+        assert_eq!(cluster_slack(5000, 4096), 3192);
+        assert_eq!(cluster_slack(5000, 0), 0);
+    }
+
+    /// Each ranking orders directories by its own measure.
+    #[test]
+    fn ranks_by_count_average_and_slack() {
+        let mut tree = FileTree::with_capacity(512);
+        let root = tree.add_root(CompactString::new("C:"));
+        let cache = tree.add_test_dir(root, "cache");
+        let shards = tree.add_test_dir(cache, "shards");
+        files(&mut tree, shards, 150, 100);
+        files(&mut tree, cache, 10, 10);
+        let media = tree.add_test_dir(root, "media");
+        files(&mut tree, media, 120, 1_000_000);
+        tree.aggregate_sizes();
+
+        let by_files = analyse_hotspots(&tree, HotspotSort::Files, DEFAULT_CLUSTER_SIZE);
+        let order: Vec<_> = by_files.iter().map(|h| h.index).collect();
+        assert_eq!(order, [root, cache, shards, media]);
+        assert_eq!((by_files[1].direct_files, by_files[1].files), (10, 160));
+
+        let direct = analyse_hotspots(&tree, HotspotSort::DirectFiles, DEFAULT_CLUSTER_SIZE);
+        assert_eq!(direct[0].index, shards);
+        assert_eq!(direct[0].slack, 150 * 3996);
+
+        let avg = analyse_hotspots(&tree, HotspotSort::AverageSize, DEFAULT_CLUSTER_SIZE);
+        let order: Vec<_> = avg.iter().map(|h| h.index).collect();
+        assert_eq!(order, [cache, shards, root, media]);
+        assert_eq!(avg[0].average_size(), (150 * 100 + 10 * 10) / 160);
+
+        let slack = analyse_hotspots(&tree, HotspotSort::Slack, DEFAULT_CLUSTER_SIZE);
+        assert_eq!(slack[0].index, root);
+        assert_eq!(slack[1].index, cache);
+        assert_eq!(slack[1].slack, 150 * 3996 + 10 * 4086);
+    }
+
+    /// Compressed files are rounded from their allocation, matching
+    /// [`analyse_slack`](crate::analysis::analyse_slack).
+    #[test]
+    fn slack_uses_stored_bytes() {
+        let mut tree = FileTree::with_capacity(4);
+        let root = tree.add_root(CompactString::new("C:"));
+        let file = tree.add_test_file(root, "packed.log", 10_000);
+        tree.nodes[file.idx()].allocated_size = 4_000;
+        tree.aggregate_sizes();
+
+        let hotspots = analyse_hotspots(&tree, HotspotSort::Slack, DEFAULT_CLUSTER_SIZE);
+        let slack = crate::analysis::analyse_slack(&tree, DEFAULT_CLUSTER_SIZE);
+        assert_eq!(hotspots[0].slack, 96);
+        assert_eq!(hotspots[0].slack, slack.actual.slack);
+    }
+}
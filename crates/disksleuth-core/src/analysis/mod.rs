@@ -1,8 +1,64 @@
 /// Analysis modules — post-scan algorithms for insights.
 pub mod age;
+pub mod artefacts;
+pub mod cache_locations;
+pub mod categories;
+pub mod compression;
 pub mod duplicates;
+pub mod empty;
 pub mod file_types;
+pub mod hotspots;
+pub mod owners;
+pub mod signature;
+pub mod size_distribution;
+pub mod slack;
+pub mod top_dirs;
 pub mod top_files;
+pub mod worker;
 
-pub use file_types::{analyse_file_types, categorise_extension, CategoryStats, FileCategory};
-pub use top_files::{top_files, LargestFile};
+pub use age::{
+    analyse_age, find_stale_files, start_age_analysis, AgeBasis, AgeBucket, AgeHandle,
+    AgeHistogram, AgeMessage, AgeReport, StaleFile,
+};
+pub use artefacts::{
+    find_artefacts, start_artefact_analysis, Artefact, ArtefactHandle, ArtefactMessage,
+    ArtefactReport, ArtefactRule, ARTEFACT_RULES, MAX_ARTEFACTS,
+};
+pub use cache_locations::{
+    find_cache_locations, find_cache_locations_here, Base, CacheLocation, CacheReport, CacheRule,
+    Platform, Safety, CACHE_RULES,
+};
+pub use categories::{
+    categories, install_categories, CategoryConfig, CategoryConfigError, CategoryInfo,
+    CategoryTable,
+};
+pub use compression::{
+    estimate_compression, sample_file, start_compression_estimate, CompressionHandle,
+    CompressionMessage, CompressionReport, DirEstimate, Estimate, ExtensionEstimate,
+    MAX_COMPRESSION_ROWS, SAMPLES_PER_STRATUM,
+};
+pub use empty::{find_empty, EmptyGroup, EmptyReport, MAX_EMPTY_ITEMS};
+pub use file_types::{
+    analyse_extensions, analyse_file_types, categorise_extension, categorise_file_name,
+    category_mask, file_extension, node_category, CategoryStats, ExtensionStats, FileCategory,
+    MAX_EXTENSION_STATS,
+};
+pub use hotspots::{
+    analyse_hotspots, cluster_slack, Hotspot, HotspotSort, DEFAULT_CLUSTER_SIZE, HOTSPOT_MIN_FILES,
+    MAX_HOTSPOTS,
+};
+pub use owners::{
+    analyse_owners, start_owner_analysis, OwnerDir, OwnerHandle, OwnerMessage, OwnerReport,
+    OwnerUsage, MAX_OWNER_DIRS,
+};
+pub use signature::{
+    detect_file, detect_header, detect_signatures, start_signature_scan, DetectedType,
+    DetectedTypes, SignatureHandle, SignatureMessage, SignatureReport, DEFAULT_SIGNATURE_MIN_SIZE,
+};
+pub use size_distribution::{analyse_sizes, size_bucket, SizeBucket, SizeHistogram, SIZE_BUCKETS};
+pub use slack::{
+    analyse_slack, DirSlack, SlackProjection, SlackReport, CANDIDATE_CLUSTER_SIZES, MAX_SLACK_DIRS,
+};
+pub use top_dirs::{largest_dirs, DirRank, LargestDir, MAX_LARGEST_DIRS};
+pub use top_files::{top_files, top_files_with_extension, LargestFile};
+pub use worker::WorkerHandle;
@@ -0,0 +1,213 @@
+/// Per-owner usage — bytes and files per file owner.
+///
+/// [`analyse_owners`] totals every file under the owner the scanner recorded
+/// for it (see [`FileNode::owner`](crate::model::FileNode::owner)) and, for
+/// drill-down, ranks each owner's directories by the bytes of that owner's
+/// files directly inside them.  Owner names come from the platform account
+/// database, which can be slow for domain accounts, so
+/// [`start_owner_analysis`] runs the whole analysis on a background thread.
+use crate::analysis::worker::{spawn_oneshot, WorkerHandle};
+use crate::model::{FileTree, NodeIndex, NodeRemap, Owner, OwnerId};
+use crate::platform::owner_name;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Number of nodes visited between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
+/// Most directories listed per owner.
+pub const MAX_OWNER_DIRS: usize = 25;
+
+/// A directory holding files of one owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerDir {
+    pub index: NodeIndex,
+    /// The owner's files directly inside.
+    pub files: u64,
+    /// Bytes of those files.
+    pub bytes: u64,
+}
+
+/// Usage of one owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerUsage {
+    /// `None` for files whose owner could not be read.
+    pub owner: Option<Owner>,
+    /// Account name, if the account database knows the owner.
+    pub name: Option<String>,
+    pub files: u64,
+    pub bytes: u64,
+    /// Up to [`MAX_OWNER_DIRS`] directories, most of the owner's bytes first.
+    pub dirs: Vec<OwnerDir>,
+}
+
+impl OwnerUsage {
+    /// Account name, else the raw uid or SID, else "Unknown owner".
+    pub fn label(&self) -> String {
+        match (&self.name, &self.owner) {
+            (Some(name), _) => name.clone(),
+            (None, Some(owner)) => owner.to_string(),
+            (None, None) => "Unknown owner".to_string(),
+        }
+    }
+}
+
+/// Result of [`analyse_owners`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnerReport {
+    /// Every owner holding files, most bytes first.
+    pub owners: Vec<OwnerUsage>,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+impl OwnerReport {
+    /// Translate directory indices after [`FileTree::without_subtrees`],
+    /// dropping removed directories.  Totals are left as they were until the
+    /// analysis runs again on the new tree.
+    pub fn remap(&mut self, remap: &NodeRemap) {
+        for usage in &mut self.owners {
+            usage.dirs.retain_mut(|d| match remap.get(d.index) {
+                Some(n) => {
+                    d.index = n;
+                    true
+                }
+                None => false,
+            });
+        }
+    }
+}
+
+/// Total the files of `tree` per owner, naming owners with `resolve`.
+///
+/// Returns `None` if `cancel` is set before the analysis finishes.
+pub fn analyse_owners(
+    tree: &FileTree,
+    cancel: &AtomicBool,
+    resolve: impl Fn(&Owner) -> Option<String>,
+) -> Option<OwnerReport> {
+    // (owner, parent directory) → (files, bytes) of that owner's files.
+    let mut per_dir: HashMap<(OwnerId, NodeIndex), (u64, u64)> = HashMap::new();
+    for (i, node) in tree.nodes.iter().enumerate() {
+        if i.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if node.is_dir || node.is_error {
+            continue;
+        }
+        let Some(parent) = node.parent else {
+            continue;
+        };
+        let entry = per_dir.entry((node.owner, parent)).or_default();
+        entry.0 += 1;
+        entry.1 += node.size;
+    }
+
+    let mut by_owner: HashMap<OwnerId, Vec<OwnerDir>> = HashMap::new();
+    for ((owner, index), (files, bytes)) in per_dir {
+        by_owner.entry(owner).or_default().push(OwnerDir {
+            index,
+            files,
+            bytes,
+        });
+    }
+
+    let mut report = OwnerReport::default();
+    for (id, mut dirs) in by_owner {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let files = dirs.iter().map(|d| d.files).sum();
+        let bytes = dirs.iter().map(|d| d.bytes).sum();
+        dirs.sort_unstable_by(|a, b| b.bytes.cmp(&a.bytes).then(a.index.cmp(&b.index)));
+        dirs.truncate(MAX_OWNER_DIRS);
+        let owner = tree.owners.get(id).cloned();
+        let name = owner.as_ref().and_then(&resolve);
+        report.files += files;
+        report.bytes += bytes;
+        report.owners.push(OwnerUsage {
+            owner,
+            name,
+            files,
+            bytes,
+            dirs,
+        });
+    }
+    report.owners.sort_unstable_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then(b.files.cmp(&a.files))
+            .then_with(|| a.label().cmp(&b.label()))
+    });
+    Some(report)
+}
+
+/// Messages sent from the owner analysis thread to the UI.
+#[derive(Debug)]
+pub enum OwnerMessage {
+    /// The analysis finished.
+    Complete(OwnerReport),
+    /// The analysis was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed owner analysis; receives exactly one
+/// [`OwnerMessage`] when it ends.
+pub type OwnerHandle = WorkerHandle<OwnerMessage>;
+
+/// Run [`analyse_owners`] on a background thread, naming owners with
+/// [`owner_name`].
+pub fn start_owner_analysis(tree: Arc<FileTree>) -> OwnerHandle {
+    spawn_oneshot("disksleuth-owners", move |cancel| {
+        match analyse_owners(&tree, cancel, owner_name) {
+            Some(report) => OwnerMessage::Complete(report),
+            None => OwnerMessage::Cancelled,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::file_node::FileNode;
+    use compact_str::CompactString;
+
+    fn file(tree: &mut FileTree, parent: NodeIndex, size: u64, owner: OwnerId) {
+        let mut node = FileNode::new_file(CompactString::new("f"), size, Some(parent));
+        node.owner = owner;
+        let idx = tree.add_node(node);
+        tree.add_child(parent, idx);
+    }
+
+    /// Totals per owner, drill-down by own bytes, and unknown owners.
+    #[test]
+    fn totals_and_ranks_dirs_per_owner() {
+        let mut tree = FileTree::with_capacity(16);
+        let alice = tree.owners.intern(Owner::Uid(1000));
+        let root_user = tree.owners.intern(Owner::Uid(0));
+        let root = tree.add_root(CompactString::new("/"));
+        let home = tree.add_node(FileNode::new_dir(CompactString::new("home"), Some(root)));
+        tree.add_child(root, home);
+        file(&mut tree, home, 3_000, alice);
+        file(&mut tree, home, 2_000, alice);
+        file(&mut tree, root, 4_000, alice);
+        file(&mut tree, root, 1_000, root_user);
+        file(&mut tree, home, 100, OwnerId::UNKNOWN);
+        tree.aggregate_sizes();
+
+        let names = |o: &Owner| (*o == Owner::Uid(1000)).then(|| "alice".to_string());
+        let report = analyse_owners(&tree, &AtomicBool::new(false), names).unwrap();
+        assert_eq!((report.files, report.bytes), (5, 10_100));
+        let labels: Vec<_> = report.owners.iter().map(|o| o.label()).collect();
+        assert_eq!(labels, ["alice", "uid 0", "Unknown owner"]);
+
+        let a = &report.owners[0];
+        assert_eq!((a.files, a.bytes), (3, 9_000));
+        let dirs: Vec<_> = a.dirs.iter().map(|d| (d.index, d.files, d.bytes)).collect();
+        assert_eq!(dirs, [(home, 2, 5_000), (root, 1, 4_000)]);
+        assert_eq!(report.owners[2].owner, None);
+
+        assert!(analyse_owners(&tree, &AtomicBool::new(true), names).is_none());
+    }
+}
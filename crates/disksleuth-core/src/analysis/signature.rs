@@ -0,0 +1,475 @@
+/// Content-based file type detection ("magic bytes").
+///
+/// Extensions lie: extensionless files, renamed archives and multi-gigabyte
+/// `.dat` / `.bin` blobs all land in the wrong category.  This optional pass
+/// reads the first [`SIGNATURE_HEADER_LEN`] bytes of every file above a size
+/// threshold and matches them against known signatures (archives, disk
+/// images, databases, executables, media containers).  Two formats need more
+/// than the header: ISO 9660 (volume descriptor at 32 KiB) and fixed VHD
+/// (footer in the last 512 bytes).
+///
+/// The pass runs on a background thread via [`start_signature_scan`]; the
+/// result is a [`SignatureReport`] whose `detected` map takes precedence over
+/// the extension wherever files are categorised
+/// ([`crate::analysis::file_types::node_category`]).
+use crate::analysis::file_types::{categorise_file_name, FileCategory};
+use crate::analysis::worker::{spawn_worker, WorkerHandle};
+use crate::model::{FileTree, NodeIndex, NodeRemap};
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Bytes read from the start of each file.
+pub const SIGNATURE_HEADER_LEN: usize = 4096;
+
+/// Files smaller than this are not read by default.
+pub const DEFAULT_SIGNATURE_MIN_SIZE: u64 = 1024 * 1024;
+
+/// Most files read in one pass; the largest are read first.
+pub const MAX_SIGNATURE_FILES: usize = 200_000;
+
+/// Files read between progress messages and cancellation checks.
+const SIGNATURE_CHUNK: usize = 256;
+
+/// Offset of the ISO 9660 primary volume descriptor's "CD001" tag.
+const ISO_TAG_OFFSET: u64 = 0x8001;
+
+/// A file type identified from its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectedType {
+    Zip,
+    SevenZip,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+    Rar,
+    Tar,
+    Cab,
+    Iso,
+    Vhd,
+    Vhdx,
+    Qcow2,
+    Vmdk,
+    Sqlite,
+    /// Windows PE executable or DLL.
+    Pe,
+    Elf,
+    MachO,
+    /// OLE compound file: MSI installers, legacy Office documents.
+    OleCompound,
+    Pdf,
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    /// ISO base media: MP4, MOV, M4A, HEIC.
+    IsoMedia,
+    /// Matroska / WebM.
+    Matroska,
+    Avi,
+    Wav,
+    /// MP3 with an ID3 tag.
+    Mp3,
+    Flac,
+    Ogg,
+}
+
+impl DetectedType {
+    /// Human-readable name for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Zip => "ZIP archive",
+            Self::SevenZip => "7-Zip archive",
+            Self::Gzip => "gzip data",
+            Self::Bzip2 => "bzip2 data",
+            Self::Xz => "xz data",
+            Self::Zstd => "Zstandard data",
+            Self::Rar => "RAR archive",
+            Self::Tar => "tar archive",
+            Self::Cab => "Cabinet archive",
+            Self::Iso => "ISO 9660 image",
+            Self::Vhd => "VHD disk image",
+            Self::Vhdx => "VHDX disk image",
+            Self::Qcow2 => "QCOW2 disk image",
+            Self::Vmdk => "VMDK disk image",
+            Self::Sqlite => "SQLite database",
+            Self::Pe => "Windows executable",
+            Self::Elf => "ELF executable",
+            Self::MachO => "Mach-O executable",
+            Self::OleCompound => "OLE compound file",
+            Self::Pdf => "PDF document",
+            Self::Png => "PNG image",
+            Self::Jpeg => "JPEG image",
+            Self::Gif => "GIF image",
+            Self::Webp => "WebP image",
+            Self::IsoMedia => "MP4 / QuickTime media",
+            Self::Matroska => "Matroska / WebM media",
+            Self::Avi => "AVI video",
+            Self::Wav => "WAV audio",
+            Self::Mp3 => "MP3 audio",
+            Self::Flac => "FLAC audio",
+            Self::Ogg => "Ogg media",
+        }
+    }
+
+    /// The file category this content belongs to.
+    pub fn category(self) -> FileCategory {
+        match self {
+            Self::Zip
+            | Self::SevenZip
+            | Self::Gzip
+            | Self::Bzip2
+            | Self::Xz
+            | Self::Zstd
+            | Self::Rar
+            | Self::Tar
+            | Self::Cab
+            | Self::Iso
+            | Self::Vhd
+            | Self::Vhdx
+            | Self::Qcow2
+            | Self::Vmdk => FileCategory::Archives,
+            Self::Sqlite => FileCategory::System,
+            Self::Pe | Self::Elf | Self::MachO => FileCategory::Executables,
+            Self::OleCompound | Self::Pdf => FileCategory::Documents,
+            Self::Png | Self::Jpeg | Self::Gif | Self::Webp => FileCategory::Images,
+            Self::IsoMedia | Self::Matroska | Self::Avi => FileCategory::Video,
+            Self::Wav | Self::Mp3 | Self::Flac | Self::Ogg => FileCategory::Audio,
+        }
+    }
+}
+
+/// Identify a file from its first bytes.
+///
+/// `header` should be the first [`SIGNATURE_HEADER_LEN`] bytes (or the
+/// whole file if shorter).  Formats identified only by data further in
+/// are handled by [`detect_file`].
+pub fn detect_header(header: &[u8]) -> Option<DetectedType> {
+    let at = |offset: usize, magic: &[u8]| header.get(offset..offset + magic.len()) == Some(magic);
+    let riff = |kind: &[u8]| at(0, b"RIFF") && at(8, kind);
+
+    let detected = if at(0, b"PK\x03\x04") || at(0, b"PK\x05\x06") {
+        DetectedType::Zip
+    } else if at(0, b"7z\xBC\xAF\x27\x1C") {
+        DetectedType::SevenZip
+    } else if at(0, b"\x1F\x8B") {
+        DetectedType::Gzip
+    } else if at(0, b"BZh") {
+        DetectedType::Bzip2
+    } else if at(0, b"\xFD7zXZ\x00") {
+        DetectedType::Xz
+    } else if at(0, b"\x28\xB5\x2F\xFD") {
+        DetectedType::Zstd
+    } else if at(0, b"Rar!\x1A\x07") {
+        DetectedType::Rar
+    } else if at(257, b"ustar") {
+        DetectedType::Tar
+    } else if at(0, b"MSCF") {
+        DetectedType::Cab
+    } else if at(0, b"conectix") {
+        DetectedType::Vhd
+    } else if at(0, b"vhdxfile") {
+        DetectedType::Vhdx
+    } else if at(0, b"QFI\xFB") {
+        DetectedType::Qcow2
+    } else if at(0, b"KDMV") || at(0, b"# Disk DescriptorFile") {
+        DetectedType::Vmdk
+    } else if at(0, b"SQLite format 3\x00") {
+        DetectedType::Sqlite
+    } else if at(0, b"MZ") {
+        DetectedType::Pe
+    } else if at(0, b"\x7FELF") {
+        DetectedType::Elf
+    } else if at(0, b"\xFE\xED\xFA\xCE")
+        || at(0, b"\xFE\xED\xFA\xCF")
+        || at(0, b"\xCE\xFA\xED\xFE")
+        || at(0, b"\xCF\xFA\xED\xFE")
+    {
+        DetectedType::MachO
+    } else if at(0, b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1") {
+        DetectedType::OleCompound
+    } else if at(0, b"%PDF-") {
+        DetectedType::Pdf
+    } else if at(0, b"\x89PNG\r\n\x1A\n") {
+        DetectedType::Png
+    } else if at(0, b"\xFF\xD8\xFF") {
+        DetectedType::Jpeg
+    } else if at(0, b"GIF87a") || at(0, b"GIF89a") {
+        DetectedType::Gif
+    } else if riff(b"WEBP") {
+        DetectedType::Webp
+    } else if riff(b"AVI ") {
+        DetectedType::Avi
+    } else if riff(b"WAVE") {
+        DetectedType::Wav
+    } else if at(4, b"ftyp") {
+        DetectedType::IsoMedia
+    } else if at(0, b"\x1A\x45\xDF\xA3") {
+        DetectedType::Matroska
+    } else if at(0, b"ID3") {
+        DetectedType::Mp3
+    } else if at(0, b"fLaC") {
+        DetectedType::Flac
+    } else if at(0, b"OggS") {
+        DetectedType::Ogg
+    } else {
+        return None;
+    };
+    Some(detected)
+}
+
+/// Identify the file at `path`, `size` bytes long.
+///
+/// Reads the header, then — only if that matched nothing — the ISO 9660
+/// descriptor tag and the VHD footer.
+pub fn detect_file(path: &Path, size: u64) -> io::Result<Option<DetectedType>> {
+    let mut file = File::open(path)?;
+    let mut header = Vec::with_capacity(SIGNATURE_HEADER_LEN);
+    (&mut file)
+        .take(SIGNATURE_HEADER_LEN as u64)
+        .read_to_end(&mut header)?;
+    if let Some(detected) = detect_header(&header) {
+        return Ok(Some(detected));
+    }
+
+    let mut tag = [0u8; 8];
+    if size >= ISO_TAG_OFFSET + 5 {
+        file.seek(SeekFrom::Start(ISO_TAG_OFFSET))?;
+        file.read_exact(&mut tag[..5])?;
+        if &tag[..5] == b"CD001" {
+            return Ok(Some(DetectedType::Iso));
+        }
+    }
+    if size >= 512 {
+        file.seek(SeekFrom::Start(size - 512))?;
+        file.read_exact(&mut tag)?;
+        if &tag == b"conectix" {
+            return Ok(Some(DetectedType::Vhd));
+        }
+    }
+    Ok(None)
+}
+
+/// Detected content types by node.
+pub type DetectedTypes = HashMap<NodeIndex, DetectedType>;
+
+/// Outcome of a signature pass.
+#[derive(Debug, Default, Clone)]
+pub struct SignatureReport {
+    /// Files whose content matched a signature.
+    pub detected: DetectedTypes,
+    /// Files read (matched or not).
+    pub files_checked: u64,
+    /// Files that could not be opened or read.
+    pub unreadable: u64,
+    /// Detected files whose content category differs from their extension's.
+    pub mismatched: u64,
+    /// `true` if more files qualified than [`MAX_SIGNATURE_FILES`].
+    pub truncated: bool,
+    /// Size threshold the pass ran with.
+    pub min_size: u64,
+}
+
+impl SignatureReport {
+    /// Translate the report to `tree`, the result of
+    /// [`FileTree::without_subtrees`]: removed files are dropped and the
+    /// mismatch count is recomputed.
+    pub fn remap(&mut self, tree: &FileTree, remap: &NodeRemap) {
+        self.detected = self
+            .detected
+            .iter()
+            .filter_map(|(&node, &kind)| Some((remap.get(node)?, kind)))
+            .collect();
+        self.mismatched = self
+            .detected
+            .iter()
+            .filter(|(&node, kind)| categorise_file_name(&tree.node(node).name) != kind.category())
+            .count() as u64;
+    }
+}
+
+/// Read every file of at least `min_size` bytes (largest first, at most
+/// [`MAX_SIGNATURE_FILES`]) and identify it.
+///
+/// `progress(checked, total)` is called after each chunk.  Returns `None`
+/// if `cancel` was set.
+pub fn detect_signatures(
+    tree: &FileTree,
+    min_size: u64,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(u64, u64),
+) -> Option<SignatureReport> {
+    let mut candidates: Vec<NodeIndex> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| !n.is_dir && !n.is_error && n.size >= min_size)
+        .map(|(i, _)| NodeIndex::new(i))
+        .collect();
+    candidates.sort_unstable_by_key(|&i| Reverse(tree.node(i).size));
+    let truncated = candidates.len() > MAX_SIGNATURE_FILES;
+    candidates.truncate(MAX_SIGNATURE_FILES);
+
+    let total = candidates.len() as u64;
+    let unreadable = AtomicU64::new(0);
+    let mut report = SignatureReport {
+        truncated,
+        min_size,
+        ..SignatureReport::default()
+    };
+    for chunk in candidates.chunks(SIGNATURE_CHUNK) {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let found: Vec<(NodeIndex, DetectedType)> = chunk
+            .par_iter()
+            .filter_map(|&idx| {
+                let result = match tree.fs_path(idx) {
+                    Some(path) => detect_file(&path, tree.node(idx).size),
+                    None => Err(io::ErrorKind::NotFound.into()),
+                };
+                match result {
+                    Ok(detected) => detected.map(|d| (idx, d)),
+                    Err(_) => {
+                        unreadable.fetch_add(1, Ordering::Relaxed);
+                        None
+                    }
+                }
+            })
+            .collect();
+        for (idx, detected) in found {
+            if categorise_file_name(&tree.node(idx).name) != detected.category() {
+                report.mismatched += 1;
+            }
+            report.detected.insert(idx, detected);
+        }
+        report.files_checked += chunk.len() as u64;
+        progress(report.files_checked, total);
+    }
+    report.unreadable = unreadable.into_inner();
+    Some(report)
+}
+
+/// Messages sent from the signature thread to the UI.
+#[derive(Debug)]
+pub enum SignatureMessage {
+    /// Files read so far, of the total to read.
+    Progress { checked: u64, total: u64 },
+    /// The pass finished.
+    Complete(SignatureReport),
+    /// The pass was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed signature pass; receives progress, then
+/// exactly one `Complete` or `Cancelled`.
+pub type SignatureHandle = WorkerHandle<SignatureMessage>;
+
+/// Run [`detect_signatures`] on a background thread.
+pub fn start_signature_scan(tree: Arc<FileTree>, min_size: u64) -> SignatureHandle {
+    spawn_worker("disksleuth-signature", 16, move |tx, cancel| {
+        let report = detect_signatures(&tree, min_size, cancel, |checked, total| {
+            let _ = tx.try_send(SignatureMessage::Progress { checked, total });
+        });
+        let msg = match report {
+            Some(report) => SignatureMessage::Complete(report),
+            None => SignatureMessage::Cancelled,
+        };
+        let _ = tx.send(msg);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::file_node::FileNode;
+    use compact_str::CompactString;
+
+    #[test]
+    fn detect_header_recognises_common_signatures() {
+        let cases: [(&[u8], DetectedType); 8] = [
+            (b"PK\x03\x04rest", DetectedType::Zip),
+            (b"7z\xBC\xAF\x27\x1C\x00\x04", DetectedType::SevenZip),
+            (b"\x28\xB5\x2F\xFD\x00", DetectedType::Zstd),
+            (b"vhdxfile\x00", DetectedType::Vhdx),
+            (b"SQLite format 3\x00....", DetectedType::Sqlite),
+            (b"MZ\x90\x00", DetectedType::Pe),
+            (b"\x00\x00\x00\x18ftypmp42", DetectedType::IsoMedia),
+            (b"RIFF\x00\x00\x00\x00WAVEfmt ", DetectedType::Wav),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(detect_header(header), Some(expected), "{expected:?}");
+        }
+        assert_eq!(detect_header(b"hello world"), None);
+        assert_eq!(detect_header(b""), None);
+
+        let mut tar = vec![0u8; 512];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(detect_header(&tar), Some(DetectedType::Tar));
+    }
+
+    /// ISO and fixed VHD are found beyond the header.
+    #[test]
+    fn detect_file_reads_iso_tag_and_vhd_footer() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let iso = dir.path().join("image.bin");
+        let mut data = vec![0u8; 0x9000];
+        data[0x8001..0x8006].copy_from_slice(b"CD001");
+        std::fs::write(&iso, &data).unwrap();
+        assert_eq!(
+            detect_file(&iso, data.len() as u64).unwrap(),
+            Some(DetectedType::Iso)
+        );
+
+        let vhd = dir.path().join("disk");
+        let mut data = vec![0u8; 2048];
+        data[2048 - 512..2048 - 504].copy_from_slice(b"conectix");
+        std::fs::write(&vhd, &data).unwrap();
+        assert_eq!(
+            detect_file(&vhd, data.len() as u64).unwrap(),
+            Some(DetectedType::Vhd)
+        );
+    }
+
+    /// The pass skips small files, records matches and counts mismatches.
+    #[test]
+    fn detect_signatures_over_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("blob.dat"), b"PK\x03\x04 zipped").unwrap();
+        std::fs::write(dir.path().join("small.zip"), b"PK\x03\x04").unwrap();
+
+        let mut tree = FileTree::with_capacity(3);
+        let root = tree.add_root(CompactString::new("C:"));
+        tree.set_root_path(root, dir.path().to_path_buf());
+        let blob = tree.add_node(FileNode::new_file(
+            CompactString::new("blob.dat"),
+            14,
+            Some(root),
+        ));
+        tree.add_child(root, blob);
+        let small = tree.add_node(FileNode::new_file(
+            CompactString::new("small.zip"),
+            4,
+            Some(root),
+        ));
+        tree.add_child(root, small);
+        tree.aggregate_sizes();
+
+        let cancel = AtomicBool::new(false);
+        let report = detect_signatures(&tree, 10, &cancel, |_, _| {}).unwrap();
+        assert_eq!(report.files_checked, 1);
+        assert_eq!(report.detected.get(&blob), Some(&DetectedType::Zip));
+        assert!(!report.detected.contains_key(&small));
+        assert_eq!(report.mismatched, 1, ".dat is System, content is Archives");
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(detect_signatures(&tree, 10, &cancel, |_, _| {}).is_none());
+    }
+}
@@ -0,0 +1,208 @@
+/// File size distribution — a power-of-two histogram with percentiles.
+///
+/// [`analyse_sizes`] makes one pass over the files of the whole tree or of a
+/// subtree, counting files and bytes per bucket: bucket 0 holds empty files
+/// and bucket `k` holds sizes in `2^(k-1) .. 2^k`.  Percentiles are exact
+/// (nearest rank), found with a linear-time selection over a copy of the
+/// sizes, as is the share of bytes held by the largest 1% of files.
+use crate::model::{FileTree, NodeIndex};
+
+/// Buckets in a [`SizeHistogram`]: empty files, then one per power of two.
+pub const SIZE_BUCKETS: usize = 65;
+
+/// Files and bytes in one size bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SizeBucket {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Result of [`analyse_sizes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeHistogram {
+    /// Subtree covered, or `None` for the whole tree.
+    pub root: Option<NodeIndex>,
+    /// [`SIZE_BUCKETS`] buckets, indexed by [`size_bucket`].
+    pub buckets: Vec<SizeBucket>,
+    pub files: u64,
+    pub bytes: u64,
+    /// Median file size.
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    /// Files in the largest 1% (at least one when there are files).
+    pub top_percent_files: u64,
+    /// Bytes held by those files.
+    pub top_percent_bytes: u64,
+}
+
+/// Bucket a file of `size` bytes falls into.
+pub fn size_bucket(size: u64) -> usize {
+    (u64::BITS - size.leading_zeros()) as usize
+}
+
+impl SizeHistogram {
+    /// Smallest size in `bucket`, and the size just past it (`None` for the
+    /// last bucket).
+    pub fn bucket_bounds(bucket: usize) -> (u64, Option<u64>) {
+        match bucket {
+            0 => (0, Some(1)),
+            b => (1 << (b - 1), 1u64.checked_shl(b as u32)),
+        }
+    }
+
+    /// Buckets from the first to the last holding any file; empty if there
+    /// are no files.
+    pub fn used_buckets(&self) -> std::ops::Range<usize> {
+        let first = self.buckets.iter().position(|b| b.files > 0);
+        let last = self.buckets.iter().rposition(|b| b.files > 0);
+        match (first, last) {
+            (Some(first), Some(last)) => first..last + 1,
+            _ => 0..0,
+        }
+    }
+
+    /// Share of all bytes held by the largest 1% of files, in `0..=1`.
+    pub fn top_percent_share(&self) -> f64 {
+        match self.bytes {
+            0 => 0.0,
+            bytes => self.top_percent_bytes as f64 / bytes as f64,
+        }
+    }
+}
+
+/// Value at `percent` (nearest rank) of `sizes`, which is reordered.
+fn percentile(sizes: &mut [u64], percent: u64) -> u64 {
+    if sizes.is_empty() {
+        return 0;
+    }
+    let rank = (sizes.len() as u64 * percent).div_ceil(100).max(1);
+    *sizes.select_nth_unstable(rank as usize - 1).1
+}
+
+/// Histogram the file sizes below `root`, or of the whole tree for `None`.
+pub fn analyse_sizes(tree: &FileTree, root: Option<NodeIndex>) -> SizeHistogram {
+    let mut sizes: Vec<u64> = match root {
+        None => tree
+            .nodes
+            .iter()
+            .filter(|n| !n.is_dir && !n.is_error)
+            .map(|n| n.size)
+            .collect(),
+        Some(root) => {
+            let mut sizes = Vec::new();
+            let mut stack = vec![root];
+            while let Some(idx) = stack.pop() {
+                let node = tree.node(idx);
+                if !node.is_dir {
+                    if !node.is_error {
+                        sizes.push(node.size);
+                    }
+                    continue;
+                }
+                let mut child = node.first_child;
+                while let Some(c) = child {
+                    stack.push(c);
+                    child = tree.node(c).next_sibling;
+                }
+            }
+            sizes
+        }
+    };
+
+    let mut buckets = vec![SizeBucket::default(); SIZE_BUCKETS];
+    for &size in &sizes {
+        let b = &mut buckets[size_bucket(size)];
+        b.files += 1;
+        b.bytes += size;
+    }
+    let files = sizes.len() as u64;
+    let bytes = buckets.iter().map(|b| b.bytes).sum();
+
+    let p50 = percentile(&mut sizes, 50);
+    let p90 = percentile(&mut sizes, 90);
+    let p99 = percentile(&mut sizes, 99);
+    let top_percent_files = files.div_ceil(100);
+    let top_percent_bytes = match sizes.len().checked_sub(top_percent_files as usize) {
+        Some(0) => bytes,
+        Some(cut) => {
+            sizes.select_nth_unstable(cut);
+            sizes[cut..].iter().sum()
+        }
+        None => 0,
+    };
+
+    SizeHistogram {
+        root,
+        buckets,
+        files,
+        bytes,
+        p50,
+        p90,
+        p99,
+        top_percent_files,
+        top_percent_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::file_node::FileNode;
+    use compact_str::CompactString;
+
+    #[test]
+    fn buckets_are_powers_of_two() {
+        assert_eq!(size_bucket(0), 0);
+        assert_eq!(size_bucket(1), 1);
+        assert_eq!(size_bucket(4095), 12);
+        assert_eq!(size_bucket(4096), 13);
+        assert_eq!(size_bucket(u64::MAX), 64);
+        assert_eq!(SizeHistogram::bucket_bounds(0), (0, Some(1)));
+        assert_eq!(SizeHistogram::bucket_bounds(13), (4096, Some(8192)));
+        assert_eq!(SizeHistogram::bucket_bounds(64), (1 << 63, None));
+    }
+
+    /// Percentiles, the top-1% share and subtree scoping.
+    #[test]
+    fn percentiles_and_top_percent() {
+        let mut tree = FileTree::with_capacity(256);
+        let root = tree.add_root(CompactString::new("C:"));
+        let big = tree.add_node(FileNode::new_dir(CompactString::new("big"), Some(root)));
+        tree.add_child(root, big);
+        // 1..=199 bytes at the root, one 1 MB file below `big`.
+        for size in 1..200 {
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new("f"),
+                size,
+                Some(root),
+            ));
+            tree.add_child(root, f);
+        }
+        let f = tree.add_node(FileNode::new_file(
+            CompactString::new("huge"),
+            1_000_000,
+            Some(big),
+        ));
+        tree.add_child(big, f);
+        tree.aggregate_sizes();
+
+        let all = analyse_sizes(&tree, None);
+        assert_eq!(all.files, 200);
+        assert_eq!(all.bytes, 199 * 200 / 2 + 1_000_000);
+        assert_eq!((all.p50, all.p90, all.p99), (100, 180, 198));
+        assert_eq!(all.top_percent_files, 2);
+        assert_eq!(all.top_percent_bytes, 1_000_000 + 199);
+        assert_eq!(all.buckets[size_bucket(1_000_000)].files, 1);
+        assert_eq!(all.used_buckets(), 1..21);
+        assert_eq!(all.buckets.iter().map(|b| b.files).sum::<u64>(), 200);
+
+        let sub = analyse_sizes(&tree, Some(big));
+        assert_eq!((sub.files, sub.p50, sub.p99), (1, 1_000_000, 1_000_000));
+        assert_eq!(sub.top_percent_share(), 1.0);
+
+        let empty = analyse_sizes(&FileTree::with_capacity(0), None);
+        assert_eq!((empty.files, empty.p50, empty.top_percent_bytes), (0, 0, 0));
+        assert_eq!(empty.used_buckets(), 0..0);
+    }
+}
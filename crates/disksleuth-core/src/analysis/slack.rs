@@ -0,0 +1,241 @@
+/// Allocation slack — space lost to rounding files up to whole clusters, and
+/// what it would be with another cluster size.
+///
+/// A file occupies its stored bytes rounded up to the volume's cluster size.
+/// Stored bytes are the logical `size`, or `allocated_size` when the
+/// filesystem reported less (compressed or sparse files).  [`analyse_slack`]
+/// totals the slack per directory for the volume's cluster size and projects
+/// the whole tree, and each listed directory, onto
+/// [`CANDIDATE_CLUSTER_SIZES`] in the same pass.
+///
+/// Tiny files stored inside the MFT record on NTFS are counted as taking a
+/// cluster, so figures are an upper bound there.
+use crate::analysis::hotspots::{cluster_slack, stored_bytes};
+use crate::model::{FileTree, NodeIndex};
+
+/// Cluster sizes compared against the volume's own.
+pub const CANDIDATE_CLUSTER_SIZES: [u64; 3] = [4 * 1024, 16 * 1024, 64 * 1024];
+
+/// Most directories listed in a [`SlackReport`], most slack first.
+pub const MAX_SLACK_DIRS: usize = 200;
+
+/// Usage of a set of files at one cluster size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SlackProjection {
+    pub cluster_size: u64,
+    /// Bytes the files would occupy.
+    pub allocated: u64,
+    /// Of which lost to rounding.
+    pub slack: u64,
+}
+
+/// Slack of the files below one directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirSlack {
+    pub index: NodeIndex,
+    pub files: u64,
+    /// Stored bytes of the files below.
+    pub stored: u64,
+    /// At the volume's cluster size.
+    pub slack: u64,
+    /// At each of [`CANDIDATE_CLUSTER_SIZES`], in order.
+    pub projected: [SlackProjection; 3],
+}
+
+/// Result of [`analyse_slack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlackReport {
+    /// Cluster size the report was computed for.
+    pub cluster_size: u64,
+    pub files: u64,
+    /// Logical bytes of every file.
+    pub logical: u64,
+    /// Stored bytes of every file (logical, less compression and sparseness).
+    pub stored: u64,
+    /// Usage of every file at `cluster_size`.
+    pub actual: SlackProjection,
+    /// Usage of every file at each of [`CANDIDATE_CLUSTER_SIZES`].
+    pub projected: [SlackProjection; 3],
+    /// Directories with the most slack at `cluster_size`.
+    pub dirs: Vec<DirSlack>,
+}
+
+/// Per-node totals gathered bottom-up.
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    files: u64,
+    stored: u64,
+    slack: u64,
+    projected: [u64; 3],
+}
+
+/// Compute slack for every directory of `tree` at `cluster_size`, and the
+/// projected usage at each of [`CANDIDATE_CLUSTER_SIZES`].
+pub fn analyse_slack(tree: &FileTree, cluster_size: u64) -> SlackReport {
+    let len = tree.nodes.len();
+    let mut totals = vec![Totals::default(); len];
+    let mut report = SlackReport {
+        cluster_size,
+        files: 0,
+        logical: 0,
+        stored: 0,
+        actual: SlackProjection {
+            cluster_size,
+            ..Default::default()
+        },
+        projected: CANDIDATE_CLUSTER_SIZES.map(|c| SlackProjection {
+            cluster_size: c,
+            ..Default::default()
+        }),
+        dirs: Vec::new(),
+    };
+
+    // Children follow their parent in the arena, so a reverse pass sees
+    // every child before its parent.
+    for i in (0..len).rev() {
+        let node = &tree.nodes[i];
+        if !node.is_dir {
+            if node.is_error {
+                continue;
+            }
+            let stored = stored_bytes(node);
+            let own = &mut totals[i];
+            own.files = 1;
+            own.stored = stored;
+            own.slack = cluster_slack(stored, cluster_size);
+            for (p, &c) in own.projected.iter_mut().zip(&CANDIDATE_CLUSTER_SIZES) {
+                *p = cluster_slack(stored, c);
+            }
+
+            report.files += 1;
+            report.logical += node.size;
+            report.stored += stored;
+            report.actual.slack += own.slack;
+            for (p, own) in report.projected.iter_mut().zip(own.projected) {
+                p.slack += own;
+            }
+        }
+        if let Some(parent) = node.parent {
+            let child = totals[i];
+            let t = &mut totals[parent.idx()];
+            t.files += child.files;
+            t.stored += child.stored;
+            t.slack += child.slack;
+            for (p, c) in t.projected.iter_mut().zip(child.projected) {
+                *p += c;
+            }
+        }
+    }
+    report.actual.allocated = report.stored + report.actual.slack;
+    for p in &mut report.projected {
+        p.allocated = report.stored + p.slack;
+    }
+
+    let mut dirs: Vec<DirSlack> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(i, node)| node.is_dir && totals[*i].slack > 0)
+        .map(|(i, _)| {
+            let t = totals[i];
+            let mut projected = [SlackProjection::default(); 3];
+            for ((p, &c), slack) in projected
+                .iter_mut()
+                .zip(&CANDIDATE_CLUSTER_SIZES)
+                .zip(t.projected)
+            {
+                *p = SlackProjection {
+                    cluster_size: c,
+                    allocated: t.stored + slack,
+                    slack,
+                };
+            }
+            DirSlack {
+                index: NodeIndex::new(i),
+                files: t.files,
+                stored: t.stored,
+                slack: t.slack,
+                projected,
+            }
+        })
+        .collect();
+    let by_slack = |a: &DirSlack, b: &DirSlack| b.slack.cmp(&a.slack).then(a.index.cmp(&b.index));
+    if dirs.len() > MAX_SLACK_DIRS {
+        dirs.select_nth_unstable_by(MAX_SLACK_DIRS - 1, by_slack);
+        dirs.truncate(MAX_SLACK_DIRS);
+    }
+    dirs.sort_unstable_by(by_slack);
+    report.dirs = dirs;
+    report
+}
+
+impl SlackReport {
+    /// Usage of every file at `cluster_size` — the volume's own or one of
+    /// the candidates — if the report covers it.
+    pub fn at(&self, cluster_size: u64) -> Option<SlackProjection> {
+        if cluster_size == self.cluster_size {
+            return Some(self.actual);
+        }
+        self.projected
+            .iter()
+            .copied()
+            .find(|p| p.cluster_size == cluster_size)
+    }
+
+    /// Slack as a share of allocated space at the volume's cluster size.
+    pub fn slack_percent(&self) -> f64 {
+        match self.actual.allocated {
+            0 => 0.0,
+            allocated => self.actual.slack as f64 / allocated as f64 * 100.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::FileNode;
+    use compact_str::CompactString;
+
+    fn file(tree: &mut FileTree, parent: NodeIndex, size: u64, allocated: u64) {
+        let mut node = FileNode::new_file(CompactString::new("f"), size, Some(parent));
+        node.allocated_size = allocated;
+        let idx = tree.add_node(node);
+        tree.add_child(parent, idx);
+    }
+
+    /// Totals and projections at the volume's cluster size and the candidates.
+    #[test]
+    fn slack_per_directory_and_projections() {
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("C:"));
+        let small = tree.add_node(FileNode::new_dir(CompactString::new("small"), Some(root)));
+        tree.add_child(root, small);
+        // Two 1 000-byte files and one exact 64 KiB file.
+        file(&mut tree, small, 1_000, 1_000);
+        file(&mut tree, small, 1_000, 1_000);
+        file(&mut tree, root, 65_536, 65_536);
+        // A compressed file: 100 KB logical, 10 000 bytes stored.
+        file(&mut tree, root, 100_000, 10_000);
+        tree.aggregate_sizes();
+
+        let report = analyse_slack(&tree, 4096);
+        assert_eq!(report.files, 4);
+        assert_eq!(report.logical, 167_536);
+        assert_eq!(report.stored, 77_536);
+        assert_eq!(report.actual.slack, 2 * 3_096 + 2_288);
+        assert_eq!(report.actual.allocated, 77_536 + 2 * 3_096 + 2_288);
+
+        let at_64k = report.at(65_536).unwrap();
+        assert_eq!(at_64k.slack, 2 * 64_536 + 55_536);
+        assert_eq!(report.at(4096), Some(report.actual));
+        assert_eq!(report.at(12_345), None);
+
+        assert_eq!(report.dirs[0].index, root);
+        assert_eq!(report.dirs[1].index, small);
+        assert_eq!(report.dirs[1].files, 2);
+        assert_eq!(report.dirs[1].slack, 2 * 3_096);
+        assert_eq!(report.dirs[1].projected[1].slack, 2 * 15_384);
+        assert_eq!(report.dirs[1].projected[1].allocated, 2 * 16_384);
+    }
+}
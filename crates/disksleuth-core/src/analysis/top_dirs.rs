@@ -0,0 +1,156 @@
+/// Top-N largest directories analysis.
+///
+/// Ranks directories either by the bytes of the files directly inside them
+/// (their "own" bytes, which finds leaf-ish folders full of medium files)
+/// or by their total size.  Ranked by total, every ancestor of a large
+/// folder is at least as large, so the top of the list is one ancestor
+/// chain; passing a depth ranks only the folders that many levels below
+/// their scan root, none of which contains another.
+use crate::model::{FileTree, NodeIndex};
+
+/// Most directories returned by [`largest_dirs`].
+pub const MAX_LARGEST_DIRS: usize = 200;
+
+/// How [`largest_dirs`] ranks directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirRank {
+    /// Bytes of the files directly inside.
+    #[default]
+    Own,
+    /// Bytes of everything below.
+    Total,
+}
+
+impl DirRank {
+    /// Every ranking, in display order.
+    pub const ALL: [DirRank; 2] = [Self::Own, Self::Total];
+
+    /// Human-readable label for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Own => "Own files",
+            Self::Total => "Total size",
+        }
+    }
+}
+
+/// A single entry in the "largest directories" results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargestDir {
+    /// Index into the file tree arena.
+    pub index: NodeIndex,
+    /// Levels below the scan root (the root itself is 0).
+    pub depth: usize,
+    /// Bytes of the files directly inside.
+    pub own_bytes: u64,
+    /// Files directly inside.
+    pub own_files: u64,
+    /// Bytes of everything below.
+    pub total_bytes: u64,
+}
+
+/// Get the top `n` (at most [`MAX_LARGEST_DIRS`]) directories by `rank`.
+///
+/// With `depth`, only directories exactly that many levels below their scan
+/// root are ranked.
+pub fn largest_dirs(
+    tree: &FileTree,
+    rank: DirRank,
+    depth: Option<usize>,
+    n: usize,
+) -> Vec<LargestDir> {
+    let n = n.min(MAX_LARGEST_DIRS);
+    if n == 0 {
+        return Vec::new();
+    }
+    let len = tree.nodes.len();
+    let mut depths = vec![0usize; len];
+    let mut own = vec![(0u64, 0u64); len];
+    // Parents precede their children in the arena, so one forward pass
+    // sees every parent's depth first.
+    for (i, node) in tree.nodes.iter().enumerate() {
+        let Some(parent) = node.parent else {
+            continue;
+        };
+        depths[i] = depths[parent.idx()] + 1;
+        if !node.is_dir && !node.is_error {
+            let (bytes, files) = &mut own[parent.idx()];
+            *bytes += node.size;
+            *files += 1;
+        }
+    }
+
+    let mut dirs: Vec<LargestDir> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(i, node)| node.is_dir && depth.is_none_or(|d| depths[*i] == d))
+        .map(|(i, node)| LargestDir {
+            index: NodeIndex::new(i),
+            depth: depths[i],
+            own_bytes: own[i].0,
+            own_files: own[i].1,
+            total_bytes: node.size,
+        })
+        .filter(|d| match rank {
+            DirRank::Own => d.own_bytes > 0,
+            DirRank::Total => d.total_bytes > 0,
+        })
+        .collect();
+
+    let by_rank = |a: &LargestDir, b: &LargestDir| {
+        match rank {
+            DirRank::Own => b.own_bytes.cmp(&a.own_bytes),
+            DirRank::Total => b.total_bytes.cmp(&a.total_bytes),
+        }
+        .then(a.index.cmp(&b.index))
+    };
+    if dirs.len() > n {
+        dirs.select_nth_unstable_by(n - 1, by_rank);
+        dirs.truncate(n);
+    }
+    dirs.sort_unstable_by(by_rank);
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+
+    /// Own bytes find the folder holding the files; totals at a depth skip
+    /// the ancestor chain.
+    #[test]
+    fn ranks_by_own_and_total_with_depth() {
+        let mut tree = FileTree::with_capacity(16);
+        let root = tree.add_root(CompactString::new("C:"));
+        let users = tree.add_test_dir(root, "Users");
+        let me = tree.add_test_dir(users, "me");
+        let photos = tree.add_test_dir(me, "Photos");
+        for _ in 0..10 {
+            tree.add_test_file(photos, "f", 1_000);
+        }
+        tree.add_test_file(me, "f", 4_000);
+        let games = tree.add_test_dir(root, "Games");
+        tree.add_test_file(games, "f", 7_000);
+        tree.add_test_file(root, "f", 500);
+        tree.aggregate_sizes();
+
+        let own = largest_dirs(&tree, DirRank::Own, None, 10);
+        let order: Vec<_> = own.iter().map(|d| d.index).collect();
+        assert_eq!(order, [photos, games, me, root]);
+        assert_eq!((own[0].own_files, own[0].depth), (10, 3));
+        assert_eq!(own[2].total_bytes, 14_000);
+
+        let total = largest_dirs(&tree, DirRank::Total, None, 3);
+        let order: Vec<_> = total.iter().map(|d| d.index).collect();
+        assert_eq!(order, [root, users, me]);
+
+        let level_one = largest_dirs(&tree, DirRank::Total, Some(1), 10);
+        let order: Vec<_> = level_one.iter().map(|d| d.index).collect();
+        assert_eq!(order, [users, games]);
+        assert_eq!(level_one[0].own_bytes, 0);
+
+        assert!(largest_dirs(&tree, DirRank::Own, Some(1), 0).is_empty());
+    }
+}
@@ -2,7 +2,9 @@
 ///
 /// Extracts the largest individual files from a scanned `FileTree`
 /// for display in the analysis panel.
+use crate::analysis::file_types::file_extension;
 use crate::model::{FileTree, NodeIndex};
+use std::cmp::Reverse;
 
 /// A single entry in the "largest files" results.
 pub struct LargestFile {
@@ -30,6 +32,39 @@ pub fn top_files(tree: &FileTree, n: usize) -> Vec<LargestFile> {
         .collect()
 }
 
+/// Get the top N largest files with extension `ext`.
+///
+/// Matching is case-insensitive; an empty `ext` matches files without an
+/// extension.  Unlike [`top_files`] this walks the whole tree, so it is
+/// meant for one extension picked by the user, not per frame.
+pub fn top_files_with_extension(tree: &FileTree, ext: &str, n: usize) -> Vec<LargestFile> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let size = |i: &NodeIndex| tree.nodes[i.idx()].size;
+    let mut matches: Vec<NodeIndex> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| !node.is_dir && file_extension(&node.name).eq_ignore_ascii_case(ext))
+        .map(|(i, _)| NodeIndex::new(i))
+        .collect();
+    if matches.len() > n {
+        // Partial selection, as in `FileTree::compute_largest_files`.
+        matches.select_nth_unstable_by(n - 1, |a, b| size(b).cmp(&size(a)));
+        matches.truncate(n);
+    }
+    matches.sort_unstable_by_key(|i| Reverse(size(i)));
+    matches
+        .into_iter()
+        .map(|idx| LargestFile {
+            index: idx,
+            path: tree.full_path(idx),
+            size: tree.node(idx).size,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +129,27 @@ mod tests {
         assert!(top[0].size >= top[1].size, "must be descending");
     }
 
+    /// Only files with the extension are returned, largest first, up to `n`.
+    #[test]
+    fn top_files_with_extension_filters_and_limits() {
+        let mut tree = FileTree::with_capacity(6);
+        let root = tree.add_root(CompactString::new("C:"));
+        for (name, size) in [("a.log", 10), ("b.LOG", 30), ("c.log", 20), ("d.bin", 99)] {
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new(name),
+                size,
+                Some(root),
+            ));
+            tree.add_child(root, f);
+        }
+        tree.aggregate_sizes();
+
+        let top = top_files_with_extension(&tree, "log", 2);
+        let sizes: Vec<u64> = top.iter().map(|f| f.size).collect();
+        assert_eq!(sizes, [30, 20]);
+        assert!(top_files_with_extension(&tree, "", 5).is_empty());
+    }
+
     /// `top_files` on an empty tree returns an empty vec — no panic.
     #[test]
     fn top_files_empty_tree() {
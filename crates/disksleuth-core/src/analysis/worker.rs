@@ -0,0 +1,106 @@
+/// Background workers — the thread, channel and cancel flag behind every
+/// `start_*` analysis.
+///
+/// Each analysis defines its own message enum and returns a
+/// [`WorkerHandle`] of it; the UI polls `result_rx` once per frame and calls
+/// [`WorkerHandle::cancel`] when the result is no longer wanted.
+/// Cancellation is cooperative: the worker checks the flag it is handed.
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// Handle to a running or completed background worker sending `M`s.
+pub struct WorkerHandle<M> {
+    /// Receives the worker's messages; the last one reports how it ended.
+    pub result_rx: Receiver<M>,
+    /// Flag to request cancellation.
+    cancel_flag: Arc<AtomicBool>,
+    /// Join handle for the worker thread.
+    _thread: Option<thread::JoinHandle<()>>,
+}
+
+impl<M> WorkerHandle<M> {
+    /// Request the worker to stop as soon as possible.
+    pub fn cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Run `work` on a thread named `name` with the message sender and the
+/// cancel flag.
+///
+/// `capacity` bounds the channel.  Workers that report progress send it
+/// with `try_send`, so it is dropped when the UI falls behind, and their
+/// final message blocks until there is room.
+pub(crate) fn spawn_worker<M, F>(name: &str, capacity: usize, work: F) -> WorkerHandle<M>
+where
+    M: Send + 'static,
+    F: FnOnce(&Sender<M>, &AtomicBool) + Send + 'static,
+{
+    let (result_tx, result_rx) = crossbeam_channel::bounded::<M>(capacity);
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let cancel_clone = cancel_flag.clone();
+
+    let thread = thread::Builder::new()
+        .name(name.into())
+        .spawn(move || work(&result_tx, &cancel_clone))
+        .unwrap_or_else(|e| panic!("failed to spawn {name} thread: {e}"));
+
+    WorkerHandle {
+        result_rx,
+        cancel_flag,
+        _thread: Some(thread),
+    }
+}
+
+/// [`spawn_worker`] for a worker whose only message is the value `work`
+/// returns.  Exactly one message is ever sent, so capacity 1 never blocks.
+pub(crate) fn spawn_oneshot<M, F>(name: &str, work: F) -> WorkerHandle<M>
+where
+    M: Send + 'static,
+    F: FnOnce(&AtomicBool) -> M + Send + 'static,
+{
+    spawn_worker(name, 1, move |tx, cancel| {
+        let _ = tx.send(work(cancel));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// A one-shot worker delivers its single result.
+    #[test]
+    fn oneshot_sends_its_result() {
+        let handle = spawn_oneshot("disksleuth-test", |_| 42);
+        assert_eq!(
+            handle.result_rx.recv_timeout(Duration::from_secs(5)),
+            Ok(42)
+        );
+        assert!(!handle.is_cancelled());
+    }
+
+    /// The worker sees cancellation requested through the handle.
+    #[test]
+    fn cancel_reaches_the_worker() {
+        let (go_tx, go_rx) = crossbeam_channel::bounded::<()>(1);
+        let handle = spawn_oneshot("disksleuth-test", move |cancel| {
+            go_rx.recv().unwrap();
+            cancel.load(Ordering::Relaxed)
+        });
+        handle.cancel();
+        go_tx.send(()).unwrap();
+        assert!(handle.is_cancelled());
+        assert_eq!(
+            handle.result_rx.recv_timeout(Duration::from_secs(5)),
+            Ok(true)
+        );
+    }
+}
@@ -0,0 +1,556 @@
+/// Cleanup module — move to trash, delete permanently, or move to an archive.
+///
+/// Deletions run on a background thread so a multi-gigabyte `remove_dir_all`
+/// never stalls the UI.  Each path is reported individually; the caller
+/// removes only the successful ones from the in-memory tree via
+/// [`FileTree::without_subtrees`].  A plan can also be exported as a shell
+/// script ([`script`]) for review instead of being run.
+pub mod script;
+pub mod trash;
+
+use crate::model::{FileTree, NodeIndex};
+use crossbeam_channel::Receiver;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Capacity of the per-item result channel.
+const CLEANUP_CHANNEL_CAPACITY: usize = 256;
+
+/// What to do with the selected paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupAction {
+    /// Move to the Recycle Bin (Windows) or freedesktop trash (Linux).
+    Trash,
+    /// Remove from disk without a way back.
+    Delete,
+    /// Move into [`CleanupPlan::archive_dir`], keeping the path relative
+    /// to the scan root.
+    Archive,
+    /// Delete a folder tree only if it still holds no files on disk.
+    RemoveEmpty,
+}
+
+impl CleanupAction {
+    /// Human-readable label for buttons and dialogs.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Trash => "Move to Trash",
+            Self::Delete => "Delete permanently",
+            Self::Archive => "Move to archive",
+            Self::RemoveEmpty => "Remove empty folders",
+        }
+    }
+}
+
+/// Why a single path could not be removed.
+#[derive(Debug, Error)]
+pub enum CleanupError {
+    /// A filesystem call failed.
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// This platform has no trash implementation.
+    #[error("moving to the trash is not supported on this platform")]
+    TrashUnsupported,
+    /// The Windows shell returned an error code.
+    #[error("the shell reported error code {0:#x}")]
+    Shell(i32),
+    /// The operation was aborted before this path was processed.
+    #[error("the operation was aborted")]
+    Aborted,
+    /// An archive move was requested without a destination folder.
+    #[error("no archive folder was chosen")]
+    NoArchiveDir,
+    /// A folder due for [`CleanupAction::RemoveEmpty`] holds files or links.
+    #[error("{} is no longer empty", .0.display())]
+    NotEmpty(PathBuf),
+}
+
+/// One path scheduled for removal.
+#[derive(Debug, Clone)]
+pub struct CleanupItem {
+    /// Index into the file tree arena.
+    pub index: NodeIndex,
+    /// On-disk path.
+    pub path: PathBuf,
+    /// Path relative to the scan root; used as the archive destination.
+    pub relative: PathBuf,
+    /// Logical size in bytes (aggregated for directories).
+    pub size: u64,
+    /// `true` if the item is a directory.
+    pub is_dir: bool,
+    /// Number of files removed with this item (1 for a file).
+    pub files: u64,
+}
+
+/// A confirmed-ready list of paths plus totals for the confirmation dialog.
+#[derive(Debug, Clone)]
+pub struct CleanupPlan {
+    /// What to do with the items.
+    pub action: CleanupAction,
+    /// Items with nested selections already folded into their ancestors.
+    pub items: Vec<CleanupItem>,
+    /// Sum of item sizes — no double counting.
+    pub total_size: u64,
+    /// Sum of item file counts.
+    pub total_files: u64,
+    /// Destination for [`CleanupAction::Archive`]; ignored otherwise.
+    pub archive_dir: Option<PathBuf>,
+}
+
+impl CleanupPlan {
+    /// Where `item` ends up when archived, if an archive folder is set.
+    pub fn archive_destination(&self, item: &CleanupItem) -> Option<PathBuf> {
+        self.archive_dir
+            .as_ref()
+            .map(|dir| dir.join(&item.relative))
+    }
+}
+
+/// Reduce `nodes` to those with no selected ancestor, dropping duplicates,
+/// out-of-range indices and scan roots.  Input order is kept.
+///
+/// Summing sizes over the result never double-counts nested selections.
+pub fn outermost_nodes(tree: &FileTree, nodes: &[NodeIndex]) -> Vec<NodeIndex> {
+    let selected: HashSet<NodeIndex> = nodes.iter().copied().collect();
+    let mut seen: HashSet<NodeIndex> = HashSet::with_capacity(selected.len());
+    let mut outermost = Vec::with_capacity(selected.len());
+
+    for &idx in nodes {
+        if idx.idx() >= tree.len() || !seen.insert(idx) {
+            continue;
+        }
+        let Some(mut ancestor) = tree.node(idx).parent else {
+            continue;
+        };
+        let nested = loop {
+            if selected.contains(&ancestor) {
+                break true;
+            }
+            match tree.node(ancestor).parent {
+                Some(p) => ancestor = p,
+                None => break false,
+            }
+        };
+        if !nested {
+            outermost.push(idx);
+        }
+    }
+    outermost
+}
+
+/// Path of `index` relative to its scan root.
+fn relative_path(tree: &FileTree, index: NodeIndex) -> PathBuf {
+    let mut segments = Vec::new();
+    let mut current = index;
+    while let Some(parent) = tree.node(current).parent {
+        segments.push(tree.node(current).name.as_str());
+        current = parent;
+    }
+    segments.iter().rev().collect()
+}
+
+/// Build a cleanup plan for `nodes`.
+///
+/// A node whose ancestor is also selected is dropped so its bytes are not
+/// counted (or deleted) twice.  Scan roots and nodes without a known
+/// filesystem path are skipped — removing a whole drive is never intended.
+pub fn plan_cleanup(tree: &FileTree, nodes: &[NodeIndex], action: CleanupAction) -> CleanupPlan {
+    let items: Vec<CleanupItem> = outermost_nodes(tree, nodes)
+        .into_iter()
+        .filter_map(|idx| {
+            let node = tree.node(idx);
+            Some(CleanupItem {
+                index: idx,
+                path: tree.fs_path(idx)?,
+                relative: relative_path(tree, idx),
+                size: node.size,
+                is_dir: node.is_dir,
+                files: if node.is_dir {
+                    node.descendant_count
+                } else {
+                    1
+                },
+            })
+        })
+        .collect();
+
+    let total_size = items.iter().map(|i| i.size).sum();
+    let total_files = items.iter().map(|i| i.files).sum();
+    CleanupPlan {
+        action,
+        items,
+        total_size,
+        total_files,
+        archive_dir: None,
+    }
+}
+
+/// Remove a single path from its current location.
+///
+/// A path that no longer exists counts as success: it is gone either way,
+/// and the tree should drop it.  `archive_to` is the full destination path
+/// for [`CleanupAction::Archive`] and is ignored by the other actions.
+pub fn remove_path(
+    path: &Path,
+    action: CleanupAction,
+    archive_to: Option<&Path>,
+) -> Result<(), CleanupError> {
+    if std::fs::symlink_metadata(path).is_err_and(|e| e.kind() == io::ErrorKind::NotFound) {
+        return Ok(());
+    }
+    match action {
+        CleanupAction::Trash => trash::move_to_trash(path),
+        CleanupAction::Delete => delete_permanently(path).map_err(CleanupError::from),
+        CleanupAction::Archive => {
+            let dest = archive_to.ok_or(CleanupError::NoArchiveDir)?;
+            move_path(path, dest).map_err(CleanupError::from)
+        }
+        CleanupAction::RemoveEmpty => remove_empty_tree(path),
+    }
+}
+
+/// Remove `path` and its subfolders after checking on disk that none of
+/// them holds a file or link.
+///
+/// The scan may be stale, so the whole tree is walked before anything is
+/// removed; `remove_dir` then refuses any folder that gained an entry in
+/// the meantime.
+fn remove_empty_tree(path: &Path) -> Result<(), CleanupError> {
+    /// Folders under `dir` (itself included), parents first.
+    fn collect(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), CleanupError> {
+        let meta = std::fs::symlink_metadata(dir)?;
+        if !meta.is_dir() {
+            return Err(CleanupError::NotEmpty(dir.to_path_buf()));
+        }
+        out.push(dir.to_path_buf());
+        for entry in std::fs::read_dir(dir)? {
+            collect(&entry?.path(), out)?;
+        }
+        Ok(())
+    }
+
+    let mut dirs = Vec::new();
+    collect(path, &mut dirs).map_err(|e| match e {
+        CleanupError::NotEmpty(_) => CleanupError::NotEmpty(path.to_path_buf()),
+        other => other,
+    })?;
+    for dir in dirs.iter().rev() {
+        std::fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// Move `src` to `dest`, creating parent folders and never overwriting.
+///
+/// A rename is tried first; across volumes the tree is copied and the
+/// source deleted only after the copy succeeded.
+fn move_path(src: &Path, dest: &Path) -> io::Result<()> {
+    if std::fs::symlink_metadata(dest).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dest.display()),
+        ));
+    }
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::rename(src, dest) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            if let Err(e) = copy_tree(src, dest) {
+                // Leave the source untouched; drop the partial copy.
+                let _ = delete_permanently(dest);
+                return Err(e);
+            }
+            delete_permanently(src)
+        }
+        other => other,
+    }
+}
+
+/// Recursively copy files and folders.  Symlinks are not followed.
+fn copy_tree(src: &Path, dest: &Path) -> io::Result<()> {
+    let meta = std::fs::symlink_metadata(src)?;
+    if meta.file_type().is_symlink() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot move link {} across volumes", src.display()),
+        ));
+    }
+    if !meta.is_dir() {
+        return std::fs::copy(src, dest).map(|_| ());
+    }
+    std::fs::create_dir(dest)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        copy_tree(&entry.path(), &dest.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+/// Delete a file, symlink or directory tree without following links.
+fn delete_permanently(path: &Path) -> io::Result<()> {
+    let meta = std::fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        // Directory symlinks / junctions on Windows need `remove_dir`.
+        return std::fs::remove_file(path).or_else(|_| std::fs::remove_dir(path));
+    }
+    if meta.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// The result of processing one [`CleanupItem`].
+#[derive(Debug)]
+pub struct CleanupOutcome {
+    /// Index into the file tree arena the plan was built from.
+    pub index: NodeIndex,
+    /// On-disk path.
+    pub path: PathBuf,
+    /// `Ok` if the path is gone from disk.
+    pub result: Result<(), CleanupError>,
+}
+
+/// Messages sent from the cleanup thread to the UI.
+#[derive(Debug)]
+pub enum CleanupMessage {
+    /// One item was processed.
+    Item(CleanupOutcome),
+    /// All items were processed, or the run was cancelled.
+    Finished {
+        /// `true` if cancellation stopped the run early.
+        cancelled: bool,
+        /// Wall-clock time spent.
+        duration: Duration,
+    },
+}
+
+/// Handle to a running or completed cleanup.
+pub struct CleanupHandle {
+    /// Receives one [`CleanupMessage::Item`] per processed path, then
+    /// [`CleanupMessage::Finished`].
+    pub message_rx: Receiver<CleanupMessage>,
+    /// Flag to request cancellation between items.
+    cancel_flag: Arc<AtomicBool>,
+    /// Join handle for the cleanup thread.
+    _thread: Option<thread::JoinHandle<()>>,
+}
+
+impl CleanupHandle {
+    /// Stop after the item currently being removed.
+    pub fn cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Process `plan` on a background thread.
+pub fn start_cleanup(plan: CleanupPlan) -> CleanupHandle {
+    let (message_tx, message_rx) =
+        crossbeam_channel::bounded::<CleanupMessage>(CLEANUP_CHANNEL_CAPACITY);
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let cancel_clone = cancel_flag.clone();
+
+    let thread = thread::Builder::new()
+        .name("disksleuth-cleanup".into())
+        .spawn(move || {
+            let start = Instant::now();
+            let mut cancelled = false;
+            for item in &plan.items {
+                if cancel_clone.load(Ordering::Relaxed) {
+                    cancelled = true;
+                    break;
+                }
+                let archive_to = plan.archive_destination(item);
+                let result = remove_path(&item.path, plan.action, archive_to.as_deref());
+                let outcome = CleanupOutcome {
+                    index: item.index,
+                    path: item.path.clone(),
+                    result,
+                };
+                if message_tx.send(CleanupMessage::Item(outcome)).is_err() {
+                    // The UI dropped the handle; nobody is listening.
+                    return;
+                }
+            }
+            let _ = message_tx.send(CleanupMessage::Finished {
+                cancelled,
+                duration: start.elapsed(),
+            });
+        })
+        .expect("failed to spawn cleanup thread");
+
+    CleanupHandle {
+        message_rx,
+        cancel_flag,
+        _thread: Some(thread),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::FileNode;
+    use compact_str::CompactString;
+
+    /// root/ -> docs/ -> (a.txt: 10, b.txt: 20), c.txt: 5
+    fn sample_tree(root_path: &Path) -> (FileTree, [NodeIndex; 5]) {
+        let mut tree = FileTree::with_capacity(5);
+        let root = tree.add_root(CompactString::new("root"));
+        tree.set_root_path(root, root_path.to_path_buf());
+        let docs = tree.add_node(FileNode::new_dir(CompactString::new("docs"), Some(root)));
+        tree.add_child(root, docs);
+        let mut files = Vec::new();
+        for (name, size, parent) in [("a.txt", 10, docs), ("b.txt", 20, docs), ("c.txt", 5, root)] {
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new(name),
+                size,
+                Some(parent),
+            ));
+            tree.add_child(parent, f);
+            files.push(f);
+        }
+        tree.aggregate_sizes();
+        (tree, [root, docs, files[0], files[1], files[2]])
+    }
+
+    #[test]
+    fn test_plan_folds_nested_selection_into_ancestor() {
+        let (tree, [_root, docs, a, _b, c]) = sample_tree(Path::new("base"));
+        let plan = plan_cleanup(&tree, &[a, docs, c, a], CleanupAction::Delete);
+
+        let indices: Vec<NodeIndex> = plan.items.iter().map(|i| i.index).collect();
+        assert_eq!(indices, vec![docs, c]);
+        assert_eq!(plan.total_size, 35);
+        assert_eq!(plan.total_files, 3);
+        assert_eq!(plan.items[0].path, Path::new("base").join("docs"));
+        assert_eq!(plan.items[0].relative, Path::new("docs"));
+    }
+
+    #[test]
+    fn test_outermost_nodes_keeps_order_and_drops_nested() {
+        let (tree, [root, docs, a, b, c]) = sample_tree(Path::new("base"));
+        assert_eq!(outermost_nodes(&tree, &[c, b, a, c]), vec![c, b, a]);
+        assert_eq!(outermost_nodes(&tree, &[b, docs, root]), Vec::new());
+    }
+
+    #[test]
+    fn test_archive_keeps_relative_path_and_never_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let scan_root = dir.path().join("scan");
+        let archive = dir.path().join("archive");
+        std::fs::create_dir_all(scan_root.join("docs")).unwrap();
+        std::fs::write(scan_root.join("docs").join("a.txt"), b"a").unwrap();
+        std::fs::write(scan_root.join("c.txt"), b"c").unwrap();
+        std::fs::create_dir_all(&archive).unwrap();
+        std::fs::write(archive.join("c.txt"), b"old").unwrap();
+
+        let (tree, [_root, docs, _a, _b, c]) = sample_tree(&scan_root);
+        let mut plan = plan_cleanup(&tree, &[docs, c], CleanupAction::Archive);
+        plan.archive_dir = Some(archive.clone());
+
+        let docs_item = &plan.items[0];
+        let dest = plan.archive_destination(docs_item).unwrap();
+        remove_path(&docs_item.path, plan.action, Some(&dest)).unwrap();
+        assert!(archive.join("docs").join("a.txt").is_file());
+        assert!(!scan_root.join("docs").exists());
+
+        let c_item = &plan.items[1];
+        let dest = plan.archive_destination(c_item).unwrap();
+        assert!(remove_path(&c_item.path, plan.action, Some(&dest)).is_err());
+        assert_eq!(std::fs::read(archive.join("c.txt")).unwrap(), b"old");
+        assert!(scan_root.join("c.txt").exists());
+    }
+
+    #[test]
+    fn test_archive_without_folder_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("f");
+        std::fs::write(&file, b"x").unwrap();
+        let err = remove_path(&file, CleanupAction::Archive, None).unwrap_err();
+        assert!(matches!(err, CleanupError::NoArchiveDir));
+        assert!(file.exists());
+    }
+
+    #[test]
+    fn test_plan_skips_scan_root() {
+        let (tree, [root, ..]) = sample_tree(Path::new("base"));
+        let plan = plan_cleanup(&tree, &[root], CleanupAction::Trash);
+        assert!(plan.items.is_empty());
+        assert_eq!(plan.total_size, 0);
+    }
+
+    #[test]
+    fn test_delete_removes_directory_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(sub.join("nested")).unwrap();
+        std::fs::write(sub.join("nested").join("f.bin"), b"data").unwrap();
+
+        remove_path(&sub, CleanupAction::Delete, None).unwrap();
+        assert!(!sub.exists());
+    }
+
+    #[test]
+    fn test_remove_empty_checks_disk_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let skeleton = dir.path().join("skeleton");
+        std::fs::create_dir_all(skeleton.join("a").join("b")).unwrap();
+        std::fs::create_dir_all(skeleton.join("c")).unwrap();
+        remove_path(&skeleton, CleanupAction::RemoveEmpty, None).unwrap();
+        assert!(!skeleton.exists());
+
+        // A file appeared since the scan: nothing is touched.
+        let busy = dir.path().join("busy");
+        std::fs::create_dir_all(busy.join("a")).unwrap();
+        std::fs::create_dir_all(busy.join("z").join("deep")).unwrap();
+        std::fs::write(busy.join("z").join("deep").join("new.txt"), b"x").unwrap();
+        let err = remove_path(&busy, CleanupAction::RemoveEmpty, None).unwrap_err();
+        assert!(matches!(err, CleanupError::NotEmpty(ref p) if p == &busy));
+        assert!(busy.join("a").is_dir());
+    }
+
+    #[test]
+    fn test_missing_path_counts_as_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let gone = dir.path().join("never-existed");
+        assert!(remove_path(&gone, CleanupAction::Delete, None).is_ok());
+    }
+
+    #[test]
+    fn test_start_cleanup_reports_each_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tree, [_root, docs, _a, _b, c]) = sample_tree(dir.path());
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs").join("a.txt"), b"x").unwrap();
+        std::fs::write(dir.path().join("c.txt"), b"y").unwrap();
+
+        let handle = start_cleanup(plan_cleanup(&tree, &[docs, c], CleanupAction::Delete));
+        let mut removed = Vec::new();
+        loop {
+            match handle.message_rx.recv().unwrap() {
+                CleanupMessage::Item(outcome) => {
+                    assert!(outcome.result.is_ok(), "{:?}", outcome.result);
+                    removed.push(outcome.index);
+                }
+                CleanupMessage::Finished { cancelled, .. } => {
+                    assert!(!cancelled);
+                    break;
+                }
+            }
+        }
+        assert_eq!(removed, vec![docs, c]);
+        assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+    }
+}
@@ -0,0 +1,272 @@
+/// Export a [`CleanupPlan`] as a shell script for review.
+///
+/// The script does exactly what running the plan would do, one command per
+/// item, so it can be inspected, edited or run later by hand.  Paths are
+/// quoted literally — no variable expansion or globbing can change them.
+/// Paths that are not valid Unicode cannot be written into the script
+/// without changing them, so their items are left out as `# skipped` lines.
+use super::{CleanupAction, CleanupPlan};
+use crate::model::size::{format_count, format_size};
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Script dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    /// Windows PowerShell (`.ps1`).
+    PowerShell,
+    /// POSIX `sh` (`.sh`).
+    Shell,
+}
+
+impl ScriptKind {
+    /// The dialect native to the platform this binary was built for.
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            Self::PowerShell
+        } else {
+            Self::Shell
+        }
+    }
+
+    /// File extension without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::PowerShell => "ps1",
+            Self::Shell => "sh",
+        }
+    }
+}
+
+/// Render `plan` as a script in the given dialect.
+pub fn cleanup_script(plan: &CleanupPlan, kind: ScriptKind) -> String {
+    let mut out = String::new();
+    let header = format!(
+        "DiskSleuth cleanup: {} — {} items, {} in {} files",
+        plan.action.label(),
+        plan.items.len(),
+        format_size(plan.total_size),
+        format_count(plan.total_files),
+    );
+
+    match kind {
+        ScriptKind::PowerShell => {
+            let _ = writeln!(out, "# {header}");
+            out.push_str("$ErrorActionPreference = 'Continue'\n");
+            if plan.action == CleanupAction::Trash {
+                out.push_str("Add-Type -AssemblyName Microsoft.VisualBasic\n");
+            }
+        }
+        ScriptKind::Shell => {
+            out.push_str("#!/bin/sh\n");
+            let _ = writeln!(out, "# {header}");
+        }
+    }
+    out.push('\n');
+
+    for item in &plan.items {
+        let _ = writeln!(out, "# {}", format_size(item.size));
+        let Some(src) = quote(&item.path, kind) else {
+            write_skipped(&mut out, &item.path);
+            continue;
+        };
+        match (plan.action, kind) {
+            (CleanupAction::Trash, ScriptKind::PowerShell) => {
+                let method = if item.is_dir {
+                    "DeleteDirectory"
+                } else {
+                    "DeleteFile"
+                };
+                let _ = writeln!(
+                    out,
+                    "[Microsoft.VisualBasic.FileIO.FileSystem]::{method}({src}, 'OnlyErrorDialogs', 'SendToRecycleBin')"
+                );
+            }
+            (CleanupAction::Trash, ScriptKind::Shell) => {
+                let _ = writeln!(out, "gio trash -- {src}");
+            }
+            (CleanupAction::Delete, ScriptKind::PowerShell) => {
+                let _ = writeln!(out, "Remove-Item -LiteralPath {src} -Recurse -Force");
+            }
+            (CleanupAction::Delete, ScriptKind::Shell) => {
+                let _ = writeln!(out, "rm -rf -- {src}");
+            }
+            (CleanupAction::RemoveEmpty, ScriptKind::PowerShell) => {
+                let _ = writeln!(
+                    out,
+                    "if (-not (Get-ChildItem -LiteralPath {src} -Recurse -Force -File)) {{ Remove-Item -LiteralPath {src} -Recurse -Force }}"
+                );
+            }
+            (CleanupAction::RemoveEmpty, ScriptKind::Shell) => {
+                // All or nothing, like the PowerShell branch: a folder that
+                // gained a file since the scan keeps its empty subfolders too.
+                let _ = writeln!(
+                    out,
+                    "[ -z \"$(find {src} ! -type d -print -quit)\" ] && find {src} -depth -type d -delete"
+                );
+            }
+            (CleanupAction::Archive, _) => match plan.archive_destination(item) {
+                Some(dest) => write_archive_move(&mut out, &src, &dest, kind),
+                None => out.push_str("# skipped: no archive folder chosen\n"),
+            },
+        }
+    }
+    out
+}
+
+/// Emit "create parent, then move without overwriting" for one item.
+fn write_archive_move(out: &mut String, src: &str, dest: &Path, kind: ScriptKind) {
+    let parent = dest.parent().unwrap_or(dest);
+    let (Some(parent), Some(dest)) = (quote(parent, kind), quote(dest, kind)) else {
+        write_skipped(out, dest);
+        return;
+    };
+    match kind {
+        ScriptKind::PowerShell => {
+            let _ = writeln!(
+                out,
+                "New-Item -ItemType Directory -Force -Path {parent} | Out-Null"
+            );
+            let _ = writeln!(out, "Move-Item -LiteralPath {src} -Destination {dest}");
+        }
+        ScriptKind::Shell => {
+            // Report an existing destination rather than skip it silently,
+            // as `Move-Item` fails on one.
+            let _ = writeln!(
+                out,
+                "mkdir -p -- {parent} && if [ -e {dest} ] || [ -L {dest} ]; then printf 'exists: %s\\n' {dest} >&2; else mv -- {src} {dest}; fi"
+            );
+        }
+    }
+}
+
+/// Note an item left out because its path cannot be quoted.  The path is
+/// shown `Debug`-escaped so a newline in it cannot end the comment.
+fn write_skipped(out: &mut String, path: &Path) {
+    let _ = writeln!(out, "# skipped, path is not valid Unicode: {path:?}");
+}
+
+/// Quote a path as a single literal argument, or `None` if it is not valid
+/// Unicode and so cannot be written into the script unchanged.
+///
+/// Both dialects use single quotes, inside which nothing is expanded; only
+/// the quote characters need escaping.  PowerShell also ends a
+/// single-quoted string at the typographic quotes U+2018–U+201B, and
+/// escapes each of them, like `'`, by doubling it.
+fn quote(path: &Path, kind: ScriptKind) -> Option<String> {
+    let raw = path.to_str()?;
+    Some(match kind {
+        ScriptKind::PowerShell => {
+            let mut out = String::with_capacity(raw.len() + 2);
+            out.push('\'');
+            for c in raw.chars() {
+                if matches!(c, '\'' | '\u{2018}'..='\u{201B}') {
+                    out.push(c);
+                }
+                out.push(c);
+            }
+            out.push('\'');
+            out
+        }
+        ScriptKind::Shell => format!("'{}'", raw.replace('\'', r"'\''")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleanup::CleanupItem;
+    use crate::model::NodeIndex;
+    use std::path::PathBuf;
+
+    fn plan(action: CleanupAction) -> CleanupPlan {
+        CleanupPlan {
+            action,
+            items: vec![CleanupItem {
+                index: NodeIndex(1),
+                path: PathBuf::from("/data/it's here"),
+                relative: PathBuf::from("it's here"),
+                size: 2048,
+                is_dir: true,
+                files: 3,
+            }],
+            total_size: 2048,
+            total_files: 3,
+            archive_dir: Some(PathBuf::from("/archive")),
+        }
+    }
+
+    #[test]
+    fn test_shell_delete_quotes_single_quotes() {
+        let script = cleanup_script(&plan(CleanupAction::Delete), ScriptKind::Shell);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(
+            script.contains(r"rm -rf -- '/data/it'\''s here'"),
+            "{script}"
+        );
+    }
+
+    #[test]
+    fn test_powershell_trash_uses_recycle_bin() {
+        let script = cleanup_script(&plan(CleanupAction::Trash), ScriptKind::PowerShell);
+        assert!(script.contains("Add-Type -AssemblyName Microsoft.VisualBasic"));
+        assert!(
+            script.contains(
+                "::DeleteDirectory('/data/it''s here', 'OnlyErrorDialogs', 'SendToRecycleBin')"
+            ),
+            "{script}"
+        );
+    }
+
+    #[test]
+    fn test_archive_moves_under_archive_folder() {
+        let script = cleanup_script(&plan(CleanupAction::Archive), ScriptKind::Shell);
+        let dest = quote(&Path::new("/archive").join("it's here"), ScriptKind::Shell).unwrap();
+        assert!(
+            script.contains(&format!(
+                "then printf 'exists: %s\\n' {dest} >&2; else mv -- '/data/it'\\''s here' {dest}; fi\n"
+            )),
+            "{script}"
+        );
+    }
+
+    /// Empty folders are only removed when no file appeared below them.
+    #[test]
+    fn test_shell_remove_empty_checks_for_files_first() {
+        let script = cleanup_script(&plan(CleanupAction::RemoveEmpty), ScriptKind::Shell);
+        let src = r"'/data/it'\''s here'";
+        assert!(
+            script.contains(&format!(
+                "[ -z \"$(find {src} ! -type d -print -quit)\" ] && find {src} -depth -type d -delete\n"
+            )),
+            "{script}"
+        );
+    }
+
+    /// Typographic single quotes also close a PowerShell literal, so each
+    /// is doubled like `'`.
+    #[test]
+    fn test_powershell_doubles_typographic_quotes() {
+        let path = Path::new("C:\\a\u{2019}; Remove-Item -Recurse C:\\ ;\u{2018}\u{201A}\u{201B}");
+        assert_eq!(
+            quote(path, ScriptKind::PowerShell).unwrap(),
+            "'C:\\a\u{2019}\u{2019}; Remove-Item -Recurse C:\\ ;\u{2018}\u{2018}\u{201A}\u{201A}\u{201B}\u{201B}'"
+        );
+    }
+
+    /// A path that is not valid UTF-8 is left out rather than mangled.
+    #[cfg(unix)]
+    #[test]
+    fn test_non_unicode_path_is_skipped() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut plan = plan(CleanupAction::Delete);
+        plan.items[0].path = PathBuf::from(std::ffi::OsStr::from_bytes(b"/data/bad\xff\nname"));
+        let script = cleanup_script(&plan, ScriptKind::Shell);
+        assert!(!script.contains("rm -rf"), "{script}");
+        assert!(
+            script.contains("# skipped, path is not valid Unicode: \"/data/bad\\xFF\\nname\"\n"),
+            "{script}"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+/// Platform trash support.
+///
+/// Windows sends items to the Recycle Bin through `SHFileOperationW`.
+/// Linux follows the freedesktop.org Trash specification: the item is renamed
+/// into `files/` and a matching `.trashinfo` record is written to `info/`, so
+/// desktop file managers can list and restore it.
+use super::CleanupError;
+use std::path::Path;
+
+/// Move `path` to the Recycle Bin without any shell UI.
+#[cfg(windows)]
+pub fn move_to_trash(path: &Path) -> Result<(), CleanupError> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::UI::Shell::{
+        SHFileOperationW, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, FO_DELETE,
+        SHFILEOPSTRUCTW,
+    };
+
+    // The shell needs an absolute path or it deletes without undo.
+    let path = std::path::absolute(path)?;
+    // `pFrom` is a list of NUL-terminated paths ending with an extra NUL.
+    let mut from: Vec<u16> = path.as_os_str().encode_wide().collect();
+    from.extend([0, 0]);
+
+    let mut op = SHFILEOPSTRUCTW {
+        wFunc: FO_DELETE,
+        pFrom: PCWSTR(from.as_ptr()),
+        fFlags: (FOF_ALLOWUNDO.0 | FOF_NOCONFIRMATION.0 | FOF_NOERRORUI.0 | FOF_SILENT.0) as u16,
+        ..Default::default()
+    };
+
+    // SAFETY: `op` is fully initialised and `from` outlives the call.
+    let code = unsafe { SHFileOperationW(&mut op) };
+    if code != 0 {
+        return Err(CleanupError::Shell(code));
+    }
+    if op.fAnyOperationsAborted.as_bool() {
+        return Err(CleanupError::Aborted);
+    }
+    Ok(())
+}
+
+/// Move `path` to the freedesktop.org trash.
+///
+/// The home trash is tried first; if the path lives on another filesystem
+/// the per-volume `$topdir/.Trash-$uid` directory is used instead, since a
+/// trash move must be a rename, never a copy.
+#[cfg(target_os = "linux")]
+pub fn move_to_trash(path: &Path) -> Result<(), CleanupError> {
+    Ok(freedesktop::move_to_trash(path)?)
+}
+
+/// Trash is not implemented on this platform.
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn move_to_trash(_path: &Path) -> Result<(), CleanupError> {
+    Err(CleanupError::TrashUnsupported)
+}
+
+#[cfg(target_os = "linux")]
+mod freedesktop {
+    use chrono::Local;
+    use std::fmt::Write as _;
+    use std::fs::{self, DirBuilder, OpenOptions};
+    use std::io::{self, Write as _};
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    use std::path::{Path, PathBuf};
+
+    /// Give up after this many `name.N` collisions.
+    const MAX_NAME_ATTEMPTS: u32 = 10_000;
+
+    pub(super) fn move_to_trash(path: &Path) -> io::Result<()> {
+        let path = std::path::absolute(path)?;
+        match trash_into(&home_trash()?, &path, &path) {
+            Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+                let topdir = mount_point(&path)?;
+                let trash = topdir.join(format!(".Trash-{}", current_uid()));
+                // Per-volume trashes record paths relative to the volume.
+                let relative = path.strip_prefix(&topdir).unwrap_or(&path);
+                trash_into(&trash, &path, relative)
+            }
+            other => other,
+        }
+    }
+
+    /// `$XDG_DATA_HOME/Trash`, defaulting to `~/.local/share/Trash`.
+    fn home_trash() -> io::Result<PathBuf> {
+        if let Some(data) = std::env::var_os("XDG_DATA_HOME").map(PathBuf::from) {
+            if data.is_absolute() {
+                return Ok(data.join("Trash"));
+            }
+        }
+        let home = std::env::var_os("HOME")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?;
+        Ok(PathBuf::from(home).join(".local/share/Trash"))
+    }
+
+    /// The topmost ancestor of `path` on the same device.
+    fn mount_point(path: &Path) -> io::Result<PathBuf> {
+        let dev = fs::symlink_metadata(path)?.dev();
+        let mut top = path.to_path_buf();
+        while let Some(parent) = top.parent() {
+            if fs::metadata(parent)?.dev() != dev {
+                break;
+            }
+            top = parent.to_path_buf();
+        }
+        Ok(top)
+    }
+
+    /// The real uid of this process.
+    fn current_uid() -> u32 {
+        // SAFETY: `getuid` takes no arguments and always succeeds.
+        unsafe { libc::getuid() }
+    }
+
+    /// Move `path` into `trash_dir`, recording `info_path` as its origin.
+    ///
+    /// The `.trashinfo` file is created with `create_new` first: that
+    /// atomically reserves the name, as the specification requires, before
+    /// the item itself is renamed into `files/`.
+    pub(super) fn trash_into(trash_dir: &Path, path: &Path, info_path: &Path) -> io::Result<()> {
+        let files = trash_dir.join("files");
+        let info = trash_dir.join("info");
+        for dir in [&files, &info] {
+            DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+        }
+
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+        let record = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode(info_path),
+            Local::now().format("%Y-%m-%dT%H:%M:%S"),
+        );
+
+        for attempt in 0..MAX_NAME_ATTEMPTS {
+            let mut trashed = name.to_os_string();
+            if attempt > 0 {
+                trashed.push(format!(".{attempt}"));
+            }
+            let mut info_name = trashed.clone();
+            info_name.push(".trashinfo");
+            let info_file = info.join(info_name);
+
+            let mut file = match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info_file)
+            {
+                Ok(f) => f,
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            };
+
+            let target = files.join(&trashed);
+            // An orphaned entry in files/ without an info record — skip it.
+            if fs::symlink_metadata(&target).is_ok() {
+                drop(file);
+                let _ = fs::remove_file(&info_file);
+                continue;
+            }
+
+            let result = file
+                .write_all(record.as_bytes())
+                .and_then(|()| fs::rename(path, &target));
+            if result.is_err() {
+                let _ = fs::remove_file(&info_file);
+            }
+            return result;
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "no free name left in the trash",
+        ))
+    }
+
+    /// Percent-encode a path for the `Path=` key (RFC 2396, `/` kept).
+    fn percent_encode(path: &Path) -> String {
+        let mut out = String::new();
+        for &b in path.as_os_str().as_bytes() {
+            if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+                out.push(b as char);
+            } else {
+                let _ = write!(out, "%{b:02X}");
+            }
+        }
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_trash_into_writes_info_and_moves_item() {
+            let dir = tempfile::tempdir().unwrap();
+            let trash = dir.path().join("Trash");
+            let victim = dir.path().join("my file.txt");
+            fs::write(&victim, b"bye").unwrap();
+
+            trash_into(&trash, &victim, &victim).unwrap();
+
+            assert!(!victim.exists());
+            assert_eq!(fs::read(trash.join("files/my file.txt")).unwrap(), b"bye");
+            let info = fs::read_to_string(trash.join("info/my file.txt.trashinfo")).unwrap();
+            assert!(info.starts_with("[Trash Info]\n"));
+            assert!(info.contains("my%20file.txt\n"), "{info}");
+            assert!(info.contains("DeletionDate="));
+        }
+
+        #[test]
+        fn test_trash_into_avoids_name_collisions() {
+            let dir = tempfile::tempdir().unwrap();
+            let trash = dir.path().join("Trash");
+            for _ in 0..2 {
+                let victim = dir.path().join("dup");
+                fs::create_dir(&victim).unwrap();
+                trash_into(&trash, &victim, &victim).unwrap();
+            }
+            assert!(trash.join("files/dup").is_dir());
+            assert!(trash.join("files/dup.1").is_dir());
+            assert!(trash.join("info/dup.1.trashinfo").is_file());
+        }
+    }
+}
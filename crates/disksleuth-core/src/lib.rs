@@ -8,10 +8,14 @@
 /// - [`model`] — Arena-allocated file tree and supporting types.
 /// - [`scanner`] — Background filesystem scanning with progress reporting.
 /// - [`analysis`] — Post-scan analysis algorithms (top files, file types, etc.).
+/// - [`cleanup`] — Move to trash / delete permanently, with per-path results.
 /// - [`platform`] — Windows-specific drive enumeration and permission checks.
 /// - [`monitor`] — Live file-write monitor using Windows ReadDirectoryChangesW.
+/// - [`search`] — Background name search (substring, glob, regex) over a completed tree.
 pub mod analysis;
+pub mod cleanup;
 pub mod model;
 pub mod monitor;
 pub mod platform;
 pub mod scanner;
+pub mod search;
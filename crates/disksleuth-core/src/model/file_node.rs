@@ -3,8 +3,10 @@
 /// Nodes are stored in a flat `Vec<FileNode>` for cache-friendly traversal.
 /// Parent-child relationships use indices rather than pointers, which also
 /// makes serialisation trivial and avoids reference-counting overhead.
+use super::metadata::ExtendedMetadata;
+use super::owner::OwnerId;
 use compact_str::CompactString;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Lightweight index into the arena `Vec<FileNode>`.
 ///
@@ -28,6 +30,50 @@ impl NodeIndex {
     }
 }
 
+/// A calendar day in 4 bytes: days since 1970-01-01 (UTC), or no date.
+///
+/// Used for the per-directory age roll-up, where day precision is enough
+/// and a full `Option<SystemTime>` would cost 16 bytes per node.  Orders
+/// like `Option<SystemTime>`: a missing date sorts before every real one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DayStamp(u32);
+
+impl DayStamp {
+    /// No date.
+    pub const NONE: Self = Self(0);
+
+    /// The day `time` falls on; `NONE` for `None` or times before 1970.
+    pub fn from_time(time: Option<SystemTime>) -> Self {
+        let Some(since_epoch) = time.and_then(|t| t.duration_since(UNIX_EPOCH).ok()) else {
+            return Self::NONE;
+        };
+        // Stored one higher so that day 0 (1970-01-01) is not `NONE`.
+        let days = since_epoch.as_secs() / 86_400 + 1;
+        Self(u32::try_from(days).unwrap_or(u32::MAX))
+    }
+
+    /// Midday UTC of the day, which falls on the same calendar date in
+    /// every time zone within ±12 hours; `None` for `NONE`.
+    pub fn to_time(self) -> Option<SystemTime> {
+        let days = u64::from(self.0.checked_sub(1)?);
+        Some(UNIX_EPOCH + Duration::from_secs(days * 86_400 + 43_200))
+    }
+
+    /// `true` if there is no date.
+    pub fn is_none(self) -> bool {
+        self == Self::NONE
+    }
+
+    /// The earlier of two days, ignoring missing ones.
+    pub fn earliest(self, other: Self) -> Self {
+        match (self.is_none(), other.is_none()) {
+            (true, _) => other,
+            (_, true) => self,
+            _ => self.min(other),
+        }
+    }
+}
+
 /// A single file or directory in the tree.
 ///
 /// Stored in a flat arena (`Vec<FileNode>`) for cache efficiency.
@@ -68,6 +114,24 @@ pub struct FileNode {
     /// Last-modified timestamp, used for age analysis.
     pub modified: Option<SystemTime>,
 
+    /// Day of the newest modification among descendant files (a file's
+    /// own `modified`).  Computed in the aggregation pass.
+    pub newest_day: DayStamp,
+
+    /// Day of the oldest modification among descendant files (a file's
+    /// own `modified`).  Computed in the aggregation pass.
+    pub oldest_day: DayStamp,
+
+    /// Owner of the file or directory itself, interned in the tree's
+    /// [`owners`](super::FileTree::owners) table.  A directory's owner does
+    /// not cover its contents; usage per owner counts files only.
+    pub owner: OwnerId,
+
+    /// Creation / access times, permissions, attributes and link target.
+    /// `None` unless the scan recorded extended metadata; boxed so that
+    /// costs one pointer per node.
+    pub extended: Option<Box<ExtendedMetadata>>,
+
     /// Pre-computed percentage of the parent's size (0.0–100.0).
     /// Calculated in the aggregation pass so rendering doesn't repeat the division.
     pub percent_of_parent: f32,
@@ -90,6 +154,10 @@ impl FileNode {
             next_sibling: None,
             descendant_count: 0,
             modified: None,
+            newest_day: DayStamp::NONE,
+            oldest_day: DayStamp::NONE,
+            owner: OwnerId::UNKNOWN,
+            extended: None,
             percent_of_parent: 0.0,
             is_error: false,
         }
@@ -107,6 +175,10 @@ impl FileNode {
             next_sibling: None,
             descendant_count: 0,
             modified: None,
+            newest_day: DayStamp::NONE,
+            oldest_day: DayStamp::NONE,
+            owner: OwnerId::UNKNOWN,
+            extended: None,
             percent_of_parent: 0.0,
             is_error: false,
         }
@@ -124,8 +196,43 @@ impl FileNode {
             next_sibling: None,
             descendant_count: 0,
             modified: None,
+            newest_day: DayStamp::NONE,
+            oldest_day: DayStamp::NONE,
+            owner: OwnerId::UNKNOWN,
+            extended: None,
             percent_of_parent: 0.0,
             is_error: true,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_stamps_order_like_options() {
+        let epoch = DayStamp::from_time(Some(UNIX_EPOCH));
+        assert!(!epoch.is_none(), "1970-01-01 is a real day");
+        assert!(DayStamp::NONE < epoch);
+        assert_eq!(DayStamp::from_time(None), DayStamp::NONE);
+        assert_eq!(
+            DayStamp::from_time(Some(UNIX_EPOCH - Duration::from_secs(1))),
+            DayStamp::NONE
+        );
+
+        // 2024-02-29T23:59:59Z and 2024-03-01T00:00:00Z
+        let leap = DayStamp::from_time(Some(UNIX_EPOCH + Duration::from_secs(1_709_251_199)));
+        let march = DayStamp::from_time(Some(UNIX_EPOCH + Duration::from_secs(1_709_251_200)));
+        assert!(leap < march);
+        assert_eq!(
+            leap.to_time(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_208_000))
+        );
+        assert_eq!(DayStamp::NONE.to_time(), None);
+
+        assert_eq!(leap.earliest(march), leap);
+        assert_eq!(DayStamp::NONE.earliest(march), march);
+        assert_eq!(march.earliest(DayStamp::NONE), march);
+    }
+}
@@ -3,8 +3,21 @@
 /// All nodes live in a single `Vec<FileNode>`. Relationships between nodes
 /// use `NodeIndex` (a thin `u32` wrapper) rather than heap pointers, giving
 /// cache-friendly traversal and trivial serialisation.
-use super::file_node::{FileNode, NodeIndex};
+use super::file_node::{DayStamp, FileNode, NodeIndex};
+use super::old_bytes::OldBytes;
+use super::owner::OwnerTable;
+use super::sort::SortOrder;
 use compact_str::CompactString;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Source of [`FileTree::generation`] values, shared by every tree in the
+/// process so two trees never report the same generation.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
 
 /// The complete file tree produced by a scan.
 #[derive(Debug, Clone)]
@@ -26,6 +39,49 @@ pub struct FileTree {
     /// Set once per aggregation pass so the render thread can display file
     /// counts without iterating millions of nodes every frame.
     pub file_count: u64,
+
+    /// Filesystem path of each scanned root, recorded by the scanner.
+    ///
+    /// Root node names are display names ("C:", "Documents"), so on-disk
+    /// operations need the real path to rebuild a node's location.
+    pub root_paths: Vec<(NodeIndex, PathBuf)>,
+
+    /// Owners referenced by [`FileNode::owner`], filled in by the scanner.
+    pub owners: OwnerTable,
+
+    /// Whether the scanner recorded [`FileNode::extended`] metadata.
+    pub extended_metadata: bool,
+
+    /// Whether the scanner recorded [`FileNode::owner`].
+    pub owners_recorded: bool,
+
+    /// Changes whenever the aggregated values change (every aggregation
+    /// pass).  Unique across trees, so views derived from a tree
+    /// (such as the treemap layout) can cache on it.
+    pub generation: u64,
+}
+
+/// Mapping from old to new node indices after [`FileTree::without_subtrees`].
+///
+/// Stored as a dense `u32` table (one entry per old node) so lookups are O(1)
+/// and the map costs 4 bytes per node.
+#[derive(Debug, Clone)]
+pub struct NodeRemap {
+    map: Vec<u32>,
+}
+
+impl NodeRemap {
+    /// Sentinel for "this node was removed".
+    const REMOVED: u32 = u32::MAX;
+
+    /// The node's index in the new tree, or `None` if it was removed
+    /// (or `old` is out of range).
+    pub fn get(&self, old: NodeIndex) -> Option<NodeIndex> {
+        match self.map.get(old.idx()) {
+            Some(&n) if n != Self::REMOVED => Some(NodeIndex(n)),
+            _ => None,
+        }
+    }
 }
 
 impl FileTree {
@@ -41,6 +97,11 @@ impl FileTree {
             total_size: 0,
             largest_files: Vec::new(),
             file_count: 0,
+            root_paths: Vec::new(),
+            owners: OwnerTable::new(),
+            extended_metadata: false,
+            owners_recorded: false,
+            generation: next_generation(),
         }
     }
 
@@ -59,6 +120,12 @@ impl FileTree {
         idx
     }
 
+    /// Record the filesystem path a root node was scanned from.
+    pub fn set_root_path(&mut self, root: NodeIndex, path: PathBuf) {
+        self.root_paths.retain(|(r, _)| *r != root);
+        self.root_paths.push((root, path));
+    }
+
     /// Attach `child` as a child of `parent`, prepending to the sibling list.
     ///
     /// This is O(1) — new children are inserted at the head of the linked list.
@@ -80,7 +147,8 @@ impl FileTree {
         self.aggregate_sizes_inner(false);
     }
 
-    /// Compute sizes, descendant counts, and percentages in a single bottom-up pass.
+    /// Compute sizes, descendant counts, percentages, and age bounds in a
+    /// single bottom-up pass.
     ///
     /// Because children are always inserted after their parent in the arena
     /// (scan order is parent-first), iterating in *reverse* guarantees that
@@ -111,11 +179,13 @@ impl FileTree {
             } else {
                 file_count += 1;
             }
+            reset_age(node);
         }
         self.file_count = file_count;
 
         // Reverse pass: children before parents.
         for i in (0..self.nodes.len()).rev() {
+            self.propagate_age(i);
             let node = &self.nodes[i];
             if !node.is_dir {
                 // Leaf file — nothing to sum, but propagate to parent.
@@ -163,6 +233,22 @@ impl FileTree {
         if compute_largest {
             self.compute_largest_files(100);
         }
+        self.generation = next_generation();
+    }
+
+    /// Fold node `i`'s age bounds into its parent.
+    ///
+    /// Called in reverse arena order, so `i` is complete when it runs.
+    fn propagate_age(&mut self, i: usize) {
+        let node = &self.nodes[i];
+        let Some(parent_idx) = node.parent else {
+            return;
+        };
+        let (newest, oldest) = (node.newest_day, node.oldest_day);
+        let parent = &mut self.nodes[parent_idx.idx()];
+        // `NONE` sorts first, so `max` already ignores missing days.
+        parent.newest_day = parent.newest_day.max(newest);
+        parent.oldest_day = parent.oldest_day.earliest(oldest);
     }
 
     /// Find the N largest individual files by size.
@@ -221,6 +307,100 @@ impl FileTree {
         segments.join("\\")
     }
 
+    /// Reconstruct the on-disk path of a node.
+    ///
+    /// Unlike [`full_path`](Self::full_path), which joins display names for
+    /// the UI, this starts from the scanned root's real path and joins with
+    /// the platform separator.  Returns `None` if the node's root has no
+    /// recorded path (e.g. hand-built trees).
+    pub fn fs_path(&self, index: NodeIndex) -> Option<PathBuf> {
+        let mut segments = Vec::new();
+        let mut current = index;
+        while let Some(parent) = self.nodes[current.idx()].parent {
+            segments.push(self.nodes[current.idx()].name.as_str());
+            current = parent;
+        }
+        let (_, root_path) = self.root_paths.iter().find(|(r, _)| *r == current)?;
+        let mut path = root_path.clone();
+        path.extend(segments.iter().rev());
+        Some(path)
+    }
+
+    /// Build a copy of this tree with the given subtrees removed, then
+    /// re-aggregate sizes.
+    ///
+    /// The arena is compacted rather than tombstoned so every consumer that
+    /// iterates `nodes` (analysis, search, file counts) stays correct without
+    /// special cases.  Parent-before-child order is preserved, so the
+    /// aggregation invariant holds.  The returned [`NodeRemap`] translates
+    /// indices held elsewhere (selection, navigation history).
+    pub fn without_subtrees(&self, removed: &[NodeIndex]) -> (FileTree, NodeRemap) {
+        let n = self.nodes.len();
+
+        // Forward pass: a node is dropped if it or any ancestor was removed.
+        // Parents always precede children, so one pass suffices.
+        let mut dropped = vec![false; n];
+        for idx in removed {
+            if idx.idx() < n {
+                dropped[idx.idx()] = true;
+            }
+        }
+        let mut map = vec![NodeRemap::REMOVED; n];
+        let mut next = 0u32;
+        for i in 0..n {
+            if let Some(p) = self.nodes[i].parent {
+                dropped[i] |= dropped[p.idx()];
+            }
+            if !dropped[i] {
+                map[i] = next;
+                next += 1;
+            }
+        }
+        let remap = NodeRemap { map };
+
+        let mut tree = FileTree::with_capacity(next as usize);
+        for (i, node) in self.nodes.iter().enumerate() {
+            if dropped[i] {
+                continue;
+            }
+            let mut copy = node.clone();
+            copy.parent = node.parent.and_then(|p| remap.get(p));
+            // Skip removed siblings so the child lists stay intact.
+            copy.first_child = self.next_kept(node.first_child, &dropped, &remap);
+            copy.next_sibling = self.next_kept(node.next_sibling, &dropped, &remap);
+            tree.nodes.push(copy);
+        }
+        tree.roots = self.roots.iter().filter_map(|r| remap.get(*r)).collect();
+        tree.root_paths = self
+            .root_paths
+            .iter()
+            .filter_map(|(r, p)| remap.get(*r).map(|r| (r, p.clone())))
+            .collect();
+        tree.owners = self.owners.clone();
+        tree.extended_metadata = self.extended_metadata;
+        tree.owners_recorded = self.owners_recorded;
+        tree.aggregate_sizes();
+        (tree, remap)
+    }
+
+    /// First node at or after `start` in a sibling chain that survives
+    /// removal, translated to its new index.
+    fn next_kept(
+        &self,
+        start: Option<NodeIndex>,
+        dropped: &[bool],
+        remap: &NodeRemap,
+    ) -> Option<NodeIndex> {
+        let mut cursor = start;
+        while let Some(idx) = cursor {
+            if !dropped[idx.idx()] {
+                return remap.get(idx);
+            }
+            cursor = self.nodes[idx.idx()].next_sibling;
+        }
+        None
+    }
+
     /// Get direct children of a node as a collected Vec, sorted by size descending.
     pub fn children_sorted_by_size(&self, parent: NodeIndex) -> Vec<NodeIndex> {
         let mut children = Vec::new();
@@ -241,6 +421,24 @@ impl FileTree {
         children
     }
 
+    /// Get direct children of a node ordered by `order`, taking the old
+    /// bytes for [`SortColumn::OldBytes`](super::SortColumn::OldBytes)
+    /// from `old_bytes`.
+    ///
+    /// Uses a stable sort so siblings with equal keys keep scan order and do
+    /// not shuffle between rebuilds of the tree view.
+    pub fn children_sorted(
+        &self,
+        parent: NodeIndex,
+        order: &SortOrder,
+        old_bytes: &OldBytes,
+    ) -> Vec<NodeIndex> {
+        let mut children = self.children(parent);
+        children
+            .sort_by(|&a, &b| order.compare(old_bytes.keyed(self, a), old_bytes.keyed(self, b)));
+        children
+    }
+
     /// Get direct children of a node (unsorted).
     pub fn children(&self, parent: NodeIndex) -> Vec<NodeIndex> {
         let mut children = Vec::new();
@@ -271,9 +469,45 @@ impl FileTree {
     }
 }
 
+/// Reset a node's age fields before a bottom-up pass: directories start
+/// empty, files seed them from their own timestamp.
+fn reset_age(node: &mut FileNode) {
+    let day = if node.is_dir {
+        DayStamp::NONE
+    } else {
+        DayStamp::from_time(node.modified)
+    };
+    node.newest_day = day;
+    node.oldest_day = day;
+}
+
+// ── Test fixtures ───────────────────────────────────────────────────────────
+
+#[cfg(test)]
+impl FileTree {
+    /// Add an empty directory `name` under `parent`.
+    pub(crate) fn add_test_dir(&mut self, parent: NodeIndex, name: &str) -> NodeIndex {
+        let idx = self.add_node(FileNode::new_dir(CompactString::new(name), Some(parent)));
+        self.add_child(parent, idx);
+        idx
+    }
+
+    /// Add a file `name` of `size` bytes under `parent`.
+    pub(crate) fn add_test_file(&mut self, parent: NodeIndex, name: &str, size: u64) -> NodeIndex {
+        let idx = self.add_node(FileNode::new_file(
+            CompactString::new(name),
+            size,
+            Some(parent),
+        ));
+        self.add_child(parent, idx);
+        idx
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn test_tree_aggregation() {
@@ -351,4 +585,153 @@ mod tests {
         assert_eq!(sorted[1], big);
         assert_eq!(sorted[2], small);
     }
+
+    #[test]
+    fn test_children_sorted_by_name_mixed() {
+        use crate::model::sort::SortColumn;
+
+        let mut tree = FileTree::with_capacity(4);
+        let root = tree.add_root(CompactString::new("C:"));
+        let b = tree.add_node(FileNode::new_file(
+            CompactString::new("b.txt"),
+            10,
+            Some(root),
+        ));
+        tree.add_child(root, b);
+        let dir = tree.add_node(FileNode::new_dir(CompactString::new("Zeta"), Some(root)));
+        tree.add_child(root, dir);
+        let a = tree.add_node(FileNode::new_file(
+            CompactString::new("A.txt"),
+            5,
+            Some(root),
+        ));
+        tree.add_child(root, a);
+        tree.aggregate_sizes();
+
+        let mut order = SortOrder {
+            column: SortColumn::Name,
+            descending: false,
+            folders_first: false,
+        };
+        let old = OldBytes::default();
+        assert_eq!(tree.children_sorted(root, &order, &old), vec![a, b, dir]);
+
+        order.folders_first = true;
+        assert_eq!(tree.children_sorted(root, &order, &old), vec![dir, a, b]);
+    }
+
+    /// root(C:) -> dir(Users) -> (a.txt: 100, b.txt: 200), plus c.txt: 50 at root.
+    fn removal_tree() -> (FileTree, [NodeIndex; 5]) {
+        let mut tree = FileTree::with_capacity(6);
+        let root = tree.add_root(CompactString::new("C:"));
+        let dir = tree.add_node(FileNode::new_dir(CompactString::new("Users"), Some(root)));
+        tree.add_child(root, dir);
+        let a = tree.add_node(FileNode::new_file(
+            CompactString::new("a.txt"),
+            100,
+            Some(dir),
+        ));
+        tree.add_child(dir, a);
+        let b = tree.add_node(FileNode::new_file(
+            CompactString::new("b.txt"),
+            200,
+            Some(dir),
+        ));
+        tree.add_child(dir, b);
+        let c = tree.add_node(FileNode::new_file(
+            CompactString::new("c.txt"),
+            50,
+            Some(root),
+        ));
+        tree.add_child(root, c);
+        tree.aggregate_sizes();
+        (tree, [root, dir, a, b, c])
+    }
+
+    #[test]
+    fn test_without_subtrees_removes_file_and_reaggregates() {
+        let (tree, [root, dir, a, b, c]) = removal_tree();
+        let (pruned, remap) = tree.without_subtrees(&[b]);
+
+        assert_eq!(pruned.len(), 4);
+        assert!(remap.get(b).is_none());
+        let new_dir = remap.get(dir).unwrap();
+        assert_eq!(pruned.node(new_dir).size, 100);
+        assert_eq!(pruned.node(new_dir).descendant_count, 1);
+        assert_eq!(pruned.node(remap.get(root).unwrap()).size, 150);
+        assert_eq!(pruned.children(new_dir), vec![remap.get(a).unwrap()]);
+        assert_eq!(pruned.file_count, 2);
+        assert!(remap.get(c).is_some());
+    }
+
+    #[test]
+    fn test_without_subtrees_removes_whole_directory() {
+        let (tree, [root, dir, a, _b, c]) = removal_tree();
+        let (pruned, remap) = tree.without_subtrees(&[dir]);
+
+        assert_eq!(pruned.len(), 2);
+        assert!(remap.get(a).is_none(), "descendants go with their folder");
+        let new_root = remap.get(root).unwrap();
+        assert_eq!(pruned.total_size, 50);
+        assert_eq!(pruned.children(new_root), vec![remap.get(c).unwrap()]);
+        assert_eq!(pruned.largest_files.len(), 1);
+    }
+
+    #[test]
+    fn test_fs_path_uses_recorded_root_path() {
+        let (mut tree, [root, _dir, a, _b, _c]) = removal_tree();
+        assert!(tree.fs_path(a).is_none(), "no root path recorded yet");
+
+        tree.set_root_path(root, PathBuf::from("scan_root"));
+        let expected: PathBuf = ["scan_root", "Users", "a.txt"].iter().collect();
+        assert_eq!(tree.fs_path(a), Some(expected));
+        assert_eq!(tree.fs_path(root), Some(PathBuf::from("scan_root")));
+    }
+
+    /// Directories carry the days of their newest and oldest descendant
+    /// modification, ignoring files without a date.
+    #[test]
+    fn test_age_aggregation() {
+        let days_ago = |d: u64| SystemTime::now() - Duration::from_secs(d * 86_400);
+        let day = |n: NodeIndex, tree: &FileTree| DayStamp::from_time(tree.node(n).modified);
+        let (mut tree, [root, dir, a, b, c]) = removal_tree();
+        tree.nodes[a.idx()].modified = Some(days_ago(10));
+        tree.nodes[b.idx()].modified = Some(days_ago(400));
+        tree.aggregate_sizes();
+
+        let users = tree.node(dir);
+        assert_eq!(users.newest_day, day(a, &tree));
+        assert_eq!(users.oldest_day, day(b, &tree));
+        assert_eq!(tree.node(c).newest_day, DayStamp::NONE);
+        assert_eq!(tree.node(root).oldest_day, day(b, &tree));
+
+        tree.nodes[c.idx()].modified = Some(days_ago(800));
+        tree.aggregate_sizes();
+        assert_eq!(tree.node(root).oldest_day, day(c, &tree));
+        assert_eq!(tree.node(root).newest_day, day(a, &tree));
+
+        let (pruned, _) = tree.without_subtrees(&[c]);
+        assert_eq!(pruned.node(pruned.roots[0]).oldest_day, day(b, &tree));
+    }
+
+    #[test]
+    fn test_generation_changes_on_every_update() {
+        let mut tree = FileTree::with_capacity(4);
+        let root = tree.add_root("C:".into());
+        let file = tree.add_node(FileNode::new_file("a.txt".into(), 10, Some(root)));
+        tree.add_child(root, file);
+
+        let initial = tree.generation;
+        tree.aggregate_sizes_live();
+        let live = tree.generation;
+        tree.aggregate_sizes();
+        let full = tree.generation;
+        assert!(initial < live && live < full);
+
+        // A clone reports the same generation; a separate tree never does.
+        assert_eq!(tree.clone().generation, full);
+        assert_ne!(FileTree::with_capacity(0).generation, full);
+        let (pruned, _) = tree.without_subtrees(&[file]);
+        assert_ne!(pruned.generation, full);
+    }
 }
@@ -0,0 +1,97 @@
+/// Extended per-node metadata, recorded only when the scan asks for it.
+///
+/// A [`FileNode`](super::FileNode) holds this behind an
+/// `Option<Box<ExtendedMetadata>>`, so a scan without
+/// [`ScanOptions::extended_metadata`](crate::scanner::ScanOptions) pays one
+/// null pointer per node rather than the full record.
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Windows-style attribute flags.  On Unix, `read_only` means no write bit
+/// is set and `hidden` means a dot-file; `system` and `compressed` stay off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FileAttributes {
+    pub read_only: bool,
+    pub hidden: bool,
+    pub system: bool,
+    pub compressed: bool,
+}
+
+impl FileAttributes {
+    /// Labels of the attributes that are set, in a fixed order.
+    pub fn labels(self) -> Vec<&'static str> {
+        [
+            (self.read_only, "Read-only"),
+            (self.hidden, "Hidden"),
+            (self.system, "System"),
+            (self.compressed, "Compressed"),
+        ]
+        .into_iter()
+        .filter_map(|(set, label)| set.then_some(label))
+        .collect()
+    }
+}
+
+/// Timestamps, permissions and attributes beyond what every node stores.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExtendedMetadata {
+    /// Creation (birth) time, where the filesystem records one.
+    pub created: Option<SystemTime>,
+    /// Last access time.  Often coarse: Linux `relatime` only updates it
+    /// once a day, and Windows may not update it at all.
+    pub accessed: Option<SystemTime>,
+    /// Unix permission bits (`st_mode & 0o7777`); `None` on Windows.
+    pub mode: Option<u32>,
+    pub attributes: FileAttributes,
+    /// Where a symbolic link (or Windows junction) points.
+    pub symlink_target: Option<PathBuf>,
+}
+
+/// `ls -l`-style permission string for Unix `mode` bits: `rwxr-x---`, with
+/// `s`/`S` for setuid/setgid and `t`/`T` for the sticky bit.
+pub fn format_mode(mode: u32) -> String {
+    let mut out = String::with_capacity(9);
+    // (read, write, execute) bits and the special bit sharing the x slot.
+    let triads = [
+        (0o400, 0o200, 0o100, 0o4000, 's'),
+        (0o040, 0o020, 0o010, 0o2000, 's'),
+        (0o004, 0o002, 0o001, 0o1000, 't'),
+    ];
+    for (r, w, x, special, mark) in triads {
+        out.push(if mode & r != 0 { 'r' } else { '-' });
+        out.push(if mode & w != 0 { 'w' } else { '-' });
+        out.push(match (mode & x != 0, mode & special != 0) {
+            (true, true) => mark,
+            (false, true) => mark.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_strings() {
+        assert_eq!(format_mode(0o755), "rwxr-xr-x");
+        assert_eq!(format_mode(0o640), "rw-r-----");
+        assert_eq!(format_mode(0o4755), "rwsr-xr-x");
+        assert_eq!(format_mode(0o1777), "rwxrwxrwt");
+        assert_eq!(format_mode(0o2644), "rw-r-Sr--");
+        assert_eq!(format_mode(0), "---------");
+    }
+
+    #[test]
+    fn attribute_labels() {
+        let attrs = FileAttributes {
+            read_only: true,
+            compressed: true,
+            ..Default::default()
+        };
+        assert_eq!(attrs.labels(), ["Read-only", "Compressed"]);
+        assert!(FileAttributes::default().labels().is_empty());
+    }
+}
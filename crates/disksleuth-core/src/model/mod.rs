@@ -3,7 +3,16 @@
 /// Re-exports the arena-allocated tree structure and supporting types.
 pub mod file_node;
 pub mod file_tree;
+pub mod metadata;
+pub mod old_bytes;
+pub mod owner;
 pub mod size;
+pub mod sort;
+pub mod time;
 
-pub use file_node::{FileNode, NodeIndex};
-pub use file_tree::FileTree;
+pub use file_node::{DayStamp, FileNode, NodeIndex};
+pub use file_tree::{FileTree, NodeRemap};
+pub use metadata::{format_mode, ExtendedMetadata, FileAttributes};
+pub use old_bytes::{OldBytes, DEFAULT_OLD_CUTOFF_DAYS};
+pub use owner::{Owner, OwnerId, OwnerTable};
+pub use sort::{SortColumn, SortOrder};
@@ -0,0 +1,116 @@
+/// Bytes in files older than a cutoff, per node.
+///
+/// Kept beside the [`FileTree`] rather than in every [`FileNode`]: the
+/// figure is only shown when the tree view's age columns are on, and the
+/// cutoff can change after the scan.  Recomputing this table is one O(n)
+/// pass that never touches the arena, so a tree shared with background
+/// workers does not have to be copied for a new cutoff.
+use super::file_node::{FileNode, NodeIndex};
+use super::file_tree::FileTree;
+use std::time::{Duration, SystemTime};
+
+/// Default age, in days, beyond which a file's bytes count as old.
+pub const DEFAULT_OLD_CUTOFF_DAYS: u64 = 365;
+
+/// Per-node bytes in descendant files not modified for `cutoff_days`.
+#[derive(Debug, Clone, Default)]
+pub struct OldBytes {
+    cutoff_days: u64,
+    /// [`FileTree::generation`] of the tree the table was computed from.
+    generation: u64,
+    bytes: Vec<u64>,
+}
+
+impl OldBytes {
+    /// Total the old bytes of every node of `tree`, bottom-up.
+    pub fn compute(tree: &FileTree, cutoff_days: u64) -> Self {
+        let age = Duration::from_secs(cutoff_days.saturating_mul(86_400));
+        let cutoff = SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let mut bytes: Vec<u64> = tree
+            .nodes
+            .iter()
+            .map(|node| match node.modified {
+                Some(m) if !node.is_dir && m <= cutoff => node.size,
+                _ => 0,
+            })
+            .collect();
+        // Parents precede children in the arena, so a reverse pass sees
+        // every node complete before folding it into its parent.
+        for (i, node) in tree.nodes.iter().enumerate().rev() {
+            if let Some(parent) = node.parent {
+                bytes[parent.idx()] += bytes[i];
+            }
+        }
+
+        Self {
+            cutoff_days,
+            generation: tree.generation,
+            bytes,
+        }
+    }
+
+    /// Whether this table still describes `tree` at `cutoff_days`.
+    pub fn is_current(&self, tree: &FileTree, cutoff_days: u64) -> bool {
+        self.generation == tree.generation && self.cutoff_days == cutoff_days
+    }
+
+    /// The cutoff the table was computed for.
+    pub fn cutoff_days(&self) -> u64 {
+        self.cutoff_days
+    }
+
+    /// Old bytes of `index`; 0 for nodes the table does not cover.
+    pub fn get(&self, index: NodeIndex) -> u64 {
+        self.bytes.get(index.idx()).copied().unwrap_or(0)
+    }
+
+    /// `node` paired with its old bytes, as [`SortOrder::compare`] takes it.
+    ///
+    /// [`SortOrder::compare`]: super::SortOrder::compare
+    pub fn keyed<'a>(&self, tree: &'a FileTree, index: NodeIndex) -> (&'a FileNode, u64) {
+        (tree.node(index), self.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+
+    /// Directories total the bytes past the cutoff; a new cutoff is a new
+    /// table, and any change to the tree makes the old one stale.
+    #[test]
+    fn totals_bytes_past_the_cutoff() {
+        let days_ago = |d: u64| Some(SystemTime::now() - Duration::from_secs(d * 86_400));
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("C:"));
+        let dir = tree.add_node(FileNode::new_dir(CompactString::new("Users"), Some(root)));
+        tree.add_child(root, dir);
+        let mut add = |parent: NodeIndex, size: u64, days: u64| {
+            let mut node = FileNode::new_file(CompactString::new("f"), size, Some(parent));
+            node.modified = days_ago(days);
+            let idx = tree.add_node(node);
+            tree.add_child(parent, idx);
+        };
+        add(dir, 100, 10);
+        add(dir, 200, 400);
+        add(root, 50, 800);
+        tree.aggregate_sizes();
+
+        let old = OldBytes::compute(&tree, DEFAULT_OLD_CUTOFF_DAYS);
+        assert!(old.is_current(&tree, DEFAULT_OLD_CUTOFF_DAYS));
+        assert_eq!(old.get(dir), 200);
+        assert_eq!(old.get(root), 250);
+        assert_eq!(old.get(NodeIndex(99)), 0);
+
+        let older = OldBytes::compute(&tree, 500);
+        assert!(!older.is_current(&tree, DEFAULT_OLD_CUTOFF_DAYS));
+        assert_eq!((older.get(dir), older.get(root)), (0, 50));
+
+        tree.aggregate_sizes();
+        assert!(!older.is_current(&tree, 500));
+    }
+}
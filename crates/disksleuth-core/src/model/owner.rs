@@ -0,0 +1,116 @@
+/// File ownership, interned per tree.
+///
+/// Each node carries a 4-byte [`OwnerId`] instead of the owner itself; the
+/// tree's [`OwnerTable`] maps ids back to the uid (Unix) or SID string
+/// (Windows) they stand for.  A volume rarely has more than a handful of
+/// distinct owners, so the table stays tiny however many files it covers.
+use compact_str::CompactString;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Owner of a file or directory as reported by the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Owner {
+    /// Numeric Unix user id.
+    Uid(u32),
+    /// Windows security identifier in string form (`S-1-5-21-…`).
+    Sid(CompactString),
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uid(uid) => write!(f, "uid {uid}"),
+            Self::Sid(sid) => f.write_str(sid),
+        }
+    }
+}
+
+/// Index into a tree's [`OwnerTable`].
+///
+/// `OwnerId::UNKNOWN` (the default) marks error nodes, entries whose owner
+/// could not be read, and every node of a scan that did not record owners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct OwnerId(pub u32);
+
+impl OwnerId {
+    /// No recorded owner.
+    pub const UNKNOWN: OwnerId = OwnerId(0);
+
+    /// `true` unless this is [`OwnerId::UNKNOWN`].
+    #[inline]
+    pub fn is_known(self) -> bool {
+        self != Self::UNKNOWN
+    }
+}
+
+/// Interning table from [`OwnerId`] to [`Owner`].
+#[derive(Debug, Clone, Default)]
+pub struct OwnerTable {
+    /// `owners[i]` is the owner of `OwnerId(i + 1)`.
+    owners: Vec<Owner>,
+    ids: HashMap<Owner, OwnerId>,
+}
+
+impl OwnerTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `owner`, adding it to the table on first sight.
+    pub fn intern(&mut self, owner: Owner) -> OwnerId {
+        if let Some(&id) = self.ids.get(&owner) {
+            return id;
+        }
+        self.owners.push(owner.clone());
+        let id = OwnerId(self.owners.len() as u32);
+        self.ids.insert(owner, id);
+        id
+    }
+
+    /// Owner behind `id`, or `None` for [`OwnerId::UNKNOWN`] and ids from
+    /// another table.
+    pub fn get(&self, id: OwnerId) -> Option<&Owner> {
+        (id.0 as usize)
+            .checked_sub(1)
+            .and_then(|i| self.owners.get(i))
+    }
+
+    /// Number of distinct owners.
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    /// Every owner with its id, in the order first seen.
+    pub fn iter(&self) -> impl Iterator<Item = (OwnerId, &Owner)> {
+        self.owners
+            .iter()
+            .enumerate()
+            .map(|(i, o)| (OwnerId(i as u32 + 1), o))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_reuses_ids() {
+        let mut table = OwnerTable::new();
+        let alice = table.intern(Owner::Uid(1000));
+        let system = table.intern(Owner::Sid(CompactString::new("S-1-5-18")));
+        assert_eq!(table.intern(Owner::Uid(1000)), alice);
+        assert_ne!(alice, system);
+        assert!(alice.is_known() && !OwnerId::UNKNOWN.is_known());
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(alice), Some(&Owner::Uid(1000)));
+        assert_eq!(table.get(OwnerId::UNKNOWN), None);
+        assert_eq!(table.get(OwnerId(9)), None);
+        assert_eq!(table.get(system).unwrap().to_string(), "S-1-5-18");
+    }
+}
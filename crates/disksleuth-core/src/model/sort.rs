@@ -0,0 +1,200 @@
+/// Sibling ordering for tree views.
+///
+/// The tree view lets the user pick a column and direction; this module turns
+/// that choice into a comparator over two `FileNode`s.  Kept in the core
+/// crate so the ordering rules are unit-tested without a UI.
+use super::file_node::FileNode;
+use std::cmp::Ordering;
+
+/// The attribute siblings are ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortColumn {
+    /// Case-insensitive name.
+    Name,
+    /// Logical size (aggregated for directories).
+    #[default]
+    Size,
+    /// Allocated (on-disk) size.
+    Allocated,
+    /// Share of the parent's size.
+    Percent,
+    /// Number of descendant files.
+    Files,
+    /// Last-modified timestamp; nodes without one sort as oldest.
+    Modified,
+    /// Day of the newest descendant modification (a file's own for files).
+    Newest,
+    /// Day of the oldest descendant modification (a file's own for files).
+    Oldest,
+    /// Bytes older than the age cutoff, from an [`OldBytes`](super::OldBytes)
+    /// table.
+    OldBytes,
+}
+
+impl SortColumn {
+    /// Direction used when the user first selects this column.
+    ///
+    /// Names read naturally A→Z; every numeric column is most useful
+    /// largest/newest first.
+    pub fn default_descending(self) -> bool {
+        !matches!(self, Self::Name)
+    }
+}
+
+/// A complete sort specification for sibling lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    /// Column to order by.
+    pub column: SortColumn,
+    /// `true` for descending order.
+    pub descending: bool,
+    /// Keep directories above files regardless of `column`.
+    pub folders_first: bool,
+}
+
+impl Default for SortOrder {
+    /// Directories first, then size descending — the historical tree order.
+    fn default() -> Self {
+        Self {
+            column: SortColumn::Size,
+            descending: true,
+            folders_first: true,
+        }
+    }
+}
+
+impl SortOrder {
+    /// Compare two sibling nodes, each paired with its old bytes (which
+    /// are kept beside the tree, see [`OldBytes::keyed`](super::OldBytes::keyed)).
+    ///
+    /// Ties compare `Equal` so a stable sort keeps arena (scan) order.
+    pub fn compare(&self, (a, a_old): (&FileNode, u64), (b, b_old): (&FileNode, u64)) -> Ordering {
+        if self.folders_first && a.is_dir != b.is_dir {
+            // Not affected by `descending`: folders always stay on top.
+            return b.is_dir.cmp(&a.is_dir);
+        }
+
+        let ord = match self.column {
+            SortColumn::Name => cmp_name_ignore_case(&a.name, &b.name),
+            SortColumn::Size => a.size.cmp(&b.size),
+            SortColumn::Allocated => a.allocated_size.cmp(&b.allocated_size),
+            SortColumn::Percent => a.percent_of_parent.total_cmp(&b.percent_of_parent),
+            SortColumn::Files => a.descendant_count.cmp(&b.descendant_count),
+            SortColumn::Modified => a.modified.cmp(&b.modified),
+            SortColumn::Newest => a.newest_day.cmp(&b.newest_day),
+            SortColumn::Oldest => a.oldest_day.cmp(&b.oldest_day),
+            SortColumn::OldBytes => a_old.cmp(&b_old),
+        };
+
+        if self.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
+
+/// Case-insensitive name comparison without allocating lowercase copies.
+fn cmp_name_ignore_case(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compact_str::CompactString;
+    use std::time::{Duration, SystemTime};
+
+    fn file(name: &str, size: u64) -> FileNode {
+        FileNode::new_file(CompactString::new(name), size, None)
+    }
+
+    #[test]
+    fn default_order_puts_folders_first_then_largest() {
+        let order = SortOrder::default();
+        let dir = FileNode::new_dir(CompactString::new("small_dir"), None);
+        let big = file("big.bin", 1_000);
+        assert_eq!(order.compare((&dir, 0), (&big, 0)), Ordering::Less);
+        assert_eq!(
+            order.compare((&big, 0), (&file("small.txt", 10), 0)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn name_order_ignores_case() {
+        let order = SortOrder {
+            column: SortColumn::Name,
+            descending: false,
+            folders_first: false,
+        };
+        assert_eq!(
+            order.compare((&file("alpha", 0), 0), (&file("Beta", 0), 0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            order.compare((&file("README", 0), 0), (&file("readme", 0), 0)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn mixed_mode_sorts_folders_with_files() {
+        let order = SortOrder {
+            column: SortColumn::Size,
+            descending: true,
+            folders_first: false,
+        };
+        let mut dir = FileNode::new_dir(CompactString::new("dir"), None);
+        dir.size = 5;
+        assert_eq!(
+            order.compare((&file("big", 50), 0), (&dir, 0)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn ascending_reverses_but_keeps_folders_on_top() {
+        let order = SortOrder {
+            column: SortColumn::Size,
+            descending: false,
+            folders_first: true,
+        };
+        let dir = FileNode::new_dir(CompactString::new("dir"), None);
+        assert_eq!(order.compare((&dir, 0), (&file("a", 1), 0)), Ordering::Less);
+        assert_eq!(
+            order.compare((&file("a", 1), 0), (&file("b", 2), 0)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn missing_modified_sorts_as_oldest() {
+        let order = SortOrder {
+            column: SortColumn::Modified,
+            descending: true,
+            folders_first: false,
+        };
+        let mut recent = file("recent", 0);
+        recent.modified = Some(SystemTime::now());
+        let mut old = file("old", 0);
+        old.modified = Some(SystemTime::now() - Duration::from_secs(86_400));
+        let unknown = file("unknown", 0);
+
+        assert_eq!(order.compare((&recent, 0), (&old, 0)), Ordering::Less);
+        assert_eq!(order.compare((&old, 0), (&unknown, 0)), Ordering::Less);
+    }
+
+    #[test]
+    fn old_bytes_come_from_the_side_table() {
+        let order = SortOrder {
+            column: SortColumn::OldBytes,
+            descending: true,
+            folders_first: false,
+        };
+        let (small, big) = (file("small", 10), file("big", 1_000));
+        assert_eq!(order.compare((&small, 500), (&big, 0)), Ordering::Less);
+    }
+}
@@ -0,0 +1,100 @@
+//! Timestamp formatting utilities -- compact calendar dates for columns.
+//!
+//! Dates are shown in the local time zone, matching what Explorer displays
+//! for the same file.
+
+use chrono::{DateTime, Local, TimeZone};
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format a timestamp as an ISO-8601 calendar date (`2024-03-15`, local time).
+///
+/// Timestamps before 1970 (or too far in the future for the calendar) are
+/// clamped to the epoch; they only occur on files with corrupt metadata.
+pub fn format_date(time: SystemTime) -> String {
+    format_date_in(time, &Local)
+}
+
+/// [`format_date`] in an explicit time zone.
+fn format_date_in<Tz: TimeZone>(time: SystemTime, tz: &Tz) -> String
+where
+    Tz::Offset: Display,
+{
+    let utc = time
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|d| i64::try_from(d.as_secs()).ok())
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    utc.with_timezone(tz).format("%Y-%m-%d").to_string()
+}
+
+/// Format an age in days compactly: `12 d`, `5 mo`, `2.4 y`.
+///
+/// Days up to two months, months up to two years, then years with one
+/// decimal, so neighbouring rows stay comparable at a glance.
+pub fn format_age_days(days: u64) -> String {
+    match days {
+        0..60 => format!("{days} d"),
+        60..730 => format!("{} mo", days * 12 / 365),
+        _ => format!("{:.1} y", days as f64 / 365.25),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use std::time::Duration;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn local(y: i32, m: u32, d: u32) -> SystemTime {
+        Local
+            .with_ymd_and_hms(y, m, d, 12, 0, 0)
+            .single()
+            .expect("unambiguous local time")
+            .into()
+    }
+
+    #[test]
+    fn test_format_date_epoch() {
+        assert_eq!(format_date_in(UNIX_EPOCH, &Utc), "1970-01-01");
+    }
+
+    #[test]
+    fn test_format_date_leap_day() {
+        assert_eq!(format_date(local(2024, 2, 29)), "2024-02-29");
+    }
+
+    #[test]
+    fn test_format_date_pads_month_and_day() {
+        assert_eq!(format_date(local(1999, 1, 5)), "1999-01-05");
+    }
+
+    #[test]
+    fn test_format_date_year_end() {
+        // 1999-12-31T23:59:59Z is already the new year one hour east.
+        let t = at(946_684_799);
+        assert_eq!(format_date_in(t, &Utc), "1999-12-31");
+        let cet = FixedOffset::east_opt(3_600).unwrap();
+        assert_eq!(format_date_in(t, &cet), "2000-01-01");
+    }
+
+    #[test]
+    fn test_format_date_before_epoch_is_clamped() {
+        let t = UNIX_EPOCH - Duration::from_secs(86_400);
+        assert_eq!(format_date_in(t, &Utc), "1970-01-01");
+        assert_eq!(format_date(t), format_date(UNIX_EPOCH));
+    }
+
+    #[test]
+    fn test_format_age_days_units() {
+        assert_eq!(format_age_days(0), "0 d");
+        assert_eq!(format_age_days(59), "59 d");
+        assert_eq!(format_age_days(365), "12 mo");
+        assert_eq!(format_age_days(1096), "3.0 y");
+    }
+}
@@ -1,13 +1,14 @@
 /// Drive enumeration using the Windows API.
 ///
 /// Lists all available drives with their type, label, total/free space,
-/// and filesystem name.
+/// and filesystem name, and looks up the cluster size of a volume.
 use crate::model::size;
 use std::ffi::OsString;
-use std::os::windows::ffi::OsStringExt;
-use std::path::PathBuf;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
 use windows::Win32::Storage::FileSystem::{
-    GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDriveStringsW, GetVolumeInformationW,
+    GetDiskFreeSpaceExW, GetDiskFreeSpaceW, GetDriveTypeW, GetLogicalDriveStringsW,
+    GetVolumeInformationW, GetVolumePathNameW,
 };
 
 // Drive type constants from the Windows API.
@@ -185,3 +186,33 @@ pub fn enumerate_drives() -> Vec<DriveInfo> {
 
     drives
 }
+
+/// Cluster (allocation unit) size in bytes of the volume holding `path`.
+///
+/// Works for folders as well as drive roots: the volume's mount point is
+/// looked up first.  Returns `None` if the volume cannot be queried.
+pub fn cluster_size(path: &Path) -> Option<u64> {
+    let path_wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut root = [0u16; 261];
+    unsafe { GetVolumePathNameW(windows::core::PCWSTR(path_wide.as_ptr()), &mut root) }.ok()?;
+
+    let mut sectors_per_cluster: u32 = 0;
+    let mut bytes_per_sector: u32 = 0;
+    unsafe {
+        GetDiskFreeSpaceW(
+            windows::core::PCWSTR(root.as_ptr()),
+            Some(&mut sectors_per_cluster as *mut u32),
+            Some(&mut bytes_per_sector as *mut u32),
+            None,
+            None,
+        )
+    }
+    .ok()?;
+
+    let size = u64::from(sectors_per_cluster) * u64::from(bytes_per_sector);
+    (size > 0).then_some(size)
+}
@@ -0,0 +1,80 @@
+/// Extended metadata capture — creation / access times, permissions,
+/// attributes and symlink targets.
+///
+/// Built from the `Metadata` the scanner already read, so the only extra
+/// syscall is `readlink` for symbolic links.  Windows attributes come from
+/// `GetFileAttributes` flags; Unix has mode bits instead, and derives
+/// read-only and hidden from them and from the file name.
+use crate::model::{ExtendedMetadata, FileAttributes};
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Extended metadata of the file or directory at `path`, whose metadata
+/// (not following links) the caller already read.
+pub fn extended_metadata(path: &Path, metadata: &Metadata) -> ExtendedMetadata {
+    let symlink_target = if metadata.file_type().is_symlink() || is_reparse_point(metadata) {
+        std::fs::read_link(path).ok()
+    } else {
+        None
+    };
+    ExtendedMetadata {
+        created: metadata.created().ok(),
+        accessed: metadata.accessed().ok(),
+        mode: mode_bits(metadata),
+        attributes: attributes(path, metadata),
+        symlink_target,
+    }
+}
+
+#[cfg(windows)]
+fn attributes(_path: &Path, metadata: &Metadata) -> FileAttributes {
+    use std::os::windows::fs::MetadataExt;
+    use windows::Win32::Storage::FileSystem::{
+        FILE_ATTRIBUTE_COMPRESSED, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY,
+        FILE_ATTRIBUTE_SYSTEM,
+    };
+
+    let flags = metadata.file_attributes();
+    FileAttributes {
+        read_only: flags & FILE_ATTRIBUTE_READONLY.0 != 0,
+        hidden: flags & FILE_ATTRIBUTE_HIDDEN.0 != 0,
+        system: flags & FILE_ATTRIBUTE_SYSTEM.0 != 0,
+        compressed: flags & FILE_ATTRIBUTE_COMPRESSED.0 != 0,
+    }
+}
+
+#[cfg(not(windows))]
+fn attributes(path: &Path, metadata: &Metadata) -> FileAttributes {
+    FileAttributes {
+        read_only: metadata.permissions().readonly(),
+        hidden: path
+            .file_name()
+            .is_some_and(|n| n.as_encoded_bytes().starts_with(b".")),
+        ..Default::default()
+    }
+}
+
+/// Junctions and other name-surrogate reparse points that `is_symlink`
+/// does not report.
+#[cfg(windows)]
+fn is_reparse_point(metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_REPARSE_POINT;
+    metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT.0 != 0
+}
+
+#[cfg(not(windows))]
+fn is_reparse_point(_metadata: &Metadata) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn mode_bits(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn mode_bits(_metadata: &Metadata) -> Option<u32> {
+    None
+}
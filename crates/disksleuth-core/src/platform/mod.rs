@@ -1,7 +1,11 @@
 /// Platform-specific functionality — Windows drive enumeration,
-/// permission checks, and system utilities.
+/// permission checks, file owners, extended metadata, and system utilities.
 pub mod drives;
+pub mod metadata;
+pub mod owners;
 pub mod permissions;
 
-pub use drives::{enumerate_drives, DriveInfo, DriveType};
+pub use drives::{cluster_size, enumerate_drives, DriveInfo, DriveType};
+pub use metadata::extended_metadata;
+pub use owners::{file_owner, owner_name};
 pub use permissions::is_elevated;
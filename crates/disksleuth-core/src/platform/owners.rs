@@ -0,0 +1,175 @@
+/// File owners and account names.
+///
+/// Windows reads the owner SID from the file's security descriptor and
+/// names it through `LookupAccountSidW`.  Unix takes the uid from the
+/// metadata the scanner already has and names it through `getpwuid_r`, so
+/// NSS sources such as LDAP are consulted too.
+use crate::model::Owner;
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Owner of the file at `path`, whose metadata the caller already read.
+#[cfg(windows)]
+pub fn file_owner(path: &Path, _metadata: &Metadata) -> Option<Owner> {
+    use compact_str::CompactString;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{LocalFree, ERROR_SUCCESS, HLOCAL};
+    use windows::Win32::Security::Authorization::{
+        ConvertSidToStringSidW, GetNamedSecurityInfoW, SE_FILE_OBJECT,
+    };
+    use windows::Win32::Security::{OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID};
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut sid = PSID::default();
+    let mut descriptor = PSECURITY_DESCRIPTOR::default();
+    // SAFETY: `wide` is NUL-terminated and outlives the call; `sid` points
+    // into `descriptor`, which is freed below once the SID is copied out.
+    unsafe {
+        let status = GetNamedSecurityInfoW(
+            PCWSTR(wide.as_ptr()),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            Some(&mut sid as *mut PSID),
+            None,
+            None,
+            None,
+            &mut descriptor,
+        );
+        if status != ERROR_SUCCESS {
+            return None;
+        }
+        let mut text = PWSTR::null();
+        let converted = ConvertSidToStringSidW(sid, &mut text).is_ok();
+        let owner = converted
+            .then(|| text.to_string().ok())
+            .flatten()
+            .map(|s| Owner::Sid(CompactString::new(s)));
+        if converted {
+            let _ = LocalFree(HLOCAL(text.0.cast()));
+        }
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        owner
+    }
+}
+
+/// Owner of the file at `path`, whose metadata the caller already read.
+#[cfg(unix)]
+pub fn file_owner(_path: &Path, metadata: &Metadata) -> Option<Owner> {
+    use std::os::unix::fs::MetadataExt;
+    Some(Owner::Uid(metadata.uid()))
+}
+
+/// Owners are not read on this platform.
+#[cfg(not(any(windows, unix)))]
+pub fn file_owner(_path: &Path, _metadata: &Metadata) -> Option<Owner> {
+    None
+}
+
+/// Account name of `owner` ("DOMAIN\user" on Windows), or `None` if the
+/// account database does not know it.
+///
+/// Lookups may be slow (a domain SID can need a round trip to a domain
+/// controller), so call this off the UI thread and cache the result.
+#[cfg(windows)]
+pub fn owner_name(owner: &Owner) -> Option<String> {
+    use windows::core::{HSTRING, PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Authorization::ConvertStringSidToSidW;
+    use windows::Win32::Security::{LookupAccountSidW, PSID, SID_NAME_USE};
+
+    let Owner::Sid(text) = owner else {
+        return None;
+    };
+    let text = HSTRING::from(text.as_str());
+    let mut sid = PSID::default();
+    // SAFETY: `sid` is allocated by `ConvertStringSidToSidW` and freed with
+    // `LocalFree`; the name buffers outlive the lookup.
+    unsafe {
+        ConvertStringSidToSidW(&text, &mut sid).ok()?;
+        let mut name = [0u16; 256];
+        let mut domain = [0u16; 256];
+        let (mut name_len, mut domain_len) = (name.len() as u32, domain.len() as u32);
+        let mut kind = SID_NAME_USE::default();
+        let found = LookupAccountSidW(
+            PCWSTR::null(),
+            sid,
+            PWSTR(name.as_mut_ptr()),
+            &mut name_len,
+            PWSTR(domain.as_mut_ptr()),
+            &mut domain_len,
+            &mut kind,
+        )
+        .is_ok();
+        let _ = LocalFree(HLOCAL(sid.0));
+        if !found {
+            return None;
+        }
+        let name = String::from_utf16_lossy(&name[..name_len as usize]);
+        Some(match domain_len {
+            0 => name,
+            len => format!(
+                "{}\\{name}",
+                String::from_utf16_lossy(&domain[..len as usize])
+            ),
+        })
+    }
+}
+
+/// Account name of `owner`, or `None` if the account database does not
+/// know it.
+#[cfg(unix)]
+pub fn owner_name(owner: &Owner) -> Option<String> {
+    use std::ffi::CStr;
+
+    /// Largest buffer offered to `getpwuid_r` before giving up.
+    const MAX_BUF: usize = 1 << 20;
+
+    let Owner::Uid(uid) = owner else {
+        return None;
+    };
+    let mut buf = vec![0 as libc::c_char; 1024];
+    loop {
+        let mut entry = std::mem::MaybeUninit::<libc::passwd>::uninit();
+        let mut result: *mut libc::passwd = std::ptr::null_mut();
+        // SAFETY: every pointer refers to a live local of the right type, and
+        // `buf.len()` is the buffer's real length.
+        let status = unsafe {
+            libc::getpwuid_r(
+                *uid,
+                entry.as_mut_ptr(),
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut result,
+            )
+        };
+        match status {
+            0 if result.is_null() => return None,
+            0 => {
+                // SAFETY: on success `result` points at `entry`, whose
+                // `pw_name` is a NUL-terminated string inside `buf`.
+                let name = unsafe { CStr::from_ptr((*result).pw_name) };
+                return Some(name.to_string_lossy().into_owned());
+            }
+            libc::ERANGE if buf.len() < MAX_BUF => buf.resize(buf.len() * 2, 0),
+            _ => return None,
+        }
+    }
+}
+
+/// Account names are not looked up on this platform.
+#[cfg(not(any(windows, unix)))]
+pub fn owner_name(_owner: &Owner) -> Option<String> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_root_and_unknown_uids() {
+        assert_eq!(owner_name(&Owner::Uid(0)).as_deref(), Some("root"));
+        assert_eq!(owner_name(&Owner::Uid(u32::MAX - 7)), None);
+    }
+}
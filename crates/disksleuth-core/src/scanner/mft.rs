@@ -25,10 +25,12 @@
 /// 4. Build a `HashMap<u64, NodeIndex>` mapping MFT reference → tree node.
 /// 5. Wire up parent → child relationships.
 /// 6. Stat files for sizes, then run `FileTree::aggregate_sizes()`.
-use crate::model::{FileNode, FileTree, NodeIndex};
+use crate::model::{ExtendedMetadata, FileNode, FileTree, NodeIndex, Owner};
+use crate::platform::metadata::extended_metadata;
+use crate::platform::owners::file_owner;
 use crate::platform::permissions::is_elevated;
 use crate::scanner::progress::ScanProgress;
-use crate::scanner::LiveTree;
+use crate::scanner::{LiveTree, ScanOptions};
 use compact_str::CompactString;
 use crossbeam_channel::Sender;
 use std::collections::HashMap;
@@ -155,9 +157,11 @@ pub fn is_mft_available(path: &Path) -> bool {
 /// Scan using MFT direct reading via `FSCTL_ENUM_USN_DATA`.
 ///
 /// Builds the full `FileTree` from MFT records and sends it via the progress
-/// channel.
+/// channel.  Extended metadata, if `options` asks for it, is recorded for
+/// files only: directories are never stat'ed.
 pub fn scan_mft(
     root_path: PathBuf,
+    options: ScanOptions,
     progress_tx: Sender<ScanProgress>,
     cancel_flag: Arc<AtomicBool>,
     live_tree: LiveTree,
@@ -409,7 +413,7 @@ pub fn scan_mft(
     // Step 3: Build the FileTree from MFT records.
     let root_display = format!("{}:", drive_letter.to_uppercase());
     let (tree, error_count) =
-        build_tree_from_mft(&records, &root_display, &root_path, &progress_tx);
+        build_tree_from_mft(&records, &root_display, &root_path, options, &progress_tx);
 
     if cancel_flag.load(Ordering::Relaxed) {
         let _ = progress_tx.send(ScanProgress::Cancelled);
@@ -471,24 +475,40 @@ fn get_ntfs_volume_data(handle: HANDLE) -> Option<NTFS_VOLUME_DATA_BUFFER> {
     }
 }
 
+/// Result of stat-ing one node in phase C of [`build_tree_from_mft`].
+#[derive(Default)]
+struct FileStat {
+    index: usize,
+    size: u64,
+    modified: Option<std::time::SystemTime>,
+    owner: Option<Owner>,
+    extended: Option<Box<ExtendedMetadata>>,
+    is_error: bool,
+}
+
 /// Build a `FileTree` from raw MFT records.
 ///
 /// Strategy:
 /// 1. Create all nodes upfront (one per MFT record).
 /// 2. Map `file_ref → NodeIndex` in a HashMap.
 /// 3. Wire parent→child relationships using `parent_ref` lookups.
-/// 4. Stat files for sizes in parallel with rayon (USN records omit file size).
+/// 4. Stat files for sizes in parallel with rayon (USN records omit file size),
+///    and directories too when owners or extended metadata are recorded.
 /// 5. Run `aggregate_sizes()`.
 fn build_tree_from_mft(
     records: &[MftEntry],
     root_display: &str,
     root_path: &Path,
+    options: ScanOptions,
     progress_tx: &Sender<ScanProgress>,
 ) -> (FileTree, u64) {
     let mut tree = FileTree::with_capacity(records.len() + 1);
     let mut error_count: u64 = 0;
 
     let root_idx = tree.add_root(CompactString::new(root_display));
+    tree.set_root_path(root_idx, root_path.to_path_buf());
+    tree.extended_metadata = options.extended_metadata;
+    tree.owners_recorded = options.owners;
 
     let mut ref_to_idx: HashMap<u64, NodeIndex> = HashMap::with_capacity(records.len() + 1);
 
@@ -546,7 +566,9 @@ fn build_tree_from_mft(
     //
     // Parallelised with rayon: `full_path` is read-only, `fs::metadata` is
     // a syscall that benefits from concurrent execution on SSDs/NVMe.
-    // Results are written back in a single sequential pass.
+    // Results are written back in a single sequential pass.  Directories
+    // are only stat'ed for their owner and extended metadata, as in the
+    // parallel scanner.
     let total_files = tree.nodes.iter().filter(|n| !n.is_dir).count();
     let _ = progress_tx.send(ScanProgress::Update {
         files_found: total_files as u64,
@@ -555,15 +577,15 @@ fn build_tree_from_mft(
         current_path: format!("Reading file sizes... 0/{total_files}"),
     });
 
-    // Collect file indices (read-only pass, no allocation per node).
-    let file_indices: Vec<usize> = (0..tree.nodes.len())
-        .filter(|&i| !tree.nodes[i].is_dir)
+    // Collect the indices to stat (read-only pass, no allocation per node).
+    let stat_indices: Vec<usize> = (0..tree.nodes.len())
+        .filter(|&i| options.owners || options.extended_metadata || !tree.nodes[i].is_dir)
         .collect();
 
-    // Parallel stat: (index, size, allocated_size, modified, is_error)
+    // Parallel stat, one `FileStat` per node.
     // `tree` is borrowed immutably here; `full_path` only reads nodes.
     use rayon::prelude::*;
-    let stats: Vec<(usize, u64, Option<std::time::SystemTime>, bool)> = file_indices
+    let stats: Vec<FileStat> = stat_indices
         .par_iter()
         .map(|&i| {
             let rel_path = tree.full_path(NodeIndex::new(i));
@@ -581,20 +603,58 @@ fn build_tree_from_mft(
                     &rel_path
                 )
             };
-            match std::fs::metadata(&full_path) {
-                Ok(meta) => (i, meta.len(), meta.modified().ok(), false),
-                Err(_) => (i, 0u64, None, true),
+            let Ok(meta) = std::fs::metadata(&full_path) else {
+                return FileStat {
+                    index: i,
+                    is_error: true,
+                    ..Default::default()
+                };
+            };
+            let path = Path::new(&full_path);
+            // `metadata` follows links; attributes and the link target must
+            // come from the link itself.
+            let extended = if options.extended_metadata {
+                std::fs::symlink_metadata(path)
+                    .ok()
+                    .map(|link| Box::new(extended_metadata(path, &link)))
+            } else {
+                None
+            };
+            FileStat {
+                index: i,
+                size: meta.len(),
+                modified: meta.modified().ok(),
+                owner: options.owners.then(|| file_owner(path, &meta)).flatten(),
+                extended,
+                is_error: false,
             }
         })
         .collect();
 
     // Sequential write-back pass.
     let mut phase_c_errors: u64 = 0;
-    for (i, size, modified, is_error) in stats {
-        tree.nodes[i].size = size;
-        tree.nodes[i].allocated_size = size;
-        tree.nodes[i].modified = modified;
-        if is_error {
+    for stat in stats {
+        let i = stat.index;
+        if tree.nodes[i].is_dir {
+            // Sizes come from aggregation; an unreadable directory keeps
+            // its children.
+            if let Some(owner) = stat.owner {
+                tree.nodes[i].owner = tree.owners.intern(owner);
+            }
+            if options.extended_metadata {
+                tree.nodes[i].modified = stat.modified;
+                tree.nodes[i].extended = stat.extended;
+            }
+            continue;
+        }
+        tree.nodes[i].size = stat.size;
+        tree.nodes[i].allocated_size = stat.size;
+        tree.nodes[i].modified = stat.modified;
+        tree.nodes[i].extended = stat.extended;
+        if let Some(owner) = stat.owner {
+            tree.nodes[i].owner = tree.owners.intern(owner);
+        }
+        if stat.is_error {
             // Mark the node so the UI can render the error icon and
             // the details panel can show the "access denied" badge.
             tree.nodes[i].is_error = true;
@@ -53,10 +53,19 @@ impl ScanHandle {
     }
 }
 
-/// Start a new scan on a background thread.
-///
-/// Returns a `ScanHandle` for receiving progress, accessing the live tree,
-/// and requesting cancellation.
+/// What a scan records beyond names, sizes and modification times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScanOptions {
+    /// Record [`ExtendedMetadata`](crate::model::ExtendedMetadata) — creation
+    /// and access times, permissions, attributes, symlink targets — for every
+    /// node.  Costs an allocation per node, so it is off by default.
+    pub extended_metadata: bool,
+    /// Record the [`Owner`](crate::model::Owner) of every file and directory.
+    /// On Windows this reads each one's security descriptor, so it is off by
+    /// default.
+    pub owners: bool,
+}
+
 /// Maximum number of progress messages that may queue up in the channel.
 ///
 /// The UI drains this channel once per frame (~60 fps). At 60 fps a burst of
@@ -65,7 +74,16 @@ impl ScanHandle {
 /// the scanner stalls briefly rather than consuming unbounded heap.
 pub const PROGRESS_CHANNEL_CAPACITY: usize = 4_096;
 
+/// Start a new scan with the default [`ScanOptions`] on a background thread.
+///
+/// Returns a `ScanHandle` for receiving progress, accessing the live tree,
+/// and requesting cancellation.
 pub fn start_scan(root_path: PathBuf) -> ScanHandle {
+    start_scan_with(root_path, ScanOptions::default())
+}
+
+/// Start a new scan recording what `options` asks for.
+pub fn start_scan_with(root_path: PathBuf, options: ScanOptions) -> ScanHandle {
     let (progress_tx, progress_rx) =
         crossbeam_channel::bounded::<ScanProgress>(PROGRESS_CHANNEL_CAPACITY);
     let cancel_flag = Arc::new(AtomicBool::new(false));
@@ -90,6 +108,7 @@ pub fn start_scan(root_path: PathBuf) -> ScanHandle {
                 });
                 mft::scan_mft(
                     root_path.clone(),
+                    options,
                     progress_tx.clone(),
                     cancel_clone.clone(),
                     tree_clone.clone(),
@@ -103,7 +122,13 @@ pub fn start_scan(root_path: PathBuf) -> ScanHandle {
                         is_mft: false,
                         is_elevated,
                     });
-                    parallel::scan_parallel(root_path, progress_tx, cancel_clone, tree_clone);
+                    parallel::scan_parallel(
+                        root_path,
+                        options,
+                        progress_tx,
+                        cancel_clone,
+                        tree_clone,
+                    );
                 }
             } else {
                 info!("Using parallel directory walker (Tier 2)");
@@ -111,7 +136,7 @@ pub fn start_scan(root_path: PathBuf) -> ScanHandle {
                     is_mft: false,
                     is_elevated,
                 });
-                parallel::scan_parallel(root_path, progress_tx, cancel_clone, tree_clone);
+                parallel::scan_parallel(root_path, options, progress_tx, cancel_clone, tree_clone);
             }
         })
         .expect("failed to spawn scanner thread");
@@ -18,9 +18,16 @@
 /// `NodeIndex(arena_base + position_in_pending_vec)`.  Directory entries are
 /// registered in `dir_map` with their pre-computed index immediately, so child
 /// entries processed in the same batch find their parent without an extra lock.
-use crate::model::{FileNode, NodeIndex};
+///
+/// With [`ScanOptions::owners`], owners are read with each entry's metadata
+/// and interned into the tree's owner table during the flush, under the
+/// same lock.  Directories are only stat'ed when owners or
+/// [`ScanOptions::extended_metadata`] are wanted; with the latter every node
+/// gets its [`ExtendedMetadata`](crate::model::ExtendedMetadata).
+use crate::model::{FileNode, NodeIndex, Owner};
+use crate::platform::{extended_metadata, file_owner};
 use crate::scanner::progress::ScanProgress;
-use crate::scanner::LiveTree;
+use crate::scanner::{LiveTree, ScanOptions};
 use compact_str::CompactString;
 use crossbeam_channel::Sender;
 use std::collections::HashMap;
@@ -40,6 +47,8 @@ const BATCH_SIZE: usize = 2_000;
 /// A node buffered in the local pending vec before being flushed under one lock.
 struct PendingEntry {
     node: FileNode,
+    /// Owner of the entry, interned into the tree when flushed.
+    owner: Option<Owner>,
     parent_idx: NodeIndex,
     /// Pre-computed index this entry will receive on insertion.
     /// Equals `arena_base + position_in_pending_vec` at push time.
@@ -57,7 +66,10 @@ fn flush_pending(live_tree: &LiveTree, pending: &mut Vec<PendingEntry>) -> usize
         return 0;
     }
     let mut tree = live_tree.write();
-    for pe in pending.drain(..) {
+    for mut pe in pending.drain(..) {
+        if let Some(owner) = pe.owner {
+            pe.node.owner = tree.owners.intern(owner);
+        }
         let idx = tree.add_node(pe.node);
         debug_assert_eq!(
             idx, pe.pre_idx,
@@ -74,6 +86,7 @@ fn flush_pending(live_tree: &LiveTree, pending: &mut Vec<PendingEntry>) -> usize
 /// tree each frame to show a real-time view while the scan runs.
 pub fn scan_parallel(
     root_path: PathBuf,
+    options: ScanOptions,
     progress_tx: Sender<ScanProgress>,
     cancel_flag: Arc<AtomicBool>,
     live_tree: LiveTree,
@@ -87,7 +100,24 @@ pub fn scan_parallel(
     // Create the root node in the shared tree.
     let root_idx = {
         let mut tree = live_tree.write();
-        tree.add_root(CompactString::new(&root_name))
+        let root = tree.add_root(CompactString::new(&root_name));
+        tree.set_root_path(root, root_path.clone());
+        tree.extended_metadata = options.extended_metadata;
+        tree.owners_recorded = options.owners;
+        if options.extended_metadata || options.owners {
+            if let Ok(meta) = std::fs::symlink_metadata(&root_path) {
+                let owner = options.owners.then(|| file_owner(&root_path, &meta));
+                if let Some(owner) = owner.flatten() {
+                    tree.nodes[root.idx()].owner = tree.owners.intern(owner);
+                }
+                if options.extended_metadata {
+                    let node = &mut tree.nodes[root.idx()];
+                    node.modified = meta.modified().ok();
+                    node.extended = Some(Box::new(extended_metadata(&root_path, &meta)));
+                }
+            }
+        }
+        root
     };
 
     // Map from directory path to its NodeIndex in the arena.
@@ -210,22 +240,40 @@ pub fn scan_parallel(
         let file_name = entry.file_name().to_string_lossy();
 
         if entry.file_type().is_dir() {
-            let dir_node =
+            let mut dir_node =
                 FileNode::new_dir(CompactString::new(file_name.as_ref()), Some(parent_idx));
+            let mut owner = None;
+            if options.extended_metadata || options.owners {
+                if let Ok(meta) = std::fs::symlink_metadata(&path) {
+                    owner = options.owners.then(|| file_owner(&path, &meta)).flatten();
+                    if options.extended_metadata {
+                        dir_node.modified = meta.modified().ok();
+                        dir_node.extended = Some(Box::new(extended_metadata(&path, &meta)));
+                    }
+                }
+            }
 
             // Register in dir_map immediately with the pre-computed index so that
             // child entries in the same batch can find this directory as their parent.
             dir_map.insert(path.clone(), pre_idx);
             pending.push(PendingEntry {
                 node: dir_node,
+                owner,
                 parent_idx,
                 pre_idx,
             });
             dirs_found += 1;
         } else {
             // Stat the file outside the lock — this is the expensive syscall.
-            let (size, modified) = match std::fs::symlink_metadata(&path) {
-                Ok(meta) => (meta.len(), meta.modified().ok()),
+            let (size, modified, owner, extended) = match std::fs::symlink_metadata(&path) {
+                Ok(meta) => (
+                    meta.len(),
+                    meta.modified().ok(),
+                    options.owners.then(|| file_owner(&path, &meta)).flatten(),
+                    options
+                        .extended_metadata
+                        .then(|| Box::new(extended_metadata(&path, &meta))),
+                ),
                 Err(err) => {
                     error_count += 1;
                     // Error node goes through the batch like any other entry.
@@ -236,6 +284,7 @@ pub fn scan_parallel(
                     );
                     pending.push(PendingEntry {
                         node: error_node,
+                        owner: None,
                         parent_idx,
                         pre_idx,
                     });
@@ -258,8 +307,10 @@ pub fn scan_parallel(
                 Some(parent_idx),
             );
             file_node.modified = modified;
+            file_node.extended = extended;
             pending.push(PendingEntry {
                 node: file_node,
+                owner,
                 parent_idx,
                 pre_idx,
             });
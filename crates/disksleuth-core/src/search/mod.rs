@@ -0,0 +1,237 @@
+/// Search module — incremental name search over a completed scan.
+///
+/// Searches run on a background thread against a shared, immutable
+/// `Arc<FileTree>` so the UI stays responsive on multi-million-node trees.
+/// Each keystroke in the UI cancels the previous search and starts a new
+/// one; cancellation is cooperative and checked every
+/// [`CANCEL_CHECK_INTERVAL`] nodes.
+pub mod pattern;
+
+pub use pattern::{NamePattern, PatternError, SearchMode};
+
+use crate::analysis::worker::{spawn_oneshot, WorkerHandle};
+use crate::model::{FileTree, NodeIndex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Maximum number of hits returned by a single search.
+///
+/// The largest matches are kept; the total match count is still reported so
+/// the UI can show "showing 10 000 of 1 234 567".
+pub const MAX_SEARCH_RESULTS: usize = 10_000;
+
+/// Number of nodes visited between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
+/// A single search match.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Index into the file tree arena.
+    pub index: NodeIndex,
+    /// Full path (reconstructed).
+    pub path: String,
+    /// Logical size in bytes (aggregated for directories).
+    pub size: u64,
+    /// `true` if the match is a directory.
+    pub is_dir: bool,
+}
+
+/// The outcome of a completed search.
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    /// Matches sorted by size descending, capped at the requested limit.
+    pub hits: Vec<SearchHit>,
+    /// Total number of matching nodes, including those beyond the cap.
+    pub total_matches: u64,
+    /// Wall-clock time spent searching.
+    pub duration: Duration,
+}
+
+/// Messages sent from the search thread to the UI.
+#[derive(Debug)]
+pub enum SearchMessage {
+    /// The search finished; results are attached.
+    Complete(SearchResults),
+    /// The search was cancelled before finishing.
+    Cancelled,
+}
+
+/// Handle to a running or completed search; receives exactly one
+/// [`SearchMessage`] when it ends.
+pub type SearchHandle = WorkerHandle<SearchMessage>;
+
+/// Start a name search on a background thread.
+///
+/// The tree is shared read-only; the thread holds its `Arc` only for the
+/// duration of the search.
+pub fn start_search(tree: Arc<FileTree>, pattern: NamePattern, max_results: usize) -> SearchHandle {
+    spawn_oneshot("disksleuth-search", move |cancel| {
+        match search_tree(&tree, &pattern, max_results, cancel) {
+            Some(results) => SearchMessage::Complete(results),
+            None => SearchMessage::Cancelled,
+        }
+    })
+}
+
+/// Search every node name in `tree` against `pattern`.
+///
+/// Returns `None` if `cancel` was set before the pass finished.  Hits are
+/// ranked by size descending; only the top `max_results` have their full
+/// path reconstructed, so the cost of a very broad query (e.g. `*`) stays
+/// proportional to the cap rather than to the number of matches.
+pub fn search_tree(
+    tree: &FileTree,
+    pattern: &NamePattern,
+    max_results: usize,
+    cancel: &AtomicBool,
+) -> Option<SearchResults> {
+    let start = Instant::now();
+    let mut matches: Vec<NodeIndex> = Vec::new();
+
+    for (i, node) in tree.nodes.iter().enumerate() {
+        if i.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if pattern.is_match(&node.name) {
+            matches.push(NodeIndex::new(i));
+        }
+    }
+
+    let total_matches = matches.len() as u64;
+    let by_size_desc = |a: &NodeIndex, b: &NodeIndex| tree.node(*b).size.cmp(&tree.node(*a).size);
+
+    // Partial sort: O(n) select + O(k log k) sort of top-k, vs O(n log n) full sort.
+    if max_results == 0 {
+        matches.clear();
+    } else if matches.len() > max_results {
+        matches.select_nth_unstable_by(max_results - 1, by_size_desc);
+        matches.truncate(max_results);
+    }
+    matches.sort_unstable_by(by_size_desc);
+
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let hits = matches
+        .into_iter()
+        .map(|idx| {
+            let node = tree.node(idx);
+            SearchHit {
+                index: idx,
+                path: tree.full_path(idx),
+                size: node.size,
+                is_dir: node.is_dir,
+            }
+        })
+        .collect();
+
+    Some(SearchResults {
+        hits,
+        total_matches,
+        duration: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::FileNode;
+    use compact_str::CompactString;
+
+    /// root/
+    ///   docs/ (dir)
+    ///     report.pdf  (300)
+    ///     Report.docx (100)
+    ///   report.bak    (200)
+    ///   photo.jpg     (50)
+    fn sample_tree() -> FileTree {
+        let mut tree = FileTree::with_capacity(8);
+        let root = tree.add_root(CompactString::new("C:"));
+        let docs = tree.add_node(FileNode::new_dir(CompactString::new("docs"), Some(root)));
+        tree.add_child(root, docs);
+        for (name, size, parent) in [
+            ("report.pdf", 300, docs),
+            ("Report.docx", 100, docs),
+            ("report.bak", 200, root),
+            ("photo.jpg", 50, root),
+        ] {
+            let f = tree.add_node(FileNode::new_file(
+                CompactString::new(name),
+                size,
+                Some(parent),
+            ));
+            tree.add_child(parent, f);
+        }
+        tree.aggregate_sizes();
+        tree
+    }
+
+    #[test]
+    fn finds_all_matches_sorted_by_size() {
+        let tree = sample_tree();
+        let pattern = NamePattern::new("report", SearchMode::Substring).unwrap();
+        let results = search_tree(&tree, &pattern, 100, &AtomicBool::new(false)).unwrap();
+
+        assert_eq!(results.total_matches, 3);
+        let sizes: Vec<u64> = results.hits.iter().map(|h| h.size).collect();
+        assert_eq!(sizes, vec![300, 200, 100]);
+        assert_eq!(results.hits[0].path, "C:\\docs\\report.pdf");
+    }
+
+    #[test]
+    fn directories_can_match() {
+        let tree = sample_tree();
+        let pattern = NamePattern::new("doc*", SearchMode::Glob).unwrap();
+        let results = search_tree(&tree, &pattern, 100, &AtomicBool::new(false)).unwrap();
+
+        assert_eq!(results.total_matches, 1);
+        assert!(results.hits[0].is_dir);
+        assert_eq!(results.hits[0].size, 400);
+    }
+
+    #[test]
+    fn max_results_caps_hits_but_not_total() {
+        let tree = sample_tree();
+        let pattern = NamePattern::new("report", SearchMode::Substring).unwrap();
+        let results = search_tree(&tree, &pattern, 1, &AtomicBool::new(false)).unwrap();
+
+        assert_eq!(results.total_matches, 3);
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(results.hits[0].size, 300, "largest match must be kept");
+    }
+
+    /// Regression guard: `max_results == 0` must not underflow the
+    /// `select_nth_unstable_by(max_results - 1)` pivot.
+    #[test]
+    fn zero_max_results_does_not_panic() {
+        let tree = sample_tree();
+        let pattern = NamePattern::new("report", SearchMode::Substring).unwrap();
+        let results = search_tree(&tree, &pattern, 0, &AtomicBool::new(false)).unwrap();
+        assert!(results.hits.is_empty());
+        assert_eq!(results.total_matches, 3);
+    }
+
+    #[test]
+    fn cancelled_search_returns_none() {
+        let tree = sample_tree();
+        let pattern = NamePattern::new("report", SearchMode::Substring).unwrap();
+        assert!(search_tree(&tree, &pattern, 100, &AtomicBool::new(true)).is_none());
+    }
+
+    #[test]
+    fn background_search_delivers_complete() {
+        let tree = Arc::new(sample_tree());
+        let pattern = NamePattern::new(r"\.jpg$", SearchMode::Regex).unwrap();
+        let handle = start_search(tree, pattern, MAX_SEARCH_RESULTS);
+
+        match handle.result_rx.recv_timeout(Duration::from_secs(10)) {
+            Ok(SearchMessage::Complete(results)) => {
+                assert_eq!(results.total_matches, 1);
+                assert_eq!(results.hits[0].path, "C:\\photo.jpg");
+            }
+            other => panic!("expected Complete, got {other:?}"),
+        }
+    }
+}
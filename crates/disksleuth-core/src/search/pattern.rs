@@ -0,0 +1,231 @@
+/// Name-matching patterns for the search engine.
+///
+/// Three modes are supported: plain substring, shell-style glob (`*` and
+/// `?`), and full regular expressions.  Matching is always case-insensitive
+/// because NTFS names are case-insensitive and users expect `readme` to find
+/// `README.md`.
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+
+/// How the query string should be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Match names containing the query anywhere.
+    #[default]
+    Substring,
+    /// Shell-style wildcard match against the whole name (`*.iso`, `log?.txt`).
+    Glob,
+    /// Regular expression (`regex` crate syntax), unanchored.
+    Regex,
+}
+
+impl SearchMode {
+    /// Human-readable label for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Substring => "Contains",
+            Self::Glob => "Glob",
+            Self::Regex => "Regex",
+        }
+    }
+}
+
+/// Error returned when a query cannot be compiled into a pattern.
+#[derive(Debug, Error)]
+pub enum PatternError {
+    /// The query was empty (after trimming).
+    #[error("search query is empty")]
+    Empty,
+    /// The regular expression failed to compile.
+    #[error("invalid regular expression: {0}")]
+    InvalidRegex(#[from] regex::Error),
+}
+
+/// A compiled, case-insensitive name pattern.
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    /// Lowercased needle.  `ascii` is `true` when the needle is pure ASCII,
+    /// which enables the allocation-free comparison path.
+    Substring { needle: String, ascii: bool },
+    /// Lowercased glob, pre-split into chars for the backtracking matcher.
+    Glob(Vec<char>),
+    /// Compiled case-insensitive regular expression.
+    Regex(Regex),
+}
+
+impl NamePattern {
+    /// Compile `query` according to `mode`.
+    ///
+    /// Leading and trailing whitespace is ignored for substring and glob
+    /// queries; regex queries are taken verbatim so `\s` anchors still work.
+    pub fn new(query: &str, mode: SearchMode) -> Result<Self, PatternError> {
+        match mode {
+            SearchMode::Substring => {
+                let trimmed = query.trim();
+                if trimmed.is_empty() {
+                    return Err(PatternError::Empty);
+                }
+                Ok(Self::Substring {
+                    needle: trimmed.to_lowercase(),
+                    ascii: trimmed.is_ascii(),
+                })
+            }
+            SearchMode::Glob => {
+                let trimmed = query.trim();
+                if trimmed.is_empty() {
+                    return Err(PatternError::Empty);
+                }
+                Ok(Self::Glob(trimmed.chars().map(fold_char).collect()))
+            }
+            SearchMode::Regex => {
+                if query.is_empty() {
+                    return Err(PatternError::Empty);
+                }
+                let re = RegexBuilder::new(query).case_insensitive(true).build()?;
+                Ok(Self::Regex(re))
+            }
+        }
+    }
+
+    /// Test whether a file or directory name matches this pattern.
+    pub fn is_match(&self, name: &str) -> bool {
+        match self {
+            Self::Substring { needle, ascii } => {
+                if *ascii {
+                    contains_ignore_ascii_case(name.as_bytes(), needle.as_bytes())
+                } else {
+                    // Non-ASCII needles need full Unicode case folding; this
+                    // path allocates but is rare in practice.
+                    name.to_lowercase().contains(needle.as_str())
+                }
+            }
+            Self::Glob(pattern) => glob_match(pattern, name),
+            Self::Regex(re) => re.is_match(name),
+        }
+    }
+}
+
+/// Case-insensitive substring test for an already-lowercased ASCII needle.
+///
+/// Zero allocation — compares byte windows in place.
+fn contains_ignore_ascii_case(haystack: &[u8], needle: &[u8]) -> bool {
+    if needle.is_empty() {
+        return true;
+    }
+    if needle.len() > haystack.len() {
+        return false;
+    }
+    haystack.windows(needle.len()).any(|w| {
+        w.iter()
+            .zip(needle)
+            .all(|(a, b)| a.to_ascii_lowercase() == *b)
+    })
+}
+
+/// Fold a single char to lowercase for comparison purposes.
+///
+/// Chars whose lowercase form expands to several chars (rare) keep only the
+/// first, which is sufficient for filename matching.
+#[inline]
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Iterative wildcard matcher supporting `*` (any run) and `?` (any one char).
+///
+/// Uses the classic single-backtrack-point algorithm: O(n·m) worst case and
+/// no recursion, so pathological patterns like `*a*a*a*b` cannot blow the stack.
+fn glob_match(pattern: &[char], name: &str) -> bool {
+    let name: Vec<char> = name.chars().map(fold_char).collect();
+    let (mut p, mut n) = (0usize, 0usize);
+    let mut star: Option<usize> = None;
+    let mut star_n = 0usize;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_n = n;
+            p += 1;
+        } else if let Some(sp) = star {
+            // Let the last `*` swallow one more char and retry.
+            p = sp + 1;
+            star_n += 1;
+            n = star_n;
+        } else {
+            return false;
+        }
+    }
+
+    // Trailing stars match the empty remainder.
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+    p == pattern.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substring_is_case_insensitive() {
+        let p = NamePattern::new("readme", SearchMode::Substring).unwrap();
+        assert!(p.is_match("README.md"));
+        assert!(p.is_match("old_ReadMe.txt"));
+        assert!(!p.is_match("read.me"));
+    }
+
+    #[test]
+    fn substring_non_ascii_needle() {
+        let p = NamePattern::new("ÄRGER", SearchMode::Substring).unwrap();
+        assert!(p.is_match("großer ärger.txt"));
+    }
+
+    #[test]
+    fn glob_star_and_question_mark() {
+        let p = NamePattern::new("*.ISO", SearchMode::Glob).unwrap();
+        assert!(p.is_match("ubuntu-24.04.iso"));
+        assert!(!p.is_match("ubuntu.iso.part"));
+
+        let q = NamePattern::new("log?.txt", SearchMode::Glob).unwrap();
+        assert!(q.is_match("log1.txt"));
+        assert!(!q.is_match("log12.txt"));
+    }
+
+    #[test]
+    fn glob_without_wildcards_is_exact() {
+        let p = NamePattern::new("Cargo.toml", SearchMode::Glob).unwrap();
+        assert!(p.is_match("cargo.TOML"));
+        assert!(!p.is_match("Cargo.toml.bak"));
+    }
+
+    #[test]
+    fn regex_is_unanchored_and_case_insensitive() {
+        let p = NamePattern::new(r"^backup_\d{4}", SearchMode::Regex).unwrap();
+        assert!(p.is_match("BACKUP_2024-01.zip"));
+        assert!(!p.is_match("old_backup_2024.zip"));
+    }
+
+    #[test]
+    fn invalid_regex_is_reported() {
+        let err = NamePattern::new("(unclosed", SearchMode::Regex).unwrap_err();
+        assert!(matches!(err, PatternError::InvalidRegex(_)));
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        for mode in [SearchMode::Substring, SearchMode::Glob, SearchMode::Regex] {
+            assert!(matches!(
+                NamePattern::new("", mode),
+                Err(PatternError::Empty)
+            ));
+        }
+        assert!(matches!(
+            NamePattern::new("   ", SearchMode::Substring),
+            Err(PatternError::Empty)
+        ));
+    }
+}
@@ -14,7 +14,9 @@ use disksleuth_core::scanner::progress::ScanProgress;
 /// interface. An integration test with `tempfile` exercises every code path
 /// — thread spawning, path resolution, arena insertion, aggregation — with
 /// zero mocking.
-use disksleuth_core::scanner::{start_scan, PROGRESS_CHANNEL_CAPACITY};
+use disksleuth_core::scanner::{
+    start_scan, start_scan_with, ScanOptions, PROGRESS_CHANNEL_CAPACITY,
+};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -249,3 +251,91 @@ fn scan_aggregate_sizes_are_correct() {
         tree.total_size
     );
 }
+
+/// Extended metadata is recorded only when `ScanOptions` asks for it: the
+/// default scan leaves every node without it, an extended scan fills in
+/// access times, mode bits and symlink targets.
+#[test]
+fn extended_metadata_follows_scan_options() {
+    let tmp = TempDir::new().expect("failed to create temp dir");
+    build_test_tree(tmp.path());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let script = tmp.path().join("run.sh");
+        write_bytes(&script, 10);
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o750)).unwrap();
+        std::os::unix::fs::symlink("d.zip", tmp.path().join("link")).unwrap();
+    }
+
+    let handle = start_scan(tmp.path().to_path_buf());
+    let tree_ref = handle.live_tree.clone();
+    drain_to_completion(handle);
+    {
+        let tree = tree_ref.read();
+        assert!(!tree.extended_metadata);
+        assert!(tree.nodes.iter().all(|n| n.extended.is_none()));
+    }
+
+    let options = ScanOptions {
+        extended_metadata: true,
+        ..ScanOptions::default()
+    };
+    let handle = start_scan_with(tmp.path().to_path_buf(), options);
+    let tree_ref = handle.live_tree.clone();
+    drain_to_completion(handle);
+    let tree = tree_ref.read();
+    assert!(tree.extended_metadata);
+    let named = |name: &str| {
+        tree.nodes
+            .iter()
+            .find(|n| n.name == name)
+            .and_then(|n| n.extended.as_deref())
+            .unwrap_or_else(|| panic!("no extended metadata for {name}"))
+    };
+    assert!(named("a.txt").accessed.is_some());
+    let _ = named("alpha");
+    #[cfg(unix)]
+    {
+        assert_eq!(named("run.sh").mode, Some(0o750));
+        assert_eq!(
+            named("link").symlink_target.as_deref(),
+            Some(Path::new("d.zip"))
+        );
+        assert_eq!(named("a.txt").symlink_target, None);
+    }
+}
+
+/// Owners are recorded only when `ScanOptions` asks for it, and then for
+/// every readable file and directory.
+#[test]
+fn owners_follow_scan_options() {
+    let tmp = TempDir::new().expect("failed to create temp dir");
+    build_test_tree(tmp.path());
+
+    let handle = start_scan(tmp.path().to_path_buf());
+    let tree_ref = handle.live_tree.clone();
+    drain_to_completion(handle);
+    {
+        let tree = tree_ref.read();
+        assert!(!tree.owners_recorded);
+        assert!(tree.owners.is_empty());
+        assert!(tree.nodes.iter().all(|n| !n.owner.is_known()));
+    }
+
+    let options = ScanOptions {
+        owners: true,
+        ..ScanOptions::default()
+    };
+    let handle = start_scan_with(tmp.path().to_path_buf(), options);
+    let tree_ref = handle.live_tree.clone();
+    drain_to_completion(handle);
+    let tree = tree_ref.read();
+    assert!(tree.owners_recorded);
+    #[cfg(unix)]
+    assert!(tree
+        .nodes
+        .iter()
+        .filter(|n| !n.is_error)
+        .all(|n| n.owner.is_known()));
+}
@@ -2,7 +2,7 @@
 ///
 /// This is the top-level UI layout that composes all panels and widgets.
 use crate::panels;
-use crate::state::AppState;
+use crate::state::{AppState, MapView};
 use crate::widgets;
 
 /// Pre-built application state.
@@ -22,6 +22,19 @@ impl DiskSleuthState {
     pub fn build() -> Self {
         let mut state = AppState::new();
 
+        // File categories must be settled before anything is categorised.
+        match disksleuth_core::analysis::categories::load_config() {
+            Ok(Some((path, table))) => {
+                tracing::info!("Loaded file categories from {}", path.display());
+                disksleuth_core::analysis::install_categories(table);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Ignoring file category config: {}", e);
+                state.category_config_error = Some(e.to_string());
+            }
+        }
+
         // Auto-scan the OS drive on startup.
         let os_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
         let os_drive_path = format!("{}\\", os_drive);
@@ -47,6 +60,10 @@ pub struct DiskSleuthApp {
     /// Avoids allocating a new `Visuals` struct every frame when the theme
     /// has not changed.
     last_dark_mode: Option<bool>,
+    /// Treemap layout kept between frames.
+    treemap_cache: widgets::treemap::TreemapCache,
+    /// Sunburst layout kept between frames.
+    sunburst_cache: widgets::sunburst::SunburstCache,
 }
 
 impl DiskSleuthApp {
@@ -132,6 +149,8 @@ impl DiskSleuthApp {
         Self {
             state: state.inner,
             last_dark_mode: None,
+            treemap_cache: widgets::treemap::TreemapCache::default(),
+            sunburst_cache: widgets::sunburst::SunburstCache::default(),
         }
     }
 }
@@ -166,10 +185,27 @@ impl eframe::App for DiskSleuthApp {
         // ── Process background messages ───────────────────────────────────
         let _data_changed = self.state.process_scan_messages();
         let _monitor_changed = self.state.process_monitor_messages();
+        let _search_changed = self.state.process_search_messages();
+        let _cleanup_changed = self.state.process_cleanup_messages();
+        let _stale_changed = self.state.process_stale_messages();
+        let _signature_changed = self.state.process_signature_messages();
+        let _reclaimable_changed = self.state.process_reclaimable_messages();
+        let _compression_changed = self.state.process_compression_messages();
+        let _owners_changed = self.state.process_owner_messages();
+
+        // ── Keyboard navigation ───────────────────────────────────────────
+        crate::keyboard::handle_keyboard(ctx, &mut self.state);
 
-        // Request continuous repaint while scanning or monitoring.
-        let needs_repaint =
-            self.state.phase == crate::state::AppPhase::Scanning || self.state.monitor_active;
+        // Request continuous repaint while scanning, monitoring, or searching.
+        let needs_repaint = self.state.phase == crate::state::AppPhase::Scanning
+            || self.state.monitor_active
+            || self.state.search_handle.is_some()
+            || self.state.cleanup_handle.is_some()
+            || self.state.stale_handle.is_some()
+            || self.state.signature_handle.is_some()
+            || self.state.reclaimable_handle.is_some()
+            || self.state.compression_handle.is_some()
+            || self.state.owner_handle.is_some();
         if needs_repaint {
             ctx.request_repaint_after(std::time::Duration::from_millis(100));
         }
@@ -261,6 +297,42 @@ impl eframe::App for DiskSleuthApp {
             });
         self.state.show_about = show_about;
 
+        // ── Keyboard shortcuts overlay ────────────────────────────────────
+        let mut show_shortcuts = self.state.show_shortcuts;
+        egui::Window::new("Keyboard Shortcuts")
+            .open(&mut show_shortcuts)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let muted = ui.visuals().weak_text_color();
+                let strong = ui.visuals().strong_text_color();
+                egui::Grid::new("shortcuts_grid")
+                    .num_columns(2)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        for (keys, action) in crate::keyboard::SHORTCUTS {
+                            ui.label(egui::RichText::new(*keys).strong().color(strong));
+                            ui.label(egui::RichText::new(*action).color(muted));
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.state.show_shortcuts = show_shortcuts;
+
+        // ── Cleanup confirmation / report ─────────────────────────────────
+        panels::cleanup_panel::cleanup_dialogs(ctx, &mut self.state);
+        panels::cart_panel::cart_window(ctx, &mut self.state);
+        panels::stale_panel::stale_window(ctx, &mut self.state);
+        panels::reclaimable_panel::reclaimable_window(ctx, &mut self.state);
+        panels::cache_panel::cache_window(ctx, &mut self.state);
+        panels::empty_panel::empty_window(ctx, &mut self.state);
+        panels::hotspot_panel::hotspot_window(ctx, &mut self.state);
+        panels::largest_dirs_panel::largest_dirs_window(ctx, &mut self.state);
+        panels::owners_panel::owners_window(ctx, &mut self.state);
+        panels::slack_panel::slack_window(ctx, &mut self.state);
+        panels::compression_panel::compression_window(ctx, &mut self.state);
+
         // ── Bottom status bar ─────────────────────────────────────────────
         egui::TopBottomPanel::bottom("status_bar")
             .min_height(24.0)
@@ -285,10 +357,11 @@ impl eframe::App for DiskSleuthApp {
         }
 
         // ── Left sidebar ──────────────────────────────────────────────────
+        // Wide enough for the name plus the six sortable tree-view columns.
         egui::SidePanel::left("left_panel")
-            .default_width(500.0)
-            .min_width(300.0)
-            .max_width(800.0)
+            .default_width(640.0)
+            .min_width(480.0)
+            .max_width(1000.0)
             .resizable(true)
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
@@ -296,6 +369,10 @@ impl eframe::App for DiskSleuthApp {
                     ui.add_space(8.0);
                     ui.separator();
                     ui.add_space(4.0);
+                    panels::search_panel::search_panel(ui, &mut self.state);
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(4.0);
                     panels::tree_panel::tree_panel(ui, &mut self.state);
                 });
             });
@@ -312,14 +389,27 @@ impl eframe::App for DiskSleuthApp {
                     ui.add_space(16.0);
                     ui.separator();
                     ui.add_space(8.0);
-                    panels::chart_panel::chart_panel(ui, &self.state);
+                    panels::chart_panel::chart_panel(ui, &mut self.state);
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+                    panels::size_panel::size_panel(ui, &mut self.state);
                 });
             });
 
-        // ── Central panel (Treemap) ───────────────────────────────────────
+        // ── Central panel (Treemap / Sunburst) ────────────────────────────
         egui::CentralPanel::default().show(ctx, |ui| {
+            use widgets::node_menu::NodeMenuAction;
             use widgets::treemap::TreemapAction;
-            if let Some(act) = widgets::treemap::treemap(ui, &self.state) {
+            let act = match self.state.map_view {
+                MapView::Treemap => {
+                    widgets::treemap::treemap(ui, &self.state, &mut self.treemap_cache)
+                }
+                MapView::Sunburst => {
+                    widgets::sunburst::sunburst(ui, &self.state, &mut self.sunburst_cache)
+                }
+            };
+            if let Some(act) = act {
                 match act {
                     TreemapAction::NavigateDir(node) => {
                         self.state.treemap_navigate_to(node);
@@ -345,6 +435,30 @@ impl eframe::App for DiskSleuthApp {
                         // treemap_go_up reads the tree internally — no clone needed.
                         self.state.treemap_go_up();
                     }
+                    TreemapAction::ContextMenu(node) => {
+                        self.state.context_menu_node = Some(node);
+                    }
+                    TreemapAction::Menu(NodeMenuAction::Cleanup(node, action)) => {
+                        self.state.request_cleanup(&[node], action);
+                    }
+                    TreemapAction::Menu(NodeMenuAction::ToggleCart(node)) => {
+                        self.state.cart_toggle(node);
+                    }
+                    TreemapAction::SetColorMode(mode) => {
+                        self.state.treemap_color = mode;
+                    }
+                    TreemapAction::SetLegend(show) => {
+                        self.state.treemap_legend = show;
+                    }
+                    TreemapAction::SetCushions(on) => {
+                        self.state.treemap_cushions = on;
+                    }
+                    TreemapAction::SetWeight(weight) => {
+                        self.state.treemap_weight = weight;
+                    }
+                    TreemapAction::SetView(view) => {
+                        self.state.map_view = view;
+                    }
                 }
             }
         });
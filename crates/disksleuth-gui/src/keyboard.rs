@@ -0,0 +1,123 @@
+/// Global keyboard shortcuts for the tree view and treemap.
+///
+/// Input is read once per frame from the raw egui event list and mapped onto
+/// `AppState` navigation methods.  Nothing here fires while a text field has
+/// keyboard focus, so typing in the search box never moves the selection.
+use crate::state::AppState;
+use egui::{Event, Key, Modifiers};
+
+/// A single entry in the shortcuts help overlay: (keys, description).
+pub const SHORTCUTS: &[(&str, &str)] = &[
+    ("↑ / k", "Select previous row"),
+    ("↓ / j", "Select next row"),
+    ("← / h", "Collapse folder, or go to parent"),
+    ("→ / l", "Expand folder, or go to first child"),
+    ("Page Up / Page Down", "Move selection by a page"),
+    ("Home / End", "Select first / last row"),
+    ("Enter", "Show selection in the treemap"),
+    ("Backspace", "Treemap: up one level"),
+    ("Insert", "Add or remove the selection in the cleanup list"),
+    ("Alt + ← / Alt + →", "Treemap: back / forward"),
+    ("Type a name", "Jump to the next matching sibling"),
+    ("Shift + letter", "Start a name with h, j, k or l"),
+    ("? / F1", "Show or hide this help"),
+    ("Esc", "Close this help"),
+];
+
+/// Handle this frame's keyboard input.
+pub fn handle_keyboard(ctx: &egui::Context, state: &mut AppState) {
+    // A focused text field (e.g. search) owns the keyboard.
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+
+    let events = ctx.input(|i| i.events.clone());
+    for event in events {
+        match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => handle_key(state, key, modifiers),
+            Event::Text(text) => handle_text(state, &text),
+            _ => {}
+        }
+    }
+}
+
+/// Non-text keys: arrows, paging, Enter, Backspace, function keys.
+fn handle_key(state: &mut AppState, key: Key, modifiers: Modifiers) {
+    if modifiers.alt {
+        match key {
+            Key::ArrowLeft => state.treemap_go_back(),
+            Key::ArrowRight => state.treemap_go_forward(),
+            _ => {}
+        }
+        return;
+    }
+    // Leave Ctrl/Cmd combinations to egui (copy, zoom, …).
+    if modifiers.command || modifiers.ctrl {
+        return;
+    }
+
+    match key {
+        Key::ArrowUp => state.move_selection(-1),
+        Key::ArrowDown => state.move_selection(1),
+        Key::ArrowLeft => state.collapse_selection(),
+        Key::ArrowRight => state.expand_selection(),
+        Key::PageUp => state.move_selection_page(-1),
+        Key::PageDown => state.move_selection_page(1),
+        Key::Home => state.select_edge_row(false),
+        Key::End => state.select_edge_row(true),
+        Key::Enter => state.drill_into_selection(),
+        Key::Backspace => state.treemap_go_up(),
+        Key::Insert => {
+            if let Some(node) = state.selected_node {
+                state.cart_toggle(node);
+            }
+        }
+        Key::F1 => state.show_shortcuts = !state.show_shortcuts,
+        Key::Escape => state.show_shortcuts = false,
+        _ => {}
+    }
+}
+
+/// Printable characters: vim-style movement keys, `?`, and type-ahead.
+///
+/// `h`/`j`/`k`/`l` and `?` only act as commands at the start of input;
+/// once a name is being typed they extend the type-ahead prefix instead.
+fn handle_text(state: &mut AppState, text: &str) {
+    for ch in text.chars() {
+        if !state.type_ahead_active() {
+            match ch {
+                'j' => {
+                    state.move_selection(1);
+                    continue;
+                }
+                'k' => {
+                    state.move_selection(-1);
+                    continue;
+                }
+                'h' => {
+                    state.collapse_selection();
+                    continue;
+                }
+                'l' => {
+                    state.expand_selection();
+                    continue;
+                }
+                '?' => {
+                    state.show_shortcuts = !state.show_shortcuts;
+                    continue;
+                }
+                // A leading space is never part of a name search.
+                ' ' => continue,
+                _ => {}
+            }
+        }
+        if !ch.is_control() {
+            state.type_ahead(ch.encode_utf8(&mut [0u8; 4]));
+        }
+    }
+}
@@ -3,6 +3,7 @@
 /// This crate contains all UI code. Business logic lives in `disksleuth-core`.
 pub mod app;
 pub mod icon;
+pub mod keyboard;
 pub mod panels;
 pub mod state;
 pub mod widgets;
@@ -0,0 +1,158 @@
+/// Caches window — tool caches and temp folders in well-known locations.
+///
+/// Shows `AppState::cache_report`, matched against the completed tree from
+/// `disksleuth_core::analysis::CACHE_RULES` after every scan or cleanup.
+/// Each row gives the owning tool, whether the location is safe to clear
+/// by hand, and the tool's own cleanup command to copy, with what that
+/// command needs where it is not obvious.  Locations that hold
+/// more than cache can only be cleared with that command, so they cannot be
+/// added to the cleanup list.
+use crate::state::AppState;
+use disksleuth_core::analysis::Safety;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+
+/// Draw the caches window if it is open.
+pub fn cache_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_caches;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Caches & Temp Folders")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([760.0, 420.0])
+        .show(ctx, |ui| cache_contents(ui, state));
+
+    state.show_caches = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum CacheAction {
+    Reveal(NodeIndex),
+    AddToCart(NodeIndex),
+}
+
+fn cache_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(ref report) = state.cache_report else {
+        ui.label(egui::RichText::new("Finish a scan to look for caches.").color(muted));
+        return;
+    };
+    if report.locations.is_empty() {
+        ui.label(
+            egui::RichText::new(
+                "No well-known cache or temp folder lies inside the scanned drive.",
+            )
+            .color(muted),
+        );
+        return;
+    }
+
+    ui.label(
+        egui::RichText::new(format!(
+            "{} locations — {}",
+            format_count(report.locations.len() as u64),
+            format_size(report.total_bytes)
+        ))
+        .strong()
+        .color(accent),
+    );
+    ui.label(
+        egui::RichText::new("Click a tool to show its folder in the tree")
+            .size(11.0)
+            .color(muted),
+    );
+    ui.add_space(4.0);
+
+    let mut action: Option<CacheAction> = None;
+    egui::ScrollArea::both()
+        .id_salt("cache_locations")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("cache_locations_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for header in ["Tool", "Size", "Safety", "Cleanup command", ""] {
+                        ui.label(egui::RichText::new(header).size(11.0).color(muted));
+                    }
+                    ui.end_row();
+
+                    for location in &report.locations {
+                        let rule = location.rule;
+                        let path = location.path.to_string_lossy();
+                        if ui
+                            .link(rule.tool)
+                            .on_hover_text(format!(
+                                "{path}\n{} files",
+                                format_count(location.files)
+                            ))
+                            .clicked()
+                        {
+                            action = Some(CacheAction::Reveal(location.index));
+                        }
+                        ui.label(egui::RichText::new(format_size(location.size)).color(accent));
+                        ui.label(
+                            egui::RichText::new(rule.safety.label())
+                                .size(12.0)
+                                .color(safety_color(ui.visuals(), rule.safety)),
+                        );
+                        match rule.cleanup {
+                            Some(command) => {
+                                ui.horizontal(|ui| {
+                                    ui.monospace(command);
+                                    if ui
+                                        .small_button("📋")
+                                        .on_hover_text("Copy command")
+                                        .clicked()
+                                    {
+                                        ui.ctx().copy_text(command.to_string());
+                                    }
+                                    if let Some(note) = rule.cleanup_note {
+                                        ui.label(egui::RichText::new(note).size(11.0).color(muted));
+                                    }
+                                });
+                            }
+                            None => {
+                                ui.label(egui::RichText::new("—").color(muted));
+                            }
+                        }
+                        let can_add = rule.safety != Safety::Caution;
+                        if ui
+                            .add_enabled(can_add, egui::Button::new("➕").small())
+                            .on_hover_text("Add to cleanup list")
+                            .on_disabled_hover_text("Clear this one with the tool's own command")
+                            .clicked()
+                        {
+                            action = Some(CacheAction::AddToCart(location.index));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    match action {
+        Some(CacheAction::Reveal(node)) => state.reveal_node(node),
+        Some(CacheAction::AddToCart(node)) => {
+            state.cart_add(node);
+            state.show_cart = true;
+        }
+        None => {}
+    }
+}
+
+/// Plain text when safe, the theme's warning colour for close-first, and
+/// its error colour for use-the-tool.
+fn safety_color(visuals: &egui::Visuals, safety: Safety) -> egui::Color32 {
+    match safety {
+        Safety::Safe => visuals.text_color(),
+        Safety::WhenClosed => visuals.warn_fg_color,
+        Safety::Caution => visuals.error_fg_color,
+    }
+}
@@ -0,0 +1,164 @@
+/// Cleanup list ("cart") window — review marked items, then run or export.
+///
+/// Items are added from the tree view and treemap context menus (or the
+/// Insert key).  The reclaimable total counts an item inside a listed folder
+/// only once; such items are shown dimmed.  Running the list goes through
+/// the same confirmation dialog as a single trash/delete.
+use crate::state::AppState;
+use disksleuth_core::cleanup::CleanupAction;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+
+/// Height of one list row in pixels.
+const ROW_HEIGHT: f32 = 22.0;
+
+/// Draw the cleanup list window if it is open.
+pub fn cart_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_cart;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Cleanup List")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([520.0, 380.0])
+        .show(ctx, |ui| cart_contents(ui, state));
+
+    state.show_cart = open;
+}
+
+fn cart_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    // Clone the Arc (cheap refcount bump) so rows can borrow the tree while
+    // state is mutated afterwards.
+    let Some(tree) = state.tree.clone() else {
+        ui.label(egui::RichText::new("Finish a scan to start a cleanup list.").color(muted));
+        return;
+    };
+
+    // ── Summary ────────────────────────────────────────────────
+    ui.label(
+        egui::RichText::new(format!(
+            "{} items — {} reclaimable in {} files",
+            format_count(state.cleanup_cart.len() as u64),
+            format_size(state.cart_reclaimable),
+            format_count(state.cart_files),
+        ))
+        .strong()
+        .color(accent),
+    );
+    ui.label(
+        egui::RichText::new("Right-click items in the tree or treemap to add them.")
+            .size(11.0)
+            .color(muted),
+    );
+    ui.separator();
+
+    // ── Action ─────────────────────────────────────────────────
+    ui.horizontal(|ui| {
+        for action in [
+            CleanupAction::Trash,
+            CleanupAction::Delete,
+            CleanupAction::Archive,
+        ] {
+            ui.radio_value(&mut state.cart_action, action, action.label());
+        }
+    });
+    if state.cart_action == CleanupAction::Archive {
+        ui.horizontal(|ui| {
+            ui.label("Archive folder:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.cart_archive_dir)
+                    .hint_text("e.g. D:\\Archive")
+                    .desired_width(f32::INFINITY),
+            );
+        });
+    }
+
+    let has_plan = !state.cleanup_cart.is_empty()
+        && (state.cart_action != CleanupAction::Archive
+            || !state.cart_archive_dir.trim().is_empty());
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                has_plan && state.can_modify_tree(),
+                egui::Button::new(format!("{}…", state.cart_action.label())),
+            )
+            .clicked()
+        {
+            state.request_cart_cleanup();
+        }
+        if ui
+            .add_enabled(has_plan, egui::Button::new("📋 Copy as script"))
+            .on_hover_text("Copy the commands this run would perform, for review")
+            .clicked()
+        {
+            if let Some(script) = state.cart_script() {
+                ui.ctx().copy_text(script);
+            }
+        }
+        if ui
+            .add_enabled(!state.cleanup_cart.is_empty(), egui::Button::new("Clear"))
+            .clicked()
+        {
+            state.cart_clear();
+        }
+    });
+    ui.separator();
+
+    // ── Items (virtualised) ────────────────────────────────────
+    let mut remove: Option<NodeIndex> = None;
+    let mut reveal: Option<NodeIndex> = None;
+    let cart = &state.cleanup_cart;
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show_rows(ui, ROW_HEIGHT, cart.len(), |ui, range| {
+            for &node in &cart[range] {
+                let n = tree.node(node);
+                let nested = state.cart_is_nested(node);
+                ui.horizontal(|ui| {
+                    if ui
+                        .small_button("✕")
+                        .on_hover_text("Remove from list")
+                        .clicked()
+                    {
+                        remove = Some(node);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(egui::RichText::new(format_size(n.size)).color(if nested {
+                            muted
+                        } else {
+                            accent
+                        }));
+                        let icon = if n.is_dir { "📁" } else { "📄" };
+                        let mut text = format!("{icon} {}", tree.full_path(node));
+                        if nested {
+                            text.push_str("  (in a listed folder)");
+                        }
+                        let label = egui::RichText::new(text).size(12.0);
+                        let label = if nested { label.color(muted) } else { label };
+                        let resp = ui.add(
+                            egui::Label::new(label)
+                                .truncate()
+                                .sense(egui::Sense::click()),
+                        );
+                        if resp.on_hover_text("Show in tree and treemap").clicked() {
+                            reveal = Some(node);
+                        }
+                    });
+                });
+            }
+        });
+
+    if let Some(node) = remove {
+        state.cart_remove(node);
+    }
+    if let Some(node) = reveal {
+        state.reveal_node(node);
+    }
+}
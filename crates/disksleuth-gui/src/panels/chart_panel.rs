@@ -1,111 +1,596 @@
-/// Chart panel — visual breakdowns of disk usage.
+/// Chart panel — file type breakdown as a donut chart plus a list.
 ///
-/// Phase 2: will contain pie/donut chart of file type breakdown
-/// and optional treemap/sunburst visualisation.
-use crate::state::{AppPhase, AppState};
-use disksleuth_core::analysis::FileCategory;
-use disksleuth_core::model::size::format_size;
-use egui::{Rect, Ui, Vec2};
+/// The donut shows each category's share of the scan, by size or by file
+/// count.  Hovering a segment or a list row brings it forward; clicking
+/// either filters the tree view to that category and highlights its files
+/// in the treemap and sunburst.  Clicking the active category again (or
+/// "Clear") shows everything.
+///
+/// Each row's arrow expands a sortable table of the category's extensions;
+/// clicking an extension lists its largest files, and clicking one of those
+/// reveals it in the tree.
+use crate::state::{AppPhase, AppState, ExtensionColumn};
+use disksleuth_core::analysis::{categories, ExtensionStats, FileCategory};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+use egui::{Color32, Pos2, Rect, Sense, Ui, Vec2};
+use std::f32::consts::TAU;
+
+/// Largest diameter (px) of the donut.
+const DONUT_MAX_SIZE: f32 = 180.0;
+
+/// Radius of the hole as a fraction of the outer radius.
+const DONUT_HOLE: f32 = 0.58;
+
+/// How far (px) the hovered or filtered segment grows outwards.
+const DONUT_POP: f32 = 5.0;
+
+/// Largest angle (radians) covered by one mesh step along an arc.
+const ARC_STEP: f32 = 0.04;
+
+/// Most extension rows listed under one category.
+const MAX_EXTENSION_ROWS: usize = 100;
+
+/// Size thresholds offered for the content-detection pass.
+const SIGNATURE_THRESHOLDS: [u64; 4] = [64 << 10, 1 << 20, 16 << 20, 256 << 20];
+
+/// What the user did in the panel this frame, applied once drawing is done.
+enum ChartAction {
+    /// Toggle the tree-view filter for a category.
+    Filter(FileCategory),
+    /// Toggle a category's extension table.
+    Expand(FileCategory),
+    SortExtensions(ExtensionColumn),
+    /// Pick (or un-pick) an extension to list its largest files.
+    PickExtension(String),
+    /// Select and reveal a file of the given category.
+    Reveal(NodeIndex, FileCategory),
+}
+
+/// One category as charted: its value under the active metric.
+struct Slice {
+    category: FileCategory,
+    value: u64,
+    /// Clockwise from twelve o'clock, in radians.
+    start: f32,
+    end: f32,
+}
 
 /// Draw the chart panel showing file type breakdown.
 ///
 /// Uses `AppState::file_type_stats` (pre-computed once after scan completion)
 /// rather than calling `analyse_file_types` on every render frame.  This
 /// avoids iterating over millions of nodes at 60 fps.
-pub fn chart_panel(ui: &mut Ui, state: &AppState) {
+pub fn chart_panel(ui: &mut Ui, state: &mut AppState) {
     // Extract theme-adaptive colours once for correct rendering in both
     // dark and light mode.
     let color_normal = ui.visuals().text_color();
     let color_muted = ui.visuals().weak_text_color();
     let bar_track_bg = ui.visuals().extreme_bg_color;
 
-    ui.heading("File Types");
+    ui.horizontal(|ui| {
+        ui.heading("File Types");
+        if state.file_type_stats.is_some() {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.selectable_value(&mut state.chart_by_count, true, "Count")
+                    .on_hover_text("Chart by number of files");
+                ui.selectable_value(&mut state.chart_by_count, false, "Size")
+                    .on_hover_text("Chart by total size");
+            });
+        }
+    });
     ui.add_space(4.0);
 
+    if let Some(ref error) = state.category_config_error {
+        ui.label(
+            egui::RichText::new(format!("⚠ Category config ignored: {error}"))
+                .color(ui.visuals().warn_fg_color)
+                .size(11.0),
+        );
+    }
+
     // Use the pre-computed cache; during an active scan show a placeholder.
-    let stats = match state.file_type_stats.as_deref() {
-        Some(s) => s,
-        None => {
-            if state.phase == AppPhase::Scanning {
-                ui.label(
-                    egui::RichText::new("Available after scan completes.")
-                        .color(color_muted)
-                        .size(12.0),
-                );
-            }
-            return;
+    let Some(stats) = state.file_type_stats.as_deref() else {
+        if state.phase == AppPhase::Scanning {
+            ui.label(
+                egui::RichText::new("Available after scan completes.")
+                    .color(color_muted)
+                    .size(12.0),
+            );
         }
+        return;
     };
 
-    // Total size is only meaningful from the completed tree.
-    let total_size = state.tree.as_ref().map_or(0u64, |t| t.total_size);
-
-    for stat in stats {
-        let cat = stat.category.unwrap_or(FileCategory::Other);
-        let pct = if total_size > 0 {
-            (stat.total_size as f64 / total_size as f64 * 100.0) as f32
+    // At most one entry per category, so this copy is tiny.
+    let by_count = state.chart_by_count;
+    let mut slices: Vec<Slice> = stats
+        .iter()
+        .map(|s| Slice {
+            category: s.category.unwrap_or(FileCategory::Other),
+            value: if by_count { s.file_count } else { s.total_size },
+            start: 0.0,
+            end: 0.0,
+        })
+        .filter(|s| s.value > 0)
+        .collect();
+    slices.sort_by(|a, b| b.value.cmp(&a.value));
+    let total: u64 = slices.iter().map(|s| s.value).sum();
+    let mut angle = 0.0;
+    for slice in &mut slices {
+        slice.start = angle;
+        angle += TAU * (slice.value as f64 / total.max(1) as f64) as f32;
+        slice.end = angle;
+    }
+    // Close the ring exactly despite rounding.
+    if let Some(last) = slices.last_mut() {
+        last.end = TAU;
+    }
+    let format_value = |v: u64| {
+        if by_count {
+            format!("{} files", format_count(v))
         } else {
-            0.0
-        };
+            format_size(v)
+        }
+    };
+    let percent = |v: u64| v as f64 / total.max(1) as f64 * 100.0;
 
-        ui.horizontal(|ui| {
-            // Category colour dot.
-            let (dot_rect, _) = ui.allocate_exact_size(Vec2::new(10.0, 10.0), egui::Sense::hover());
-            let color = category_color(cat);
-            ui.painter_at(dot_rect)
-                .circle_filled(dot_rect.center(), 4.0, color);
+    let filter = state.category_filter;
+    let mut action: Option<ChartAction> = None;
 
-            // Label.
+    if let Some(cat) = filter {
+        ui.horizontal(|ui| {
             ui.label(
-                egui::RichText::new(cat.label())
-                    .color(color_normal)
-                    .size(12.0),
+                egui::RichText::new(format!("Tree shows only {}", cat.label()))
+                    .color(color_muted)
+                    .size(11.0),
             );
+            if ui.small_button("Clear").clicked() {
+                action = Some(ChartAction::Filter(cat));
+            }
+        });
+    }
 
-            // Size.
-            ui.label(
-                egui::RichText::new(format_size(stat.total_size))
-                    .color(color_normal)
-                    .size(12.0),
-            );
+    // A list row hovered last frame highlights its segment too.
+    let hover_id = ui.id().with("file_type_row_hover");
+    let row_hover_prev = ui
+        .ctx()
+        .data(|d| d.get_temp::<Option<FileCategory>>(hover_id))
+        .flatten();
 
-            // Percentage.
-            ui.label(
-                egui::RichText::new(format!("({:.1}%)", pct))
-                    .color(color_muted)
-                    .size(11.0),
+    // ── Donut ─────────────────────────────────────────────────────
+    let size = ui.available_width().min(DONUT_MAX_SIZE);
+    let (rect, response) = ui.allocate_exact_size(Vec2::splat(size), Sense::click());
+    let centre = rect.center();
+    let outer = (size / 2.0 - DONUT_POP).max(1.0);
+    let inner = outer * DONUT_HOLE;
+
+    let donut_hover = response.hover_pos().and_then(|pos| {
+        let d = pos - centre;
+        let r = d.length();
+        if r < inner || r > outer + DONUT_POP {
+            return None;
+        }
+        let a = d.x.atan2(-d.y).rem_euclid(TAU);
+        slices.iter().find(|s| a >= s.start && a < s.end)
+    });
+    let hovered = donut_hover.map(|s| s.category).or(row_hover_prev);
+    if response.clicked() {
+        if let Some(slice) = donut_hover {
+            action = Some(ChartAction::Filter(slice.category));
+        }
+    }
+    if donut_hover.is_some() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+
+    let painter = ui.painter_at(rect);
+    let mut mesh = egui::Mesh::default();
+    for slice in &slices {
+        let active = Some(slice.category) == hovered || Some(slice.category) == filter;
+        let mut color = category_color(slice.category);
+        if filter.is_some_and(|f| f != slice.category) {
+            color = color.gamma_multiply(0.35);
+        }
+        let r1 = if active { outer + DONUT_POP } else { outer };
+        add_sector(&mut mesh, centre, inner, r1, slice.start, slice.end, color);
+    }
+    painter.add(egui::Shape::mesh(mesh));
+
+    // Thin gaps between segments in the panel colour.
+    if slices.len() > 1 {
+        let gap = egui::Stroke::new(1.5, ui.visuals().panel_fill);
+        for slice in &slices {
+            painter.line_segment(
+                [
+                    polar(centre, slice.start, inner),
+                    polar(centre, slice.start, outer + DONUT_POP),
+                ],
+                gap,
             );
+        }
+    }
+
+    // Centre: the hovered category, else the total.
+    let shown = hovered.and_then(|cat| slices.iter().find(|s| s.category == cat));
+    let (title, value, detail) = match shown {
+        Some(s) => (
+            s.category.label().to_string(),
+            format_value(s.value),
+            format!("{:.1}%", percent(s.value)),
+        ),
+        None => ("Total".to_string(), format_value(total), String::new()),
+    };
+    painter.text(
+        centre - Vec2::new(0.0, 14.0),
+        egui::Align2::CENTER_CENTER,
+        title,
+        egui::FontId::proportional(11.0),
+        color_muted,
+    );
+    painter.text(
+        centre,
+        egui::Align2::CENTER_CENTER,
+        value,
+        egui::FontId::proportional(13.0),
+        color_normal,
+    );
+    painter.text(
+        centre + Vec2::new(0.0, 14.0),
+        egui::Align2::CENTER_CENTER,
+        detail,
+        egui::FontId::proportional(11.0),
+        color_muted,
+    );
+    ui.add_space(6.0);
+
+    // ── List ──────────────────────────────────────────────────────
+    let mut row_hover: Option<FileCategory> = None;
+    for slice in &slices {
+        let cat = slice.category;
+        let pct = percent(slice.value) as f32;
+        let background = ui.painter().add(egui::Shape::Noop);
+
+        let expanded = state.chart_expanded == Some(cat);
+        let row = ui.vertical(|ui| {
+            let arrow = ui.horizontal(|ui| {
+                // Extension table toggle.
+                let arrow = ui
+                    .add(egui::Button::new(if expanded { "▼" } else { "▶" }).frame(false))
+                    .on_hover_text("Show the extensions in this category");
+                if arrow.clicked() {
+                    action = Some(ChartAction::Expand(cat));
+                }
+
+                // Category colour dot.
+                let (dot_rect, _) = ui.allocate_exact_size(Vec2::new(10.0, 10.0), Sense::hover());
+                ui.painter_at(dot_rect)
+                    .circle_filled(dot_rect.center(), 4.0, category_color(cat));
+
+                // Label.
+                ui.label(
+                    egui::RichText::new(cat.label())
+                        .color(color_normal)
+                        .size(12.0),
+                );
+
+                // Size or count.
+                ui.label(
+                    egui::RichText::new(format_value(slice.value))
+                        .color(color_normal)
+                        .size(12.0),
+                );
+
+                // Percentage.
+                ui.label(
+                    egui::RichText::new(format!("({:.1}%)", pct))
+                        .color(color_muted)
+                        .size(11.0),
+                );
+                arrow.rect
+            });
+
+            // Mini bar.
+            let bar_width = ui.available_width() - 16.0;
+            let bar_height = 4.0;
+            let (bar_rect, _) =
+                ui.allocate_exact_size(Vec2::new(bar_width, bar_height), Sense::hover());
+            let painter = ui.painter_at(bar_rect);
+            painter.rect_filled(bar_rect, 2.0, bar_track_bg);
+
+            let fill_w = bar_width * (pct / 100.0).clamp(0.0, 1.0);
+            if fill_w > 0.5 {
+                let fill_rect = Rect::from_min_size(bar_rect.min, Vec2::new(fill_w, bar_height));
+                painter.rect_filled(fill_rect, 2.0, category_color(cat));
+            }
+            arrow.inner
         });
 
-        // Mini bar.
-        let bar_width = ui.available_width() - 16.0;
-        let bar_height = 4.0;
-        let (bar_rect, _) =
-            ui.allocate_exact_size(Vec2::new(bar_width, bar_height), egui::Sense::hover());
-        let painter = ui.painter_at(bar_rect);
-        painter.rect_filled(bar_rect, 2.0, bar_track_bg);
+        // The arrow keeps its own clicks; the rest of the row filters.
+        let row_rect = row.response.rect.expand(2.0);
+        let click_rect =
+            Rect::from_min_max(egui::pos2(row.inner.right(), row_rect.top()), row_rect.max);
+        let response = ui
+            .interact(
+                click_rect,
+                ui.id().with(("file_type_row", cat)),
+                Sense::click(),
+            )
+            .on_hover_text(if filter == Some(cat) {
+                "Click to show all files again"
+            } else {
+                "Click to show only these files in the tree"
+            });
+        if response.hovered() {
+            row_hover = Some(cat);
+        }
+        if response.clicked() {
+            action = Some(ChartAction::Filter(cat));
+        }
+        if filter == Some(cat) || Some(cat) == hovered {
+            let fill = if filter == Some(cat) {
+                ui.visuals().selection.bg_fill.gamma_multiply(0.35)
+            } else {
+                ui.visuals().widgets.hovered.weak_bg_fill
+            };
+            ui.painter()
+                .set(background, egui::Shape::rect_filled(row_rect, 3.0, fill));
+        }
 
-        let fill_w = bar_width * (pct / 100.0).clamp(0.0, 1.0);
-        if fill_w > 0.5 {
-            let fill_rect = Rect::from_min_size(bar_rect.min, Vec2::new(fill_w, bar_height));
-            painter.rect_filled(fill_rect, 2.0, category_color(cat));
+        if expanded {
+            extension_table(ui, state, cat, &mut action);
         }
 
         ui.add_space(2.0);
     }
+    ui.ctx().data_mut(|d| d.insert_temp(hover_id, row_hover));
+
+    match action {
+        Some(ChartAction::Filter(cat)) => {
+            let next = if filter == Some(cat) { None } else { Some(cat) };
+            state.set_category_filter(next);
+        }
+        Some(ChartAction::Expand(cat)) => {
+            state.chart_expanded = if state.chart_expanded == Some(cat) {
+                None
+            } else {
+                Some(cat)
+            };
+        }
+        Some(ChartAction::SortExtensions(column)) => state.set_extension_sort(column),
+        Some(ChartAction::PickExtension(ext)) => state.select_extension(Some(&ext)),
+        Some(ChartAction::Reveal(node, cat)) => {
+            // A filter on another category would hide the file.
+            if filter.is_some_and(|f| f != cat) {
+                state.set_category_filter(None);
+            }
+            state.reveal_node(node);
+        }
+        None => {}
+    }
+
+    ui.add_space(6.0);
+    ui.separator();
+    content_detection(ui, state);
 }
 
-/// Map a file category to a display colour (dark mode).
-fn category_color(cat: FileCategory) -> egui::Color32 {
-    match cat {
-        FileCategory::Documents => egui::Color32::from_rgb(0x89, 0xb4, 0xfa),
-        FileCategory::Images => egui::Color32::from_rgb(0xf9, 0xe2, 0xaf),
-        FileCategory::Video => egui::Color32::from_rgb(0xf3, 0x8b, 0xa8),
-        FileCategory::Audio => egui::Color32::from_rgb(0xcb, 0xa6, 0xf7),
-        FileCategory::Archives => egui::Color32::from_rgb(0xfa, 0xb3, 0x87),
-        FileCategory::Code => egui::Color32::from_rgb(0xa6, 0xe3, 0xa1),
-        FileCategory::Executables => egui::Color32::from_rgb(0xf3, 0x8b, 0xa8),
-        FileCategory::System => egui::Color32::from_rgb(0x94, 0xe2, 0xd5),
-        FileCategory::Other => egui::Color32::from_rgb(0x6c, 0x70, 0x86),
+/// Controls and summary for the signature pass, which identifies large
+/// files by their content rather than their extension.
+fn content_detection(ui: &mut Ui, state: &mut AppState) {
+    let color_muted = ui.visuals().weak_text_color();
+
+    ui.horizontal(|ui| {
+        if state.signature_handle.is_some() {
+            ui.spinner();
+            let (checked, total) = state.signature_progress;
+            ui.label(format!(
+                "Reading {} of {} files…",
+                format_count(checked),
+                format_count(total)
+            ));
+            if ui.button("Cancel").clicked() {
+                state.cancel_signature_scan();
+            }
+            return;
+        }
+        if ui
+            .button("🔍 Detect by content")
+            .on_hover_text(
+                "Read the start of each large file and categorise it by its signature \
+                 (archives, disk images, databases, executables, media) instead of its extension",
+            )
+            .clicked()
+        {
+            state.start_signature_scan();
+        }
+        egui::ComboBox::from_id_salt("signature_min_size")
+            .selected_text(format!("≥ {}", format_size(state.signature_min_size)))
+            .show_ui(ui, |ui| {
+                for size in SIGNATURE_THRESHOLDS {
+                    ui.selectable_value(
+                        &mut state.signature_min_size,
+                        size,
+                        format!("≥ {}", format_size(size)),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Only files at least this large are read");
+    });
+
+    if let Some(ref report) = state.signature_report {
+        let mut summary = format!(
+            "{} of {} files identified by content",
+            format_count(report.detected.len() as u64),
+            format_count(report.files_checked)
+        );
+        if report.mismatched > 0 {
+            summary += &format!(", {} misnamed", format_count(report.mismatched));
+        }
+        if report.unreadable > 0 {
+            summary += &format!(", {} unreadable", format_count(report.unreadable));
+        }
+        if report.truncated {
+            summary += " (largest files only)";
+        }
+        ui.label(egui::RichText::new(summary).color(color_muted).size(11.0));
     }
 }
+
+/// The extensions of `category` as a sortable table, followed by the
+/// largest files of the picked extension if it is one of them.
+fn extension_table(
+    ui: &mut Ui,
+    state: &AppState,
+    category: FileCategory,
+    action: &mut Option<ChartAction>,
+) {
+    let Some(all) = state.extension_stats.as_deref() else {
+        return;
+    };
+    let color_muted = ui.visuals().weak_text_color();
+    let mut rows: Vec<&ExtensionStats> = all.iter().filter(|e| e.category == category).collect();
+    let key = |e: &ExtensionStats| match state.extension_sort {
+        ExtensionColumn::Extension | ExtensionColumn::Size => e.total_size,
+        ExtensionColumn::Files => e.file_count,
+        ExtensionColumn::Average => e.average_size(),
+        ExtensionColumn::Largest => e.largest_size,
+    };
+    rows.sort_by(|a, b| {
+        let order = match state.extension_sort {
+            ExtensionColumn::Extension => a.extension.cmp(&b.extension),
+            _ => key(a).cmp(&key(b)),
+        };
+        if state.extension_sort_descending {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+    let hidden = rows.len().saturating_sub(MAX_EXTENSION_ROWS);
+    rows.truncate(MAX_EXTENSION_ROWS);
+
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+    ui.indent(("extensions", category), |ui| {
+        egui::ScrollArea::horizontal()
+            .id_salt(("extension_scroll", category))
+            .show(ui, |ui| {
+                egui::Grid::new(("extension_table", category))
+                    .striped(true)
+                    .spacing([10.0, 2.0])
+                    .show(ui, |ui| {
+                        for column in ExtensionColumn::ALL {
+                            let marker = match state.extension_sort == column {
+                                true if state.extension_sort_descending => " ▼",
+                                true => " ▲",
+                                false => "",
+                            };
+                            let header = egui::Label::new(
+                                small(format!("{}{}", column.label(), marker)).strong(),
+                            )
+                            .sense(Sense::click());
+                            if ui
+                                .add(header)
+                                .on_hover_text("Sort by this column")
+                                .clicked()
+                            {
+                                *action = Some(ChartAction::SortExtensions(column));
+                            }
+                        }
+                        ui.end_row();
+
+                        for e in &rows {
+                            let name = if e.extension.is_empty() {
+                                "(none)".to_string()
+                            } else {
+                                format!(".{}", e.extension)
+                            };
+                            let picked =
+                                state.selected_extension.as_deref() == Some(e.extension.as_str());
+                            if ui
+                                .selectable_label(picked, small(name))
+                                .on_hover_text("List the largest files")
+                                .clicked()
+                            {
+                                *action = Some(ChartAction::PickExtension(e.extension.to_string()));
+                            }
+                            ui.label(small(format_size(e.total_size)));
+                            ui.label(small(format_count(e.file_count)));
+                            ui.label(small(format_size(e.average_size())));
+                            ui.label(small(format_size(e.largest_size)));
+                            ui.end_row();
+                        }
+                    });
+            });
+        if hidden > 0 {
+            ui.label(
+                small(format!("{} more not shown", format_count(hidden as u64))).color(color_muted),
+            );
+        }
+
+        // Largest files of the picked extension.
+        let picked = state.selected_extension.as_deref();
+        let Some(ext) = picked.filter(|p| rows.iter().any(|e| e.extension == *p)) else {
+            return;
+        };
+        let Some(ref tree) = state.tree else {
+            return;
+        };
+        ui.add_space(4.0);
+        let title = if ext.is_empty() {
+            "Largest files without an extension".to_string()
+        } else {
+            format!("Largest .{ext} files")
+        };
+        ui.label(small(title).color(color_muted));
+        for file in &state.extension_files {
+            if file.index.idx() >= tree.len() {
+                continue;
+            }
+            let text = format!("{}  {}", tree.node(file.index).name, format_size(file.size));
+            if ui
+                .selectable_label(state.selected_node == Some(file.index), small(text))
+                .on_hover_text(&file.path)
+                .clicked()
+            {
+                *action = Some(ChartAction::Reveal(file.index, category));
+            }
+        }
+    });
+}
+
+/// Point at `angle` (clockwise from twelve o'clock) and radius `r`.
+fn polar(centre: Pos2, angle: f32, r: f32) -> Pos2 {
+    centre + Vec2::new(angle.sin(), -angle.cos()) * r
+}
+
+/// Append an annular sector to `mesh` as a strip of quads along its arc.
+fn add_sector(
+    mesh: &mut egui::Mesh,
+    centre: Pos2,
+    r0: f32,
+    r1: f32,
+    a0: f32,
+    a1: f32,
+    color: Color32,
+) {
+    let steps = ((a1 - a0) / ARC_STEP).ceil().max(1.0) as u32;
+    let base = mesh.vertices.len() as u32;
+    for k in 0..=steps {
+        let a = a0 + (a1 - a0) * k as f32 / steps as f32;
+        mesh.colored_vertex(polar(centre, a, r0), color);
+        mesh.colored_vertex(polar(centre, a, r1), color);
+    }
+    for k in 0..steps {
+        let i = base + 2 * k;
+        mesh.add_triangle(i, i + 1, i + 2);
+        mesh.add_triangle(i + 1, i + 3, i + 2);
+    }
+}
+
+/// Display colour of a file category, from the category table.
+///
+/// Shared with the treemap's file-type colour mode so both views agree.
+pub(crate) fn category_color(cat: FileCategory) -> egui::Color32 {
+    let [r, g, b] = categories().color(cat);
+    egui::Color32::from_rgb(r, g, b)
+}
@@ -0,0 +1,194 @@
+/// Cleanup dialogs — trash/delete confirmation and the per-path report.
+///
+/// Both are modal-style windows anchored to the centre of the screen.  The
+/// confirmation shows exactly what will be removed (size, item and file
+/// counts); the report lists every path that could not be removed.
+use crate::state::AppState;
+use disksleuth_core::cleanup::CleanupAction;
+use disksleuth_core::model::size::{format_count, format_size};
+
+/// Maximum number of item paths listed in the confirmation dialog.
+const MAX_LISTED_ITEMS: usize = 8;
+
+/// Draw whichever cleanup windows are currently active.
+pub fn cleanup_dialogs(ctx: &egui::Context, state: &mut AppState) {
+    confirm_dialog(ctx, state);
+    report_window(ctx, state);
+}
+
+/// "Move N items to the Trash?" confirmation.
+fn confirm_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(ref plan) = state.cleanup_confirm else {
+        return;
+    };
+
+    let mut open = true;
+    let mut confirmed = false;
+    let mut dismissed = false;
+
+    egui::Window::new(plan.action.label())
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([420.0, 0.0])
+        .show(ctx, |ui| {
+            let muted = ui.visuals().weak_text_color();
+            let warning = egui::Color32::from_rgb(0xfa, 0xb3, 0x87);
+
+            let count = plan.items.len();
+            let noun = if count == 1 { "item" } else { "items" };
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} {} — {} in {} files",
+                    count,
+                    noun,
+                    format_size(plan.total_size),
+                    format_count(plan.total_files),
+                ))
+                .strong(),
+            );
+            ui.add_space(4.0);
+
+            for item in plan.items.iter().take(MAX_LISTED_ITEMS) {
+                ui.label(
+                    egui::RichText::new(item.path.display().to_string())
+                        .size(11.0)
+                        .color(muted),
+                );
+            }
+            if count > MAX_LISTED_ITEMS {
+                ui.label(
+                    egui::RichText::new(format!("…and {} more", count - MAX_LISTED_ITEMS))
+                        .size(11.0)
+                        .color(muted),
+                );
+            }
+
+            ui.add_space(6.0);
+            match plan.action {
+                CleanupAction::Trash => {
+                    ui.label("Items can be restored from the Recycle Bin / Trash.");
+                }
+                CleanupAction::Delete => {
+                    ui.label(
+                        egui::RichText::new("This cannot be undone.")
+                            .strong()
+                            .color(warning),
+                    );
+                }
+                CleanupAction::Archive => {
+                    let dest = plan
+                        .archive_dir
+                        .as_ref()
+                        .map(|d| d.display().to_string())
+                        .unwrap_or_default();
+                    ui.label(format!(
+                        "Items will be moved into {dest}, keeping their folder structure. \
+                         Existing files there are never overwritten."
+                    ));
+                }
+                CleanupAction::RemoveEmpty => {
+                    ui.label(
+                        "Each folder is checked again first; any that gained files \
+                         since the scan is left alone.",
+                    );
+                }
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(plan.action.label()).clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    dismissed = true;
+                }
+            });
+        });
+
+    if confirmed {
+        state.confirm_cleanup();
+    } else if dismissed || !open {
+        state.cleanup_confirm = None;
+    }
+}
+
+/// Progress while running, then removed/failed totals and failure details.
+fn report_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(ref report) = state.cleanup_report else {
+        return;
+    };
+
+    let mut open = true;
+    let mut cancel = false;
+
+    let mut window = egui::Window::new("Cleanup");
+    // No close button while running: the report is the only progress view.
+    if report.finished {
+        window = window.open(&mut open);
+    }
+    window
+        .collapsible(false)
+        .resizable(true)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            let muted = ui.visuals().weak_text_color();
+            let warning = egui::Color32::from_rgb(0xfa, 0xb3, 0x87);
+
+            if !report.finished {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!(
+                        "{}: {} / {}",
+                        report.action.label(),
+                        report.processed,
+                        report.total
+                    ));
+                });
+                if ui.button("⏹ Stop").clicked() {
+                    cancel = true;
+                }
+                return;
+            }
+
+            ui.label(
+                egui::RichText::new(format!(
+                    "Removed {} of {} items — {} freed",
+                    report.removed,
+                    report.total,
+                    format_size(report.freed),
+                ))
+                .strong(),
+            );
+            if report.cancelled {
+                ui.label(egui::RichText::new("Stopped before finishing.").color(muted));
+            }
+
+            if report.failure_count > 0 {
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(format!("{} failed:", report.failure_count)).color(warning),
+                );
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        for (path, reason) in &report.failures {
+                            ui.label(egui::RichText::new(path).size(11.0));
+                            ui.label(
+                                egui::RichText::new(format!("    {reason}"))
+                                    .size(11.0)
+                                    .color(muted),
+                            );
+                        }
+                    });
+            }
+        });
+
+    if cancel {
+        state.cancel_cleanup();
+    }
+    if !open {
+        state.cleanup_report = None;
+    }
+}
@@ -0,0 +1,241 @@
+/// Compressibility window — estimated savings from filesystem compression.
+///
+/// Starts `AppState::start_compression_estimate` for the selected folder
+/// (or the whole scan) and shows `AppState::compression_report`: the total
+/// with its 95% confidence interval, then the folders directly inside and
+/// the extensions below, most savings first.  Estimates come from sampled
+/// chunks, so ranges are shown rather than single figures.
+use crate::state::AppState;
+use disksleuth_core::analysis::{Estimate, SAMPLES_PER_STRATUM};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::{FileTree, NodeIndex};
+
+/// Draw the compressibility window if it is open.
+pub fn compression_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_compression;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Compressibility")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([720.0, 500.0])
+        .show(ctx, |ui| compression_contents(ui, state));
+
+    state.show_compression = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum CompressionAction {
+    Estimate(NodeIndex),
+    Cancel,
+    Reveal(NodeIndex),
+}
+
+/// Folder an estimate would cover: the selected folder, the folder of the
+/// selected file, or the scan root.
+fn target_folder(tree: &FileTree, selected: Option<NodeIndex>) -> Option<NodeIndex> {
+    match selected.filter(|n| n.idx() < tree.len()) {
+        Some(n) if tree.node(n).is_dir => Some(n),
+        Some(n) => tree.node(n).parent,
+        None => tree.roots.first().copied(),
+    }
+}
+
+/// "1.2 GB (0.9 GB – 1.5 GB)"-style savings with their 95% range.
+fn savings_text(estimate: &Estimate) -> String {
+    let (low, high) = estimate.savings_range();
+    format!(
+        "{} ({} – {})",
+        format_size(estimate.savings()),
+        format_size(low),
+        format_size(high)
+    )
+}
+
+/// Compressed share of the original as "42% ± 3%".
+fn ratio_text(estimate: &Estimate) -> String {
+    format!(
+        "{:.0}% ± {:.0}%",
+        estimate.ratio() * 100.0,
+        estimate.margin * 100.0
+    )
+}
+
+fn compression_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(ref tree) = state.tree else {
+        ui.label(egui::RichText::new("Finish a scan to estimate compressibility.").color(muted));
+        return;
+    };
+
+    let mut action: Option<CompressionAction> = None;
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+
+    // ── Target ─────────────────────────────────────────────────
+    if let Some(root) = state.compression_root {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            let (sampled, total, bytes_read) = state.compression_progress;
+            ui.label(format!(
+                "Sampling {} of {} files in {} ({} read)…",
+                format_count(sampled),
+                format_count(total),
+                tree.full_path(root),
+                format_size(bytes_read)
+            ));
+            if ui.button("Cancel").clicked() {
+                action = Some(CompressionAction::Cancel);
+            }
+        });
+    } else if let Some(target) = target_folder(tree, state.selected_node) {
+        ui.horizontal(|ui| {
+            if ui
+                .button("🗜 Estimate")
+                .on_hover_text(format!(
+                    "Compress up to {SAMPLES_PER_STRATUM} sampled files of each type and size"
+                ))
+                .clicked()
+            {
+                action = Some(CompressionAction::Estimate(target));
+            }
+            ui.label(small(tree.full_path(target)).color(muted));
+        });
+    }
+    ui.separator();
+
+    let Some(ref report) = state.compression_report else {
+        ui.label(
+            egui::RichText::new("Select a folder and estimate how well it would compress.")
+                .color(muted),
+        );
+        apply(state, action);
+        return;
+    };
+    if report.root.idx() >= tree.len() {
+        apply(state, action);
+        return;
+    }
+
+    // ── Summary ────────────────────────────────────────────────
+    ui.label(
+        egui::RichText::new(format!(
+            "About {} saved of {} — compressed to {}",
+            savings_text(&report.total),
+            format_size(report.total.bytes),
+            ratio_text(&report.total)
+        ))
+        .strong()
+        .color(accent),
+    );
+    let mut detail = format!(
+        "{} · {} files · {} sampled, {} read",
+        tree.full_path(report.root),
+        format_count(report.total.files),
+        format_count(report.files_sampled),
+        format_size(report.bytes_read)
+    );
+    if report.unreadable > 0 {
+        detail.push_str(&format!(
+            " · {} unreadable",
+            format_count(report.unreadable)
+        ));
+    }
+    ui.label(small(detail).color(muted));
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .id_salt("compression")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            // ── Folders ────────────────────────────────────────
+            if !report.dirs.is_empty() {
+                egui::CollapsingHeader::new(format!("Folders ({})", report.dirs.len()))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("compression_dirs")
+                            .num_columns(4)
+                            .striped(true)
+                            .spacing([12.0, 2.0])
+                            .show(ui, |ui| {
+                                for header in ["Folder", "Size", "Compressed to", "Saved (95%)"] {
+                                    ui.label(small(header.to_string()).strong());
+                                }
+                                ui.end_row();
+                                for d in &report.dirs {
+                                    if d.index.idx() >= tree.len() {
+                                        continue;
+                                    }
+                                    let selected = state.selected_node == Some(d.index);
+                                    if ui
+                                        .selectable_label(
+                                            selected,
+                                            small(tree.node(d.index).name.to_string()),
+                                        )
+                                        .on_hover_text(tree.full_path(d.index))
+                                        .clicked()
+                                    {
+                                        action = Some(CompressionAction::Reveal(d.index));
+                                    }
+                                    ui.label(small(format_size(d.estimate.bytes)));
+                                    ui.label(small(ratio_text(&d.estimate)));
+                                    ui.label(small(savings_text(&d.estimate)).color(accent));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+
+            // ── Extensions ─────────────────────────────────────
+            egui::CollapsingHeader::new(format!("Extensions ({})", report.extensions.len()))
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("compression_extensions")
+                        .num_columns(5)
+                        .striped(true)
+                        .spacing([12.0, 2.0])
+                        .show(ui, |ui| {
+                            for header in
+                                ["Extension", "Category", "Size", "Compressed to", "Saved (95%)"]
+                            {
+                                ui.label(small(header.to_string()).strong());
+                            }
+                            ui.end_row();
+                            for e in &report.extensions {
+                                let name = if e.extension.is_empty() {
+                                    "(none)".to_string()
+                                } else {
+                                    format!(".{}", e.extension)
+                                };
+                                let basis = if e.sampled {
+                                    format!("From {} samples of its own", e.estimate.samples)
+                                } else {
+                                    "Too few samples of its own — estimated from files of the same category and size".to_string()
+                                };
+                                ui.label(small(name)).on_hover_text(basis);
+                                ui.label(small(e.category.label().to_string()).color(muted));
+                                ui.label(small(format_size(e.estimate.bytes)));
+                                let ratio = small(ratio_text(&e.estimate));
+                                ui.label(if e.sampled { ratio } else { ratio.italics() });
+                                ui.label(small(savings_text(&e.estimate)).color(accent));
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    apply(state, action);
+}
+
+fn apply(state: &mut AppState, action: Option<CompressionAction>) {
+    match action {
+        Some(CompressionAction::Estimate(root)) => state.start_compression_estimate(root),
+        Some(CompressionAction::Cancel) => state.cancel_compression_estimate(),
+        Some(CompressionAction::Reveal(node)) => state.reveal_node(node),
+        None => {}
+    }
+}
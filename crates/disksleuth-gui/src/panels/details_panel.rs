@@ -1,8 +1,11 @@
 /// Details panel — shows information about the currently selected node.
 use crate::state::AppState;
+use disksleuth_core::analysis::categorise_file_name;
 use disksleuth_core::model::size::{format_count, format_size};
-use disksleuth_core::model::FileTree;
+use disksleuth_core::model::time::format_date;
+use disksleuth_core::model::{format_mode, FileTree};
 use egui::Ui;
+use std::time::SystemTime;
 
 /// Draw the details panel for the currently selected node.
 pub fn details_panel(ui: &mut Ui, state: &AppState) {
@@ -123,20 +126,86 @@ pub fn details_panel(ui: &mut Ui, state: &AppState) {
                 ui.end_row();
             }
 
-            if let Some(modified) = node.modified {
-                if let Ok(duration) = modified.elapsed() {
-                    let days = duration.as_secs() / 86400;
-                    let date_str = if days == 0 {
-                        "Today".to_string()
-                    } else if days == 1 {
-                        "Yesterday".to_string()
-                    } else if days < 365 {
-                        format!("{days} days ago")
-                    } else {
-                        format!("{:.1} years ago", days as f64 / 365.0)
-                    };
-                    ui.label(egui::RichText::new("Modified:").color(color_muted));
-                    ui.label(egui::RichText::new(date_str).color(color_normal));
+            let extended = node.extended.as_deref();
+            let times = [
+                ("Modified:", node.modified),
+                ("Created:", extended.and_then(|e| e.created)),
+                ("Accessed:", extended.and_then(|e| e.accessed)),
+            ];
+            for (label, time) in times {
+                let Some((time, age)) = time.and_then(|t| Some((t, relative_age(t)?))) else {
+                    continue;
+                };
+                ui.label(egui::RichText::new(label).color(color_muted));
+                ui.label(egui::RichText::new(age).color(color_normal))
+                    .on_hover_text(format_date(time));
+                ui.end_row();
+            }
+
+            // Owner, when the scan recorded it; named once the owner
+            // analysis has looked the account up.
+            if let Some(owner) = tree.owners.get(node.owner) {
+                let name = state
+                    .owner_report
+                    .as_ref()
+                    .and_then(|r| r.owners.iter().find(|u| u.owner.as_ref() == Some(owner)))
+                    .and_then(|u| u.name.as_deref());
+                ui.label(egui::RichText::new("Owner:").color(color_muted));
+                match name {
+                    Some(name) => {
+                        ui.label(egui::RichText::new(name).color(color_normal))
+                            .on_hover_text(owner.to_string());
+                    }
+                    None => {
+                        ui.label(egui::RichText::new(owner.to_string()).color(color_normal));
+                    }
+                }
+                ui.end_row();
+            }
+
+            // Extended metadata, when the scan recorded it.
+            if let Some(extended) = extended {
+                if let Some(mode) = extended.mode {
+                    ui.label(egui::RichText::new("Permissions:").color(color_muted));
+                    ui.label(
+                        egui::RichText::new(format!("{} ({mode:04o})", format_mode(mode)))
+                            .monospace()
+                            .color(color_normal),
+                    );
+                    ui.end_row();
+                }
+                let attributes = extended.attributes.labels();
+                if !attributes.is_empty() {
+                    ui.label(egui::RichText::new("Attributes:").color(color_muted));
+                    ui.label(egui::RichText::new(attributes.join(", ")).color(color_normal));
+                    ui.end_row();
+                }
+                if let Some(ref target) = extended.symlink_target {
+                    ui.label(egui::RichText::new("Link target:").color(color_muted));
+                    ui.label(egui::RichText::new(target.to_string_lossy()).color(color_normal));
+                    ui.end_row();
+                }
+            }
+
+            // Content type from the signature pass (completed tree only —
+            // the report is cleared when a new scan starts).
+            let detected = state
+                .signature_report
+                .as_ref()
+                .and_then(|r| r.detected.get(&selected));
+            if let Some(&kind) = detected {
+                ui.label(egui::RichText::new("Content:").color(color_muted));
+                ui.label(egui::RichText::new(kind.label()).color(color_normal));
+                ui.end_row();
+
+                let by_name = categorise_file_name(&node.name);
+                if by_name != kind.category() {
+                    ui.label("");
+                    ui.label(
+                        egui::RichText::new(format!("⚠ Extension suggests {}", by_name.label()))
+                            .size(11.0)
+                            .color(color_warning),
+                    );
                     ui.end_row();
                 }
             }
@@ -162,3 +231,18 @@ pub fn details_panel(ui: &mut Ui, state: &AppState) {
         }
     });
 }
+
+/// "Today", "Yesterday", "N days ago" or "N.N years ago"; `None` for times
+/// in the future.
+fn relative_age(time: SystemTime) -> Option<String> {
+    let days = time.elapsed().ok()?.as_secs() / 86400;
+    Some(if days == 0 {
+        "Today".to_string()
+    } else if days == 1 {
+        "Yesterday".to_string()
+    } else if days < 365 {
+        format!("{days} days ago")
+    } else {
+        format!("{:.1} years ago", days as f64 / 365.0)
+    })
+}
@@ -0,0 +1,151 @@
+/// Empty window — folders with no files anywhere below them and zero-byte
+/// files, grouped by the top-level folder they sit in.
+///
+/// Shows `AppState::empty_report`, recomputed from the completed tree after
+/// every scan or cleanup.  Removing empty folders goes through the usual
+/// confirmation dialog with `CleanupAction::RemoveEmpty`, which checks each
+/// folder on disk again and leaves alone any that gained files.
+use crate::state::AppState;
+use disksleuth_core::cleanup::CleanupAction;
+use disksleuth_core::model::size::format_count;
+use disksleuth_core::model::NodeIndex;
+
+/// Most rows drawn per group; the rest are summarised.
+const MAX_GROUP_ROWS: usize = 200;
+
+/// Draw the empty folders window if it is open.
+pub fn empty_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_empty;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Empty Folders & Zero-Byte Files")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([620.0, 440.0])
+        .show(ctx, |ui| empty_contents(ui, state));
+
+    state.show_empty = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum EmptyAction {
+    Reveal(NodeIndex),
+    RemoveFolders,
+    AddFilesToCart,
+}
+
+fn empty_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let (Some(ref tree), Some(ref report)) = (&state.tree, &state.empty_report) else {
+        ui.label(egui::RichText::new("Finish a scan to look for empty folders.").color(muted));
+        return;
+    };
+    if report.groups.is_empty() {
+        ui.label(
+            egui::RichText::new("No empty folders or zero-byte files were found.").color(muted),
+        );
+        return;
+    }
+
+    // ── Summary and bulk actions ───────────────────────────────
+    ui.label(
+        egui::RichText::new(format!(
+            "{} empty folders ({} including nested) · {} zero-byte files",
+            format_count(report.empty_dir_count),
+            format_count(report.empty_dir_total),
+            format_count(report.zero_byte_count)
+        ))
+        .strong()
+        .color(accent),
+    );
+
+    let mut action: Option<EmptyAction> = None;
+    let can_modify = state.can_modify_tree();
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                report.empty_dir_count > 0 && can_modify,
+                egui::Button::new(format!("🧹 {}…", CleanupAction::RemoveEmpty.label())),
+            )
+            .on_hover_text("Folders that gained files since the scan are skipped")
+            .clicked()
+        {
+            action = Some(EmptyAction::RemoveFolders);
+        }
+        if ui
+            .add_enabled(
+                report.zero_byte_count > 0,
+                egui::Button::new("➕ Add zero-byte files to cleanup list"),
+            )
+            .clicked()
+        {
+            action = Some(EmptyAction::AddFilesToCart);
+        }
+    });
+    ui.label(
+        egui::RichText::new("Click an item to show it in the tree")
+            .size(11.0)
+            .color(muted),
+    );
+    ui.add_space(4.0);
+
+    // ── Groups ─────────────────────────────────────────────────
+    egui::ScrollArea::vertical()
+        .id_salt("empty_groups")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for group in &report.groups {
+                let title = format!(
+                    "{} — {} folders, {} files",
+                    tree.full_path(group.top),
+                    format_count(group.empty_dirs.len() as u64),
+                    format_count(group.zero_byte_files.len() as u64)
+                );
+                egui::CollapsingHeader::new(title)
+                    .id_salt(("empty_group", group.top))
+                    .show(ui, |ui| {
+                        let rows = group
+                            .empty_dirs
+                            .iter()
+                            .map(|&i| (i, "📁"))
+                            .chain(group.zero_byte_files.iter().map(|&i| (i, "📄")));
+                        for (index, icon) in rows.take(MAX_GROUP_ROWS) {
+                            let label = format!("{icon} {}", tree.full_path(index));
+                            if ui.link(label).clicked() {
+                                action = Some(EmptyAction::Reveal(index));
+                            }
+                        }
+                        if group.len() > MAX_GROUP_ROWS {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "…and {} more",
+                                    format_count((group.len() - MAX_GROUP_ROWS) as u64)
+                                ))
+                                .size(11.0)
+                                .color(muted),
+                            );
+                        }
+                    });
+            }
+        });
+
+    match action {
+        Some(EmptyAction::Reveal(node)) => state.reveal_node(node),
+        Some(EmptyAction::RemoveFolders) => state.remove_empty_folders(),
+        Some(EmptyAction::AddFilesToCart) => {
+            let files = state
+                .empty_report
+                .as_ref()
+                .map(|r| r.all_zero_byte_files())
+                .unwrap_or_default();
+            state.cart_add_many(&files);
+            state.show_cart = true;
+        }
+        None => {}
+    }
+}
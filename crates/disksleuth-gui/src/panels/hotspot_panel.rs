@@ -0,0 +1,146 @@
+/// Hotspots window — directories with the most (and smallest) files.
+///
+/// Shows `AppState::hotspots`, ranked in core by
+/// `disksleuth_core::analysis::analyse_hotspots` after every scan or cleanup
+/// and again when a column header is clicked.  Slack is estimated for the
+/// scanned volume's cluster size.  A button switches the treemap to file-count
+/// weighting so the same folders stand out there.
+use crate::state::{AppState, MapView, TreemapWeight};
+use disksleuth_core::analysis::{HotspotSort, HOTSPOT_MIN_FILES};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+use egui::Sense;
+
+/// Draw the hotspots window if it is open.
+pub fn hotspot_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_hotspots;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("File-Count Hotspots")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([760.0, 460.0])
+        .show(ctx, |ui| hotspot_contents(ui, state));
+
+    state.show_hotspots = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum HotspotAction {
+    Sort(HotspotSort),
+    Reveal(NodeIndex),
+    WeighTreemap,
+}
+
+fn hotspot_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(ref tree) = state.tree else {
+        ui.label(
+            egui::RichText::new("Finish a scan to look for file-count hotspots.").color(muted),
+        );
+        return;
+    };
+
+    let mut action: Option<HotspotAction> = None;
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(format!(
+                "Slack estimated for {} clusters",
+                format_size(state.cluster_size)
+            ))
+            .size(11.0)
+            .color(muted),
+        );
+        let weighted = state.treemap_weight == TreemapWeight::Files;
+        if ui
+            .add_enabled(!weighted, egui::Button::new("▦ Treemap by file count"))
+            .on_hover_text("Size treemap rectangles by the number of files instead of bytes")
+            .clicked()
+        {
+            action = Some(HotspotAction::WeighTreemap);
+        }
+    });
+    if state.hotspot_sort == HotspotSort::AverageSize {
+        ui.label(
+            egui::RichText::new(format!(
+                "Only folders with at least {} files are ranked by average size",
+                format_count(HOTSPOT_MIN_FILES)
+            ))
+            .size(11.0)
+            .color(muted),
+        );
+    }
+    ui.add_space(4.0);
+
+    if state.hotspots.is_empty() {
+        ui.label(egui::RichText::new("No folder qualifies.").color(muted));
+    }
+
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+    egui::ScrollArea::both()
+        .id_salt("hotspots")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("hotspot_table")
+                .num_columns(6)
+                .striped(true)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    ui.label(small("Folder".to_string()).strong());
+                    for sort in HotspotSort::ALL {
+                        let marker = match (state.hotspot_sort == sort, sort) {
+                            (false, _) => "",
+                            (true, HotspotSort::AverageSize) => " ▲",
+                            (true, _) => " ▼",
+                        };
+                        let header =
+                            egui::Label::new(small(format!("{}{marker}", sort.label())).strong())
+                                .sense(Sense::click());
+                        if ui
+                            .add(header)
+                            .on_hover_text("Rank by this column")
+                            .clicked()
+                        {
+                            action = Some(HotspotAction::Sort(sort));
+                        }
+                    }
+                    ui.label(small("Size".to_string()).strong());
+                    ui.end_row();
+
+                    for h in &state.hotspots {
+                        if h.index.idx() >= tree.len() {
+                            continue;
+                        }
+                        let selected = state.selected_node == Some(h.index);
+                        if ui
+                            .selectable_label(selected, small(tree.full_path(h.index)))
+                            .on_hover_text("Show in the tree")
+                            .clicked()
+                        {
+                            action = Some(HotspotAction::Reveal(h.index));
+                        }
+                        ui.label(small(format_count(h.direct_files)));
+                        ui.label(small(format_count(h.files)));
+                        ui.label(small(format_size(h.average_size())));
+                        ui.label(small(format_size(h.slack)).color(accent));
+                        ui.label(small(format_size(h.bytes)).color(muted));
+                        ui.end_row();
+                    }
+                });
+        });
+
+    match action {
+        Some(HotspotAction::Sort(sort)) => state.set_hotspot_sort(sort),
+        Some(HotspotAction::Reveal(node)) => state.reveal_node(node),
+        Some(HotspotAction::WeighTreemap) => {
+            state.treemap_weight = TreemapWeight::Files;
+            state.map_view = MapView::Treemap;
+        }
+        None => {}
+    }
+}
@@ -0,0 +1,145 @@
+/// Largest folders window — directories ranked by their own files or by
+/// total size.
+///
+/// Shows `AppState::largest_dirs`, ranked in core by
+/// `disksleuth_core::analysis::largest_dirs` after every scan or cleanup and
+/// again when the ranking or depth changes.  "Own files" counts only the
+/// files directly inside a folder, so the folders actually holding the data
+/// come first; ranking by total size at a fixed depth keeps one ancestor
+/// chain from filling the list.
+use crate::state::AppState;
+use disksleuth_core::analysis::DirRank;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+
+/// Deepest level offered in the depth picker.
+const MAX_DEPTH_CHOICE: usize = 8;
+
+/// Draw the largest folders window if it is open.
+pub fn largest_dirs_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_largest_dirs;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Largest Folders")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([720.0, 460.0])
+        .show(ctx, |ui| largest_dirs_contents(ui, state));
+
+    state.show_largest_dirs = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum LargestAction {
+    View(DirRank, Option<usize>),
+    Reveal(NodeIndex),
+}
+
+/// Label of a depth choice.
+fn depth_label(depth: Option<usize>) -> String {
+    match depth {
+        None => "Any depth".to_string(),
+        Some(1) => "1 level down".to_string(),
+        Some(d) => format!("{d} levels down"),
+    }
+}
+
+fn largest_dirs_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(ref tree) = state.tree else {
+        ui.label(egui::RichText::new("Finish a scan to rank folders by size.").color(muted));
+        return;
+    };
+
+    let mut action: Option<LargestAction> = None;
+    let (rank, depth) = (state.largest_dirs_rank, state.largest_dirs_depth);
+    ui.horizontal(|ui| {
+        ui.label("Rank by");
+        for r in DirRank::ALL {
+            if ui.selectable_label(rank == r, r.label()).clicked() {
+                action = Some(LargestAction::View(r, depth));
+            }
+        }
+        ui.separator();
+        egui::ComboBox::from_id_salt("largest_dirs_depth")
+            .selected_text(depth_label(depth))
+            .show_ui(ui, |ui| {
+                let choices = std::iter::once(None).chain((1..=MAX_DEPTH_CHOICE).map(Some));
+                for d in choices {
+                    if ui.selectable_label(depth == d, depth_label(d)).clicked() {
+                        action = Some(LargestAction::View(rank, d));
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Rank only folders this many levels below the scan root");
+    });
+    let hint = match rank {
+        DirRank::Own => "Bytes of the files directly inside each folder, not counting subfolders",
+        DirRank::Total => "Everything below each folder",
+    };
+    ui.label(egui::RichText::new(hint).size(11.0).color(muted));
+    ui.add_space(4.0);
+
+    if state.largest_dirs.is_empty() {
+        ui.label(egui::RichText::new("No folder qualifies.").color(muted));
+    }
+
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+    egui::ScrollArea::both()
+        .id_salt("largest_dirs")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("largest_dirs_table")
+                .num_columns(4)
+                .striped(true)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    for header in ["Folder", "Own files", "Own size", "Total size"] {
+                        ui.label(small(header.to_string()).strong());
+                    }
+                    ui.end_row();
+
+                    for d in &state.largest_dirs {
+                        if d.index.idx() >= tree.len() {
+                            continue;
+                        }
+                        let selected = state.selected_node == Some(d.index);
+                        if ui
+                            .selectable_label(selected, small(tree.full_path(d.index)))
+                            .on_hover_text("Show in the tree")
+                            .clicked()
+                        {
+                            action = Some(LargestAction::Reveal(d.index));
+                        }
+                        ui.label(small(format_count(d.own_files)));
+                        let (own, total) = (
+                            small(format_size(d.own_bytes)),
+                            small(format_size(d.total_bytes)),
+                        );
+                        match rank {
+                            DirRank::Own => {
+                                ui.label(own.color(accent));
+                                ui.label(total.color(muted));
+                            }
+                            DirRank::Total => {
+                                ui.label(own.color(muted));
+                                ui.label(total.color(accent));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    match action {
+        Some(LargestAction::View(rank, depth)) => state.set_largest_dirs_view(rank, depth),
+        Some(LargestAction::Reveal(node)) => state.reveal_node(node),
+        None => {}
+    }
+}
@@ -1,6 +1,19 @@
 /// UI panels for DiskSleuth.
+pub mod cache_panel;
+pub mod cart_panel;
 pub mod chart_panel;
+pub mod cleanup_panel;
+pub mod compression_panel;
 pub mod details_panel;
+pub mod empty_panel;
+pub mod hotspot_panel;
+pub mod largest_dirs_panel;
 pub mod monitor_panel;
+pub mod owners_panel;
+pub mod reclaimable_panel;
 pub mod scan_panel;
+pub mod search_panel;
+pub mod size_panel;
+pub mod slack_panel;
+pub mod stale_panel;
 pub mod tree_panel;
@@ -0,0 +1,134 @@
+/// Owners window — disk usage per file owner.
+///
+/// Shows `AppState::owner_report`, computed in the background by
+/// `disksleuth_core::analysis::start_owner_analysis` after every scan that
+/// recorded owners, and after cleanup.  Each owner expands into the folders
+/// holding most of their files, counting only the files directly inside
+/// each folder.
+use crate::state::AppState;
+use disksleuth_core::analysis::MAX_OWNER_DIRS;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+
+/// Draw the owners window if it is open.
+pub fn owners_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_owners;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Owners")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([640.0, 460.0])
+        .show(ctx, |ui| owners_contents(ui, state));
+
+    state.show_owners = open;
+}
+
+fn owners_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(ref tree) = state.tree else {
+        ui.label(egui::RichText::new("Finish a scan to see usage per owner.").color(muted));
+        return;
+    };
+    if !tree.owners_recorded {
+        ui.label(
+            egui::RichText::new("Owners are only recorded when \"Owners\" is on for the scan.")
+                .color(muted),
+        );
+        return;
+    }
+    let Some(ref report) = state.owner_report else {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Totalling files per owner…");
+        });
+        return;
+    };
+    if state.owner_handle.is_some() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(egui::RichText::new("Updating…").size(11.0).color(muted));
+        });
+    }
+
+    ui.label(
+        egui::RichText::new(format!(
+            "{} owners · {} files · {}",
+            format_count(report.owners.len() as u64),
+            format_count(report.files),
+            format_size(report.bytes)
+        ))
+        .size(11.0)
+        .color(muted),
+    );
+    ui.add_space(4.0);
+
+    let mut reveal: Option<NodeIndex> = None;
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+    egui::ScrollArea::vertical()
+        .id_salt("owners")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (i, usage) in report.owners.iter().enumerate() {
+                let share = match report.bytes {
+                    0 => 0.0,
+                    total => usage.bytes as f64 / total as f64 * 100.0,
+                };
+                let header = format!(
+                    "{} — {} ({share:.1}%) · {} files",
+                    usage.label(),
+                    format_size(usage.bytes),
+                    format_count(usage.files)
+                );
+                let response = egui::CollapsingHeader::new(header)
+                    .id_salt(("owner", i))
+                    .show(ui, |ui| {
+                        egui::Grid::new(("owner_dirs", i))
+                            .num_columns(3)
+                            .striped(true)
+                            .spacing([12.0, 2.0])
+                            .show(ui, |ui| {
+                                for header in ["Folder", "Files", "Size"] {
+                                    ui.label(small(header.to_string()).strong());
+                                }
+                                ui.end_row();
+                                for d in &usage.dirs {
+                                    if d.index.idx() >= tree.len() {
+                                        continue;
+                                    }
+                                    let selected = state.selected_node == Some(d.index);
+                                    if ui
+                                        .selectable_label(selected, small(tree.full_path(d.index)))
+                                        .on_hover_text("Show in the tree")
+                                        .clicked()
+                                    {
+                                        reveal = Some(d.index);
+                                    }
+                                    ui.label(small(format_count(d.files)));
+                                    ui.label(small(format_size(d.bytes)).color(accent));
+                                    ui.end_row();
+                                }
+                            });
+                        if usage.dirs.len() == MAX_OWNER_DIRS {
+                            ui.label(
+                                small(format!("Largest {MAX_OWNER_DIRS} folders shown"))
+                                    .color(muted),
+                            );
+                        }
+                    });
+                // The raw uid or SID, for owners shown by name.
+                if let (Some(_), Some(owner)) = (&usage.name, &usage.owner) {
+                    response.header_response.on_hover_text(owner.to_string());
+                }
+            }
+        });
+
+    if let Some(node) = reveal {
+        state.reveal_node(node);
+    }
+}
@@ -0,0 +1,297 @@
+/// Reclaimable window — build outputs and dependency caches that can be
+/// regenerated.
+///
+/// The list comes from a background pass (`AppState::start_reclaimable_analysis`)
+/// re-run after every scan or cleanup.  Ticked artefacts can be sent to the
+/// cleanup list or trashed in one go through the usual confirmation dialog;
+/// clicking a row reveals it in the tree.
+use crate::state::AppState;
+use disksleuth_core::analysis::ArtefactReport;
+use disksleuth_core::cleanup::CleanupAction;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::time::format_date;
+use disksleuth_core::model::NodeIndex;
+
+/// Height of one list row in pixels.
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Width of the tick-box column.
+const CHECK_COL_WIDTH: f32 = 22.0;
+
+/// Widths of the right-hand columns (kind, size, last activity).
+const KIND_COL_WIDTH: f32 = 140.0;
+const SIZE_COL_WIDTH: f32 = 80.0;
+const DATE_COL_WIDTH: f32 = 84.0;
+
+/// Draw the reclaimable window if it is open.
+pub fn reclaimable_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_reclaimable;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Reclaimable Space")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([720.0, 480.0])
+        .show(ctx, |ui| reclaimable_contents(ui, state));
+
+    state.show_reclaimable = open;
+}
+
+/// What the user did in the window this frame, applied once drawing is done.
+enum ReclaimAction {
+    /// Tick all artefacts, or all of one kind.
+    SelectAll(Option<&'static str>),
+    ClearSelection,
+    Toggle(NodeIndex),
+    Reveal(NodeIndex),
+    AddToCart,
+    Trash,
+}
+
+fn reclaimable_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    if state.tree.is_none() {
+        ui.label(
+            egui::RichText::new("Finish a scan to look for build outputs and caches.").color(muted),
+        );
+        return;
+    }
+    let Some(ref report) = state.reclaimable_report else {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Looking for build outputs and caches…");
+        });
+        return;
+    };
+
+    // ── Totals by kind ─────────────────────────────────────────
+    let mut summary = format!(
+        "{} build outputs and caches — {}",
+        format_count(report.total_count),
+        format_size(report.total_bytes)
+    );
+    let shown = report.artefacts.len() as u64;
+    if report.total_count > shown {
+        summary.push_str(&format!(" (showing largest {})", format_count(shown)));
+    }
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(summary).strong().color(accent));
+        if state.reclaimable_handle.is_some() {
+            ui.spinner();
+        }
+    });
+    if report.artefacts.is_empty() {
+        return;
+    }
+
+    let mut action: Option<ReclaimAction> = None;
+    kind_totals(ui, report, &mut action);
+    ui.separator();
+
+    // ── Selection ──────────────────────────────────────────────
+    let (selected, selected_bytes) = state.reclaimable_selection_totals();
+    let can_modify = state.can_modify_tree();
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} selected — {}",
+            format_count(selected as u64),
+            format_size(selected_bytes)
+        ));
+        if ui.button("Select all").clicked() {
+            action = Some(ReclaimAction::SelectAll(None));
+        }
+        if ui
+            .add_enabled(selected > 0, egui::Button::new("Clear selection"))
+            .clicked()
+        {
+            action = Some(ReclaimAction::ClearSelection);
+        }
+        if ui
+            .add_enabled(selected > 0, egui::Button::new("➕ Add to cleanup list"))
+            .clicked()
+        {
+            action = Some(ReclaimAction::AddToCart);
+        }
+        if ui
+            .add_enabled(
+                selected > 0 && can_modify,
+                egui::Button::new(format!("{}…", CleanupAction::Trash.label())),
+            )
+            .on_hover_text("Everything here can be rebuilt or re-downloaded by its tool")
+            .clicked()
+        {
+            action = Some(ReclaimAction::Trash);
+        }
+    });
+    ui.label(
+        egui::RichText::new("Tick to select · click a row to show it in the tree")
+            .size(11.0)
+            .color(muted),
+    );
+    ui.add_space(2.0);
+
+    // ── Artefact list (virtualised) ────────────────────────────
+    let color_normal = ui.visuals().text_color();
+    let color_hover = ui.visuals().widgets.hovered.weak_bg_fill;
+    let columns_width = KIND_COL_WIDTH + SIZE_COL_WIDTH + DATE_COL_WIDTH;
+
+    let (header, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), ROW_HEIGHT),
+        egui::Sense::hover(),
+    );
+    let header_font = egui::FontId::proportional(11.0);
+    let painter = ui.painter_at(header);
+    painter.text(
+        header.left_center() + egui::vec2(CHECK_COL_WIDTH + 4.0, 0.0),
+        egui::Align2::LEFT_CENTER,
+        "Path",
+        header_font.clone(),
+        muted,
+    );
+    for (label, right) in column_edges(header) {
+        painter.text(
+            egui::pos2(right - 4.0, header.center().y),
+            egui::Align2::RIGHT_CENTER,
+            label,
+            header_font.clone(),
+            muted,
+        );
+    }
+
+    let artefacts = &report.artefacts;
+    egui::ScrollArea::vertical()
+        .id_salt("reclaimable_artefacts")
+        .auto_shrink([false, false])
+        .show_rows(ui, ROW_HEIGHT, artefacts.len(), |ui, range| {
+            for artefact in &artefacts[range] {
+                let (rect, resp) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), ROW_HEIGHT),
+                    egui::Sense::click(),
+                );
+                if resp.hovered() {
+                    ui.painter().rect_filled(rect, 2.0, color_hover);
+                }
+
+                let check_rect =
+                    egui::Rect::from_min_size(rect.min, egui::vec2(CHECK_COL_WIDTH, ROW_HEIGHT));
+                let mut ticked = state.reclaimable_selected.contains(&artefact.index);
+                if ui
+                    .put(check_rect, egui::Checkbox::without_text(&mut ticked))
+                    .changed()
+                {
+                    action = Some(ReclaimAction::Toggle(artefact.index));
+                }
+
+                let font = egui::FontId::proportional(12.0);
+                let path_clip = egui::Rect::from_min_max(
+                    egui::pos2(check_rect.right(), rect.top()),
+                    egui::pos2(rect.right() - columns_width, rect.bottom()),
+                );
+                ui.painter_at(path_clip).text(
+                    path_clip.left_center() + egui::vec2(4.0, 0.0),
+                    egui::Align2::LEFT_CENTER,
+                    &artefact.path,
+                    font.clone(),
+                    color_normal,
+                );
+                let cells = [
+                    (artefact.kind.to_string(), color_normal),
+                    (format_size(artefact.size), accent),
+                    (
+                        artefact.last_activity.map(format_date).unwrap_or_default(),
+                        muted,
+                    ),
+                ];
+                let painter = ui.painter_at(rect);
+                for ((text, color), (_, right)) in cells.into_iter().zip(column_edges(rect)) {
+                    painter.text(
+                        egui::pos2(right - 4.0, rect.center().y),
+                        egui::Align2::RIGHT_CENTER,
+                        text,
+                        font.clone(),
+                        color,
+                    );
+                }
+
+                if resp.clicked() {
+                    action = Some(ReclaimAction::Reveal(artefact.index));
+                }
+                resp.on_hover_text(format!(
+                    "{}\n{} files",
+                    artefact.path,
+                    format_count(artefact.files)
+                ));
+            }
+        });
+
+    match action {
+        Some(ReclaimAction::SelectAll(kind)) => state.reclaimable_select_all(kind),
+        Some(ReclaimAction::ClearSelection) => state.reclaimable_selected.clear(),
+        Some(ReclaimAction::Toggle(node)) => state.reclaimable_toggle(node),
+        Some(ReclaimAction::Reveal(node)) => state.reveal_node(node),
+        Some(ReclaimAction::AddToCart) => {
+            let picked = state.reclaimable_picked();
+            state.cart_add_many(&picked);
+            state.show_cart = true;
+        }
+        Some(ReclaimAction::Trash) => state.reclaimable_cleanup(CleanupAction::Trash),
+        None => {}
+    }
+}
+
+/// One line per artefact kind with its count and bytes, largest first, each
+/// with a link that selects just that kind.
+fn kind_totals(ui: &mut egui::Ui, report: &ArtefactReport, action: &mut Option<ReclaimAction>) {
+    let muted = ui.visuals().weak_text_color();
+    // Few kinds exist, so a linear scan per artefact is fine.
+    let mut kinds: Vec<(&'static str, u64, u64)> = Vec::new();
+    for a in &report.artefacts {
+        match kinds.iter_mut().find(|(k, _, _)| *k == a.kind) {
+            Some(entry) => {
+                entry.1 += 1;
+                entry.2 += a.size;
+            }
+            None => kinds.push((a.kind, 1, a.size)),
+        }
+    }
+    kinds.sort_by(|a, b| b.2.cmp(&a.2));
+
+    egui::Grid::new("reclaimable_kinds")
+        .num_columns(3)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for (kind, count, bytes) in kinds {
+                ui.label(egui::RichText::new(kind).size(12.0));
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} in {}",
+                        format_size(bytes),
+                        format_count(count)
+                    ))
+                    .size(11.0)
+                    .color(muted),
+                );
+                if ui.small_button("Select").clicked() {
+                    *action = Some(ReclaimAction::SelectAll(Some(kind)));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// Header labels paired with the right edge of their column in `row`.
+fn column_edges(row: egui::Rect) -> [(&'static str, f32); 3] {
+    let date_right = row.right();
+    let size_right = date_right - DATE_COL_WIDTH;
+    let kind_right = size_right - SIZE_COL_WIDTH;
+    [
+        ("Kind", kind_right),
+        ("Size", size_right),
+        ("Last activity", date_right),
+    ]
+}
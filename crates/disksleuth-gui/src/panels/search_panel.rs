@@ -0,0 +1,189 @@
+/// Search panel — incremental name search with reveal-in-tree.
+///
+/// The query is re-run on a background thread on every edit (see
+/// `AppState::start_search`), so typing never blocks the render thread even
+/// on multi-million-node trees.  Clicking a result selects it, expands the
+/// tree view down to it, and shows its parent directory in the treemap.
+use crate::state::{AppPhase, AppState};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::search::SearchMode;
+use egui::Ui;
+
+/// Height of a single result row in pixels.
+const RESULT_ROW_HEIGHT: f32 = 34.0;
+
+/// Maximum height of the results list before it scrolls.
+const RESULTS_MAX_HEIGHT: f32 = 260.0;
+
+/// Draw the search panel (left sidebar, above the tree).
+pub fn search_panel(ui: &mut Ui, state: &mut AppState) {
+    let color_muted = ui.visuals().weak_text_color();
+    let color_normal = ui.visuals().text_color();
+    let color_accent = ui.visuals().hyperlink_color;
+    let color_warning = egui::Color32::from_rgb(0xfa, 0xb3, 0x87);
+
+    ui.heading("Search");
+    ui.add_space(4.0);
+
+    if state.tree.is_none() {
+        let msg = if state.phase == AppPhase::Scanning {
+            "Available after scan completes."
+        } else {
+            "Run a scan to search file names."
+        };
+        ui.label(egui::RichText::new(msg).color(color_muted).size(12.0));
+        return;
+    }
+
+    // ── Query row ─────────────────────────────────────────────────────
+    let mut query_changed = false;
+    ui.horizontal(|ui| {
+        let prev_mode = state.search_mode;
+        egui::ComboBox::from_id_salt("search_mode")
+            .width(78.0)
+            .selected_text(state.search_mode.label())
+            .show_ui(ui, |ui| {
+                for mode in [SearchMode::Substring, SearchMode::Glob, SearchMode::Regex] {
+                    ui.selectable_value(&mut state.search_mode, mode, mode.label());
+                }
+            });
+        if state.search_mode != prev_mode {
+            query_changed = true;
+        }
+
+        let hint = match state.search_mode {
+            SearchMode::Substring => "Name contains…",
+            SearchMode::Glob => "e.g. *.iso",
+            SearchMode::Regex => r"e.g. ^backup_\d+",
+        };
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut state.search_query)
+                .hint_text(hint)
+                .desired_width(ui.available_width() - 30.0),
+        );
+        if edit.changed() {
+            query_changed = true;
+        }
+
+        let can_clear = !state.search_query.is_empty();
+        if ui
+            .add_enabled(can_clear, egui::Button::new("✖").small())
+            .on_hover_text("Clear search")
+            .clicked()
+        {
+            state.search_query.clear();
+            query_changed = true;
+        }
+    });
+
+    if query_changed {
+        state.start_search();
+    }
+
+    // ── Status line ───────────────────────────────────────────────────
+    if let Some(ref err) = state.search_error {
+        ui.label(egui::RichText::new(err).color(color_warning).size(11.0));
+        return;
+    }
+    if state.search_query.trim().is_empty() {
+        return;
+    }
+    ui.horizontal(|ui| {
+        if state.search_handle.is_some() {
+            ui.spinner();
+            ui.label(
+                egui::RichText::new("Searching…")
+                    .color(color_muted)
+                    .size(11.0),
+            );
+        } else {
+            let shown = state.search_results.len() as u64;
+            let text = if state.search_total_matches > shown {
+                format!(
+                    "Showing largest {} of {} matches",
+                    format_count(shown),
+                    format_count(state.search_total_matches)
+                )
+            } else {
+                format!("{} matches", format_count(state.search_total_matches))
+            };
+            ui.label(egui::RichText::new(text).color(color_muted).size(11.0));
+        }
+    });
+
+    if state.search_results.is_empty() {
+        return;
+    }
+
+    // ── Results list (virtualised) ────────────────────────────────────
+    let color_selection = ui.visuals().selection.bg_fill;
+    let color_hover = ui.visuals().widgets.hovered.weak_bg_fill;
+    let mut clicked: Option<disksleuth_core::model::NodeIndex> = None;
+
+    egui::ScrollArea::vertical()
+        .id_salt("search_results")
+        .max_height(RESULTS_MAX_HEIGHT)
+        .auto_shrink([false, true])
+        .show_rows(
+            ui,
+            RESULT_ROW_HEIGHT,
+            state.search_results.len(),
+            |ui, range| {
+                for hit in &state.search_results[range] {
+                    let (rect, resp) = ui.allocate_exact_size(
+                        egui::vec2(ui.available_width(), RESULT_ROW_HEIGHT),
+                        egui::Sense::click(),
+                    );
+                    // Clip to the row so long paths never bleed into neighbours.
+                    let painter = ui.painter_at(rect);
+
+                    if state.selected_node == Some(hit.index) {
+                        painter.rect_filled(rect, 2.0, color_selection);
+                    } else if resp.hovered() {
+                        painter.rect_filled(rect, 2.0, color_hover);
+                    }
+
+                    let name = hit.path.rsplit('\\').next().unwrap_or(&hit.path);
+                    let icon = if hit.is_dir { "📁" } else { "📄" };
+                    let size_text = format_size(hit.size);
+                    let size_w = size_text.len() as f32 * 6.5 + 8.0;
+
+                    painter.text(
+                        rect.right_top() + egui::vec2(-4.0, 3.0),
+                        egui::Align2::RIGHT_TOP,
+                        &size_text,
+                        egui::FontId::proportional(11.0),
+                        color_accent,
+                    );
+                    // Name line is clipped short of the size column.
+                    let name_clip = egui::Rect::from_min_max(
+                        rect.min,
+                        egui::pos2(rect.right() - size_w, rect.bottom()),
+                    );
+                    ui.painter_at(name_clip).text(
+                        rect.left_top() + egui::vec2(4.0, 2.0),
+                        egui::Align2::LEFT_TOP,
+                        format!("{icon} {name}"),
+                        egui::FontId::proportional(12.0),
+                        color_normal,
+                    );
+                    painter.text(
+                        rect.left_top() + egui::vec2(22.0, 19.0),
+                        egui::Align2::LEFT_TOP,
+                        &hit.path,
+                        egui::FontId::proportional(10.0),
+                        color_muted,
+                    );
+
+                    if resp.clicked() {
+                        clicked = Some(hit.index);
+                    }
+                    resp.on_hover_text(&hit.path);
+                }
+            },
+        );
+
+    if let Some(node) = clicked {
+        state.reveal_node(node);
+    }
+}
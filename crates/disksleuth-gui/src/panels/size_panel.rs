@@ -0,0 +1,195 @@
+/// Size panel — file size distribution as a power-of-two histogram.
+///
+/// Shows `AppState::size_histogram`, computed by
+/// `disksleuth_core::analysis::analyse_sizes` with the other post-scan
+/// reports, for the whole scan or the folder picked here.  Bars are drawn by
+/// bytes or by file count; hovering one shows its range and totals.  Below
+/// the bars: the p50 / p90 / p99 file sizes and the share of bytes in the
+/// largest 1% of files.
+use crate::state::{AppPhase, AppState};
+use disksleuth_core::analysis::SizeHistogram;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+use egui::{Pos2, Rect, Sense, Ui, Vec2};
+
+/// Height (px) of the histogram bars area.
+const HISTOGRAM_HEIGHT: f32 = 90.0;
+
+/// Gap (px) between bars.
+const BAR_GAP: f32 = 1.0;
+
+/// "4 KB – 8 KB"-style range of a bucket.
+fn bucket_label(bucket: usize) -> String {
+    match SizeHistogram::bucket_bounds(bucket) {
+        (0, _) => "Empty files".to_string(),
+        (low, Some(high)) => format!("{} – {}", format_size(low), format_size(high)),
+        (low, None) => format!("{} and up", format_size(low)),
+    }
+}
+
+/// Draw the size distribution panel.
+pub fn size_panel(ui: &mut Ui, state: &mut AppState) {
+    let color_muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    ui.horizontal(|ui| {
+        ui.heading("File Sizes");
+        if state.size_histogram.is_some() {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.selectable_value(&mut state.size_chart_by_count, true, "Count")
+                    .on_hover_text("Bars by number of files");
+                ui.selectable_value(&mut state.size_chart_by_count, false, "Size")
+                    .on_hover_text("Bars by total size");
+            });
+        }
+    });
+    ui.add_space(4.0);
+
+    let (Some(ref tree), Some(ref histogram)) = (&state.tree, &state.size_histogram) else {
+        if state.phase == AppPhase::Scanning {
+            ui.label(
+                egui::RichText::new("Available after scan completes.")
+                    .color(color_muted)
+                    .size(12.0),
+            );
+        }
+        return;
+    };
+
+    // ── Scope ──────────────────────────────────────────────────────
+    // The selected folder, or the folder of the selected file.
+    let selected_folder = state
+        .selected_node
+        .filter(|n| n.idx() < tree.len())
+        .and_then(|n| {
+            if tree.node(n).is_dir {
+                Some(n)
+            } else {
+                tree.node(n).parent
+            }
+        });
+    let mut scope: Option<Option<NodeIndex>> = None;
+    ui.horizontal(|ui| {
+        if ui
+            .selectable_label(histogram.root.is_none(), "Whole scan")
+            .clicked()
+        {
+            scope = Some(None);
+        }
+        let here = selected_folder.is_some() && histogram.root == selected_folder;
+        if ui
+            .add_enabled(
+                selected_folder.is_some(),
+                egui::SelectableLabel::new(here, "Selected folder"),
+            )
+            .clicked()
+        {
+            scope = Some(selected_folder);
+        }
+    });
+    if let Some(root) = histogram.root.filter(|n| n.idx() < tree.len()) {
+        ui.label(
+            egui::RichText::new(tree.full_path(root))
+                .size(11.0)
+                .color(color_muted),
+        );
+    }
+
+    let used = histogram.used_buckets();
+    if used.is_empty() {
+        ui.label(egui::RichText::new("No files.").color(color_muted));
+    } else {
+        // ── Bars ───────────────────────────────────────────────────
+        let by_count = state.size_chart_by_count;
+        let value = |b: usize| {
+            let bucket = histogram.buckets[b];
+            if by_count {
+                bucket.files
+            } else {
+                bucket.bytes
+            }
+        };
+        let max = used.clone().map(value).max().unwrap_or(0).max(1);
+        let width = ui.available_width();
+        let (rect, response) =
+            ui.allocate_exact_size(Vec2::new(width, HISTOGRAM_HEIGHT), Sense::hover());
+        let bar_width = width / used.len() as f32;
+        let bar_rect = |i: usize| {
+            let left = rect.left() + i as f32 * bar_width;
+            Rect::from_min_max(
+                Pos2::new(left, rect.top()),
+                Pos2::new(left + bar_width - BAR_GAP, rect.bottom()),
+            )
+        };
+
+        let hovered = response
+            .hover_pos()
+            .map(|pos| ((pos.x - rect.left()) / bar_width) as usize)
+            .filter(|&i| i < used.len())
+            .map(|i| used.start + i);
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        for (i, b) in used.clone().enumerate() {
+            let slot = bar_rect(i);
+            let fraction = value(b) as f32 / max as f32;
+            let top = slot.bottom() - slot.height() * fraction;
+            let color = if hovered == Some(b) {
+                accent
+            } else {
+                accent.gamma_multiply(0.6)
+            };
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(slot.left(), top), slot.max),
+                1.0,
+                color,
+            );
+        }
+        if let Some(b) = hovered {
+            let bucket = histogram.buckets[b];
+            response.on_hover_text(format!(
+                "{}\n{} files · {}",
+                bucket_label(b),
+                format_count(bucket.files),
+                format_size(bucket.bytes)
+            ));
+        }
+
+        // Axis: smallest and largest bucket.
+        ui.horizontal(|ui| {
+            let small = |text: String| egui::RichText::new(text).size(10.0).color(color_muted);
+            ui.label(small(format_size(
+                SizeHistogram::bucket_bounds(used.start).0,
+            )));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(small(format_size(
+                    SizeHistogram::bucket_bounds(used.end - 1).0,
+                )));
+            });
+        });
+    }
+
+    // ── Percentiles ────────────────────────────────────────────────
+    ui.label(
+        egui::RichText::new(format!(
+            "Median {} · p90 {} · p99 {}",
+            format_size(histogram.p50),
+            format_size(histogram.p90),
+            format_size(histogram.p99)
+        ))
+        .size(12.0),
+    );
+    ui.label(
+        egui::RichText::new(format!(
+            "Largest 1% ({} files) hold {:.1}% of {}",
+            format_count(histogram.top_percent_files),
+            histogram.top_percent_share() * 100.0,
+            format_size(histogram.bytes)
+        ))
+        .size(11.0)
+        .color(color_muted),
+    );
+
+    if let Some(scope) = scope {
+        state.set_size_scope(scope);
+    }
+}
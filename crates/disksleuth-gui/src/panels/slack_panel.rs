@@ -0,0 +1,169 @@
+/// Slack window — space lost to cluster rounding, and what it would be on a
+/// volume formatted with another cluster size.
+///
+/// Shows `AppState::slack_report`, computed by
+/// `disksleuth_core::analysis::analyse_slack` for the scanned volume's
+/// cluster size after every scan or cleanup.  The projection table compares
+/// the whole scan at each candidate size; the directory table lists the
+/// folders losing the most, with their usage at each candidate.
+use crate::state::AppState;
+use disksleuth_core::analysis::{SlackProjection, CANDIDATE_CLUSTER_SIZES};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::NodeIndex;
+
+/// Draw the slack window if it is open.
+pub fn slack_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_slack;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Cluster Slack")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([720.0, 480.0])
+        .show(ctx, |ui| slack_contents(ui, state));
+
+    state.show_slack = open;
+}
+
+/// Signed size difference, e.g. "+1.2 GB" or "−300 MB".
+fn format_change(from: u64, to: u64) -> String {
+    if to >= from {
+        format!("+{}", format_size(to - from))
+    } else {
+        format!("−{}", format_size(from - to))
+    }
+}
+
+fn slack_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let (Some(ref tree), Some(ref report)) = (&state.tree, &state.slack_report) else {
+        ui.label(egui::RichText::new("Finish a scan to measure cluster slack.").color(muted));
+        return;
+    };
+
+    // ── Summary ────────────────────────────────────────────────
+    ui.label(
+        egui::RichText::new(format!(
+            "{} lost to cluster rounding — {:.1}% of allocated space",
+            format_size(report.actual.slack),
+            report.slack_percent()
+        ))
+        .strong()
+        .color(accent),
+    );
+    let mut detail = format!(
+        "{} files · {} cluster size · {} logical, {} allocated",
+        format_count(report.files),
+        format_size(report.cluster_size),
+        format_size(report.logical),
+        format_size(report.actual.allocated)
+    );
+    if report.stored < report.logical {
+        detail.push_str(&format!(
+            " ({} saved by compression or sparse files)",
+            format_size(report.logical - report.stored)
+        ));
+    }
+    ui.label(egui::RichText::new(detail).size(11.0).color(muted));
+    ui.add_space(6.0);
+
+    let small = |text: String| egui::RichText::new(text).size(11.0);
+
+    // ── Projections ────────────────────────────────────────────
+    let rows = std::iter::once((report.actual, true)).chain(
+        report
+            .projected
+            .iter()
+            .filter(|p| p.cluster_size != report.cluster_size)
+            .map(|&p| (p, false)),
+    );
+    egui::Grid::new("slack_projections")
+        .num_columns(4)
+        .striped(true)
+        .spacing([16.0, 2.0])
+        .show(ui, |ui| {
+            for header in ["Cluster size", "Allocated", "Slack", "Change"] {
+                ui.label(small(header.to_string()).strong());
+            }
+            ui.end_row();
+            for (p, current) in rows {
+                let SlackProjection {
+                    cluster_size,
+                    allocated,
+                    slack,
+                } = p;
+                let name = if current {
+                    format!("{} (this volume)", format_size(cluster_size))
+                } else {
+                    format_size(cluster_size)
+                };
+                ui.label(small(name));
+                ui.label(small(format_size(allocated)));
+                ui.label(small(format_size(slack)));
+                if current {
+                    ui.label(small("—".to_string()).color(muted));
+                } else {
+                    ui.label(
+                        small(format_change(report.actual.allocated, allocated)).color(accent),
+                    );
+                }
+                ui.end_row();
+            }
+        });
+    ui.separator();
+
+    // ── Directories ────────────────────────────────────────────
+    ui.label(
+        egui::RichText::new("Folders losing the most · click one to show it in the tree")
+            .size(11.0)
+            .color(muted),
+    );
+    let mut reveal: Option<NodeIndex> = None;
+    egui::ScrollArea::both()
+        .id_salt("slack_dirs")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("slack_dirs_grid")
+                .num_columns(3 + CANDIDATE_CLUSTER_SIZES.len())
+                .striped(true)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    ui.label(small("Folder".to_string()).strong());
+                    ui.label(small("Files".to_string()).strong());
+                    ui.label(small("Slack".to_string()).strong());
+                    for c in CANDIDATE_CLUSTER_SIZES {
+                        ui.label(small(format!("At {}", format_size(c))).strong());
+                    }
+                    ui.end_row();
+
+                    for d in &report.dirs {
+                        if d.index.idx() >= tree.len() {
+                            continue;
+                        }
+                        let selected = state.selected_node == Some(d.index);
+                        if ui
+                            .selectable_label(selected, small(tree.full_path(d.index)))
+                            .clicked()
+                        {
+                            reveal = Some(d.index);
+                        }
+                        ui.label(small(format_count(d.files)));
+                        ui.label(small(format_size(d.slack)).color(accent));
+                        for p in &d.projected {
+                            ui.label(small(format_size(p.allocated)))
+                                .on_hover_text(format!("{} slack", format_size(p.slack)));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(node) = reveal {
+        state.reveal_node(node);
+    }
+}
@@ -0,0 +1,343 @@
+/// Stale files window — large files nobody has touched in a long time.
+///
+/// The list and the age histogram come from one background pass
+/// (`AppState::start_stale_analysis`), re-run whenever the threshold or
+/// limit changes and after every scan or cleanup.  Age is measured from
+/// modification by default, or from last access when the scan recorded
+/// extended metadata.  A plain click selects a
+/// file and reveals it in the tree; Ctrl/Shift+click build a multi-selection
+/// that can be sent to the cleanup list.
+use crate::state::{AppState, MAX_STALE_RESULTS};
+use disksleuth_core::analysis::{AgeBasis, AgeBucket, AgeHistogram};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::time::{format_age_days, format_date};
+use disksleuth_core::model::NodeIndex;
+
+/// Height of one list row in pixels.
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Widths of the right-hand columns (size, age, date).
+const SIZE_COL_WIDTH: f32 = 80.0;
+const AGE_COL_WIDTH: f32 = 60.0;
+const DATE_COL_WIDTH: f32 = 84.0;
+
+/// Threshold presets offered next to the day count.
+const AGE_PRESETS: [(&str, u64); 4] = [("90 d", 90), ("1 y", 365), ("2 y", 730), ("3 y", 1095)];
+
+/// Draw the stale files window if it is open.
+pub fn stale_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_stale;
+    if !open {
+        return;
+    }
+
+    egui::Window::new("Stale Files")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size([640.0, 480.0])
+        .show(ctx, |ui| stale_contents(ui, state));
+
+    state.show_stale = open;
+}
+
+fn stale_contents(ui: &mut egui::Ui, state: &mut AppState) {
+    let muted = ui.visuals().weak_text_color();
+    let accent = ui.visuals().hyperlink_color;
+
+    let Some(has_access_times) = state.tree.as_ref().map(|t| t.extended_metadata) else {
+        ui.label(egui::RichText::new("Finish a scan to look for stale files.").color(muted));
+        return;
+    };
+
+    // ── Controls ───────────────────────────────────────────────
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("Age by");
+        for basis in AgeBasis::ALL {
+            let enabled = basis == AgeBasis::Modified || has_access_times;
+            let response = ui
+                .add_enabled(
+                    enabled,
+                    egui::SelectableLabel::new(state.stale_basis == basis, basis.label()),
+                )
+                .on_disabled_hover_text(
+                    "Access times are only recorded when \"Extended details\" is on for the scan",
+                );
+            if response.clicked() && state.stale_basis != basis {
+                state.stale_basis = basis;
+                changed = true;
+            }
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(match state.stale_basis {
+            AgeBasis::Modified => "Not modified for",
+            AgeBasis::Accessed => "Not used for",
+        });
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut state.stale_min_age_days)
+                    .range(0..=36_500)
+                    .suffix(" days"),
+            )
+            .changed();
+        for (label, days) in AGE_PRESETS {
+            if ui
+                .selectable_label(state.stale_min_age_days == days, label)
+                .clicked()
+            {
+                state.stale_min_age_days = days;
+                changed = true;
+            }
+        }
+        ui.separator();
+        ui.label("Show up to");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut state.stale_max_results)
+                    .range(1..=MAX_STALE_RESULTS)
+                    .speed(10.0),
+            )
+            .changed();
+        ui.label("files");
+        if state.stale_handle.is_some() {
+            ui.spinner();
+        }
+    });
+    if changed {
+        state.start_stale_analysis();
+    }
+
+    let Some(ref report) = state.stale_report else {
+        return;
+    };
+    ui.separator();
+
+    // ── Age histogram ──────────────────────────────────────────
+    age_histogram(ui, &report.histogram);
+    ui.separator();
+
+    // ── Totals and selection ───────────────────────────────────
+    let shown = report.stale.len() as u64;
+    let mut summary = format!(
+        "{} stale files — {}",
+        format_count(report.stale_count),
+        format_size(report.stale_bytes)
+    );
+    if report.stale_count > shown {
+        summary.push_str(&format!(" (showing largest {})", format_count(shown)));
+    }
+    ui.label(egui::RichText::new(summary).strong().color(accent));
+
+    let (selected, selected_bytes) = state.stale_selection_totals();
+    let mut select_all = false;
+    let mut clear = false;
+    let mut add_to_cart = false;
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} selected — {}",
+            format_count(selected as u64),
+            format_size(selected_bytes)
+        ));
+        if ui.button("Select all").clicked() {
+            select_all = true;
+        }
+        if ui
+            .add_enabled(selected > 0, egui::Button::new("Clear selection"))
+            .clicked()
+        {
+            clear = true;
+        }
+        if ui
+            .add_enabled(selected > 0, egui::Button::new("➕ Add to cleanup list"))
+            .clicked()
+        {
+            add_to_cart = true;
+        }
+    });
+    ui.label(
+        egui::RichText::new("Click to show in tree · Ctrl/Shift+click to select several")
+            .size(11.0)
+            .color(muted),
+    );
+    ui.add_space(2.0);
+
+    // ── File list (virtualised) ────────────────────────────────
+    let color_normal = ui.visuals().text_color();
+    let color_selection = ui.visuals().selection.bg_fill;
+    let color_hover = ui.visuals().widgets.hovered.weak_bg_fill;
+    let columns_width = SIZE_COL_WIDTH + AGE_COL_WIDTH + DATE_COL_WIDTH;
+
+    let (header, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), ROW_HEIGHT),
+        egui::Sense::hover(),
+    );
+    let header_font = egui::FontId::proportional(11.0);
+    let painter = ui.painter_at(header);
+    painter.text(
+        header.left_center() + egui::vec2(4.0, 0.0),
+        egui::Align2::LEFT_CENTER,
+        "Path",
+        header_font.clone(),
+        muted,
+    );
+    for (label, right) in column_edges(header, report.basis) {
+        painter.text(
+            egui::pos2(right - 4.0, header.center().y),
+            egui::Align2::RIGHT_CENTER,
+            label,
+            header_font.clone(),
+            muted,
+        );
+    }
+
+    let mut clicked: Option<(usize, NodeIndex, egui::Modifiers)> = None;
+    let stale = &report.stale;
+    egui::ScrollArea::vertical()
+        .id_salt("stale_files")
+        .auto_shrink([false, false])
+        .show_rows(ui, ROW_HEIGHT, stale.len(), |ui, range| {
+            let start = range.start;
+            for (offset, file) in stale[range].iter().enumerate() {
+                let (rect, resp) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), ROW_HEIGHT),
+                    egui::Sense::click(),
+                );
+                let painter = ui.painter_at(rect);
+                if state.stale_selected.contains(&file.index) {
+                    painter.rect_filled(rect, 2.0, color_selection);
+                } else if resp.hovered() {
+                    painter.rect_filled(rect, 2.0, color_hover);
+                }
+
+                let font = egui::FontId::proportional(12.0);
+                let path_clip = egui::Rect::from_min_max(
+                    rect.min,
+                    egui::pos2(rect.right() - columns_width, rect.bottom()),
+                );
+                ui.painter_at(path_clip).text(
+                    rect.left_center() + egui::vec2(4.0, 0.0),
+                    egui::Align2::LEFT_CENTER,
+                    &file.path,
+                    font.clone(),
+                    color_normal,
+                );
+                let cells = [
+                    (format_size(file.size), accent),
+                    (format_age_days(file.age_days), color_normal),
+                    (format_date(file.last_used), muted),
+                ];
+                let edges = column_edges(rect, report.basis);
+                for ((text, color), (_, right)) in cells.into_iter().zip(edges) {
+                    painter.text(
+                        egui::pos2(right - 4.0, rect.center().y),
+                        egui::Align2::RIGHT_CENTER,
+                        text,
+                        font.clone(),
+                        color,
+                    );
+                }
+
+                if resp.clicked() {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    clicked = Some((start + offset, file.index, modifiers));
+                }
+                resp.on_hover_text(&file.path);
+            }
+        });
+
+    if select_all {
+        state.stale_select_all();
+    }
+    if clear {
+        state.stale_selected.clear();
+    }
+    if add_to_cart {
+        state.stale_add_selected_to_cart();
+        state.show_cart = true;
+    }
+    if let Some((row, node, modifiers)) = clicked {
+        state.stale_click(row, modifiers.command, modifiers.shift);
+        if !modifiers.command && !modifiers.shift {
+            state.reveal_node(node);
+        }
+    }
+}
+
+/// Header labels paired with the right edge of their column in `row`; the
+/// date column is named after the timestamp `basis` measures from.
+fn column_edges(row: egui::Rect, basis: AgeBasis) -> [(&'static str, f32); 3] {
+    let date_right = row.right();
+    let age_right = date_right - DATE_COL_WIDTH;
+    let size_right = age_right - AGE_COL_WIDTH;
+    [
+        ("Size", size_right),
+        ("Age", age_right),
+        (basis.label(), date_right),
+    ]
+}
+
+/// One bar per age bucket, scaled to the largest bucket.
+fn age_histogram(ui: &mut egui::Ui, histogram: &AgeHistogram) {
+    let muted = ui.visuals().weak_text_color();
+    let track = ui.visuals().extreme_bg_color;
+    let largest = histogram.bytes.iter().copied().max().unwrap_or(0).max(1);
+
+    egui::Grid::new("stale_age_histogram")
+        .num_columns(3)
+        .spacing([8.0, 3.0])
+        .show(ui, |ui| {
+            for bucket in AgeBucket::ALL {
+                let i = bucket.index();
+                ui.label(egui::RichText::new(bucket.label()).size(12.0));
+
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(220.0, 10.0), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 2.0, track);
+                let frac = histogram.bytes[i] as f32 / largest as f32;
+                if frac > 0.0 {
+                    let fill = egui::Rect::from_min_size(
+                        rect.min,
+                        egui::vec2((rect.width() * frac).max(1.0), rect.height()),
+                    );
+                    painter.rect_filled(fill, 2.0, bucket_color(bucket));
+                }
+
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} in {} files",
+                        format_size(histogram.bytes[i]),
+                        format_count(histogram.files[i])
+                    ))
+                    .size(11.0)
+                    .color(muted),
+                );
+                ui.end_row();
+            }
+        });
+
+    if histogram.unknown_files > 0 {
+        ui.label(
+            egui::RichText::new(format!(
+                "{} in {} files without a usable date",
+                format_size(histogram.unknown_bytes),
+                format_count(histogram.unknown_files)
+            ))
+            .size(11.0)
+            .color(muted),
+        );
+    }
+}
+
+/// Fresh files green, through yellow, to the oldest in red.
+fn bucket_color(bucket: AgeBucket) -> egui::Color32 {
+    match bucket {
+        AgeBucket::Under30Days => egui::Color32::from_rgb(0xa6, 0xe3, 0xa1),
+        AgeBucket::Days30To90 => egui::Color32::from_rgb(0x94, 0xe2, 0xd5),
+        AgeBucket::Days90To365 => egui::Color32::from_rgb(0xf9, 0xe2, 0xaf),
+        AgeBucket::Years1To3 => egui::Color32::from_rgb(0xfa, 0xb3, 0x87),
+        AgeBucket::Over3Years => egui::Color32::from_rgb(0xf3, 0x8b, 0xa8),
+    }
+}
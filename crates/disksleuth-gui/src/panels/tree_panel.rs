@@ -1,11 +1,70 @@
 /// Main TreeView results panel.
 use crate::state::AppState;
 use crate::widgets;
+use crate::widgets::tree_view::{
+    columns_width, COL_ALLOCATED, COL_BAR, COL_FILES, COL_MODIFIED, COL_NEWEST, COL_OLDEST,
+    COL_OLD_BYTES, COL_PERCENT, COL_SIZE,
+};
+use disksleuth_core::model::SortColumn;
 use egui::Ui;
 
+/// Choices for the "old data" cutoff, in days.
+const OLD_CUTOFF_PRESETS: [(&str, u64); 6] = [
+    ("90 days", 90),
+    ("6 months", 182),
+    ("1 year", 365),
+    ("2 years", 730),
+    ("3 years", 1_095),
+    ("5 years", 1_826),
+];
+
 /// Draw the tree panel (centre content area).
 pub fn tree_panel(ui: &mut Ui, state: &mut AppState) {
-    // Column headers.
+    // Sort and column options.
+    ui.horizontal(|ui| {
+        let mut folders_first = state.tree_sort.folders_first;
+        if ui
+            .checkbox(&mut folders_first, "Folders first")
+            .on_hover_text("Group folders above files; untick to sort both together")
+            .changed()
+        {
+            state.set_tree_folders_first(folders_first);
+        }
+
+        ui.checkbox(&mut state.tree_age_columns, "Age columns")
+            .on_hover_text("Show the newest and oldest file dates and the old data in each folder");
+        if state.tree_age_columns {
+            let mut cutoff = state.old_cutoff_days;
+            let current = OLD_CUTOFF_PRESETS
+                .iter()
+                .find(|(_, days)| *days == cutoff)
+                .map_or_else(|| format!("{cutoff} days"), |(label, _)| label.to_string());
+            ui.label("Old data: older than");
+            egui::ComboBox::from_id_salt("old_cutoff")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (label, days) in OLD_CUTOFF_PRESETS {
+                        ui.selectable_value(&mut cutoff, days, label);
+                    }
+                });
+            state.set_old_cutoff_days(cutoff);
+        }
+
+        if let Some(cat) = state.category_filter {
+            ui.separator();
+            ui.label(format!("Only {}", cat.label()));
+            if ui
+                .small_button("✖")
+                .on_hover_text("Show all files again")
+                .clicked()
+            {
+                state.set_category_filter(None);
+            }
+        }
+    });
+
+    // Column headers — every column except "Usage" is clickable to sort.
+    let mut clicked_column: Option<SortColumn> = None;
     ui.horizontal(|ui| {
         let header_height = 20.0;
         let rect = egui::Rect::from_min_size(
@@ -23,51 +82,132 @@ pub fn tree_panel(ui: &mut Ui, state: &mut AppState) {
         );
 
         let muted = ui.visuals().weak_text_color();
-        painter.text(
-            egui::pos2(rect.left() + 8.0, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            "Name",
-            egui::FontId::proportional(12.0),
-            muted,
-        );
+        let active = ui.visuals().strong_text_color();
+        let hover_fill = ui.visuals().widgets.hovered.weak_bg_fill;
+        let sort = state.tree_sort;
+        let right_start = rect.right() - columns_width(state.tree_age_columns);
+        let modified_end = if state.tree_age_columns {
+            right_start + COL_NEWEST
+        } else {
+            rect.right()
+        };
 
-        let right_start = rect.right() - 300.0;
-        painter.text(
-            egui::pos2(right_start, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            "Size",
-            egui::FontId::proportional(12.0),
-            muted,
-        );
+        // (label, sort column, x start, x end)
+        let mut columns: Vec<(&str, Option<SortColumn>, f32, f32)> = vec![
+            ("Name", Some(SortColumn::Name), rect.left(), right_start),
+            (
+                "Size",
+                Some(SortColumn::Size),
+                right_start + COL_SIZE,
+                right_start + COL_ALLOCATED,
+            ),
+            (
+                "Allocated",
+                Some(SortColumn::Allocated),
+                right_start + COL_ALLOCATED,
+                right_start + COL_PERCENT,
+            ),
+            (
+                "%",
+                Some(SortColumn::Percent),
+                right_start + COL_PERCENT,
+                right_start + COL_BAR,
+            ),
+            // "Usage" is self-explanatory; "Bar" was opaque to new users.
+            (
+                "Usage",
+                None,
+                right_start + COL_BAR,
+                right_start + COL_FILES,
+            ),
+            (
+                "Files",
+                Some(SortColumn::Files),
+                right_start + COL_FILES,
+                right_start + COL_MODIFIED,
+            ),
+            (
+                "Modified",
+                Some(SortColumn::Modified),
+                right_start + COL_MODIFIED,
+                modified_end,
+            ),
+        ];
+        if state.tree_age_columns {
+            columns.extend([
+                (
+                    "Newest",
+                    Some(SortColumn::Newest),
+                    right_start + COL_NEWEST,
+                    right_start + COL_OLDEST,
+                ),
+                (
+                    "Oldest",
+                    Some(SortColumn::Oldest),
+                    right_start + COL_OLDEST,
+                    right_start + COL_OLD_BYTES,
+                ),
+                (
+                    "Old data",
+                    Some(SortColumn::OldBytes),
+                    right_start + COL_OLD_BYTES,
+                    rect.right(),
+                ),
+            ]);
+        }
 
-        painter.text(
-            egui::pos2(right_start + 80.0, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            "%",
-            egui::FontId::proportional(12.0),
-            muted,
-        );
+        for (label, column, x0, x1) in columns {
+            // The name label is indented; numeric labels sit at the column start.
+            let text_x = if column == Some(SortColumn::Name) {
+                x0 + 8.0
+            } else {
+                x0
+            };
+            let is_active = column.is_some_and(|c| c == sort.column);
+            let text = if is_active {
+                let arrow = if sort.descending { "▼" } else { "▲" };
+                format!("{label} {arrow}")
+            } else {
+                label.to_string()
+            };
 
-        // "Usage" is self-explanatory; "Bar" was opaque to new users.
-        painter.text(
-            egui::pos2(right_start + 130.0, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            "Usage",
-            egui::FontId::proportional(12.0),
-            muted,
-        );
+            if let Some(column) = column {
+                let cell = egui::Rect::from_min_max(
+                    egui::pos2(x0 - 2.0, rect.top()),
+                    egui::pos2(x1 - 2.0, rect.bottom()),
+                );
+                let resp = ui
+                    .interact(
+                        cell,
+                        ui.id().with(("tree_header", label)),
+                        egui::Sense::click(),
+                    )
+                    .on_hover_text(format!("Sort by {}", label.to_lowercase()));
+                if resp.hovered() {
+                    painter.rect_filled(cell, 0.0, hover_fill);
+                }
+                if resp.clicked() {
+                    clicked_column = Some(column);
+                }
+            }
 
-        painter.text(
-            egui::pos2(right_start + 240.0, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            "Files",
-            egui::FontId::proportional(12.0),
-            muted,
-        );
+            painter.text(
+                egui::pos2(text_x, rect.center().y),
+                egui::Align2::LEFT_CENTER,
+                text,
+                egui::FontId::proportional(12.0),
+                if is_active { active } else { muted },
+            );
+        }
     });
 
+    if let Some(column) = clicked_column {
+        state.set_tree_sort_column(column);
+    }
+
     ui.separator();
 
-    // Tree view.
+    // Tree view, with old bytes brought up to date for the age columns.
+    state.refresh_old_bytes();
     widgets::tree_view::tree_view(ui, state);
 }
@@ -1,4 +1,19 @@
-use disksleuth_core::analysis::{analyse_file_types, CategoryStats};
+use disksleuth_core::analysis::{
+    analyse_extensions, analyse_file_types, analyse_hotspots, analyse_sizes, analyse_slack,
+    category_mask, find_cache_locations_here, find_empty, largest_dirs, start_age_analysis,
+    start_artefact_analysis, start_compression_estimate, start_owner_analysis,
+    start_signature_scan, top_files_with_extension, AgeBasis, AgeHandle, AgeMessage, AgeReport,
+    ArtefactHandle, ArtefactMessage, ArtefactReport, CacheReport, CategoryStats, CompressionHandle,
+    CompressionMessage, CompressionReport, DetectedTypes, DirRank, EmptyReport, ExtensionStats,
+    FileCategory, Hotspot, HotspotSort, LargestDir, LargestFile, OwnerHandle, OwnerMessage,
+    OwnerReport, SignatureHandle, SignatureMessage, SignatureReport, SizeHistogram, SlackReport,
+    DEFAULT_CLUSTER_SIZE, DEFAULT_SIGNATURE_MIN_SIZE, MAX_LARGEST_DIRS,
+};
+use disksleuth_core::cleanup::script::{cleanup_script, ScriptKind};
+use disksleuth_core::cleanup::{
+    outermost_nodes, plan_cleanup, start_cleanup, CleanupAction, CleanupHandle, CleanupMessage,
+    CleanupPlan,
+};
 /// Application state management.
 ///
 /// Centralises all mutable state that the UI reads and writes.
@@ -7,14 +22,20 @@ use disksleuth_core::analysis::{analyse_file_types, CategoryStats};
 ///
 /// During scanning, the tree view reads from a **shared `LiveTree`**
 /// (`Arc<RwLock<FileTree>>`) so results appear in real time.
-use disksleuth_core::model::{FileTree, NodeIndex};
+use disksleuth_core::model::{
+    FileTree, NodeIndex, OldBytes, SortColumn, SortOrder, DEFAULT_OLD_CUTOFF_DAYS,
+};
 use disksleuth_core::monitor::{MonitorHandle, WriteEvent};
-use disksleuth_core::platform::DriveInfo;
+use disksleuth_core::platform::{cluster_size, DriveInfo};
 use disksleuth_core::scanner::progress::ScanProgress;
-use disksleuth_core::scanner::{LiveTree, ScanHandle};
-use std::collections::VecDeque;
+use disksleuth_core::scanner::{LiveTree, ScanHandle, ScanOptions};
+use disksleuth_core::search::{
+    NamePattern, SearchHandle, SearchHit, SearchMessage, SearchMode, MAX_SEARCH_RESULTS,
+};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppPhase {
     /// Idle — no scan in progress, possibly showing previous results.
@@ -25,6 +46,107 @@ pub enum AppPhase {
     Results,
 }
 
+/// Which visualisation fills the central panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MapView {
+    /// Nested rectangles.
+    #[default]
+    Treemap,
+    /// Concentric rings around the current root.
+    Sunburst,
+}
+
+/// How treemap rectangles are coloured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreemapColorMode {
+    /// File category, in the same colours as the file-type chart.
+    #[default]
+    FileType,
+    /// Newest descendant modification time: recent green, untouched red.
+    Age,
+    /// Nesting level below the treemap root.
+    Depth,
+    /// A stable colour per extension, derived from a hash of it.
+    Extension,
+}
+
+impl TreemapColorMode {
+    /// Every mode, in picker order.
+    pub const ALL: [TreemapColorMode; 4] =
+        [Self::FileType, Self::Age, Self::Depth, Self::Extension];
+
+    /// Label for the colour-mode picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FileType => "File type",
+            Self::Age => "Age",
+            Self::Depth => "Depth",
+            Self::Extension => "Extension",
+        }
+    }
+}
+
+/// What treemap rectangle areas are proportional to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreemapWeight {
+    /// Logical size in bytes.
+    #[default]
+    Bytes,
+    /// Number of files, so folders full of tiny files stand out.
+    Files,
+}
+
+impl TreemapWeight {
+    /// Every weight, in picker order.
+    pub const ALL: [TreemapWeight; 2] = [Self::Bytes, Self::Files];
+
+    /// Label for the weight picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bytes => "Size",
+            Self::Files => "File count",
+        }
+    }
+}
+
+/// Column the per-extension table is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtensionColumn {
+    Extension,
+    #[default]
+    Size,
+    Files,
+    Average,
+    Largest,
+}
+
+impl ExtensionColumn {
+    /// Every column, in table order.
+    pub const ALL: [ExtensionColumn; 5] = [
+        Self::Extension,
+        Self::Size,
+        Self::Files,
+        Self::Average,
+        Self::Largest,
+    ];
+
+    /// Column header.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Extension => "Ext",
+            Self::Size => "Size",
+            Self::Files => "Files",
+            Self::Average => "Avg",
+            Self::Largest => "Largest",
+        }
+    }
+
+    /// Whether a newly chosen column starts largest first.
+    pub fn default_descending(self) -> bool {
+        self != Self::Extension
+    }
+}
+
 /// A row in the flattened visible-rows list for the virtualised TreeView.
 #[derive(Clone, Debug)]
 pub struct VisibleRow {
@@ -71,6 +193,34 @@ const MAX_VISIBLE_ROWS: usize = 500_000;
 /// patterns without unbounded growth on heavily-restricted volumes.
 const MAX_SCAN_ERRORS: usize = 1_000;
 
+/// Maximum number of per-path failures kept for the cleanup report.
+///
+/// A failed bulk delete of a locked folder can produce one entry per item;
+/// the count is still shown, only the detail list is capped.
+const MAX_CLEANUP_FAILURES: usize = 1_000;
+
+/// Maximum number of entries in the cleanup list.
+///
+/// Each entry is a path in the review window and a row in a batch; beyond
+/// this, marking a parent folder is the better tool.
+pub const MAX_CART_ITEMS: usize = 100_000;
+
+/// Upper bound for the stale-files result limit.
+///
+/// Every listed file carries its full path; beyond this the totals line
+/// (which always covers every stale file) is the more useful figure.
+pub const MAX_STALE_RESULTS: usize = 10_000;
+
+/// Largest files listed for the extension picked in the chart panel.
+pub const EXTENSION_FILES_SHOWN: usize = 25;
+
+/// Rows moved by Page Up / Page Down in the tree view.
+const PAGE_ROWS: isize = 20;
+
+/// Pause after which the next typed character starts a new type-ahead
+/// prefix instead of extending the current one.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1_000);
+
 /// All application state.
 pub struct AppState {
     // ── Drives ─────────────────────────────────────────
@@ -87,6 +237,8 @@ pub struct AppState {
     // ── Scan ───────────────────────────────────────────
     pub phase: AppPhase,
     pub scan_handle: Option<ScanHandle>,
+    /// Options for the next scan (extended metadata on or off).
+    pub scan_options: ScanOptions,
     pub scan_files_found: u64,
     pub scan_dirs_found: u64,
     pub scan_total_size: u64,
@@ -102,11 +254,25 @@ pub struct AppState {
 
     // ── Results ────────────────────────────────────────
     /// The completed scan tree (set once scan finishes).
-    pub tree: Option<FileTree>,
+    ///
+    /// Wrapped in an `Arc` so background workers (e.g. search) can read the
+    /// immutable tree without cloning it.
+    pub tree: Option<Arc<FileTree>>,
     /// The live tree reference during scanning (for real-time view).
     pub live_tree: Option<LiveTree>,
     pub visible_rows: Vec<VisibleRow>,
     pub selected_node: Option<NodeIndex>,
+    /// Sibling order used whenever tree-view rows are built or expanded.
+    pub tree_sort: SortOrder,
+    /// Show the newest / oldest / old-data columns in the tree view.
+    pub tree_age_columns: bool,
+    /// Age in days beyond which file bytes count as old
+    /// (see [`AppState::set_old_cutoff_days`]).
+    pub old_cutoff_days: u64,
+    /// Old bytes per node of the tree shown, computed by
+    /// [`AppState::refresh_old_bytes`] only while the age columns or the
+    /// old-data sort need them.
+    pub old_bytes: OldBytes,
     /// Tracks node count from the last live-tree snapshot so we know
     /// when to rebuild visible rows.
     live_tree_last_len: usize,
@@ -118,11 +284,30 @@ pub struct AppState {
     pub treemap_back: VecDeque<NodeIndex>,
     /// Forward stack for treemap navigation (VecDeque for O(1) front eviction).
     pub treemap_forward: VecDeque<NodeIndex>,
+    /// Treemap or sunburst; both share the navigation above.
+    pub map_view: MapView,
+    /// How treemap rectangles are coloured.
+    pub treemap_color: TreemapColorMode,
+    /// What treemap rectangle areas are proportional to.
+    pub treemap_weight: TreemapWeight,
+    /// Whether the colour legend is drawn over the treemap.
+    pub treemap_legend: bool,
+    /// Whether treemap rectangles are drawn with cushion shading.
+    pub treemap_cushions: bool,
 
     // ── UI state ───────────────────────────────────────
     pub tree_scroll_offset: f32,
+    /// Set when the selection moved programmatically (keyboard, reveal) so
+    /// the tree view scrolls the selected row into view on the next frame.
+    pub scroll_tree_to_selection: bool,
     pub show_errors: bool,
     pub show_about: bool,
+    /// Keyboard shortcuts help overlay.
+    pub show_shortcuts: bool,
+    /// Name prefix typed so far for tree-view type-ahead.
+    type_ahead: String,
+    /// When the last type-ahead character arrived.
+    type_ahead_at: Option<Instant>,
     pub scan_errors: Vec<(String, String)>,
     pub context_menu_node: Option<NodeIndex>,
     // ── Theme ──────────────────────────────────────────────
@@ -133,6 +318,45 @@ pub struct AppState {
     /// File-type breakdown — computed once after scan completes,
     /// not on every render frame.
     pub file_type_stats: Option<Vec<CategoryStats>>,
+    /// Chart the file-type breakdown by file count instead of size.
+    pub chart_by_count: bool,
+    /// File size histogram of the completed tree, or of `size_scope`.
+    pub size_histogram: Option<SizeHistogram>,
+    /// Folder the size histogram covers; `None` for the whole scan.
+    pub size_scope: Option<NodeIndex>,
+    /// Chart the size histogram by file count instead of bytes.
+    pub size_chart_by_count: bool,
+    /// Category picked in the file-type chart.  The tree view lists only
+    /// its files (and the folders holding them); the map views highlight them.
+    pub category_filter: Option<FileCategory>,
+    /// [`category_mask`] of the completed tree for `category_filter`.
+    category_mask: Option<Vec<bool>>,
+    /// Why the category config file could not be used, if it could not.
+    pub category_config_error: Option<String>,
+    /// Per-extension breakdown, computed alongside `file_type_stats`.
+    pub extension_stats: Option<Vec<ExtensionStats>>,
+    /// Category whose extensions are listed under its chart row.
+    pub chart_expanded: Option<FileCategory>,
+    /// Sort column and direction of the per-extension table.
+    pub extension_sort: ExtensionColumn,
+    pub extension_sort_descending: bool,
+    /// Extension picked in the table (lowercase), and its largest files.
+    pub selected_extension: Option<String>,
+    pub extension_files: Vec<LargestFile>,
+
+    // ── Search ─────────────────────────────────────────────
+    /// Text currently in the search box.
+    pub search_query: String,
+    /// How `search_query` is interpreted.
+    pub search_mode: SearchMode,
+    /// Hits from the most recent completed search (capped at `MAX_SEARCH_RESULTS`).
+    pub search_results: Vec<SearchHit>,
+    /// Total matches for the most recent search, including those beyond the cap.
+    pub search_total_matches: u64,
+    /// Pattern compile error for the current query (e.g. invalid regex).
+    pub search_error: Option<String>,
+    /// Handle to the in-flight background search, if any.
+    pub search_handle: Option<SearchHandle>,
 
     // ── Live write monitor ───────────────────────────────
     /// Whether the monitor bottom panel is visible.
@@ -145,6 +369,159 @@ pub struct AppState {
     pub monitor_entries: Vec<WriteEvent>,
     /// Handle to the background monitor thread.
     pub monitor_handle: Option<MonitorHandle>,
+
+    // ── Cleanup (trash / delete) ─────────────────────────
+    /// Plan waiting for the user to confirm in the cleanup dialog.
+    pub cleanup_confirm: Option<CleanupPlan>,
+    /// Handle to the running cleanup thread.
+    pub cleanup_handle: Option<CleanupHandle>,
+    /// Progress and outcome of the current or most recent cleanup.
+    pub cleanup_report: Option<CleanupReport>,
+    /// Nodes removed from disk by the running cleanup; pruned from the tree
+    /// in one pass when it finishes.
+    cleanup_removed: Vec<NodeIndex>,
+
+    // ── Cleanup list ("cart") ────────────────────────────
+    /// Nodes marked for batch cleanup, in the order they were added.
+    pub cleanup_cart: Vec<NodeIndex>,
+    /// Membership set for `cleanup_cart` — O(1) lookups while painting rows.
+    cart_set: HashSet<NodeIndex>,
+    /// Cart entries not covered by another entry (their totals count).
+    cart_outermost: HashSet<NodeIndex>,
+    /// Bytes the cart would reclaim; nested entries are counted once.
+    pub cart_reclaimable: u64,
+    /// Files the cart covers; nested entries are counted once.
+    pub cart_files: u64,
+    /// What the cleanup list does when run or exported.
+    pub cart_action: CleanupAction,
+    /// Destination folder for [`CleanupAction::Archive`], as typed.
+    pub cart_archive_dir: String,
+    /// Whether the cleanup list window is visible.
+    pub show_cart: bool,
+
+    // ── Stale files ──────────────────────────────────────
+    /// Whether the stale-files window is open.
+    pub show_stale: bool,
+    /// Files at least this many days old (by `stale_basis`) are listed.
+    pub stale_min_age_days: u64,
+    /// Maximum number of stale files listed (largest first).
+    pub stale_max_results: usize,
+    /// Whether age is measured from modification or last access.
+    pub stale_basis: AgeBasis,
+    /// Result of the last completed age analysis.
+    pub stale_report: Option<AgeReport>,
+    /// In-flight age analysis.
+    pub stale_handle: Option<AgeHandle>,
+    /// Rows of `stale_report.stale` selected in the list.
+    pub stale_selected: HashSet<NodeIndex>,
+    /// Row the next Shift+click extends the selection from.
+    stale_anchor: Option<usize>,
+
+    // ── Content detection ────────────────────────────────
+    /// Files smaller than this are not read by the signature pass.
+    pub signature_min_size: u64,
+    /// Result of the last completed signature pass.  Its detected types
+    /// override extensions in the type stats and the category filter.
+    pub signature_report: Option<SignatureReport>,
+    /// Bumped whenever `signature_report` changes, so map layouts coloured
+    /// by its detected types are recomputed.
+    pub signature_generation: u64,
+    /// In-flight signature pass.
+    pub signature_handle: Option<SignatureHandle>,
+    /// Files read and files to read by the in-flight pass.
+    pub signature_progress: (u64, u64),
+
+    // ── Reclaimable (developer artefacts) ────────────────
+    /// Whether the reclaimable window is open.
+    pub show_reclaimable: bool,
+    /// Result of the last completed artefact analysis.
+    pub reclaimable_report: Option<ArtefactReport>,
+    /// In-flight artefact analysis.
+    pub reclaimable_handle: Option<ArtefactHandle>,
+    /// Artefacts ticked in the list.
+    pub reclaimable_selected: HashSet<NodeIndex>,
+
+    // ── Cache locations ──────────────────────────────────
+    /// Whether the caches window is open.
+    pub show_caches: bool,
+    /// Well-known cache and temp locations in the completed tree.
+    pub cache_report: Option<CacheReport>,
+
+    // ── Empty folders ────────────────────────────────────
+    /// Whether the empty folders window is open.
+    pub show_empty: bool,
+    /// Empty folders and zero-byte files in the completed tree.
+    pub empty_report: Option<EmptyReport>,
+
+    // ── File-count hotspots ──────────────────────────────
+    /// Whether the hotspots window is open.
+    pub show_hotspots: bool,
+    /// How the hotspot table is ranked.
+    pub hotspot_sort: HotspotSort,
+    /// Directories of the completed tree, ranked by `hotspot_sort`.
+    pub hotspots: Vec<Hotspot>,
+
+    // ── Largest folders ──────────────────────────────────
+    /// Whether the largest folders window is open.
+    pub show_largest_dirs: bool,
+    /// How the largest folders are ranked.
+    pub largest_dirs_rank: DirRank,
+    /// Rank only folders this many levels below the scan root.
+    pub largest_dirs_depth: Option<usize>,
+    /// Directories of the completed tree, ranked by `largest_dirs_rank`.
+    pub largest_dirs: Vec<LargestDir>,
+
+    // ── Owners ───────────────────────────────────────────
+    /// Whether the owners window is open.
+    pub show_owners: bool,
+    /// Result of the last completed owner analysis.
+    pub owner_report: Option<OwnerReport>,
+    /// In-flight owner analysis.
+    pub owner_handle: Option<OwnerHandle>,
+
+    // ── Allocation slack ─────────────────────────────────
+    /// Cluster size of the scanned volume, or the default if unknown.
+    pub cluster_size: u64,
+    /// Whether the slack window is open.
+    pub show_slack: bool,
+    /// Slack per directory and projections for other cluster sizes.
+    pub slack_report: Option<SlackReport>,
+
+    // ── Compressibility ──────────────────────────────────
+    /// Whether the compressibility window is open.
+    pub show_compression: bool,
+    /// Result of the last completed estimate.
+    pub compression_report: Option<CompressionReport>,
+    /// In-flight estimate.
+    pub compression_handle: Option<CompressionHandle>,
+    /// Folder the in-flight estimate covers.
+    pub compression_root: Option<NodeIndex>,
+    /// Files sampled, files to sample and bytes read by the in-flight
+    /// estimate.
+    pub compression_progress: (u64, u64, u64),
+}
+
+/// Progress and outcome of a cleanup run, shown until dismissed.
+#[derive(Debug, Clone)]
+pub struct CleanupReport {
+    /// What was done.
+    pub action: CleanupAction,
+    /// Number of items in the plan.
+    pub total: usize,
+    /// Items processed so far (removed or failed).
+    pub processed: usize,
+    /// Items removed from disk.
+    pub removed: usize,
+    /// Bytes freed by removed items.
+    pub freed: u64,
+    /// Total failures, including those beyond the `failures` cap.
+    pub failure_count: usize,
+    /// (path, reason) for failed items, capped at `MAX_CLEANUP_FAILURES`.
+    pub failures: Vec<(String, String)>,
+    /// `true` once the run is over.
+    pub finished: bool,
+    /// `true` if the user stopped the run early.
+    pub cancelled: bool,
 }
 
 impl Default for AppState {
@@ -167,6 +544,7 @@ impl AppState {
             is_elevated,
             phase: AppPhase::Idle,
             scan_handle: None,
+            scan_options: ScanOptions::default(),
             scan_files_found: 0,
             scan_dirs_found: 0,
             scan_total_size: 0,
@@ -180,22 +558,105 @@ impl AppState {
             live_tree: None,
             visible_rows: Vec::new(),
             selected_node: None,
+            tree_sort: SortOrder::default(),
+            tree_age_columns: false,
+            old_cutoff_days: DEFAULT_OLD_CUTOFF_DAYS,
+            old_bytes: OldBytes::default(),
             live_tree_last_len: 0,
             treemap_root: None,
             treemap_back: VecDeque::new(),
             treemap_forward: VecDeque::new(),
+            map_view: MapView::default(),
+            treemap_color: TreemapColorMode::default(),
+            treemap_weight: TreemapWeight::default(),
+            treemap_legend: true,
+            treemap_cushions: false,
             tree_scroll_offset: 0.0,
+            scroll_tree_to_selection: false,
             show_errors: false,
             show_about: false,
+            show_shortcuts: false,
+            type_ahead: String::new(),
+            type_ahead_at: None,
             scan_errors: Vec::new(),
             context_menu_node: None,
             dark_mode: true,
             file_type_stats: None,
+            chart_by_count: false,
+            size_histogram: None,
+            size_scope: None,
+            size_chart_by_count: false,
+            category_filter: None,
+            category_mask: None,
+            category_config_error: None,
+            extension_stats: None,
+            chart_expanded: None,
+            extension_sort: ExtensionColumn::default(),
+            extension_sort_descending: true,
+            selected_extension: None,
+            extension_files: Vec::new(),
+            search_query: String::new(),
+            search_mode: SearchMode::default(),
+            search_results: Vec::new(),
+            search_total_matches: 0,
+            search_error: None,
+            search_handle: None,
             show_monitor_panel: false,
             monitor_active: false,
             monitor_path: String::new(),
             monitor_entries: Vec::new(),
             monitor_handle: None,
+            cleanup_confirm: None,
+            cleanup_handle: None,
+            cleanup_report: None,
+            cleanup_removed: Vec::new(),
+            cleanup_cart: Vec::new(),
+            cart_set: HashSet::new(),
+            cart_outermost: HashSet::new(),
+            cart_reclaimable: 0,
+            cart_files: 0,
+            cart_action: CleanupAction::Trash,
+            cart_archive_dir: String::new(),
+            show_cart: false,
+            show_stale: false,
+            stale_min_age_days: 365,
+            stale_max_results: 1_000,
+            stale_basis: AgeBasis::Modified,
+            stale_report: None,
+            stale_handle: None,
+            stale_selected: HashSet::new(),
+            stale_anchor: None,
+            signature_min_size: DEFAULT_SIGNATURE_MIN_SIZE,
+            signature_report: None,
+            signature_generation: 0,
+            signature_handle: None,
+            signature_progress: (0, 0),
+            show_reclaimable: false,
+            reclaimable_report: None,
+            reclaimable_handle: None,
+            reclaimable_selected: HashSet::new(),
+            show_caches: false,
+            cache_report: None,
+            show_empty: false,
+            empty_report: None,
+            show_hotspots: false,
+            hotspot_sort: HotspotSort::default(),
+            hotspots: Vec::new(),
+            show_largest_dirs: false,
+            largest_dirs_rank: DirRank::default(),
+            largest_dirs_depth: None,
+            largest_dirs: Vec::new(),
+            show_owners: false,
+            owner_report: None,
+            owner_handle: None,
+            cluster_size: DEFAULT_CLUSTER_SIZE,
+            show_slack: false,
+            slack_report: None,
+            show_compression: false,
+            compression_report: None,
+            compression_handle: None,
+            compression_root: None,
+            compression_progress: (0, 0, 0),
         }
     }
 
@@ -225,14 +686,51 @@ impl AppState {
         self.scan_errors.clear();
         self.tree = None;
         self.file_type_stats = None;
+        self.category_filter = None;
+        self.category_mask = None;
+        self.extension_stats = None;
+        self.selected_extension = None;
+        self.extension_files.clear();
         self.visible_rows.clear();
         self.selected_node = None;
         self.live_tree_last_len = 0;
+        self.old_bytes = OldBytes::default();
         self.treemap_root = None;
         self.treemap_back.clear();
         self.treemap_forward.clear();
+        self.clear_search_results();
+        // Node indices in a pending or running cleanup refer to the old tree.
+        self.cleanup_confirm = None;
+        self.context_menu_node = None;
+        if let Some(h) = self.cleanup_handle.take() {
+            h.cancel();
+        }
+        self.cleanup_removed.clear();
+        self.cart_clear();
+        self.cancel_stale_analysis();
+        self.stale_report = None;
+        self.stale_selected.clear();
+        self.stale_anchor = None;
+        self.cancel_signature_scan();
+        self.signature_report = None;
+        self.signature_generation += 1;
+        self.cancel_reclaimable_analysis();
+        self.reclaimable_report = None;
+        self.reclaimable_selected.clear();
+        self.cache_report = None;
+        self.empty_report = None;
+        self.size_histogram = None;
+        self.size_scope = None;
+        self.hotspots.clear();
+        self.largest_dirs.clear();
+        self.cancel_owner_analysis();
+        self.owner_report = None;
+        self.slack_report = None;
+        self.cancel_compression_estimate();
+        self.compression_report = None;
+        self.cluster_size = cluster_size(&path).unwrap_or(DEFAULT_CLUSTER_SIZE);
 
-        let handle = disksleuth_core::scanner::start_scan(path);
+        let handle = disksleuth_core::scanner::start_scan_with(path, self.scan_options);
         self.live_tree = Some(handle.live_tree.clone());
         self.scan_handle = Some(handle);
     }
@@ -328,7 +826,7 @@ impl AppState {
     /// After scan completion returns the final tree.
     /// Returns `None` if no data is available yet.
     pub fn current_tree(&self) -> Option<&FileTree> {
-        self.tree.as_ref()
+        self.tree.as_deref()
     }
 
     /// Process pending scan progress messages. Called once per frame.
@@ -396,8 +894,13 @@ impl AppState {
                         self.build_initial_visible_rows(&tree);
                         // Pre-compute analysis cache so chart panel never runs
                         // analyse_file_types on the render thread.
-                        self.file_type_stats = Some(analyse_file_types(&tree));
-                        self.tree = Some(tree);
+                        self.refresh_reports(&tree);
+                        self.tree = Some(Arc::new(tree));
+                        // Re-run any query typed while the scan was running.
+                        self.start_search();
+                        self.start_stale_analysis();
+                        self.start_reclaimable_analysis();
+                        self.start_owner_analysis();
                     }
 
                     self.scan_handle = None;
@@ -414,8 +917,13 @@ impl AppState {
                                 .unwrap_or_else(|arc| parking_lot::RwLock::new(arc.read().clone())),
                         );
                         self.build_initial_visible_rows(&tree);
-                        self.file_type_stats = Some(analyse_file_types(&tree));
-                        self.tree = Some(tree);
+                        self.refresh_reports(&tree);
+                        self.tree = Some(Arc::new(tree));
+                        // Re-run any query typed while the scan was running.
+                        self.start_search();
+                        self.start_stale_analysis();
+                        self.start_reclaimable_analysis();
+                        self.start_owner_analysis();
                     }
 
                     self.scan_handle = None;
@@ -448,6 +956,7 @@ impl AppState {
     /// drives with millions of direct root-level entries don't allocate an
     /// unbounded Vec.
     fn build_initial_visible_rows(&mut self, tree: &FileTree) {
+        self.refresh_old_bytes_for(tree);
         self.visible_rows.clear();
 
         for &root_idx in &tree.roots {
@@ -461,7 +970,13 @@ impl AppState {
             });
 
             // Expand root's children by default.
-            let children = tree.children_sorted_by_size(root_idx);
+            let children = listed_children(
+                tree,
+                root_idx,
+                &self.tree_sort,
+                &self.old_bytes,
+                self.category_mask.as_deref(),
+            );
             for child_idx in children {
                 if self.visible_rows.len() >= MAX_VISIBLE_ROWS {
                     break;
@@ -479,7 +994,11 @@ impl AppState {
     ///
     /// Preserves expansion state: any directory that was previously expanded
     /// stays expanded. New directories at depth 0–1 are auto-expanded.
+    ///
+    /// Also used after the sort order changes, on either tree, so re-sorting
+    /// never collapses what the user has opened.
     fn rebuild_live_visible_rows(&mut self, tree: &FileTree) {
+        self.refresh_old_bytes_for(tree);
         // Remember which nodes were expanded.
         //
         // Pre-size the HashSet to the upper bound of expanded rows + roots
@@ -548,7 +1067,13 @@ impl AppState {
             });
 
             if is_expanded {
-                let children = tree.children_sorted_by_size(current_idx);
+                let children = listed_children(
+                    tree,
+                    current_idx,
+                    &self.tree_sort,
+                    &self.old_bytes,
+                    self.category_mask.as_deref(),
+                );
                 // Push in reverse so the first child is processed first (LIFO stack).
                 let next_depth = depth.saturating_add(1);
                 for child_idx in children.into_iter().rev() {
@@ -565,10 +1090,24 @@ impl AppState {
         // Use disjoint field borrows to satisfy the borrow checker:
         // tree/live_tree are borrowed immutably while visible_rows is borrowed mutably.
         if let Some(ref tree) = self.tree {
-            toggle_expand_inner(&mut self.visible_rows, row_index, tree);
+            toggle_expand_inner(
+                &mut self.visible_rows,
+                row_index,
+                tree,
+                &self.tree_sort,
+                &self.old_bytes,
+                self.category_mask.as_deref(),
+            );
         } else if let Some(ref lt) = self.live_tree {
             let tree = lt.read();
-            toggle_expand_inner(&mut self.visible_rows, row_index, &tree);
+            toggle_expand_inner(
+                &mut self.visible_rows,
+                row_index,
+                &tree,
+                &self.tree_sort,
+                &self.old_bytes,
+                None,
+            );
         }
     }
 
@@ -579,6 +1118,8 @@ impl AppState {
     /// to a free function that receives `&mut visible_rows` and `&FileTree`
     /// separately — the same pattern used by `toggle_expand`.
     pub fn reveal_node_in_tree(&mut self, target: NodeIndex) {
+        self.scroll_tree_to_selection = true;
+
         // Already visible? Just scroll to it.
         if let Some(pos) = self
             .visible_rows
@@ -601,6 +1142,9 @@ impl AppState {
                 &mut self.tree_scroll_offset,
                 target,
                 tree,
+                &self.tree_sort,
+                &self.old_bytes,
+                self.category_mask.as_deref(),
             );
         } else if let Some(ref lt) = self.live_tree {
             let guard = lt.read();
@@ -609,15 +1153,387 @@ impl AppState {
                 &mut self.tree_scroll_offset,
                 target,
                 &guard,
+                &self.tree_sort,
+                &self.old_bytes,
+                None,
             );
         }
     }
 }
 
+impl AppState {
+    /// Sort the tree view by `column`.
+    ///
+    /// Clicking the active column again flips the direction; a new column
+    /// starts in its natural direction (see [`SortColumn::default_descending`]).
+    pub fn set_tree_sort_column(&mut self, column: SortColumn) {
+        if self.tree_sort.column == column {
+            self.tree_sort.descending = !self.tree_sort.descending;
+        } else {
+            self.tree_sort.column = column;
+            self.tree_sort.descending = column.default_descending();
+        }
+        self.resort_visible_rows();
+    }
+
+    /// Choose whether folders are grouped above files or mixed with them.
+    pub fn set_tree_folders_first(&mut self, folders_first: bool) {
+        if self.tree_sort.folders_first != folders_first {
+            self.tree_sort.folders_first = folders_first;
+            self.resort_visible_rows();
+        }
+    }
+
+    /// Change the age cutoff behind the "old data" column and recompute it.
+    ///
+    /// Only the [`OldBytes`] side table is rebuilt; the tree itself, which
+    /// background workers may share, is left alone.
+    pub fn set_old_cutoff_days(&mut self, days: u64) {
+        if self.old_cutoff_days == days {
+            return;
+        }
+        self.old_cutoff_days = days;
+        if self.refresh_old_bytes() && self.tree_sort.column == SortColumn::OldBytes {
+            self.resort_visible_rows();
+        }
+    }
+
+    /// Recompute [`AppState::old_bytes`] if something shows or sorts by it
+    /// and the tree or cutoff changed since.  Cheap when nothing changed, so
+    /// the tree view calls it every frame.  Returns `true` if recomputed.
+    pub fn refresh_old_bytes(&mut self) -> bool {
+        if let Some(tree) = self.tree.clone() {
+            self.refresh_old_bytes_for(&tree)
+        } else if let Some(lt) = self.live_tree.clone() {
+            let tree = lt.read();
+            self.refresh_old_bytes_for(&tree)
+        } else {
+            false
+        }
+    }
+
+    /// [`AppState::refresh_old_bytes`] for a tree the caller already holds.
+    fn refresh_old_bytes_for(&mut self, tree: &FileTree) -> bool {
+        let needed = self.tree_age_columns || self.tree_sort.column == SortColumn::OldBytes;
+        if !needed || self.old_bytes.is_current(tree, self.old_cutoff_days) {
+            return false;
+        }
+        self.old_bytes = OldBytes::compute(tree, self.old_cutoff_days);
+        true
+    }
+
+    /// Filter the tree view to one file category, or show everything again.
+    ///
+    /// Only applies to the completed tree; the file-type chart that sets it
+    /// is not shown while a scan is running.
+    pub fn set_category_filter(&mut self, category: Option<FileCategory>) {
+        if self.category_filter == category {
+            return;
+        }
+        self.category_filter = category;
+        self.category_mask = match (category, &self.tree) {
+            (Some(cat), Some(tree)) => Some(category_mask(tree, cat, self.detected_types())),
+            _ => None,
+        };
+        self.resort_visible_rows();
+    }
+
+    /// Recompute every report derived synchronously from a completed or
+    /// pruned `tree`: type stats, caches, empty folders, hotspots and slack.
+    fn refresh_reports(&mut self, tree: &FileTree) {
+        self.refresh_type_stats(tree);
+        self.size_histogram = Some(analyse_sizes(tree, self.size_scope));
+        self.cache_report = Some(find_cache_locations_here(tree));
+        self.empty_report = Some(find_empty(tree));
+        self.hotspots = analyse_hotspots(tree, self.hotspot_sort, self.cluster_size);
+        self.largest_dirs = largest_dirs(
+            tree,
+            self.largest_dirs_rank,
+            self.largest_dirs_depth,
+            MAX_LARGEST_DIRS,
+        );
+        self.slack_report = Some(analyse_slack(tree, self.cluster_size));
+    }
+
+    /// Recompute the file-type and per-extension breakdowns for `tree`,
+    /// and the picked extension's largest files.
+    fn refresh_type_stats(&mut self, tree: &FileTree) {
+        self.file_type_stats = Some(analyse_file_types(tree, self.detected_types()));
+        self.extension_stats = Some(analyse_extensions(tree));
+        self.extension_files = match self.selected_extension {
+            Some(ref ext) => top_files_with_extension(tree, ext, EXTENSION_FILES_SHOWN),
+            None => Vec::new(),
+        };
+    }
+
+    /// Histogram the file sizes below `scope`, or of the whole scan.
+    pub fn set_size_scope(&mut self, scope: Option<NodeIndex>) {
+        self.size_scope = scope;
+        if let Some(ref tree) = self.tree {
+            self.size_histogram = Some(analyse_sizes(tree, scope));
+        }
+    }
+
+    /// Sort the per-extension table by `column`; choosing the active
+    /// column again flips the direction.
+    pub fn set_extension_sort(&mut self, column: ExtensionColumn) {
+        if self.extension_sort == column {
+            self.extension_sort_descending = !self.extension_sort_descending;
+        } else {
+            self.extension_sort = column;
+            self.extension_sort_descending = column.default_descending();
+        }
+    }
+
+    /// Pick an extension in the table and list its largest files, or clear
+    /// the pick.  Picking the selected extension again clears it.
+    pub fn select_extension(&mut self, extension: Option<&str>) {
+        let extension = extension.filter(|e| self.selected_extension.as_deref() != Some(*e));
+        self.selected_extension = extension.map(str::to_owned);
+        self.extension_files = match (extension, &self.tree) {
+            (Some(ext), Some(tree)) => top_files_with_extension(tree, ext, EXTENSION_FILES_SHOWN),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Rebuild `visible_rows` in the current sort order, keeping every
+    /// expanded directory expanded.
+    fn resort_visible_rows(&mut self) {
+        // Clone the Arc (cheap refcount bump) so the rebuild can borrow self mutably.
+        if let Some(tree) = self.tree.clone() {
+            self.rebuild_live_visible_rows(&tree);
+        } else if let Some(lt) = self.live_tree.clone() {
+            let tree = lt.read();
+            self.rebuild_live_visible_rows(&tree);
+        }
+    }
+}
+
+// ── Keyboard navigation ──────────────────────────────────────────────────────
+
+impl AppState {
+    /// Run `f` against the final tree, or the live tree during a scan.
+    fn with_any_tree<R>(&self, f: impl FnOnce(&FileTree) -> R) -> Option<R> {
+        if let Some(ref tree) = self.tree {
+            Some(f(tree))
+        } else {
+            self.live_tree.as_ref().map(|lt| f(&lt.read()))
+        }
+    }
+
+    /// Position of the selected node in `visible_rows`, if it is visible.
+    pub fn selected_row(&self) -> Option<usize> {
+        let selected = self.selected_node?;
+        self.visible_rows
+            .iter()
+            .position(|r| r.node_index == selected)
+    }
+
+    /// Select the visible row at `row` and scroll it into view.
+    fn select_row(&mut self, row: usize) {
+        if let Some(r) = self.visible_rows.get(row) {
+            self.selected_node = Some(r.node_index);
+            self.scroll_tree_to_selection = true;
+        }
+    }
+
+    /// Move the tree-view selection by `delta` rows (Up/Down, j/k, paging).
+    ///
+    /// With nothing selected, any movement selects the first row.  The
+    /// selection clamps at both ends rather than wrapping.
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.visible_rows.is_empty() {
+            return;
+        }
+        let target = match self.selected_row() {
+            Some(cur) => cur
+                .saturating_add_signed(delta)
+                .min(self.visible_rows.len() - 1),
+            None => 0,
+        };
+        self.select_row(target);
+    }
+
+    /// Move the selection one page up (`-1`) or down (`1`).
+    pub fn move_selection_page(&mut self, direction: isize) {
+        self.move_selection(direction * PAGE_ROWS);
+    }
+
+    /// Select the first (`Home`) or last (`End`) visible row.
+    pub fn select_edge_row(&mut self, last: bool) {
+        if !self.visible_rows.is_empty() {
+            let row = if last { self.visible_rows.len() - 1 } else { 0 };
+            self.select_row(row);
+        }
+    }
+
+    /// Right / `l`: expand the selected folder, or step into its first child
+    /// when it is already expanded.
+    pub fn expand_selection(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        let is_dir = self
+            .with_any_tree(|t| t.node(self.visible_rows[row].node_index).is_dir)
+            .unwrap_or(false);
+        if !is_dir {
+            return;
+        }
+        if self.visible_rows[row].is_expanded {
+            // First child directly follows an expanded row, one level deeper.
+            let depth = self.visible_rows[row].depth;
+            if self
+                .visible_rows
+                .get(row + 1)
+                .is_some_and(|r| r.depth > depth)
+            {
+                self.select_row(row + 1);
+            }
+        } else {
+            self.toggle_expand(row);
+        }
+    }
+
+    /// Left / `h`: collapse the selected folder, or step out to its parent
+    /// row when it is already collapsed (or is a file).
+    pub fn collapse_selection(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        if self.visible_rows[row].is_expanded {
+            self.toggle_expand(row);
+            return;
+        }
+        // The parent is the nearest row above with a smaller depth.
+        let depth = self.visible_rows[row].depth;
+        if let Some(parent_row) = self.visible_rows[..row]
+            .iter()
+            .rposition(|r| r.depth < depth)
+        {
+            self.select_row(parent_row);
+        }
+    }
+
+    /// Enter: show the selected folder in the treemap.  For a file, its
+    /// parent folder is shown instead so the file is visible in context.
+    pub fn drill_into_selection(&mut self) {
+        let Some(node) = self.selected_node else {
+            return;
+        };
+        let target = self.with_any_tree(|t| {
+            if node.idx() >= t.len() {
+                None
+            } else if t.node(node).is_dir {
+                Some(node)
+            } else {
+                t.node(node).parent
+            }
+        });
+        if let Some(Some(dir)) = target {
+            self.treemap_navigate_to(dir);
+        }
+    }
+
+    /// Type-ahead: extend the typed prefix with `text` and jump to the next
+    /// sibling of the selection whose name starts with it.
+    ///
+    /// Typing the same single letter repeatedly cycles through siblings
+    /// that start with it, matching Explorer's behaviour.
+    pub fn type_ahead(&mut self, text: &str) {
+        let now = Instant::now();
+        if self
+            .type_ahead_at
+            .is_none_or(|t| now.duration_since(t) > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.type_ahead_at = Some(now);
+        self.type_ahead
+            .extend(text.chars().flat_map(char::to_lowercase));
+
+        if self.visible_rows.is_empty() {
+            return;
+        }
+        let current = self.selected_row();
+
+        // A repeated single character (including the first keypress) moves
+        // past the current row; a longer prefix re-checks it first so
+        // refining the name does not skip ahead.
+        let repeated_char = {
+            let mut chars = self.type_ahead.chars();
+            let first = chars.next();
+            chars.all(|c| Some(c) == first)
+        };
+        let start = match current {
+            Some(r) if repeated_char => r + 1,
+            Some(r) => r,
+            None => 0,
+        };
+        let prefix: String = if repeated_char {
+            self.type_ahead.chars().take(1).collect()
+        } else {
+            self.type_ahead.clone()
+        };
+
+        let n = self.visible_rows.len();
+        let found = self.with_any_tree(|tree| {
+            // Siblings share the selection's parent; with no selection any
+            // visible row qualifies.
+            let parent = current.map(|r| tree.node(self.visible_rows[r].node_index).parent);
+            (0..n).map(|i| (start + i) % n).find(|&i| {
+                let node = tree.node(self.visible_rows[i].node_index);
+                parent.is_none_or(|p| node.parent == p)
+                    && starts_with_ignore_case(&node.name, &prefix)
+            })
+        });
+        if let Some(Some(row)) = found {
+            self.select_row(row);
+        }
+    }
+
+    /// `true` while a type-ahead prefix is still being typed.
+    ///
+    /// Used so that `h`/`j`/`k`/`l` extend a name mid-word instead of
+    /// being treated as navigation keys.
+    pub fn type_ahead_active(&self) -> bool {
+        self.type_ahead_at
+            .is_some_and(|t| t.elapsed() <= TYPE_AHEAD_TIMEOUT)
+    }
+}
+
+/// Case-insensitive prefix test against an already-lowercased `prefix`.
+fn starts_with_ignore_case(name: &str, prefix: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    prefix.chars().all(|p| name_chars.next() == Some(p))
+}
+
+/// Children of `parent` as the tree view lists them: sorted by `order` and,
+/// when a category filter is active, limited to the nodes its mask keeps.
+fn listed_children(
+    tree: &FileTree,
+    parent: NodeIndex,
+    order: &SortOrder,
+    old_bytes: &OldBytes,
+    mask: Option<&[bool]>,
+) -> Vec<NodeIndex> {
+    let mut children = tree.children_sorted(parent, order, old_bytes);
+    if let Some(mask) = mask {
+        children.retain(|c| mask.get(c.idx()).copied().unwrap_or(true));
+    }
+    children
+}
+
 /// Toggle-expand implementation operating on the visible_rows vec directly.
 ///
 /// Free function to avoid `&mut self` / `&self.tree` borrow conflict.
-fn toggle_expand_inner(visible_rows: &mut Vec<VisibleRow>, row_index: usize, tree: &FileTree) {
+fn toggle_expand_inner(
+    visible_rows: &mut Vec<VisibleRow>,
+    row_index: usize,
+    tree: &FileTree,
+    order: &SortOrder,
+    old_bytes: &OldBytes,
+    mask: Option<&[bool]>,
+) {
     let row = &visible_rows[row_index];
     let node = tree.node(row.node_index);
 
@@ -641,7 +1557,7 @@ fn toggle_expand_inner(visible_rows: &mut Vec<VisibleRow>, row_index: usize, tre
         // Respect MAX_VISIBLE_ROWS: only add as many children as headroom allows.
         let node_idx = row.node_index;
         let child_depth = row.depth + 1;
-        let children = tree.children_sorted_by_size(node_idx);
+        let children = listed_children(tree, node_idx, order, old_bytes, mask);
         let insert_pos = row_index + 1;
         let headroom = MAX_VISIBLE_ROWS.saturating_sub(visible_rows.len());
 
@@ -670,6 +1586,9 @@ fn reveal_node_inner(
     scroll_offset: &mut f32,
     target: NodeIndex,
     tree: &FileTree,
+    order: &SortOrder,
+    old_bytes: &OldBytes,
+    mask: Option<&[bool]>,
 ) {
     // Build ancestor chain from target up to root.
     let mut ancestors: Vec<NodeIndex> = Vec::new();
@@ -684,7 +1603,7 @@ fn reveal_node_inner(
     for ancestor in &ancestors {
         if let Some(row_idx) = visible_rows.iter().position(|r| r.node_index == *ancestor) {
             if !visible_rows[row_idx].is_expanded {
-                toggle_expand_inner(visible_rows, row_idx, tree);
+                toggle_expand_inner(visible_rows, row_idx, tree, order, old_bytes, mask);
             }
         }
     }
@@ -778,3 +1697,943 @@ impl AppState {
         }
     }
 }
+
+impl AppState {
+    /// (Re)start the background search for the current `search_query`.
+    ///
+    /// Cancels any in-flight search first, so calling this on every keystroke
+    /// is cheap: the stale search stops at its next cancellation check.
+    /// Searching requires the completed tree; during a scan this is a no-op.
+    pub fn start_search(&mut self) {
+        self.cancel_search();
+        self.search_error = None;
+
+        let tree = match self.tree {
+            Some(ref t) => Arc::clone(t),
+            None => return,
+        };
+
+        let pattern = match NamePattern::new(&self.search_query, self.search_mode) {
+            Ok(p) => p,
+            Err(disksleuth_core::search::PatternError::Empty) => {
+                self.search_results.clear();
+                self.search_total_matches = 0;
+                return;
+            }
+            Err(e) => {
+                self.search_error = Some(e.to_string());
+                return;
+            }
+        };
+
+        self.search_handle = Some(disksleuth_core::search::start_search(
+            tree,
+            pattern,
+            MAX_SEARCH_RESULTS,
+        ));
+    }
+
+    /// Cancel the in-flight search, if any.  Existing results are kept.
+    pub fn cancel_search(&mut self) {
+        if let Some(h) = self.search_handle.take() {
+            h.cancel();
+        }
+    }
+
+    /// Cancel any search and discard its results (the query text is kept).
+    pub fn clear_search_results(&mut self) {
+        self.cancel_search();
+        self.search_results.clear();
+        self.search_total_matches = 0;
+        self.search_error = None;
+    }
+
+    /// Poll the background search for its result. Called once per frame.
+    ///
+    /// Returns `true` if new results arrived and the UI should repaint.
+    pub fn process_search_messages(&mut self) -> bool {
+        let msg = match self.search_handle {
+            Some(ref h) => match h.result_rx.try_recv() {
+                Ok(m) => m,
+                Err(crossbeam_channel::TryRecvError::Empty) => return false,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.search_handle = None;
+                    return false;
+                }
+            },
+            None => return false,
+        };
+
+        self.search_handle = None;
+        match msg {
+            SearchMessage::Complete(results) => {
+                tracing::debug!(
+                    "Search matched {} nodes in {:?}",
+                    results.total_matches,
+                    results.duration
+                );
+                self.search_results = results.hits;
+                self.search_total_matches = results.total_matches;
+                true
+            }
+            SearchMessage::Cancelled => false,
+        }
+    }
+
+    /// Jump to a node from a list (search hit, cleanup list): select it,
+    /// reveal it in the tree view, and show its parent directory in the
+    /// treemap so it is visible in context.
+    pub fn reveal_node(&mut self, node: NodeIndex) {
+        // Guard against stale indices from a previous scan's results.
+        let parent = match self.current_tree() {
+            Some(t) if node.idx() < t.len() => t.node(node).parent,
+            _ => return,
+        };
+
+        // A root hit has no parent — the treemap already shows it.
+        if let Some(parent) = parent {
+            if self.treemap_root != Some(parent) {
+                self.treemap_navigate_to(parent);
+            }
+        }
+        self.selected_node = Some(node);
+        self.reveal_node_in_tree(node);
+    }
+}
+
+// ── Cleanup (trash / delete) ─────────────────────────────────────────────────
+
+impl AppState {
+    /// Whether trash/delete may be offered right now.
+    ///
+    /// Only the completed tree can be modified, and one cleanup runs at a time.
+    pub fn can_modify_tree(&self) -> bool {
+        self.tree.is_some() && self.phase != AppPhase::Scanning && self.cleanup_handle.is_none()
+    }
+
+    /// Prepare a cleanup of `nodes` and open the confirmation dialog.
+    pub fn request_cleanup(&mut self, nodes: &[NodeIndex], action: CleanupAction) {
+        if !self.can_modify_tree() {
+            return;
+        }
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        let plan = plan_cleanup(tree, nodes, action);
+        if !plan.items.is_empty() {
+            self.cleanup_confirm = Some(plan);
+        }
+    }
+
+    /// Prepare a cleanup of the whole cleanup list with `cart_action` and
+    /// open the confirmation dialog.
+    pub fn request_cart_cleanup(&mut self) {
+        if !self.can_modify_tree() {
+            return;
+        }
+        if let Some(plan) = self.cart_plan() {
+            if !plan.items.is_empty() {
+                self.cleanup_confirm = Some(plan);
+            }
+        }
+    }
+
+    /// Start the cleanup the user confirmed in the dialog.
+    pub fn confirm_cleanup(&mut self) {
+        let Some(plan) = self.cleanup_confirm.take() else {
+            return;
+        };
+        if !self.can_modify_tree() {
+            return;
+        }
+        self.cleanup_report = Some(CleanupReport {
+            action: plan.action,
+            total: plan.items.len(),
+            processed: 0,
+            removed: 0,
+            freed: 0,
+            failure_count: 0,
+            failures: Vec::new(),
+            finished: false,
+            cancelled: false,
+        });
+        self.cleanup_removed.clear();
+        self.cleanup_handle = Some(start_cleanup(plan));
+    }
+
+    /// Stop the running cleanup after the current item.
+    ///
+    /// Items already removed are still pruned from the tree.
+    pub fn cancel_cleanup(&mut self) {
+        if let Some(ref h) = self.cleanup_handle {
+            h.cancel();
+        }
+    }
+
+    /// Drain cleanup results. Called once per frame.
+    ///
+    /// When the run finishes, every successfully removed node is dropped from
+    /// the tree in a single [`FileTree::without_subtrees`] pass and sizes are
+    /// re-aggregated.  Returns `true` if the UI should repaint.
+    pub fn process_cleanup_messages(&mut self) -> bool {
+        let Some(ref handle) = self.cleanup_handle else {
+            return false;
+        };
+
+        let mut repaint = false;
+        let mut finished = None;
+        for _ in 0..MAX_MESSAGES_PER_FRAME {
+            let msg = match handle.message_rx.try_recv() {
+                Ok(m) => m,
+                Err(crossbeam_channel::TryRecvError::Empty) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    finished = Some(handle.is_cancelled());
+                    break;
+                }
+            };
+            repaint = true;
+            match msg {
+                CleanupMessage::Item(outcome) => {
+                    let Some(report) = self.cleanup_report.as_mut() else {
+                        continue;
+                    };
+                    report.processed += 1;
+                    match outcome.result {
+                        Ok(()) => {
+                            report.removed += 1;
+                            self.cleanup_removed.push(outcome.index);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Cleanup failed for {}: {}", outcome.path.display(), e);
+                            report.failure_count += 1;
+                            if report.failures.len() < MAX_CLEANUP_FAILURES {
+                                report
+                                    .failures
+                                    .push((outcome.path.display().to_string(), e.to_string()));
+                            }
+                        }
+                    }
+                }
+                CleanupMessage::Finished {
+                    cancelled,
+                    duration,
+                } => {
+                    tracing::info!("Cleanup finished in {:?}", duration);
+                    finished = Some(cancelled);
+                    break;
+                }
+            }
+        }
+
+        if let Some(cancelled) = finished {
+            self.cleanup_handle = None;
+            let removed = std::mem::take(&mut self.cleanup_removed);
+            let freed = self.remove_nodes_from_tree(&removed);
+            if let Some(report) = self.cleanup_report.as_mut() {
+                report.freed = freed;
+                report.finished = true;
+                report.cancelled = cancelled;
+            }
+            repaint = true;
+        }
+        repaint
+    }
+
+    /// Drop `removed` (and their descendants) from the final tree and remap
+    /// every stored node index.  Returns the bytes removed.
+    fn remove_nodes_from_tree(&mut self, removed: &[NodeIndex]) -> u64 {
+        let Some(old) = self.tree.clone() else {
+            return 0;
+        };
+        if removed.is_empty() {
+            return 0;
+        }
+        // The plan never contains nested nodes, so sizes do not overlap.
+        let freed = removed.iter().map(|&n| old.node(n).size).sum();
+        let (tree, remap) = old.without_subtrees(removed);
+
+        // A removed node maps to its nearest surviving ancestor, so the
+        // treemap and selection stay where the user was looking.
+        let surviving = |mut node: NodeIndex| loop {
+            if let Some(n) = remap.get(node) {
+                return Some(n);
+            }
+            node = old.node(node).parent?;
+        };
+        self.selected_node = self.selected_node.and_then(surviving);
+        self.treemap_root = self.treemap_root.and_then(surviving);
+        for history in [&mut self.treemap_back, &mut self.treemap_forward] {
+            *history = history.iter().filter_map(|&n| remap.get(n)).collect();
+        }
+        self.context_menu_node = None;
+        self.cleanup_cart.retain_mut(|node| match remap.get(*node) {
+            Some(n) => {
+                *node = n;
+                true
+            }
+            None => false,
+        });
+        if let Some(ref mut report) = self.stale_report {
+            report.stale.retain_mut(|f| match remap.get(f.index) {
+                Some(n) => {
+                    f.index = n;
+                    true
+                }
+                None => false,
+            });
+        }
+        self.stale_selected = self
+            .stale_selected
+            .iter()
+            .filter_map(|&n| remap.get(n))
+            .collect();
+        self.stale_anchor = None;
+        if let Some(ref mut report) = self.reclaimable_report {
+            report.remap(&tree, &remap);
+        }
+        self.reclaimable_selected = self
+            .reclaimable_selected
+            .iter()
+            .filter_map(|&n| remap.get(n))
+            .collect();
+        if let Some(ref mut report) = self.owner_report {
+            report.remap(&remap);
+        }
+        self.search_results
+            .retain_mut(|hit| match remap.get(hit.index) {
+                Some(n) => {
+                    hit.index = n;
+                    true
+                }
+                None => false,
+            });
+
+        // Remap rows first so the rebuild keeps every expanded folder open.
+        self.visible_rows
+            .retain_mut(|row| match remap.get(row.node_index) {
+                Some(n) => {
+                    row.node_index = n;
+                    true
+                }
+                None => false,
+            });
+        if let Some(ref mut report) = self.signature_report {
+            report.remap(&tree, &remap);
+            self.signature_generation += 1;
+        }
+        self.category_mask = self
+            .category_filter
+            .map(|cat| category_mask(&tree, cat, self.detected_types()));
+        self.rebuild_live_visible_rows(&tree);
+        self.size_scope = self.size_scope.and_then(|n| remap.get(n));
+
+        self.refresh_reports(&tree);
+        self.tree = Some(Arc::new(tree));
+        self.refresh_cart();
+        // Refresh hit sizes and totals against the pruned tree.
+        self.start_search();
+        self.start_stale_analysis();
+        self.start_reclaimable_analysis();
+        self.start_owner_analysis();
+        // A pass still running holds indices into the old tree.
+        if self.signature_handle.is_some() {
+            self.start_signature_scan();
+        }
+        // Sampled sizes no longer add up once files are gone; estimate again
+        // on request.
+        self.cancel_compression_estimate();
+        self.compression_report = None;
+        freed
+    }
+}
+
+// ── Cleanup list ("cart") ────────────────────────────────────────────────────
+
+impl AppState {
+    /// Whether `node` is in the cleanup list.
+    pub fn is_in_cart(&self, node: NodeIndex) -> bool {
+        self.cart_set.contains(&node)
+    }
+
+    /// Whether `node` is in the cleanup list but already covered by an
+    /// ancestor that is also listed.
+    pub fn cart_is_nested(&self, node: NodeIndex) -> bool {
+        self.cart_set.contains(&node) && !self.cart_outermost.contains(&node)
+    }
+
+    /// Add `node` to the cleanup list.
+    ///
+    /// Only nodes of the completed tree can be listed, and never a scan root.
+    pub fn cart_add(&mut self, node: NodeIndex) {
+        self.cart_add_many(&[node]);
+    }
+
+    /// Add several nodes at once, recomputing the totals only once.
+    pub fn cart_add_many(&mut self, nodes: &[NodeIndex]) {
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        let mut added = false;
+        for &node in nodes {
+            let valid = node.idx() < tree.len() && tree.node(node).parent.is_some();
+            if !valid || self.cleanup_cart.len() >= MAX_CART_ITEMS || !self.cart_set.insert(node) {
+                continue;
+            }
+            self.cleanup_cart.push(node);
+            added = true;
+        }
+        if added {
+            self.refresh_cart();
+        }
+    }
+
+    /// Remove `node` from the cleanup list.
+    pub fn cart_remove(&mut self, node: NodeIndex) {
+        if self.cart_set.remove(&node) {
+            self.cleanup_cart.retain(|&n| n != node);
+            self.refresh_cart();
+        }
+    }
+
+    /// Add `node` if it is not listed, otherwise remove it.
+    pub fn cart_toggle(&mut self, node: NodeIndex) {
+        if self.is_in_cart(node) {
+            self.cart_remove(node);
+        } else {
+            self.cart_add(node);
+        }
+    }
+
+    /// Empty the cleanup list.
+    pub fn cart_clear(&mut self) {
+        self.cleanup_cart.clear();
+        self.refresh_cart();
+    }
+
+    /// Rebuild the membership sets and reclaimable totals after any change.
+    fn refresh_cart(&mut self) {
+        self.cart_set.clear();
+        self.cart_set.extend(self.cleanup_cart.iter().copied());
+        self.cart_outermost.clear();
+        self.cart_reclaimable = 0;
+        self.cart_files = 0;
+
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        for node in outermost_nodes(tree, &self.cleanup_cart) {
+            let n = tree.node(node);
+            self.cart_reclaimable += n.size;
+            self.cart_files += if n.is_dir { n.descendant_count } else { 1 };
+            self.cart_outermost.insert(node);
+        }
+    }
+
+    /// Build a plan for the whole cleanup list with `cart_action`.
+    ///
+    /// Returns `None` without a tree, or for an archive run with no folder.
+    pub fn cart_plan(&self) -> Option<CleanupPlan> {
+        let tree = self.tree.as_ref()?;
+        let mut plan = plan_cleanup(tree, &self.cleanup_cart, self.cart_action);
+        if self.cart_action == CleanupAction::Archive {
+            let dir = self.cart_archive_dir.trim();
+            if dir.is_empty() {
+                return None;
+            }
+            plan.archive_dir = Some(std::path::PathBuf::from(dir));
+        }
+        Some(plan)
+    }
+
+    /// The cleanup list as a script for this platform's shell.
+    pub fn cart_script(&self) -> Option<String> {
+        self.cart_plan()
+            .map(|plan| cleanup_script(&plan, ScriptKind::native()))
+    }
+}
+
+// ── Content detection ────────────────────────────────────────────────────────
+
+/// Stands in for the detected types before any signature pass has run.
+static NO_DETECTED_TYPES: std::sync::OnceLock<DetectedTypes> = std::sync::OnceLock::new();
+
+impl AppState {
+    /// Content types found by the last signature pass, if any.
+    pub(crate) fn detected_types(&self) -> &DetectedTypes {
+        match self.signature_report {
+            Some(ref report) => &report.detected,
+            None => NO_DETECTED_TYPES.get_or_init(DetectedTypes::new),
+        }
+    }
+
+    /// (Re)start the background signature pass over the completed tree
+    /// with the current size threshold.  Cancels any in-flight pass; the
+    /// previous report stays in effect until the new one arrives.
+    pub fn start_signature_scan(&mut self) {
+        self.cancel_signature_scan();
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        self.signature_progress = (0, 0);
+        self.signature_handle = Some(start_signature_scan(
+            Arc::clone(tree),
+            self.signature_min_size,
+        ));
+    }
+
+    /// Cancel the in-flight signature pass, if any.
+    pub fn cancel_signature_scan(&mut self) {
+        if let Some(h) = self.signature_handle.take() {
+            h.cancel();
+        }
+    }
+
+    /// Drain progress and the result of the signature pass. Called once
+    /// per frame.
+    ///
+    /// On completion the type stats and category filter are recomputed
+    /// with the detected types.  Returns `true` if anything changed.
+    pub fn process_signature_messages(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            let msg = match self.signature_handle {
+                Some(ref h) => match h.result_rx.try_recv() {
+                    Ok(m) => m,
+                    Err(crossbeam_channel::TryRecvError::Empty) => return changed,
+                    Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                        self.signature_handle = None;
+                        return changed;
+                    }
+                },
+                None => return changed,
+            };
+            changed = true;
+            match msg {
+                SignatureMessage::Progress { checked, total } => {
+                    self.signature_progress = (checked, total);
+                }
+                SignatureMessage::Complete(report) => {
+                    self.signature_handle = None;
+                    self.signature_report = Some(report);
+                    self.signature_generation += 1;
+                    if let Some(tree) = self.tree.clone() {
+                        self.category_mask = self
+                            .category_filter
+                            .map(|cat| category_mask(&tree, cat, self.detected_types()));
+                        self.refresh_type_stats(&tree);
+                        if self.category_filter.is_some() {
+                            self.rebuild_live_visible_rows(&tree);
+                        }
+                    }
+                    return true;
+                }
+                SignatureMessage::Cancelled => {
+                    self.signature_handle = None;
+                    return changed;
+                }
+            }
+        }
+    }
+}
+
+// ── Stale files ──────────────────────────────────────────────────────────────
+
+impl AppState {
+    /// (Re)start the background age analysis with the current threshold,
+    /// limit and basis.
+    ///
+    /// Like [`AppState::start_search`], this cancels any in-flight run, so it
+    /// can be called on every edit of the controls.  Requires the completed
+    /// tree; the previous report stays visible until the new one arrives.
+    pub fn start_stale_analysis(&mut self) {
+        self.cancel_stale_analysis();
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        self.stale_max_results = self.stale_max_results.clamp(1, MAX_STALE_RESULTS);
+        // Without recorded access times the basis would silently fall back
+        // to modification times; say so by switching back.
+        if !tree.extended_metadata {
+            self.stale_basis = AgeBasis::Modified;
+        }
+        self.stale_handle = Some(start_age_analysis(
+            Arc::clone(tree),
+            self.stale_min_age_days,
+            self.stale_max_results,
+            self.stale_basis,
+        ));
+    }
+
+    /// Cancel the in-flight age analysis, if any.
+    pub fn cancel_stale_analysis(&mut self) {
+        if let Some(h) = self.stale_handle.take() {
+            h.cancel();
+        }
+    }
+
+    /// Poll the background age analysis for its result. Called once per frame.
+    ///
+    /// Returns `true` if a new report arrived and the UI should repaint.
+    pub fn process_stale_messages(&mut self) -> bool {
+        let msg = match self.stale_handle {
+            Some(ref h) => match h.result_rx.try_recv() {
+                Ok(m) => m,
+                Err(crossbeam_channel::TryRecvError::Empty) => return false,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.stale_handle = None;
+                    return false;
+                }
+            },
+            None => return false,
+        };
+
+        self.stale_handle = None;
+        match msg {
+            AgeMessage::Complete(report) => {
+                // Keep the selection for files that are still listed.
+                let listed: HashSet<NodeIndex> = report.stale.iter().map(|f| f.index).collect();
+                self.stale_selected.retain(|n| listed.contains(n));
+                self.stale_anchor = None;
+                self.stale_report = Some(report);
+                true
+            }
+            AgeMessage::Cancelled => false,
+        }
+    }
+
+    /// Update the stale-file selection for a click on list row `row`.
+    ///
+    /// A plain click selects only that row, Ctrl toggles it, and Shift
+    /// selects the range from the last clicked row.
+    pub fn stale_click(&mut self, row: usize, ctrl: bool, shift: bool) {
+        let Some(ref report) = self.stale_report else {
+            return;
+        };
+        let Some(file) = report.stale.get(row) else {
+            return;
+        };
+        match (shift, self.stale_anchor) {
+            (true, Some(anchor)) => {
+                if !ctrl {
+                    self.stale_selected.clear();
+                }
+                let (lo, hi) = (anchor.min(row), anchor.max(row));
+                let end = hi.min(report.stale.len() - 1);
+                self.stale_selected
+                    .extend(report.stale[lo..=end].iter().map(|f| f.index));
+                // The anchor stays put so further Shift+clicks re-span from it.
+                return;
+            }
+            _ if ctrl => {
+                if !self.stale_selected.remove(&file.index) {
+                    self.stale_selected.insert(file.index);
+                }
+            }
+            _ => {
+                self.stale_selected.clear();
+                self.stale_selected.insert(file.index);
+            }
+        }
+        self.stale_anchor = Some(row);
+    }
+
+    /// Select every listed stale file.
+    pub fn stale_select_all(&mut self) {
+        if let Some(ref report) = self.stale_report {
+            self.stale_selected = report.stale.iter().map(|f| f.index).collect();
+        }
+    }
+
+    /// Number and total size of the selected stale files.
+    pub fn stale_selection_totals(&self) -> (usize, u64) {
+        let Some(ref report) = self.stale_report else {
+            return (0, 0);
+        };
+        report
+            .stale
+            .iter()
+            .filter(|f| self.stale_selected.contains(&f.index))
+            .fold((0, 0), |(n, bytes), f| (n + 1, bytes + f.size))
+    }
+
+    /// Add the selected stale files to the cleanup list, in list order.
+    pub fn stale_add_selected_to_cart(&mut self) {
+        let Some(ref report) = self.stale_report else {
+            return;
+        };
+        let picked: Vec<NodeIndex> = report
+            .stale
+            .iter()
+            .map(|f| f.index)
+            .filter(|n| self.stale_selected.contains(n))
+            .collect();
+        self.cart_add_many(&picked);
+    }
+}
+
+// ── Reclaimable (developer artefacts) ────────────────────────────────────────
+
+impl AppState {
+    /// (Re)start the background artefact analysis over the completed tree.
+    ///
+    /// Cancels any in-flight run; the previous report stays visible until
+    /// the new one arrives.
+    pub fn start_reclaimable_analysis(&mut self) {
+        self.cancel_reclaimable_analysis();
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        self.reclaimable_handle = Some(start_artefact_analysis(Arc::clone(tree)));
+    }
+
+    /// Cancel the in-flight artefact analysis, if any.
+    pub fn cancel_reclaimable_analysis(&mut self) {
+        if let Some(h) = self.reclaimable_handle.take() {
+            h.cancel();
+        }
+    }
+
+    /// Poll the background artefact analysis for its result. Called once
+    /// per frame.
+    ///
+    /// Returns `true` if a new report arrived and the UI should repaint.
+    pub fn process_reclaimable_messages(&mut self) -> bool {
+        let msg = match self.reclaimable_handle {
+            Some(ref h) => match h.result_rx.try_recv() {
+                Ok(m) => m,
+                Err(crossbeam_channel::TryRecvError::Empty) => return false,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.reclaimable_handle = None;
+                    return false;
+                }
+            },
+            None => return false,
+        };
+
+        self.reclaimable_handle = None;
+        match msg {
+            ArtefactMessage::Complete(report) => {
+                // Keep ticks on artefacts that are still listed.
+                let listed: HashSet<NodeIndex> = report.artefacts.iter().map(|a| a.index).collect();
+                self.reclaimable_selected.retain(|n| listed.contains(n));
+                self.reclaimable_report = Some(report);
+                true
+            }
+            ArtefactMessage::Cancelled => false,
+        }
+    }
+
+    /// Tick or untick one artefact.
+    pub fn reclaimable_toggle(&mut self, node: NodeIndex) {
+        if !self.reclaimable_selected.remove(&node) {
+            self.reclaimable_selected.insert(node);
+        }
+    }
+
+    /// Tick every listed artefact, or only those of one kind.
+    pub fn reclaimable_select_all(&mut self, kind: Option<&str>) {
+        if let Some(ref report) = self.reclaimable_report {
+            self.reclaimable_selected = report
+                .artefacts
+                .iter()
+                .filter(|a| kind.is_none_or(|k| a.kind == k))
+                .map(|a| a.index)
+                .collect();
+        }
+    }
+
+    /// Ticked artefacts in list order.
+    pub fn reclaimable_picked(&self) -> Vec<NodeIndex> {
+        let Some(ref report) = self.reclaimable_report else {
+            return Vec::new();
+        };
+        report
+            .artefacts
+            .iter()
+            .map(|a| a.index)
+            .filter(|n| self.reclaimable_selected.contains(n))
+            .collect()
+    }
+
+    /// Number and total size of the ticked artefacts.
+    pub fn reclaimable_selection_totals(&self) -> (usize, u64) {
+        let Some(ref report) = self.reclaimable_report else {
+            return (0, 0);
+        };
+        report
+            .artefacts
+            .iter()
+            .filter(|a| self.reclaimable_selected.contains(&a.index))
+            .fold((0, 0), |(n, bytes), a| (n + 1, bytes + a.size))
+    }
+
+    /// Open the confirmation dialog for cleaning up the ticked artefacts.
+    pub fn reclaimable_cleanup(&mut self, action: CleanupAction) {
+        let picked = self.reclaimable_picked();
+        if !picked.is_empty() {
+            self.request_cleanup(&picked, action);
+        }
+    }
+
+    // ── Empty folders ────────────────────────────────────
+
+    /// Open the confirmation dialog for removing every listed empty folder.
+    /// Each one is checked on disk again before it is removed.
+    pub fn remove_empty_folders(&mut self) {
+        let Some(ref report) = self.empty_report else {
+            return;
+        };
+        let dirs = report.all_empty_dirs();
+        if !dirs.is_empty() {
+            self.request_cleanup(&dirs, CleanupAction::RemoveEmpty);
+        }
+    }
+
+    // ── File-count hotspots ──────────────────────────────
+
+    /// Rank the hotspot table by `sort`.
+    pub fn set_hotspot_sort(&mut self, sort: HotspotSort) {
+        if self.hotspot_sort == sort {
+            return;
+        }
+        self.hotspot_sort = sort;
+        if let Some(ref tree) = self.tree {
+            self.hotspots = analyse_hotspots(tree, sort, self.cluster_size);
+        }
+    }
+
+    // ── Largest folders ──────────────────────────────────
+
+    /// Rank the largest folders by `rank`, only at `depth` if given.
+    pub fn set_largest_dirs_view(&mut self, rank: DirRank, depth: Option<usize>) {
+        if (self.largest_dirs_rank, self.largest_dirs_depth) == (rank, depth) {
+            return;
+        }
+        self.largest_dirs_rank = rank;
+        self.largest_dirs_depth = depth;
+        if let Some(ref tree) = self.tree {
+            self.largest_dirs = largest_dirs(tree, rank, depth, MAX_LARGEST_DIRS);
+        }
+    }
+}
+
+// ── Owners ───────────────────────────────────────────────────────────────────
+
+impl AppState {
+    /// (Re)start the background owner analysis over the completed tree.
+    ///
+    /// Cancels any in-flight run; the previous report stays visible until
+    /// the new one arrives.  Does nothing for a scan that recorded no owners.
+    pub fn start_owner_analysis(&mut self) {
+        self.cancel_owner_analysis();
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        if !tree.owners_recorded {
+            return;
+        }
+        self.owner_handle = Some(start_owner_analysis(Arc::clone(tree)));
+    }
+
+    /// Cancel the in-flight owner analysis, if any.
+    pub fn cancel_owner_analysis(&mut self) {
+        if let Some(h) = self.owner_handle.take() {
+            h.cancel();
+        }
+    }
+
+    /// Poll the background owner analysis for its result. Called once per
+    /// frame.
+    ///
+    /// Returns `true` if a new report arrived and the UI should repaint.
+    pub fn process_owner_messages(&mut self) -> bool {
+        let msg = match self.owner_handle {
+            Some(ref h) => match h.result_rx.try_recv() {
+                Ok(m) => m,
+                Err(crossbeam_channel::TryRecvError::Empty) => return false,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.owner_handle = None;
+                    return false;
+                }
+            },
+            None => return false,
+        };
+
+        self.owner_handle = None;
+        match msg {
+            OwnerMessage::Complete(report) => {
+                self.owner_report = Some(report);
+                true
+            }
+            OwnerMessage::Cancelled => false,
+        }
+    }
+}
+
+// ── Compressibility ──────────────────────────────────────────────────────────
+
+impl AppState {
+    /// (Re)start the background compressibility estimate for the files
+    /// below `root`.  Cancels any in-flight estimate.
+    pub fn start_compression_estimate(&mut self, root: NodeIndex) {
+        self.cancel_compression_estimate();
+        let Some(ref tree) = self.tree else {
+            return;
+        };
+        if root.idx() >= tree.len() {
+            return;
+        }
+        self.compression_progress = (0, 0, 0);
+        self.compression_root = Some(root);
+        self.compression_handle = Some(start_compression_estimate(Arc::clone(tree), root));
+    }
+
+    /// Cancel the in-flight estimate, if any.
+    pub fn cancel_compression_estimate(&mut self) {
+        if let Some(h) = self.compression_handle.take() {
+            h.cancel();
+        }
+        self.compression_root = None;
+    }
+
+    /// Drain progress and the result of the compressibility estimate.
+    /// Called once per frame; returns `true` if anything changed.
+    pub fn process_compression_messages(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            let msg = match self.compression_handle {
+                Some(ref h) => match h.result_rx.try_recv() {
+                    Ok(m) => m,
+                    Err(crossbeam_channel::TryRecvError::Empty) => return changed,
+                    Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                        self.compression_handle = None;
+                        self.compression_root = None;
+                        return changed;
+                    }
+                },
+                None => return changed,
+            };
+            changed = true;
+            match msg {
+                CompressionMessage::Progress {
+                    sampled,
+                    total,
+                    bytes_read,
+                } => {
+                    self.compression_progress = (sampled, total, bytes_read);
+                }
+                CompressionMessage::Complete(report) => {
+                    self.compression_handle = None;
+                    self.compression_root = None;
+                    self.compression_report = Some(report);
+                    return true;
+                }
+                CompressionMessage::Cancelled => {
+                    self.compression_handle = None;
+                    self.compression_root = None;
+                    return changed;
+                }
+            }
+        }
+    }
+}
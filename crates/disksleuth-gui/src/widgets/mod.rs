@@ -1,7 +1,9 @@
 /// UI widgets for DiskSleuth.
 pub mod drive_picker;
+pub mod node_menu;
 pub mod size_bar;
 pub mod status_bar;
+pub mod sunburst;
 pub mod toolbar;
 pub mod tree_view;
 pub mod treemap;
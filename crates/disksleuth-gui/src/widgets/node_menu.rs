@@ -0,0 +1,124 @@
+/// Right-click context menu shared by the tree view and the treemap.
+///
+/// Read-only items (open, copy) act immediately.  Anything that changes the
+/// disk or the app state is returned as a [`NodeMenuAction`] so the caller
+/// can apply it after its tree borrow has ended.
+use crate::state::AppState;
+use disksleuth_core::cleanup::CleanupAction;
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::{FileTree, NodeIndex};
+use egui::Ui;
+
+/// A deferred action chosen from the node context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeMenuAction {
+    /// Ask for confirmation, then trash or delete the node.
+    Cleanup(NodeIndex, CleanupAction),
+    /// Add the node to the cleanup list, or remove it if already listed.
+    ToggleCart(NodeIndex),
+}
+
+/// Which menu items are available, decided from app state before the tree
+/// is borrowed for drawing.
+#[derive(Debug, Clone, Copy)]
+pub struct NodeMenuFlags {
+    /// `false` while a scan or another cleanup is running; trash/delete are
+    /// then shown disabled.
+    pub can_modify: bool,
+    /// `true` once the scan is complete, so the node can be listed.
+    pub can_list: bool,
+    /// The node is already in the cleanup list.
+    pub in_cart: bool,
+}
+
+impl NodeMenuFlags {
+    /// Flags for `node` under the current state.
+    pub fn for_node(state: &AppState, node: NodeIndex) -> Self {
+        Self {
+            can_modify: state.can_modify_tree(),
+            can_list: state.tree.is_some(),
+            in_cart: state.is_in_cart(node),
+        }
+    }
+}
+
+/// Draw the menu contents for `node_index`.
+pub fn node_menu(
+    ui: &mut Ui,
+    tree: &FileTree,
+    node_index: NodeIndex,
+    flags: NodeMenuFlags,
+) -> Option<NodeMenuAction> {
+    if node_index.idx() >= tree.len() {
+        ui.close_menu();
+        return None;
+    }
+
+    let node = tree.node(node_index);
+    // Prefer the real on-disk path; the display path only matches it for
+    // whole-drive scans.
+    let full_path = tree
+        .fs_path(node_index)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| tree.full_path(node_index));
+    let mut action = None;
+
+    if ui.button("📂 Open in Explorer").clicked() {
+        // For directories: open the folder itself.  For files: open the
+        // parent folder with the file pre-selected via "/select,<path>".
+        let explorer_arg = if node.is_dir {
+            full_path.clone()
+        } else {
+            format!("/select,{}", full_path)
+        };
+        let _ = std::process::Command::new("explorer.exe")
+            .arg(explorer_arg)
+            .spawn();
+        ui.close_menu();
+    }
+
+    if ui.button("📋 Copy Path").clicked() {
+        ui.ctx().copy_text(full_path);
+        ui.close_menu();
+    }
+
+    ui.separator();
+
+    // Scan roots are never offered for removal.
+    let is_root = node.parent.is_none();
+    let cart_label = if flags.in_cart {
+        "➖ Remove from cleanup list"
+    } else {
+        "➕ Add to cleanup list"
+    };
+    if ui
+        .add_enabled(flags.can_list && !is_root, egui::Button::new(cart_label))
+        .clicked()
+    {
+        action = Some(NodeMenuAction::ToggleCart(node_index));
+        ui.close_menu();
+    }
+
+    let removable = flags.can_modify && !is_root;
+    for (cleanup, label) in [
+        (CleanupAction::Trash, "🗑 Move to Trash…"),
+        (CleanupAction::Delete, "⚠ Delete permanently…"),
+    ] {
+        if ui
+            .add_enabled(removable, egui::Button::new(label))
+            .clicked()
+        {
+            action = Some(NodeMenuAction::Cleanup(node_index, cleanup));
+            ui.close_menu();
+        }
+    }
+
+    ui.separator();
+
+    ui.label(format!("Size: {}", format_size(node.size)));
+    if node.is_dir {
+        ui.label(format!("Files: {}", format_count(node.descendant_count)));
+    }
+
+    action
+}
@@ -0,0 +1,498 @@
+/// Sunburst widget — the treemap's hierarchy drawn as concentric rings.
+///
+/// The current treemap root fills the centre disc; each ring outwards is one
+/// level deeper, and a segment's angle is proportional to its size within
+/// its parent's angle.  Angles start at twelve o'clock and run clockwise.
+///
+/// **Navigation**: the same as the treemap — double-click a directory to
+/// drill in, click the centre to go up, and the shared toolbar for
+/// back/forward and the breadcrumb.
+/// **Culling**: segments narrower than [`MIN_SEGMENT_ANGLE`] are skipped
+/// together with their subtree, the angular counterpart of the treemap's
+/// minimum area.
+/// **Hover / context menu / colours**: shared with the treemap.
+use crate::state::{AppState, TreemapWeight};
+use crate::widgets::treemap::{
+    children_by_weight, darken, draw_legend, fill_color, hover_color, label_colors, legend_entries,
+    map_background, map_context_menu, map_toolbar, node_click_action, node_tooltip, node_weight,
+    selection_color, truncate_name, weight_text, ColorContext, LayoutKey, LegendEntry,
+    TreemapAction,
+};
+use compact_str::CompactString;
+use disksleuth_core::analysis::DetectedTypes;
+use disksleuth_core::model::{FileTree, NodeIndex};
+use egui::{Color32, Pos2, Rect, Sense, Ui, Vec2};
+use std::f32::consts::TAU;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Number of rings drawn around the centre.
+const RINGS: usize = 6;
+
+/// Radius of the centre disc, in ring widths.
+const CENTRE_RINGS: f32 = 1.2;
+
+/// Segments narrower than this (radians, about a quarter of a degree) are
+/// not drawn, and neither is anything inside them.
+const MIN_SEGMENT_ANGLE: f32 = 0.004;
+
+/// Maximum number of segments in one layout.
+const MAX_SUNBURST_SEGMENTS: usize = 50_000;
+
+/// Largest angle (radians) covered by one mesh step along an arc.
+const ARC_STEP: f32 = 0.04;
+
+/// Gap (px) between neighbouring segments and rings.
+const SEGMENT_GAP: f32 = 0.75;
+
+/// Minimum room (px) to show a label.
+const MIN_LABEL_W: f32 = 40.0;
+
+/// One annular sector.
+struct Segment {
+    node_idx: NodeIndex,
+    /// Clockwise from twelve o'clock, in radians.
+    start: f32,
+    end: f32,
+    color: Color32,
+    name: CompactString,
+    size: u64,
+    /// Files below a directory; 0 for a file.
+    files: u64,
+    /// Share of the parent under the layout's weight.
+    percent: f32,
+    is_dir: bool,
+}
+
+/// A computed sunburst.
+struct SunburstLayout {
+    key: LayoutKey,
+    colors: ColorContext,
+    centre: Pos2,
+    /// Radius of the centre disc.
+    inner: f32,
+    ring_w: f32,
+    /// Segments per ring, ordered by start angle.
+    rings: Vec<Vec<Segment>>,
+    legend: Vec<LegendEntry>,
+    mesh: Arc<egui::Mesh>,
+}
+
+/// Sunburst layout kept across frames, recomputed when its key changes
+/// (same key as the treemap's layout cache).
+#[derive(Default)]
+pub struct SunburstCache {
+    layout: Option<SunburstLayout>,
+}
+
+/// Draw the sunburst widget. Returns an optional action for the caller to handle.
+pub fn sunburst(ui: &mut Ui, state: &AppState, cache: &mut SunburstCache) -> Option<TreemapAction> {
+    // Obtain tree reference — final tree, then live tree.
+    let live_guard;
+    let tree: &FileTree;
+
+    if let Some(ref t) = state.tree {
+        tree = t;
+    } else if let Some(ref lt) = state.live_tree {
+        live_guard = lt.read();
+        if live_guard.is_empty() {
+            return None;
+        }
+        tree = &*live_guard;
+    } else {
+        ui.centered_and_justified(|ui| {
+            ui.label(
+                egui::RichText::new("No scan results. Select a drive and click Scan.")
+                    .color(ui.visuals().weak_text_color()),
+            );
+        });
+        return None;
+    };
+
+    if tree.roots.is_empty() {
+        return None;
+    }
+    let root_node = state.treemap_root.unwrap_or_else(|| tree.roots[0]);
+    if root_node.idx() >= tree.nodes.len() {
+        return None;
+    }
+    let root = &tree.nodes[root_node.idx()];
+    let is_light = !ui.visuals().dark_mode;
+
+    let mut action = map_toolbar(ui, state, tree, root_node);
+
+    // ── Allocate the drawing area ──────────────────────────────────
+    let available = ui.available_size();
+    let map_rect = ui.allocate_rect(
+        Rect::from_min_size(ui.cursor().min, available),
+        Sense::click(),
+    );
+    let bounds = map_rect.rect;
+    if bounds.width() < 10.0 || bounds.height() < 10.0 {
+        return action;
+    }
+
+    let painter = ui.painter_at(bounds);
+    painter.rect_filled(bounds, 0.0, map_background(is_light));
+
+    let key = LayoutKey {
+        generation: tree.generation,
+        root: root_node,
+        bounds,
+        mode: state.treemap_color,
+        is_light,
+        highlight: state.category_filter,
+        weight: state.treemap_weight,
+        detected: state.signature_generation,
+    };
+    if cache.layout.as_ref().is_none_or(|l| l.key != key) {
+        cache.layout = Some(SunburstLayout::compute(tree, key, state.detected_types()));
+    }
+    let Some(layout) = cache.layout.as_ref() else {
+        return action;
+    };
+
+    // ── Render ─────────────────────────────────────────────────────
+    painter.add(egui::Shape::Mesh(layout.mesh.clone()));
+
+    let (label_color, label_color_dim) = label_colors(is_light);
+    let centre_fill = darken(map_background(!is_light), 0.25);
+    painter.circle_filled(layout.centre, layout.inner - SEGMENT_GAP, centre_fill);
+    let centre_text = |offset: f32, text: String, size: f32, color: Color32| {
+        painter.text(
+            layout.centre + Vec2::new(0.0, offset),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(size),
+            color,
+        );
+    };
+    let max_chars = ((layout.inner * 2.0 - 12.0) / 6.0).max(4.0) as usize;
+    centre_text(
+        -7.0,
+        truncate_name(&root.name, max_chars).into_owned(),
+        12.0,
+        label_color,
+    );
+    centre_text(
+        8.0,
+        weight_text(state.treemap_weight, root.size, root.descendant_count),
+        11.0,
+        label_color_dim,
+    );
+
+    if root.first_child.is_none() || node_weight(root, state.treemap_weight) == 0 {
+        return action;
+    }
+
+    for (ring, segments) in layout.rings.iter().enumerate() {
+        for seg in segments {
+            layout.draw_label(&painter, ring, seg, label_color);
+        }
+    }
+
+    // ── Hover highlight + tooltip ──────────────────────────────────
+    let hover_pos = ui
+        .input(|i| i.pointer.hover_pos())
+        .filter(|pos| bounds.contains(*pos));
+    let by_files = state.treemap_weight == TreemapWeight::Files;
+    match hover_pos.and_then(|pos| layout.hit(pos)) {
+        Some(Hit::Centre) => {
+            if root.parent.is_some() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            node_tooltip(
+                ui,
+                map_rect.id,
+                &root.name,
+                root.size,
+                by_files.then_some(root.descendant_count),
+                100.0,
+                root.is_dir,
+            );
+            if action.is_none()
+                && root.parent.is_some()
+                && ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Primary))
+            {
+                action = Some(TreemapAction::Up);
+            }
+        }
+        Some(Hit::Segment(ring, i)) => {
+            let seg = &layout.rings[ring][i];
+            layout.outline(&painter, ring, seg, hover_color(is_light), 2.0);
+            if seg.is_dir {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            node_tooltip(
+                ui,
+                map_rect.id,
+                &seg.name,
+                seg.size,
+                by_files.then_some(seg.files),
+                seg.percent,
+                seg.is_dir,
+            );
+            if action.is_none() {
+                action = node_click_action(ui, &map_rect, tree, seg.node_idx, seg.is_dir);
+            }
+        }
+        None => {}
+    }
+
+    // ── Selection highlight for the tree-view-selected node ────────
+    if let Some(sel) = state.selected_node {
+        let found = layout
+            .rings
+            .iter()
+            .enumerate()
+            .find_map(|(ring, segments)| {
+                segments
+                    .iter()
+                    .find(|s| s.node_idx == sel)
+                    .map(|s| (ring, s))
+            });
+        if let Some((ring, seg)) = found {
+            layout.outline(&painter, ring, seg, selection_color(is_light), 2.5);
+        }
+    }
+
+    if state.treemap_legend {
+        draw_legend(&painter, bounds, &layout.legend, &layout.colors);
+    }
+
+    map_context_menu(&map_rect, state, tree, &mut action);
+
+    action
+}
+
+/// What lies under the pointer.
+enum Hit {
+    Centre,
+    /// Ring and index within the ring.
+    Segment(usize, usize),
+}
+
+impl SunburstLayout {
+    fn compute(tree: &FileTree, key: LayoutKey, detected: &DetectedTypes) -> Self {
+        let colors = ColorContext {
+            mode: key.mode,
+            is_light: key.is_light,
+            now: SystemTime::now(),
+            highlight: key.highlight,
+        };
+        let radius = (key.bounds.width().min(key.bounds.height()) / 2.0 - 8.0).max(1.0);
+        let ring_w = radius / (RINGS as f32 + CENTRE_RINGS);
+        let mut layout = Self {
+            key,
+            colors,
+            centre: key.bounds.center(),
+            inner: ring_w * CENTRE_RINGS,
+            ring_w,
+            rings: (0..RINGS).map(|_| Vec::new()).collect(),
+            legend: Vec::new(),
+            mesh: Arc::default(),
+        };
+
+        let root = tree.node(key.root);
+        let mut count = 0;
+        let root_weight = node_weight(root, key.weight);
+        layout.layout_children(
+            tree,
+            detected,
+            key.root,
+            root_weight,
+            0.0,
+            TAU,
+            0,
+            &mut count,
+        );
+
+        let mut mesh = egui::Mesh::default();
+        for (ring, segments) in layout.rings.iter().enumerate() {
+            for seg in segments {
+                layout.add_segment(&mut mesh, ring, seg);
+            }
+        }
+        layout.mesh = Arc::new(mesh);
+        layout.legend = legend_entries(
+            tree,
+            layout
+                .rings
+                .iter()
+                .enumerate()
+                .flat_map(|(ring, segments)| {
+                    let (r0, r1) = layout.radii(ring);
+                    segments.iter().map(move |s| {
+                        (
+                            s.node_idx,
+                            ring,
+                            (s.end - s.start) * (r1 * r1 - r0 * r0) / 2.0,
+                        )
+                    })
+                }),
+            &colors,
+            detected,
+        );
+        layout
+    }
+
+    /// Lay out `parent`'s children in ring `ring` across `span` radians from
+    /// `start`, recursing outwards into directories.
+    #[allow(clippy::too_many_arguments)]
+    fn layout_children(
+        &mut self,
+        tree: &FileTree,
+        detected: &DetectedTypes,
+        parent: NodeIndex,
+        parent_size: u64,
+        start: f32,
+        span: f32,
+        ring: usize,
+        count: &mut usize,
+    ) {
+        if ring >= RINGS || parent_size == 0 {
+            return;
+        }
+        let mut angle = start;
+        // Folders come before files, so a thin child is skipped on its own
+        // rather than ending the level, as in the treemap.
+        for child_idx in children_by_weight(tree, parent, self.key.weight) {
+            if *count >= MAX_SUNBURST_SEGMENTS {
+                return;
+            }
+            let child = tree.node(child_idx);
+            let child_weight = node_weight(child, self.key.weight);
+            let child_span = (child_weight as f64 / parent_size as f64) as f32 * span;
+            if child_span < MIN_SEGMENT_ANGLE {
+                angle += child_span;
+                continue;
+            }
+            *count += 1;
+            self.rings[ring].push(Segment {
+                node_idx: child_idx,
+                start: angle,
+                end: angle + child_span,
+                color: fill_color(tree, child_idx, ring, &self.colors, detected),
+                name: child.name.clone(),
+                size: child.size,
+                files: child.descendant_count,
+                percent: (child_weight as f64 / parent_size as f64 * 100.0) as f32,
+                is_dir: child.is_dir,
+            });
+            if child.is_dir {
+                self.layout_children(
+                    tree,
+                    detected,
+                    child_idx,
+                    child_weight,
+                    angle,
+                    child_span,
+                    ring + 1,
+                    count,
+                );
+            }
+            angle += child_span;
+        }
+    }
+
+    /// Inner and outer radius of `ring`.
+    fn radii(&self, ring: usize) -> (f32, f32) {
+        let r0 = self.inner + ring as f32 * self.ring_w;
+        (r0, r0 + self.ring_w)
+    }
+
+    /// Screen position at `angle` and radius `r`.
+    fn point(&self, angle: f32, r: f32) -> Pos2 {
+        self.centre + Vec2::new(angle.sin(), -angle.cos()) * r
+    }
+
+    /// Append a segment to `mesh` as a strip of quads along its arc, inset
+    /// by [`SEGMENT_GAP`] so the background shows between neighbours.
+    fn add_segment(&self, mesh: &mut egui::Mesh, ring: usize, seg: &Segment) {
+        let (r0, r1) = self.radii(ring);
+        let (r0, r1) = (r0 + SEGMENT_GAP, r1 - SEGMENT_GAP);
+        let gap = (SEGMENT_GAP / r0).min((seg.end - seg.start) / 4.0);
+        let (a0, a1) = (seg.start + gap, seg.end - gap);
+        let steps = ((a1 - a0) / ARC_STEP).ceil().max(1.0) as u32;
+
+        let base = mesh.vertices.len() as u32;
+        for k in 0..=steps {
+            let a = a0 + (a1 - a0) * k as f32 / steps as f32;
+            mesh.colored_vertex(self.point(a, r0), seg.color);
+            mesh.colored_vertex(self.point(a, r1), seg.color);
+        }
+        for k in 0..steps {
+            let i = base + 2 * k;
+            mesh.add_triangle(i, i + 1, i + 2);
+            mesh.add_triangle(i + 1, i + 3, i + 2);
+        }
+    }
+
+    /// Outline a segment (hover and selection highlight).
+    fn outline(
+        &self,
+        painter: &egui::Painter,
+        ring: usize,
+        seg: &Segment,
+        color: Color32,
+        width: f32,
+    ) {
+        let (r0, r1) = self.radii(ring);
+        let steps = ((seg.end - seg.start) / ARC_STEP).ceil().max(1.0) as usize;
+        let arc = |r: f32| {
+            (0..=steps)
+                .map(move |k| seg.start + (seg.end - seg.start) * k as f32 / steps as f32)
+                .map(move |a| (a, r))
+        };
+        let points: Vec<Pos2> = arc(r1)
+            .chain(arc(r0).collect::<Vec<_>>().into_iter().rev())
+            .map(|(a, r)| self.point(a, r))
+            .collect();
+        painter.add(egui::Shape::closed_line(
+            points,
+            egui::Stroke::new(width, color),
+        ));
+    }
+
+    /// Name at the middle of the segment, if there is room for it.
+    fn draw_label(&self, painter: &egui::Painter, ring: usize, seg: &Segment, color: Color32) {
+        let (r0, r1) = self.radii(ring);
+        let mid_r = (r0 + r1) / 2.0;
+        let mid_a = (seg.start + seg.end) / 2.0;
+        let arc_len = (seg.end - seg.start) * mid_r;
+        if arc_len < MIN_LABEL_W && seg.end - seg.start < TAU / 2.0 {
+            return;
+        }
+        // Horizontal text runs along the arc at the top and bottom, and
+        // across the ring at the sides.
+        let across = mid_a.cos().abs();
+        let room = self.ring_w + (arc_len.min(mid_r * 2.0) - self.ring_w).max(0.0) * across;
+        if room < MIN_LABEL_W {
+            return;
+        }
+        let max_chars = ((room - 6.0) / 6.0) as usize;
+        painter.text(
+            self.point(mid_a, mid_r),
+            egui::Align2::CENTER_CENTER,
+            truncate_name(&seg.name, max_chars).as_ref(),
+            egui::FontId::proportional(10.0),
+            color,
+        );
+    }
+
+    /// What is under `pos`.
+    fn hit(&self, pos: Pos2) -> Option<Hit> {
+        let d = pos - self.centre;
+        let r = d.length();
+        if r < self.inner {
+            return Some(Hit::Centre);
+        }
+        let ring = ((r - self.inner) / self.ring_w) as usize;
+        let segments = self.rings.get(ring)?;
+        let angle = d.x.atan2(-d.y).rem_euclid(TAU);
+        let i = segments
+            .partition_point(|s| s.start <= angle)
+            .checked_sub(1)?;
+        (angle < segments[i].end).then_some(Hit::Segment(ring, i))
+    }
+}
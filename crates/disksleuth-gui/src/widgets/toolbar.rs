@@ -39,6 +39,28 @@ pub fn toolbar(ui: &mut Ui, state: &mut AppState) {
             state.cancel_scan();
         }
 
+        // Scan options apply to the next scan; changing them mid-scan
+        // would leave the tree half-filled.
+        ui.add_enabled(
+            state.phase != AppPhase::Scanning,
+            egui::Checkbox::new(
+                &mut state.scan_options.extended_metadata,
+                "Extended details",
+            ),
+        )
+        .on_hover_text(
+            "Record creation and access times, permissions, attributes and link \
+             targets during the scan. Uses more memory on large drives.",
+        );
+        ui.add_enabled(
+            state.phase != AppPhase::Scanning,
+            egui::Checkbox::new(&mut state.scan_options.owners, "Owners"),
+        )
+        .on_hover_text(
+            "Record each file's owner during the scan, for usage per owner. \
+             Reads every file's security descriptor, so scans take longer.",
+        );
+
         // Refresh drives — disabled during a scan to prevent a jarring
         // state reset while results are being accumulated.
         let can_refresh = state.phase != AppPhase::Scanning;
@@ -70,6 +92,92 @@ pub fn toolbar(ui: &mut Ui, state: &mut AppState) {
             // TODO: implement CSV/JSON export.
         }
 
+        // Cleanup list — the count doubles as a reminder that items are marked.
+        let cart_label = if state.cleanup_cart.is_empty() {
+            "🧺 Cleanup list".to_string()
+        } else {
+            format!("🧺 Cleanup list ({})", state.cleanup_cart.len())
+        };
+        if ui
+            .selectable_label(state.show_cart, cart_label)
+            .on_hover_text("Review items marked for cleanup (Insert marks the selection)")
+            .clicked()
+        {
+            state.show_cart = !state.show_cart;
+        }
+
+        if ui
+            .selectable_label(state.show_stale, "🕰 Stale files")
+            .on_hover_text("Large files that have not been modified in a long time")
+            .clicked()
+        {
+            state.show_stale = !state.show_stale;
+        }
+
+        if ui
+            .selectable_label(state.show_reclaimable, "♻ Reclaimable")
+            .on_hover_text("Build outputs and dependency caches that can be regenerated")
+            .clicked()
+        {
+            state.show_reclaimable = !state.show_reclaimable;
+        }
+
+        if ui
+            .selectable_label(state.show_caches, "🗄 Caches")
+            .on_hover_text("Tool caches and temp folders in well-known locations")
+            .clicked()
+        {
+            state.show_caches = !state.show_caches;
+        }
+
+        if ui
+            .selectable_label(state.show_empty, "📭 Empty")
+            .on_hover_text("Empty folders and zero-byte files")
+            .clicked()
+        {
+            state.show_empty = !state.show_empty;
+        }
+
+        if ui
+            .selectable_label(state.show_hotspots, "🗂 Hotspots")
+            .on_hover_text("Folders with the most and smallest files")
+            .clicked()
+        {
+            state.show_hotspots = !state.show_hotspots;
+        }
+
+        if ui
+            .selectable_label(state.show_largest_dirs, "📁 Largest")
+            .on_hover_text("Folders ranked by the size of their own files or in total")
+            .clicked()
+        {
+            state.show_largest_dirs = !state.show_largest_dirs;
+        }
+
+        if ui
+            .selectable_label(state.show_owners, "👤 Owners")
+            .on_hover_text("Disk usage per file owner")
+            .clicked()
+        {
+            state.show_owners = !state.show_owners;
+        }
+
+        if ui
+            .selectable_label(state.show_slack, "🧮 Slack")
+            .on_hover_text("Space lost to cluster rounding, and other cluster sizes compared")
+            .clicked()
+        {
+            state.show_slack = !state.show_slack;
+        }
+
+        if ui
+            .selectable_label(state.show_compression, "🗜 Compress")
+            .on_hover_text("Estimate how much a folder would shrink if compressed")
+            .clicked()
+        {
+            state.show_compression = !state.show_compression;
+        }
+
         // Right-aligned controls.
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             // About button.
@@ -77,6 +185,15 @@ pub fn toolbar(ui: &mut Ui, state: &mut AppState) {
                 state.show_about = true;
             }
 
+            // Keyboard shortcuts help.
+            if ui
+                .button("⌨")
+                .on_hover_text("Keyboard shortcuts (?)")
+                .clicked()
+            {
+                state.show_shortcuts = !state.show_shortcuts;
+            }
+
             // ── Theme toggle (☀ light / 🌙 dark) ──────────────────
             let theme_label = if state.dark_mode { "☀" } else { "🌙" };
             let theme_tip = if state.dark_mode {
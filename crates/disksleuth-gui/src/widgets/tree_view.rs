@@ -7,7 +7,9 @@
 /// During scanning, reads from the shared `LiveTree` via a read-lock
 /// so the user can see the tree populate in real time.
 use crate::state::{AppPhase, AppState};
+use crate::widgets::node_menu::{node_menu, NodeMenuAction, NodeMenuFlags};
 use disksleuth_core::model::size::format_size;
+use disksleuth_core::model::time::format_date;
 use disksleuth_core::model::FileTree;
 use egui::{Rect, Response, Sense, Ui, Vec2};
 
@@ -17,6 +19,45 @@ const ROW_HEIGHT: f32 = 24.0;
 /// Indentation per depth level in pixels.
 const INDENT_PX: f32 = 20.0;
 
+// ── Column layout ────────────────────────────────────────────────────────────
+// Offsets are relative to the start of the right-hand column block, which is
+// `columns_width()` pixels from the row's right edge.  Shared with the header
+// row in `tree_panel` so headers always line up with their values.
+
+/// Width of the right-hand column block (size … modified).
+const COLUMNS_WIDTH: f32 = 430.0;
+/// Extra width taken by the optional age columns (newest … old data).
+const AGE_COLUMNS_WIDTH: f32 = 232.0;
+/// Logical size column.
+pub(crate) const COL_SIZE: f32 = 0.0;
+/// Allocated (on-disk) size column.
+pub(crate) const COL_ALLOCATED: f32 = 72.0;
+/// Percent-of-parent column.
+pub(crate) const COL_PERCENT: f32 = 144.0;
+/// Usage bar column.
+pub(crate) const COL_BAR: f32 = 192.0;
+/// Width of the usage bar.
+const BAR_WIDTH: f32 = 80.0;
+/// Descendant file count column.
+pub(crate) const COL_FILES: f32 = 282.0;
+/// Last-modified date column.
+pub(crate) const COL_MODIFIED: f32 = 352.0;
+/// Newest descendant modification date (age columns).
+pub(crate) const COL_NEWEST: f32 = 430.0;
+/// Oldest descendant modification date (age columns).
+pub(crate) const COL_OLDEST: f32 = 508.0;
+/// Bytes older than the cutoff (age columns).
+pub(crate) const COL_OLD_BYTES: f32 = 586.0;
+
+/// Width of the right-hand column block, with or without the age columns.
+pub(crate) fn columns_width(age_columns: bool) -> f32 {
+    if age_columns {
+        COLUMNS_WIDTH + AGE_COLUMNS_WIDTH
+    } else {
+        COLUMNS_WIDTH
+    }
+}
+
 /// Draw the virtualised tree view.
 ///
 /// Returns the response for the outer scroll area so callers can
@@ -66,10 +107,13 @@ pub fn tree_view(ui: &mut Ui, state: &mut AppState) -> Response {
         ui.add_space(2.0);
     }
 
+    // Consume the one-shot scroll request (keyboard navigation, reveal).
+    let scroll_to_selection = std::mem::take(&mut state.scroll_tree_to_selection);
+
     // ── Render the tree and collect deferred actions ────────────
     // Scoped block so that tree references (including any RwLockReadGuard)
     // are dropped before we mutate state.
-    let (toggle_row, new_selection) = {
+    let (toggle_row, new_selection, menu_action) = {
         // Obtain tree reference inside the block.
         let live_guard;
         let tree: &FileTree;
@@ -81,7 +125,7 @@ pub fn tree_view(ui: &mut Ui, state: &mut AppState) -> Response {
             tree = &*live_guard;
         }
 
-        render_tree_rows(ui, state, tree)
+        render_tree_rows(ui, state, tree, scroll_to_selection)
     };
     // tree / live_guard dropped here — safe to mutate state.
 
@@ -94,17 +138,26 @@ pub fn tree_view(ui: &mut Ui, state: &mut AppState) -> Response {
     if let Some(row_idx) = toggle_row {
         state.toggle_expand(row_idx);
     }
+    match menu_action {
+        Some(NodeMenuAction::Cleanup(node, action)) => state.request_cleanup(&[node], action),
+        Some(NodeMenuAction::ToggleCart(node)) => state.cart_toggle(node),
+        None => {}
+    }
 
     ui.interact(ui.max_rect(), ui.id().with("tree_bg"), Sense::hover())
 }
 
 /// Render the virtualised tree rows. Returns (toggle_row, new_selection)
-/// indices for deferred state mutation.
+/// indices and any context-menu action for deferred state mutation.
+///
+/// When `scroll_to_selection` is set, the selected row is scrolled into view
+/// even if it is currently outside the viewport.
 fn render_tree_rows(
     ui: &mut Ui,
     state: &AppState,
     tree: &FileTree,
-) -> (Option<usize>, Option<usize>) {
+    scroll_to_selection: bool,
+) -> (Option<usize>, Option<usize>, Option<NodeMenuAction>) {
     // ── Extract theme-adaptive colours once ─────────────────────────────
     // Using visuals here avoids scattering dark/light conditionals across the
     // painter calls below.
@@ -139,6 +192,7 @@ fn render_tree_rows(
 
     let mut toggle_row: Option<usize> = None;
     let mut new_selection: Option<usize> = None;
+    let mut menu_action: Option<NodeMenuAction> = None;
 
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -152,6 +206,16 @@ fn render_tree_rows(
             let viewport = ui.clip_rect();
             let top_y = response.rect.top();
 
+            if scroll_to_selection {
+                if let Some(pos) = state.selected_row() {
+                    let selected_rect = Rect::from_min_size(
+                        egui::pos2(response.rect.left(), top_y + pos as f32 * ROW_HEIGHT),
+                        Vec2::new(response.rect.width(), ROW_HEIGHT),
+                    );
+                    ui.scroll_to_rect(selected_rect, None);
+                }
+            }
+
             // Determine visible range.
             let first_visible = ((viewport.top() - top_y) / ROW_HEIGHT).floor().max(0.0) as usize;
             let last_visible = ((viewport.bottom() - top_y) / ROW_HEIGHT)
@@ -194,6 +258,12 @@ fn render_tree_rows(
                     painter.rect_filled(row_rect, 0.0, color_hover);
                 }
 
+                // Cleanup-list marker: a thin stripe on the row's left edge.
+                if state.is_in_cart(row.node_index) {
+                    let stripe = Rect::from_min_size(row_rect.min, Vec2::new(3.0, ROW_HEIGHT));
+                    painter.rect_filled(stripe, 0.0, color_warning);
+                }
+
                 // Click handling.
                 if row_response.clicked() {
                     new_selection = Some(row_idx);
@@ -204,7 +274,10 @@ fn render_tree_rows(
 
                 // Context menu.
                 row_response.context_menu(|ui| {
-                    context_menu(ui, state, row.node_index);
+                    let flags = NodeMenuFlags::for_node(state, row.node_index);
+                    if let Some(a) = node_menu(ui, tree, row.node_index, flags) {
+                        menu_action = Some(a);
+                    }
                 });
 
                 // Tooltip with full name when hovered (useful for truncated names).
@@ -274,7 +347,7 @@ fn render_tree_rows(
                 // File/directory name — rendered with proper text clipping.
                 // Error nodes display in muted/warning colour.
                 let name_x = text_x + 20.0;
-                let right_area_start = row_rect.right() - 300.0;
+                let right_area_start = row_rect.right() - columns_width(state.tree_age_columns);
                 let max_name_w = (right_area_start - name_x - 4.0).max(20.0);
                 let name_str = node.name.as_str();
 
@@ -324,17 +397,26 @@ fn render_tree_rows(
                 let size_text = format_size(node.size);
 
                 painter.text(
-                    egui::pos2(right_area_start, text_y),
+                    egui::pos2(right_area_start + COL_SIZE, text_y),
                     egui::Align2::LEFT_CENTER,
                     &size_text,
                     egui::FontId::proportional(12.0),
                     color_weak,
                 );
 
+                // Allocated size.
+                painter.text(
+                    egui::pos2(right_area_start + COL_ALLOCATED, text_y),
+                    egui::Align2::LEFT_CENTER,
+                    format_size(node.allocated_size),
+                    egui::FontId::proportional(12.0),
+                    color_weak,
+                );
+
                 // Percentage.
                 let pct_text = format!("{:.1}%", node.percent_of_parent);
                 painter.text(
-                    egui::pos2(right_area_start + 80.0, text_y),
+                    egui::pos2(right_area_start + COL_PERCENT, text_y),
                     egui::Align2::LEFT_CENTER,
                     &pct_text,
                     egui::FontId::proportional(12.0),
@@ -342,8 +424,8 @@ fn render_tree_rows(
                 );
 
                 // Size bar.
-                let bar_x = right_area_start + 130.0;
-                let bar_width = 100.0;
+                let bar_x = right_area_start + COL_BAR;
+                let bar_width = BAR_WIDTH;
                 let bar_height = 10.0;
                 let bar_rect = Rect::from_min_size(
                     egui::pos2(bar_x, text_y - bar_height / 2.0),
@@ -365,72 +447,64 @@ fn render_tree_rows(
                     painter.rect_filled(fill_rect, 2.0, egui::Color32::from_rgb(r, g, b));
                 }
 
-                // File count for directories.
+                // File count for directories.  The header already says
+                // "Files", so the value is shown bare to save width.
                 if node.is_dir && node.descendant_count > 0 {
-                    let count_text = format!(
-                        "{} files",
-                        disksleuth_core::model::size::format_count(node.descendant_count)
-                    );
+                    let count_text =
+                        disksleuth_core::model::size::format_count(node.descendant_count);
                     painter.text(
-                        egui::pos2(bar_x + bar_width + 10.0, text_y),
+                        egui::pos2(right_area_start + COL_FILES, text_y),
                         egui::Align2::LEFT_CENTER,
                         &count_text,
                         egui::FontId::proportional(11.0),
                         color_weak,
                     );
                 }
+
+                // Last-modified date.
+                if let Some(modified) = node.modified {
+                    painter.text(
+                        egui::pos2(right_area_start + COL_MODIFIED, text_y),
+                        egui::Align2::LEFT_CENTER,
+                        format_date(modified),
+                        egui::FontId::proportional(11.0),
+                        color_weak,
+                    );
+                }
+
+                // Age columns: a file's own date is already under
+                // "Modified", so newest/oldest are shown for folders only.
+                if state.tree_age_columns {
+                    let dates = [
+                        (COL_NEWEST, node.newest_day.to_time()),
+                        (COL_OLDEST, node.oldest_day.to_time()),
+                    ];
+                    for (column, date) in dates {
+                        if let Some(date) = date.filter(|_| node.is_dir) {
+                            painter.text(
+                                egui::pos2(right_area_start + column, text_y),
+                                egui::Align2::LEFT_CENTER,
+                                format_date(date),
+                                egui::FontId::proportional(11.0),
+                                color_weak,
+                            );
+                        }
+                    }
+                    let old_bytes = state.old_bytes.get(row.node_index);
+                    if old_bytes > 0 {
+                        painter.text(
+                            egui::pos2(right_area_start + COL_OLD_BYTES, text_y),
+                            egui::Align2::LEFT_CENTER,
+                            format_size(old_bytes),
+                            egui::FontId::proportional(11.0),
+                            color_weak,
+                        );
+                    }
+                }
             }
 
             response
         });
 
-    (toggle_row, new_selection)
-}
-
-/// Right-click context menu for a tree node.
-fn context_menu(ui: &mut Ui, state: &AppState, node_index: disksleuth_core::model::NodeIndex) {
-    // Get tree reference — final tree first, then live tree.
-    let live_guard;
-    let tree: &FileTree;
-
-    if let Some(ref t) = state.tree {
-        tree = t;
-    } else if let Some(ref lt) = state.live_tree {
-        live_guard = lt.read();
-        tree = &*live_guard;
-    } else {
-        return;
-    };
-
-    let full_path = tree.full_path(node_index);
-    let node = tree.node(node_index);
-
-    if ui.button("📂 Open in Explorer").clicked() {
-        // For directories: open the folder itself.  For files: open the
-        // parent folder with the file pre-selected via "/select,<path>".
-        let explorer_arg = if node.is_dir {
-            full_path.clone()
-        } else {
-            format!("/select,{}", full_path)
-        };
-        let _ = std::process::Command::new("explorer.exe")
-            .arg(explorer_arg)
-            .spawn();
-        ui.close_menu();
-    }
-
-    if ui.button("📋 Copy Path").clicked() {
-        ui.ctx().copy_text(full_path);
-        ui.close_menu();
-    }
-
-    ui.separator();
-
-    ui.label(format!("Size: {}", format_size(node.size)));
-    if node.is_dir {
-        ui.label(format!(
-            "Files: {}",
-            disksleuth_core::model::size::format_count(node.descendant_count)
-        ));
-    }
+    (toggle_row, new_selection, menu_action)
 }
@@ -9,15 +9,35 @@
 /// - Click a directory to drill in (becomes the new root).
 /// - Click a file to open Explorer at that location.
 /// - Back/Forward/Up buttons and breadcrumb for navigation history.
+/// - Right-click for the same context menu as the tree view.
 ///
 /// **Labels**: Shown when the rectangle is large enough.
 /// **Hover**: Tooltip with name, size, percentage, type.
-use crate::state::AppState;
+/// **Colour**: by file type, age, depth, or extension, with a legend overlay.
+/// **Weight**: areas follow bytes, or file counts to expose folders full of
+/// tiny files.
+/// **Cushions**: optional van Wijk shading, one ridge per nesting level.
+///
+/// **Caching**: the layout is kept in a [`TreemapCache`] and rebuilt only
+/// when the tree, root, viewport or colours change.  While a scan is
+/// running the rebuild happens on a background thread.
+use crate::panels::chart_panel::category_color;
+use crate::state::{AppState, MapView, TreemapColorMode, TreemapWeight};
+use crate::widgets::node_menu::{node_menu, NodeMenuAction, NodeMenuFlags};
 use compact_str::CompactString;
-use disksleuth_core::model::size::format_size;
-use disksleuth_core::model::{FileTree, NodeIndex};
+use crossbeam_channel::Receiver;
+use disksleuth_core::analysis::{file_extension, node_category, DetectedTypes, FileCategory};
+use disksleuth_core::model::size::{format_count, format_size};
+use disksleuth_core::model::{FileNode, FileTree, NodeIndex};
+use disksleuth_core::scanner::LiveTree;
 use egui::{Color32, Rect, Sense, Ui, Vec2};
+use parking_lot::RwLock;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::SystemTime;
 
 /// Maximum recursion depth for nested layout.
 const MAX_NEST_DEPTH: usize = 6;
@@ -42,7 +62,7 @@ const FRAME_PAD: f32 = 1.5;
 const MIN_LABEL_W: f32 = 40.0;
 const MIN_LABEL_H: f32 = 16.0;
 
-/// Colour palette for top-level categories.
+/// Colour palette for the depth colour mode, one entry per level.
 const PALETTE: &[(u8, u8, u8)] = &[
     (0x89, 0xb4, 0xfa), // blue
     (0xa6, 0xe3, 0xa1), // green
@@ -56,6 +76,50 @@ const PALETTE: &[(u8, u8, u8)] = &[
     (0xb4, 0xbe, 0xfe), // lavender
 ];
 
+/// Age (days) at which the age colour mode reaches its oldest colour.
+const AGE_COLOR_MAX_DAYS: f32 = 1_095.0;
+
+/// Directory fill in modes that colour files only (file type, extension).
+const NEUTRAL_DIR: (u8, u8, u8) = (0x7f, 0x84, 0x9c);
+
+/// Maximum entries listed in the legend.
+const MAX_LEGEND_ENTRIES: usize = 9;
+
+/// Height of one legend row.
+const LEGEND_ROW_H: f32 = 15.0;
+
+/// Ridge height of a top-level cushion.
+const CUSHION_HEIGHT: f32 = 0.5;
+
+/// Ridge height factor per nesting level, so each level reads as a bump
+/// on top of its parent's.
+const CUSHION_FALLOFF: f32 = 0.75;
+
+/// Target size (px) of one shading cell; colours are interpolated between
+/// cell corners.
+const CUSHION_CELL: f32 = 6.0;
+
+/// Maximum shading cells along one side of a rectangle.
+const CUSHION_MAX_CELLS: usize = 24;
+
+/// Ambient and diffuse light intensity for cushion shading.
+const CUSHION_AMBIENT: f32 = 0.25;
+const CUSHION_DIFFUSE: f32 = 0.8;
+
+/// Side (px) of one hover hit-test grid cell.
+const HIT_CELL: f32 = 32.0;
+
+/// Everything the layout needs to colour a rectangle.
+#[derive(Clone, Copy)]
+pub(crate) struct ColorContext {
+    pub(crate) mode: TreemapColorMode,
+    pub(crate) is_light: bool,
+    /// Reference time for the age colour mode, read once per frame.
+    pub(crate) now: SystemTime,
+    /// Category picked in the file type chart; other files are washed out.
+    pub(crate) highlight: Option<FileCategory>,
+}
+
 /// A rectangle in the treemap ready for rendering and hit testing.
 #[derive(Clone)]
 struct TreemapRect {
@@ -66,11 +130,16 @@ struct TreemapRect {
     /// allocation, which matters at up to 75 000 rects per frame.
     name: CompactString,
     size: u64,
+    /// Files below a directory; 0 for a file.
+    files: u64,
+    /// Share of the parent under the layout's weight.
     percent: f32,
     is_dir: bool,
     depth: usize,
     /// The header bar rect for directories (click target for drilling in).
     header_rect: Option<Rect>,
+    /// Cushion surface including every ancestor's ridge.
+    cushion: Cushion,
 }
 
 /// Action returned from the treemap widget.
@@ -87,10 +156,24 @@ pub enum TreemapAction {
     Forward,
     /// Navigate up to parent.
     Up,
+    /// User right-clicked an item — remember it while its menu is open.
+    ContextMenu(NodeIndex),
+    /// User picked an item from the context menu.
+    Menu(NodeMenuAction),
+    /// User picked a different colour mode.
+    SetColorMode(TreemapColorMode),
+    /// User toggled the colour legend.
+    SetLegend(bool),
+    /// User toggled cushion shading.
+    SetCushions(bool),
+    /// User switched between the treemap and the sunburst.
+    SetView(MapView),
+    /// User picked what the areas are proportional to.
+    SetWeight(TreemapWeight),
 }
 
 /// Draw the treemap widget. Returns an optional action for the caller to handle.
-pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
+pub fn treemap(ui: &mut Ui, state: &AppState, cache: &mut TreemapCache) -> Option<TreemapAction> {
     // Obtain tree reference — final tree, then live tree.
     let live_guard;
     let tree: &FileTree;
@@ -131,104 +214,15 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
     // when the user toggles the theme toggle in the toolbar.
     let is_light = !ui.visuals().dark_mode;
 
-    let mut action: Option<TreemapAction> = None;
-
-    // ── Navigation toolbar ─────────────────────────────────────────
-    ui.horizontal(|ui| {
-        let can_back = !state.treemap_back.is_empty();
-        let can_forward = !state.treemap_forward.is_empty();
-        let can_up = root.parent.is_some();
-
-        if ui
-            .add_enabled(
-                can_back,
-                egui::Button::new("◀").min_size(Vec2::new(28.0, 22.0)),
-            )
-            .on_hover_text("Back")
-            .clicked()
-        {
-            action = Some(TreemapAction::Back);
-        }
-        if ui
-            .add_enabled(
-                can_forward,
-                egui::Button::new("▶").min_size(Vec2::new(28.0, 22.0)),
-            )
-            .on_hover_text("Forward")
-            .clicked()
-        {
-            action = Some(TreemapAction::Forward);
-        }
-        if ui
-            .add_enabled(
-                can_up,
-                egui::Button::new("▲").min_size(Vec2::new(28.0, 22.0)),
-            )
-            .on_hover_text("Up to parent")
-            .clicked()
-        {
-            action = Some(TreemapAction::Up);
-        }
-
-        ui.add_space(8.0);
-
-        // ── Breadcrumb ─────────────────────────────────────────────
-        let mut breadcrumb_chain: Vec<NodeIndex> = Vec::new();
-        let mut cursor = root_node;
-        loop {
-            breadcrumb_chain.push(cursor);
-            match tree.nodes[cursor.idx()].parent {
-                Some(p) => cursor = p,
-                None => break,
-            }
-        }
-        breadcrumb_chain.reverse();
-
-        for (i, &bc_node) in breadcrumb_chain.iter().enumerate() {
-            if i > 0 {
-                ui.label(
-                    egui::RichText::new(" › ")
-                        .color(ui.visuals().weak_text_color())
-                        .size(12.0),
-                );
-            }
-            let name = &tree.nodes[bc_node.idx()].name;
-            let is_current = bc_node == root_node;
-            let text = if is_current {
-                egui::RichText::new(name.as_str())
-                    .color(ui.visuals().text_color())
-                    .size(12.0)
-                    .strong()
-            } else {
-                egui::RichText::new(name.as_str())
-                    .color(ui.visuals().hyperlink_color)
-                    .size(12.0)
-            };
-            let resp = ui.add(egui::Label::new(text).sense(Sense::click()));
-            if resp.clicked() && !is_current {
-                action = Some(TreemapAction::NavigateDir(bc_node));
-            }
-            if resp.hovered() && !is_current {
-                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-            }
-        }
-
-        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            ui.label(
-                egui::RichText::new(format_size(root.size))
-                    .color(ui.visuals().hyperlink_color)
-                    .size(12.0),
-            );
-        });
-    });
-
-    ui.add_space(2.0);
+    let mut action = map_toolbar(ui, state, tree, root_node);
 
     // ── Allocate the treemap area ──────────────────────────────────
     let available = ui.available_size();
     let map_rect = ui.allocate_rect(
         Rect::from_min_size(ui.cursor().min, available),
-        Sense::hover(),
+        // Click sense so right-clicks open the context menu; primary clicks
+        // are still read from raw pointer input below.
+        Sense::click(),
     );
     let bounds = map_rect.rect;
 
@@ -239,16 +233,10 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
     let painter = ui.painter_at(bounds);
 
     // Fill background.
-    let bg = if is_light {
-        Color32::from_rgb(0xe0, 0xe0, 0xe4)
-    } else {
-        Color32::from_rgb(0x20, 0x20, 0x24)
-    };
-    painter.rect_filled(bounds, 0.0, bg);
+    painter.rect_filled(bounds, 0.0, map_background(is_light));
 
     // ── Build nested rectangles via recursive squarified layout ────
-    let children = tree.children_sorted_by_size(root_node);
-    if children.is_empty() || root.size == 0 {
+    if root.first_child.is_none() || node_weight(root, state.treemap_weight) == 0 {
         painter.text(
             bounds.center(),
             egui::Align2::CENTER_CENTER,
@@ -263,49 +251,44 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
         return action;
     }
 
-    let mut rects: Vec<TreemapRect> = Vec::with_capacity(512);
-    layout_nested(
-        tree, &children, root.size, bounds, 0, 0, is_light, &mut rects,
-    );
-
-    // Sort by depth ascending so deeper items are drawn (and hit-tested) on top.
-    rects.sort_by_key(|r| r.depth);
+    let key = LayoutKey {
+        generation: tree.generation,
+        root: root_node,
+        bounds,
+        mode: state.treemap_color,
+        is_light,
+        highlight: state.category_filter,
+        weight: state.treemap_weight,
+        detected: state.signature_generation,
+    };
+    let layout = cache.layout(ui.ctx(), state, tree, key);
 
     // ── Render ─────────────────────────────────────────────────────
     let hover_pos = ui.input(|i| i.pointer.hover_pos());
-    let clicked = ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Primary));
-    let double_clicked = ui.input(|i| {
-        i.pointer
-            .button_double_clicked(egui::PointerButton::Primary)
-    });
 
     // Highlight the currently selected node from the tree view.
     let selected_node = state.selected_node;
 
-    // Label colors.
-    let label_color = if is_light {
-        Color32::from_rgb(0x10, 0x10, 0x10)
-    } else {
-        Color32::WHITE
-    };
-    let label_color_dim = if is_light {
-        Color32::from_rgb(0x30, 0x30, 0x30)
-    } else {
-        Color32::from_rgb(0xd0, 0xd0, 0xd0)
-    };
+    let (label_color, label_color_dim) = label_colors(is_light);
+
+    // With cushions on, every fill goes into one shaded mesh, drawn in
+    // depth order; headers, borders and labels are painted over it below.
+    let cushions = state.treemap_cushions;
+    if cushions {
+        painter.add(egui::Shape::Mesh(layout.cushion_mesh()));
+    }
+    let layout: &TreemapLayout = layout;
+    let rects = &layout.rects;
 
     // Draw all rects.
-    for tr in &rects {
+    for tr in rects {
         let fill = tr.color;
 
         if tr.is_dir {
             // Directory: draw frame background + header bar.
-            let frame_bg = if is_light {
-                darken(fill, 0.08)
-            } else {
-                darken(fill, 0.35)
-            };
-            painter.rect_filled(tr.rect, 0.0, frame_bg);
+            if !cushions {
+                painter.rect_filled(tr.rect, 0.0, frame_fill(fill, is_light));
+            }
 
             // Header bar.
             if let Some(hdr) = tr.header_rect {
@@ -328,8 +311,8 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
                         label_color,
                     );
 
-                    // Size in header if there's room.
-                    let size_text = format_size(tr.size);
+                    // Size (or file count) in header if there's room.
+                    let size_text = weight_text(layout.key.weight, tr.size, tr.files);
                     let name_approx_w = display.len() as f32 * 6.0 + 8.0;
                     if hdr.width() > name_approx_w + size_text.len() as f32 * 6.0 + 8.0 {
                         painter.text(
@@ -357,7 +340,9 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
             );
         } else {
             // File: solid fill.
-            painter.rect_filled(tr.rect, 0.0, fill);
+            if !cushions {
+                painter.rect_filled(tr.rect, 0.0, fill);
+            }
 
             // Border.
             let border_color = if is_light {
@@ -400,47 +385,14 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
     }
 
     // ── Hover highlight + tooltip (deepest hovered item wins) ──────
-    let mut hovered_rect: Option<&TreemapRect> = None;
-    if let Some(pos) = hover_pos {
-        if bounds.contains(pos) {
-            // Find the deepest rect containing the cursor.
-            for tr in rects.iter().rev() {
-                // For directories, only consider the header bar as the primary hover target,
-                // unless the rect has no children rendered inside it.
-                let hit = if tr.is_dir {
-                    if let Some(hdr) = tr.header_rect {
-                        hdr.contains(pos)
-                    } else {
-                        tr.rect.contains(pos)
-                    }
-                } else {
-                    tr.rect.contains(pos)
-                };
-                if hit {
-                    hovered_rect = Some(tr);
-                    break;
-                }
-            }
-
-            // If no specific item hit but cursor is in bounds, check for any rect.
-            if hovered_rect.is_none() {
-                for tr in rects.iter().rev() {
-                    if tr.rect.contains(pos) {
-                        hovered_rect = Some(tr);
-                        break;
-                    }
-                }
-            }
-        }
-    }
+    let hovered_rect = hover_pos
+        .filter(|pos| bounds.contains(*pos))
+        .and_then(|pos| layout.hit(pos))
+        .map(|i| &rects[i]);
 
     if let Some(tr) = hovered_rect {
         // Highlight: draw a bright border on the hovered item.
-        let highlight_color = if is_light {
-            Color32::from_rgb(0x00, 0x60, 0xff)
-        } else {
-            Color32::from_rgb(0x80, 0xc0, 0xff)
-        };
+        let highlight_color = hover_color(is_light);
         let highlight_rect = if tr.is_dir {
             tr.header_rect.unwrap_or(tr.rect)
         } else {
@@ -457,56 +409,28 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
             ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
         }
 
-        egui::show_tooltip_at_pointer(
-            ui.ctx(),
-            egui::LayerId::new(egui::Order::Tooltip, map_rect.id),
-            map_rect.id.with("treemap_tip"),
-            |ui| {
-                ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
-                ui.label(
-                    egui::RichText::new(tr.name.as_str())
-                        .strong()
-                        .size(12.0)
-                        .color(label_color),
-                );
-                let kind = if tr.is_dir { "Directory" } else { "File" };
-                ui.label(
-                    egui::RichText::new(format!(
-                        "{} — {:.1}%  ({})",
-                        format_size(tr.size),
-                        tr.percent,
-                        kind,
-                    ))
-                    .color(label_color_dim),
-                );
-            },
+        let files = (layout.key.weight == TreemapWeight::Files).then_some(tr.files);
+        node_tooltip(
+            ui,
+            map_rect.id,
+            &tr.name,
+            tr.size,
+            files,
+            tr.percent,
+            tr.is_dir,
         );
 
-        // Handle click: single = select, double = navigate/open.
         // Only if no navigation button already set an action this frame.
         if action.is_none() {
-            if double_clicked {
-                if tr.is_dir {
-                    action = Some(TreemapAction::NavigateDir(tr.node_idx));
-                } else {
-                    let full_path = tree.full_path(tr.node_idx);
-                    action = Some(TreemapAction::OpenFile(full_path));
-                }
-            } else if clicked {
-                action = Some(TreemapAction::SelectNode(tr.node_idx));
-            }
+            action = node_click_action(ui, &map_rect, tree, tr.node_idx, tr.is_dir);
         }
     }
 
     // ── Draw selection highlight for the tree-view-selected node ───
     if let Some(sel) = selected_node {
-        for tr in &rects {
+        for tr in rects {
             if tr.node_idx == sel {
-                let sel_color = if is_light {
-                    Color32::from_rgb(0xff, 0xa0, 0x00)
-                } else {
-                    Color32::from_rgb(0xff, 0xd0, 0x40)
-                };
+                let sel_color = selection_color(is_light);
                 let sel_rect = if tr.is_dir {
                     tr.header_rect.unwrap_or(tr.rect)
                 } else {
@@ -523,12 +447,348 @@ pub fn treemap(ui: &mut Ui, state: &AppState) -> Option<TreemapAction> {
         }
     }
 
+    // ── Legend ─────────────────────────────────────────────────────
+    if state.treemap_legend {
+        draw_legend(&painter, bounds, &layout.legend, &layout.colors);
+    }
+
+    // ── Context menu ───────────────────────────────────────────────
+    map_context_menu(&map_rect, state, tree, &mut action);
+
+    action
+}
+
+// ── Shared with the sunburst ────────────────────────────────────────
+
+/// Navigation toolbar shared by the map views: back/forward/up, the
+/// breadcrumb, and the view, colour and weight pickers.
+pub(crate) fn map_toolbar(
+    ui: &mut Ui,
+    state: &AppState,
+    tree: &FileTree,
+    root_node: NodeIndex,
+) -> Option<TreemapAction> {
+    let root = &tree.nodes[root_node.idx()];
+    let mut action: Option<TreemapAction> = None;
+    let mut view = state.map_view;
+    let mut color_mode = state.treemap_color;
+    let mut weight = state.treemap_weight;
+    let mut show_legend = state.treemap_legend;
+    let mut cushions = state.treemap_cushions;
+
+    // ── Navigation toolbar ─────────────────────────────────────────
+    ui.horizontal(|ui| {
+        let can_back = !state.treemap_back.is_empty();
+        let can_forward = !state.treemap_forward.is_empty();
+        let can_up = root.parent.is_some();
+
+        if ui
+            .add_enabled(
+                can_back,
+                egui::Button::new("◀").min_size(Vec2::new(28.0, 22.0)),
+            )
+            .on_hover_text("Back")
+            .clicked()
+        {
+            action = Some(TreemapAction::Back);
+        }
+        if ui
+            .add_enabled(
+                can_forward,
+                egui::Button::new("▶").min_size(Vec2::new(28.0, 22.0)),
+            )
+            .on_hover_text("Forward")
+            .clicked()
+        {
+            action = Some(TreemapAction::Forward);
+        }
+        if ui
+            .add_enabled(
+                can_up,
+                egui::Button::new("▲").min_size(Vec2::new(28.0, 22.0)),
+            )
+            .on_hover_text("Up to parent")
+            .clicked()
+        {
+            action = Some(TreemapAction::Up);
+        }
+
+        ui.add_space(8.0);
+
+        // ── Breadcrumb ─────────────────────────────────────────────
+        let mut breadcrumb_chain: Vec<NodeIndex> = Vec::new();
+        let mut cursor = root_node;
+        loop {
+            breadcrumb_chain.push(cursor);
+            match tree.nodes[cursor.idx()].parent {
+                Some(p) => cursor = p,
+                None => break,
+            }
+        }
+        breadcrumb_chain.reverse();
+
+        for (i, &bc_node) in breadcrumb_chain.iter().enumerate() {
+            if i > 0 {
+                ui.label(
+                    egui::RichText::new(" › ")
+                        .color(ui.visuals().weak_text_color())
+                        .size(12.0),
+                );
+            }
+            let name = &tree.nodes[bc_node.idx()].name;
+            let is_current = bc_node == root_node;
+            let text = if is_current {
+                egui::RichText::new(name.as_str())
+                    .color(ui.visuals().text_color())
+                    .size(12.0)
+                    .strong()
+            } else {
+                egui::RichText::new(name.as_str())
+                    .color(ui.visuals().hyperlink_color)
+                    .size(12.0)
+            };
+            let resp = ui.add(egui::Label::new(text).sense(Sense::click()));
+            if resp.clicked() && !is_current {
+                action = Some(TreemapAction::NavigateDir(bc_node));
+            }
+            if resp.hovered() && !is_current {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.label(
+                egui::RichText::new(weight_text(weight, root.size, root.descendant_count))
+                    .color(ui.visuals().hyperlink_color)
+                    .size(12.0),
+            );
+            ui.checkbox(&mut show_legend, "Legend");
+            if view == MapView::Treemap {
+                ui.checkbox(&mut cushions, "Cushions")
+                    .on_hover_text("Shade each rectangle as a cushion so nesting reads as bumps");
+            }
+            egui::ComboBox::from_id_salt("treemap_color")
+                .width(80.0)
+                .selected_text(color_mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in TreemapColorMode::ALL {
+                        ui.selectable_value(&mut color_mode, mode, mode.label());
+                    }
+                })
+                .response
+                .on_hover_text("What the colours show");
+            egui::ComboBox::from_id_salt("treemap_weight")
+                .width(80.0)
+                .selected_text(weight.label())
+                .show_ui(ui, |ui| {
+                    for w in TreemapWeight::ALL {
+                        ui.selectable_value(&mut weight, w, w.label());
+                    }
+                })
+                .response
+                .on_hover_text("What the areas show");
+            ui.separator();
+            ui.selectable_value(&mut view, MapView::Sunburst, "◎")
+                .on_hover_text("Sunburst");
+            ui.selectable_value(&mut view, MapView::Treemap, "▦")
+                .on_hover_text("Treemap");
+        });
+    });
+
+    if view != state.map_view {
+        action = Some(TreemapAction::SetView(view));
+    }
+    if color_mode != state.treemap_color {
+        action = Some(TreemapAction::SetColorMode(color_mode));
+    }
+    if weight != state.treemap_weight {
+        action = Some(TreemapAction::SetWeight(weight));
+    }
+    if show_legend != state.treemap_legend {
+        action = Some(TreemapAction::SetLegend(show_legend));
+    }
+    if cushions != state.treemap_cushions {
+        action = Some(TreemapAction::SetCushions(cushions));
+    }
+
+    ui.add_space(2.0);
     action
 }
 
+/// Hover tooltip for a node: name, size, share of its parent and kind.
+/// `files` is given when the map is weighted by file count, and shown for
+/// directories.
+pub(crate) fn node_tooltip(
+    ui: &Ui,
+    id: egui::Id,
+    name: &str,
+    size: u64,
+    files: Option<u64>,
+    percent: f32,
+    is_dir: bool,
+) {
+    let (label_color, label_color_dim) = label_colors(!ui.visuals().dark_mode);
+    egui::show_tooltip_at_pointer(
+        ui.ctx(),
+        egui::LayerId::new(egui::Order::Tooltip, id),
+        id.with("map_tip"),
+        |ui| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+            ui.label(
+                egui::RichText::new(name)
+                    .strong()
+                    .size(12.0)
+                    .color(label_color),
+            );
+            let kind = if is_dir { "Directory" } else { "File" };
+            let amount = match files {
+                Some(n) if is_dir => format!("{} files, {}", format_count(n), format_size(size)),
+                _ => format_size(size),
+            };
+            ui.label(
+                egui::RichText::new(format!("{amount} — {percent:.1}%  ({kind})"))
+                    .color(label_color_dim),
+            );
+        },
+    );
+}
+
+/// Pointer action on the hovered node: double-click drills into a directory
+/// or opens a file, a click selects, a right-click opens the context menu.
+pub(crate) fn node_click_action(
+    ui: &Ui,
+    response: &egui::Response,
+    tree: &FileTree,
+    node_idx: NodeIndex,
+    is_dir: bool,
+) -> Option<TreemapAction> {
+    let (clicked, double_clicked) = ui.input(|i| {
+        (
+            i.pointer.button_clicked(egui::PointerButton::Primary),
+            i.pointer
+                .button_double_clicked(egui::PointerButton::Primary),
+        )
+    });
+    if double_clicked {
+        if is_dir {
+            Some(TreemapAction::NavigateDir(node_idx))
+        } else {
+            Some(TreemapAction::OpenFile(tree.full_path(node_idx)))
+        }
+    } else if clicked {
+        Some(TreemapAction::SelectNode(node_idx))
+    } else if response.secondary_clicked() {
+        Some(TreemapAction::ContextMenu(node_idx))
+    } else {
+        None
+    }
+}
+
+/// Attach the node context menu to a map view.
+///
+/// The menu stays open across frames while the pointer moves, so the node
+/// comes from state after the frame it was opened in.
+pub(crate) fn map_context_menu(
+    response: &egui::Response,
+    state: &AppState,
+    tree: &FileTree,
+    action: &mut Option<TreemapAction>,
+) {
+    let right_clicked = match action {
+        Some(TreemapAction::ContextMenu(node)) => Some(*node),
+        _ => None,
+    };
+    let menu_node = right_clicked.or(state.context_menu_node);
+    response.context_menu(|ui| {
+        if let Some(node) = menu_node {
+            let flags = NodeMenuFlags::for_node(state, node);
+            if let Some(a) = node_menu(ui, tree, node, flags) {
+                *action = Some(TreemapAction::Menu(a));
+            }
+        }
+    });
+}
+
+/// Label colours (primary, dimmed) for the theme.
+pub(crate) fn label_colors(is_light: bool) -> (Color32, Color32) {
+    if is_light {
+        (
+            Color32::from_rgb(0x10, 0x10, 0x10),
+            Color32::from_rgb(0x30, 0x30, 0x30),
+        )
+    } else {
+        (Color32::WHITE, Color32::from_rgb(0xd0, 0xd0, 0xd0))
+    }
+}
+
+/// Outline of the hovered item.
+pub(crate) fn hover_color(is_light: bool) -> Color32 {
+    if is_light {
+        Color32::from_rgb(0x00, 0x60, 0xff)
+    } else {
+        Color32::from_rgb(0x80, 0xc0, 0xff)
+    }
+}
+
+/// Outline of the item selected in the tree view.
+pub(crate) fn selection_color(is_light: bool) -> Color32 {
+    if is_light {
+        Color32::from_rgb(0xff, 0xa0, 0x00)
+    } else {
+        Color32::from_rgb(0xff, 0xd0, 0x40)
+    }
+}
+
+/// Background behind the map views.
+pub(crate) fn map_background(is_light: bool) -> Color32 {
+    if is_light {
+        Color32::from_rgb(0xe0, 0xe0, 0xe4)
+    } else {
+        Color32::from_rgb(0x20, 0x20, 0x24)
+    }
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────
 
-fn truncate_name(name: &str, max_chars: usize) -> Cow<'_, str> {
+/// A node's share of the map under `weight`: its bytes, or the files it
+/// holds (a file counts as one).
+pub(crate) fn node_weight(node: &FileNode, weight: TreemapWeight) -> u64 {
+    match weight {
+        TreemapWeight::Bytes => node.size,
+        TreemapWeight::Files if node.is_dir => node.descendant_count,
+        TreemapWeight::Files => 1,
+    }
+}
+
+/// `parent`'s children in layout order: directories first, then heaviest
+/// under `weight` first.
+pub(crate) fn children_by_weight(
+    tree: &FileTree,
+    parent: NodeIndex,
+    weight: TreemapWeight,
+) -> Vec<NodeIndex> {
+    let mut children = tree.children_sorted_by_size(parent);
+    if weight != TreemapWeight::Bytes {
+        children.sort_by(|a, b| {
+            let (a, b) = (tree.node(*a), tree.node(*b));
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then(node_weight(b, weight).cmp(&node_weight(a, weight)))
+        });
+    }
+    children
+}
+
+/// Header text for a node: its size, or its file count when the map is
+/// weighted by files.
+pub(crate) fn weight_text(weight: TreemapWeight, size: u64, files: u64) -> String {
+    match weight {
+        TreemapWeight::Bytes => format_size(size),
+        TreemapWeight::Files => format!("{} files", format_count(files)),
+    }
+}
+
+pub(crate) fn truncate_name(name: &str, max_chars: usize) -> Cow<'_, str> {
     // Use char count, not byte length: slicing by bytes panics on multi-byte
     // UTF-8 filenames (Cyrillic, CJK, emoji, accented latin, etc.).
     let char_count = name.chars().count();
@@ -552,6 +812,7 @@ fn truncate_name(name: &str, max_chars: usize) -> Cow<'_, str> {
 
 /// Recursively lay out children, producing nested rectangles.
 /// Directories get a header bar and their children are laid out inside.
+/// `cushion` is the parent's surface; each child adds its own ridge to it.
 #[allow(clippy::too_many_arguments)]
 fn layout_nested(
     tree: &FileTree,
@@ -559,8 +820,10 @@ fn layout_nested(
     parent_size: u64,
     bounds: Rect,
     depth: usize,
-    base_color_idx: usize,
-    is_light: bool,
+    cushion: Cushion,
+    colors: &ColorContext,
+    detected: &DetectedTypes,
+    weight: TreemapWeight,
     rects: &mut Vec<TreemapRect>,
 ) {
     if children.is_empty() || parent_size == 0 {
@@ -571,7 +834,7 @@ fn layout_nested(
     let items: Vec<(NodeIndex, f32)> = children
         .iter()
         .filter_map(|&idx| {
-            let size = tree.nodes[idx.idx()].size;
+            let size = node_weight(&tree.nodes[idx.idx()], weight);
             if size == 0 {
                 return None;
             }
@@ -594,8 +857,10 @@ fn layout_nested(
         tree,
         parent_size,
         depth,
-        base_color_idx,
-        is_light,
+        cushion,
+        colors,
+        detected,
+        weight,
         rects,
     );
 }
@@ -608,8 +873,10 @@ fn squarify_nested(
     tree: &FileTree,
     parent_size: u64,
     depth: usize,
-    base_color_idx: usize,
-    is_light: bool,
+    parent_cushion: Cushion,
+    colors: &ColorContext,
+    detected: &DetectedTypes,
+    weight: TreemapWeight,
     rects: &mut Vec<TreemapRect>,
 ) {
     if items.is_empty() || bounds.width() < 2.0 || bounds.height() < 2.0 {
@@ -720,28 +987,16 @@ fn squarify_nested(
             };
 
             let child = &tree.nodes[node_idx.idx()];
-            let ci = (base_color_idx + ri) % PALETTE.len();
-            let (cr, cg, cb) = PALETTE[ci];
-            let base_color = if is_light {
-                Color32::from_rgb(
-                    (cr as f32 * 0.75) as u8,
-                    (cg as f32 * 0.75) as u8,
-                    (cb as f32 * 0.75) as u8,
-                )
-            } else {
-                Color32::from_rgb(cr, cg, cb)
-            };
+            let cushion = parent_cushion.with_ridge(
+                item_rect,
+                CUSHION_HEIGHT * CUSHION_FALLOFF.powi(depth as i32),
+            );
 
-            // Darken/lighten by depth for visual distinction.
-            let depth_factor = 0.06 * depth as f32;
-            let color = if child.is_dir {
-                darken(base_color, depth_factor)
-            } else {
-                darken(base_color, depth_factor + 0.10)
-            };
+            let color = fill_color(tree, node_idx, depth, colors, detected);
 
+            let child_weight = node_weight(child, weight);
             let pct = if parent_size > 0 {
-                child.size as f64 / parent_size as f64 * 100.0
+                child_weight as f64 / parent_size as f64 * 100.0
             } else {
                 0.0
             };
@@ -766,10 +1021,12 @@ fn squarify_nested(
                     color,
                     name: child.name.clone(),
                     size: child.size,
+                    files: child.descendant_count,
                     percent: pct as f32,
                     is_dir: true,
                     depth,
                     header_rect,
+                    cushion,
                 });
 
                 // Recurse into children if there's room and we aren't too deep.
@@ -783,16 +1040,18 @@ fn squarify_nested(
                     );
 
                     if content_rect.width() > 4.0 && content_rect.height() > 4.0 {
-                        let sub_children = tree.children_sorted_by_size(node_idx);
-                        if !sub_children.is_empty() && child.size > 0 {
+                        let sub_children = children_by_weight(tree, node_idx, weight);
+                        if !sub_children.is_empty() && child_weight > 0 {
                             layout_nested(
                                 tree,
                                 &sub_children,
-                                child.size,
+                                child_weight,
                                 content_rect,
                                 depth + 1,
-                                ci,
-                                is_light,
+                                cushion,
+                                colors,
+                                detected,
+                                weight,
                                 rects,
                             );
                         }
@@ -806,10 +1065,12 @@ fn squarify_nested(
                     color,
                     name: child.name.clone(),
                     size: child.size,
+                    files: 0,
                     percent: pct as f32,
                     is_dir: false,
                     depth,
                     header_rect: None,
+                    cushion,
                 });
             }
         }
@@ -839,6 +1100,662 @@ fn worst_ratio(areas: &[f32], side: f32, total: f32) -> f32 {
     worst
 }
 
+// ── Layout cache ────────────────────────────────────────────────────
+
+/// Everything a layout depends on; any change invalidates it.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct LayoutKey {
+    /// [`FileTree::generation`] the layout was computed from.
+    pub(crate) generation: u64,
+    pub(crate) root: NodeIndex,
+    pub(crate) bounds: Rect,
+    pub(crate) mode: TreemapColorMode,
+    pub(crate) is_light: bool,
+    pub(crate) highlight: Option<FileCategory>,
+    pub(crate) weight: TreemapWeight,
+    /// [`AppState::signature_generation`] the file types were taken from.
+    pub(crate) detected: u64,
+}
+
+impl LayoutKey {
+    /// `true` if only the tree generation differs.
+    fn same_view(&self, other: &LayoutKey) -> bool {
+        self.root == other.root
+            && self.bounds == other.bounds
+            && self.mode == other.mode
+            && self.is_light == other.is_light
+            && self.highlight == other.highlight
+            && self.weight == other.weight
+            && self.detected == other.detected
+    }
+}
+
+/// A computed treemap: rectangles in draw order plus everything derived
+/// from them.
+struct TreemapLayout {
+    key: LayoutKey,
+    /// The scan's live tree, if the layout came from it.  Only compared by
+    /// pointer; a `Weak` so the cache never stops the final tree from being
+    /// unwrapped when the scan completes.
+    source: Option<Weak<RwLock<FileTree>>>,
+    /// Nodes in the tree when the layout was computed.  The live tree only
+    /// grows, so while it is at least this long every index stays valid.
+    node_count: usize,
+    colors: ColorContext,
+    /// Sorted by depth ascending so deeper items are drawn (and hit-tested)
+    /// on top.
+    rects: Vec<TreemapRect>,
+    grid: HitGrid,
+    legend: Vec<LegendEntry>,
+    /// Cushion mesh, built the first time cushions are drawn.
+    mesh: Option<Arc<egui::Mesh>>,
+}
+
+impl TreemapLayout {
+    fn compute(
+        tree: &FileTree,
+        key: LayoutKey,
+        source: Option<Weak<RwLock<FileTree>>>,
+        detected: &DetectedTypes,
+    ) -> Self {
+        let colors = ColorContext {
+            mode: key.mode,
+            is_light: key.is_light,
+            now: SystemTime::now(),
+            highlight: key.highlight,
+        };
+        let mut rects: Vec<TreemapRect> = Vec::with_capacity(512);
+        if let Some(root) = tree.nodes.get(key.root.idx()) {
+            let children = children_by_weight(tree, key.root, key.weight);
+            layout_nested(
+                tree,
+                &children,
+                node_weight(root, key.weight),
+                key.bounds,
+                0,
+                Cushion::default(),
+                &colors,
+                detected,
+                key.weight,
+                &mut rects,
+            );
+        }
+        rects.sort_by_key(|r| r.depth);
+        let grid = HitGrid::new(key.bounds, &rects);
+        let legend = legend_entries(
+            tree,
+            rects.iter().map(|r| (r.node_idx, r.depth, r.rect.area())),
+            &colors,
+            detected,
+        );
+        Self {
+            key,
+            source,
+            node_count: tree.len(),
+            colors,
+            rects,
+            grid,
+            legend,
+            mesh: None,
+        }
+    }
+
+    /// Index of the rectangle under `pos`: the deepest file or directory
+    /// header, or failing that the deepest rectangle of any kind.
+    fn hit(&self, pos: egui::Pos2) -> Option<usize> {
+        let candidates = self.grid.candidates(pos);
+        let deepest = |hit: &dyn Fn(&TreemapRect) -> bool| {
+            candidates
+                .iter()
+                .rev()
+                .map(|&i| i as usize)
+                .find(|&i| hit(&self.rects[i]))
+        };
+        // Directories are primarily targeted by their header bar, unless
+        // no children are rendered inside them.
+        deepest(&|tr| tr.header_rect.unwrap_or(tr.rect).contains(pos))
+            .or_else(|| deepest(&|tr| tr.rect.contains(pos)))
+    }
+
+    /// The cushion mesh for every rectangle, in draw order.
+    fn cushion_mesh(&mut self) -> Arc<egui::Mesh> {
+        let is_light = self.key.is_light;
+        let rects = &self.rects;
+        self.mesh
+            .get_or_insert_with(|| {
+                let mut mesh = egui::Mesh::default();
+                for tr in rects {
+                    let fill = if tr.is_dir {
+                        frame_fill(tr.color, is_light)
+                    } else {
+                        tr.color
+                    };
+                    add_cushion(&mut mesh, tr.rect, &tr.cushion, fill);
+                }
+                Arc::new(mesh)
+            })
+            .clone()
+    }
+}
+
+/// Treemap layout kept across frames.
+///
+/// Owned by the app and passed to [`treemap`] every frame.  Squarifying up
+/// to [`MAX_TREEMAP_RECTS`] rectangles is only redone when the layout's key
+/// changes.  While a scan grows the live tree, the previous layout stays on
+/// screen and its replacement is computed on a background thread.
+#[derive(Default)]
+pub struct TreemapCache {
+    layout: Option<TreemapLayout>,
+    /// Background layout of a newer live-tree generation.
+    pending: Option<Receiver<TreemapLayout>>,
+}
+
+impl TreemapCache {
+    /// The layout for `key`, recomputed or refreshed as needed.
+    fn layout(
+        &mut self,
+        ctx: &egui::Context,
+        state: &AppState,
+        tree: &FileTree,
+        key: LayoutKey,
+    ) -> &mut TreemapLayout {
+        if let Some(rx) = &self.pending {
+            match rx.try_recv() {
+                Ok(layout) => {
+                    self.layout = Some(layout);
+                    self.pending = None;
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {}
+                Err(crossbeam_channel::TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+
+        let live = match state.tree {
+            Some(_) => None,
+            None => state.live_tree.as_ref(),
+        };
+        let cached = self.layout.as_ref();
+        if cached.is_none_or(|l| l.key != key) {
+            // Only the tree grew: keep showing the old layout meanwhile.
+            let refresh = live.filter(|lt| {
+                cached.is_some_and(|l| {
+                    l.key.same_view(&key)
+                        && tree.len() >= l.node_count
+                        && l.source
+                            .as_ref()
+                            .is_some_and(|s| s.as_ptr() == Arc::as_ptr(lt))
+                })
+            });
+            if let Some(lt) = refresh {
+                if self.pending.is_none() {
+                    self.pending = Some(spawn_layout(lt, key));
+                }
+                ctx.request_repaint();
+            } else {
+                self.pending = None;
+                self.layout = Some(TreemapLayout::compute(
+                    tree,
+                    key,
+                    live.map(Arc::downgrade),
+                    state.detected_types(),
+                ));
+            }
+        }
+        self.layout.as_mut().expect("layout computed above")
+    }
+}
+
+/// Compute the layout of `live`'s newest generation on a background thread.
+fn spawn_layout(live: &LiveTree, key: LayoutKey) -> Receiver<TreemapLayout> {
+    let (tx, rx) = crossbeam_channel::bounded(1);
+    let source = Arc::downgrade(live);
+    let spawned = thread::Builder::new()
+        .name("disksleuth-treemap".into())
+        .spawn(move || {
+            // The scan may have finished (and taken the tree) meanwhile.
+            let Some(live) = source.upgrade() else {
+                return;
+            };
+            let tree = live.read();
+            let key = LayoutKey {
+                generation: tree.generation,
+                ..key
+            };
+            // Content detection only runs once the scan has finished.
+            let layout = TreemapLayout::compute(&tree, key, Some(source), &DetectedTypes::new());
+            drop(tree);
+            let _ = tx.send(layout);
+        });
+    if let Err(e) = spawned {
+        tracing::warn!("Could not start treemap layout thread: {}", e);
+    }
+    rx
+}
+
+/// Uniform grid over the treemap for hover hit-testing.  Each cell lists
+/// the rectangles overlapping it in draw order, so a lookup only checks a
+/// handful of rectangles instead of all of them.
+struct HitGrid {
+    origin: egui::Pos2,
+    cols: usize,
+    rows: usize,
+    /// Cell `c` owns `entries[starts[c]..starts[c + 1]]`.
+    starts: Vec<u32>,
+    entries: Vec<u32>,
+}
+
+impl HitGrid {
+    fn new(bounds: Rect, rects: &[TreemapRect]) -> Self {
+        let cols = ((bounds.width() / HIT_CELL).ceil() as usize).max(1);
+        let rows = ((bounds.height() / HIT_CELL).ceil() as usize).max(1);
+        let mut grid = Self {
+            origin: bounds.min,
+            cols,
+            rows,
+            starts: vec![0; cols * rows + 1],
+            entries: Vec::new(),
+        };
+
+        // Count per cell, prefix-sum into offsets, then fill.
+        for tr in rects {
+            let (xs, ys) = grid.span(tr.rect);
+            for y in ys {
+                for x in xs.clone() {
+                    grid.starts[y * cols + x + 1] += 1;
+                }
+            }
+        }
+        for c in 1..grid.starts.len() {
+            grid.starts[c] += grid.starts[c - 1];
+        }
+        let mut next: Vec<u32> = grid.starts[..cols * rows].to_vec();
+        grid.entries = vec![0; grid.starts[cols * rows] as usize];
+        for (i, tr) in rects.iter().enumerate() {
+            let (xs, ys) = grid.span(tr.rect);
+            for y in ys {
+                for x in xs.clone() {
+                    let slot = &mut next[y * cols + x];
+                    grid.entries[*slot as usize] = i as u32;
+                    *slot += 1;
+                }
+            }
+        }
+        grid
+    }
+
+    /// Column and row ranges of the cells `rect` overlaps.
+    fn span(&self, rect: Rect) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+        let cell = |v: f32, origin: f32, count: usize| {
+            (((v - origin) / HIT_CELL).floor().max(0.0) as usize).min(count - 1)
+        };
+        (
+            cell(rect.left(), self.origin.x, self.cols)
+                ..=cell(rect.right(), self.origin.x, self.cols),
+            cell(rect.top(), self.origin.y, self.rows)
+                ..=cell(rect.bottom(), self.origin.y, self.rows),
+        )
+    }
+
+    /// Rectangles (indices, in draw order) that may contain `pos`.
+    fn candidates(&self, pos: egui::Pos2) -> &[u32] {
+        let x = ((pos.x - self.origin.x) / HIT_CELL).floor();
+        let y = ((pos.y - self.origin.y) / HIT_CELL).floor();
+        if x < 0.0 || y < 0.0 || x as usize >= self.cols || y as usize >= self.rows {
+            return &[];
+        }
+        let c = y as usize * self.cols + x as usize;
+        &self.entries[self.starts[c] as usize..self.starts[c + 1] as usize]
+    }
+}
+
+// ── Cushion shading ─────────────────────────────────────────────────
+
+/// A van Wijk cushion: the parabolic surface
+/// `z = s2x·x² + s1x·x + s2y·y² + s1y·y`, built up from one ridge per
+/// nesting level so a rectangle's shading also shows its ancestors.
+#[derive(Clone, Copy, Default)]
+struct Cushion {
+    s1x: f32,
+    s2x: f32,
+    s1y: f32,
+    s2y: f32,
+}
+
+impl Cushion {
+    /// Add a ridge of height `h` spanning `rect` in both directions.
+    fn with_ridge(self, rect: Rect, h: f32) -> Self {
+        let (x1, x2, y1, y2) = (rect.left(), rect.right(), rect.top(), rect.bottom());
+        let mut c = self;
+        if x2 > x1 {
+            c.s1x += 4.0 * h * (x2 + x1) / (x2 - x1);
+            c.s2x -= 4.0 * h / (x2 - x1);
+        }
+        if y2 > y1 {
+            c.s1y += 4.0 * h * (y2 + y1) / (y2 - y1);
+            c.s2y -= 4.0 * h / (y2 - y1);
+        }
+        c
+    }
+
+    /// Light intensity at `(x, y)`, lit from the top left.
+    fn intensity(&self, x: f32, y: f32) -> f32 {
+        // Normalised (-1, -2, 10): mostly overhead, slightly up and left.
+        const L: (f32, f32, f32) = (-0.097_590, -0.195_180, 0.975_900);
+        let nx = -(2.0 * self.s2x * x + self.s1x);
+        let ny = -(2.0 * self.s2y * y + self.s1y);
+        let cos = (nx * L.0 + ny * L.1 + L.2) / (nx * nx + ny * ny + 1.0).sqrt();
+        CUSHION_AMBIENT + CUSHION_DIFFUSE * cos.max(0.0)
+    }
+}
+
+/// Append `rect` to `mesh` as a grid of cells, each corner coloured by the
+/// cushion's light intensity.  Cells are about [`CUSHION_CELL`] pixels so
+/// the interpolated shading stays smooth at any size.
+fn add_cushion(mesh: &mut egui::Mesh, rect: Rect, cushion: &Cushion, fill: Color32) {
+    let cells = |len: f32| ((len / CUSHION_CELL).ceil() as usize).clamp(1, CUSHION_MAX_CELLS);
+    let (nx, ny) = (cells(rect.width()), cells(rect.height()));
+    let base = mesh.vertices.len() as u32;
+
+    for j in 0..=ny {
+        let y = rect.top() + rect.height() * j as f32 / ny as f32;
+        for i in 0..=nx {
+            let x = rect.left() + rect.width() * i as f32 / nx as f32;
+            let k = cushion.intensity(x, y);
+            let shade = |c: u8| (c as f32 * k).min(255.0) as u8;
+            let color = Color32::from_rgb(shade(fill.r()), shade(fill.g()), shade(fill.b()));
+            mesh.colored_vertex(egui::pos2(x, y), color);
+        }
+    }
+
+    let stride = nx as u32 + 1;
+    for j in 0..ny as u32 {
+        for i in 0..nx as u32 {
+            let tl = base + j * stride + i;
+            let bl = tl + stride;
+            mesh.add_triangle(tl, tl + 1, bl);
+            mesh.add_triangle(tl + 1, bl + 1, bl);
+        }
+    }
+}
+
+/// Background of a directory frame (visible around and between children).
+fn frame_fill(fill: Color32, is_light: bool) -> Color32 {
+    if is_light {
+        darken(fill, 0.08)
+    } else {
+        darken(fill, 0.35)
+    }
+}
+
+// ── Legend ──────────────────────────────────────────────────────────
+
+/// One legend row: a swatch and its label.
+pub(crate) type LegendEntry = (Color32, String);
+
+/// Visible area and colour accumulated per categorical legend key.
+type LegendArea = (f32, (u8, u8, u8));
+
+/// Draw the legend for the active colour mode in the bottom-right corner.
+///
+/// Categorical modes list what is actually on screen, largest area first,
+/// so the legend always explains the colours being looked at.
+pub(crate) fn draw_legend(
+    painter: &egui::Painter,
+    bounds: Rect,
+    entries: &[LegendEntry],
+    colors: &ColorContext,
+) {
+    let is_age = colors.mode == TreemapColorMode::Age;
+    if entries.is_empty() && !is_age {
+        return;
+    }
+
+    let font = egui::FontId::proportional(11.0);
+    let (text, bg) = if colors.is_light {
+        (
+            Color32::from_rgb(0x20, 0x20, 0x20),
+            Color32::from_rgba_unmultiplied(0xf4, 0xf4, 0xf6, 230),
+        )
+    } else {
+        (
+            Color32::from_rgb(0xe0, 0xe0, 0xe0),
+            Color32::from_rgba_unmultiplied(0x18, 0x18, 0x1c, 230),
+        )
+    };
+
+    let rows = if is_age { 2 } else { entries.len() };
+    let size = Vec2::new(170.0, rows as f32 * LEGEND_ROW_H + 10.0);
+    let frame = Rect::from_min_size(bounds.right_bottom() - size - Vec2::splat(8.0), size);
+    if !bounds.contains_rect(frame) {
+        return;
+    }
+    painter.rect_filled(frame, 4.0, bg);
+    let inner = frame.shrink(5.0);
+
+    if is_age {
+        // Gradient bar with tick labels, on the same log scale as the fill.
+        let bar = Rect::from_min_size(inner.min, Vec2::new(inner.width(), 8.0));
+        let steps = 32;
+        let step_w = bar.width() / steps as f32;
+        for i in 0..steps {
+            let t = (i as f32 + 0.5) / steps as f32;
+            let days = (1.0 + AGE_COLOR_MAX_DAYS).powf(t) - 1.0;
+            let newest = colors
+                .now
+                .checked_sub(std::time::Duration::from_secs_f32(days * 86_400.0));
+            let seg = Rect::from_min_size(
+                bar.min + Vec2::new(i as f32 * step_w, 0.0),
+                Vec2::new(step_w + 0.5, bar.height()),
+            );
+            painter.rect_filled(seg, 0.0, dim(age_color(newest, colors.now), colors));
+        }
+        for (days, label) in [
+            (0.0, "today"),
+            (30.0, "1 mo"),
+            (365.0, "1 y"),
+            (1_095.0, "3 y+"),
+        ] {
+            let t = (1.0 + days as f32).ln() / (1.0 + AGE_COLOR_MAX_DAYS).ln();
+            let x = bar.left() + bar.width() * t;
+            let align = match label {
+                "today" => egui::Align2::LEFT_TOP,
+                "3 y+" => egui::Align2::RIGHT_TOP,
+                _ => egui::Align2::CENTER_TOP,
+            };
+            painter.text(
+                egui::pos2(x, bar.bottom() + 2.0),
+                align,
+                label,
+                font.clone(),
+                text,
+            );
+        }
+        return;
+    }
+
+    for (i, (color, label)) in entries.iter().enumerate() {
+        let y = inner.top() + i as f32 * LEGEND_ROW_H;
+        let swatch = Rect::from_min_size(egui::pos2(inner.left(), y + 2.0), Vec2::splat(10.0));
+        painter.rect_filled(swatch, 2.0, *color);
+        painter.text(
+            egui::pos2(swatch.right() + 6.0, y + LEGEND_ROW_H / 2.0),
+            egui::Align2::LEFT_CENTER,
+            label,
+            font.clone(),
+            text,
+        );
+    }
+}
+
+/// Swatches for the categorical colour modes (none for age).
+///
+/// `items` yields every drawn node with its depth and on-screen area.
+pub(crate) fn legend_entries(
+    tree: &FileTree,
+    items: impl Iterator<Item = (NodeIndex, usize, f32)>,
+    colors: &ColorContext,
+    detected: &DetectedTypes,
+) -> Vec<LegendEntry> {
+    match colors.mode {
+        TreemapColorMode::Age => Vec::new(),
+        TreemapColorMode::Depth => {
+            let deepest = items.map(|(_, depth, _)| depth).max().unwrap_or(0);
+            (0..=deepest.min(MAX_LEGEND_ENTRIES - 1))
+                .map(|d| {
+                    let color = dim(PALETTE[d % PALETTE.len()], colors);
+                    (color, format!("Level {}", d + 1))
+                })
+                .collect()
+        }
+        TreemapColorMode::FileType | TreemapColorMode::Extension => {
+            // Visible file area per key; directories are neutral frames.
+            let mut area: HashMap<String, LegendArea> = HashMap::new();
+            for (node_idx, _, node_area) in items {
+                let node = tree.node(node_idx);
+                if node.is_dir {
+                    continue;
+                }
+                let name = &node.name;
+                let (key, color) = if colors.mode == TreemapColorMode::FileType {
+                    let cat = node_category(tree, node_idx, detected);
+                    (cat.label().to_string(), rgb(category_color(cat)))
+                } else {
+                    let ext = file_extension(name);
+                    let key = if ext.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        format!(".{}", ext.to_ascii_lowercase())
+                    };
+                    (key, extension_color(ext))
+                };
+                area.entry(key).or_insert((0.0, color)).0 += node_area;
+            }
+            let mut entries: Vec<(String, LegendArea)> = area.into_iter().collect();
+            entries.sort_by(|a, b| b.1 .0.total_cmp(&a.1 .0));
+            entries.truncate(MAX_LEGEND_ENTRIES);
+            entries
+                .into_iter()
+                .map(|(label, (_, color))| (dim(color, colors), label))
+                .collect()
+        }
+    }
+}
+
+/// Apply the light-theme dimming used for rectangle fills.
+fn dim((r, g, b): (u8, u8, u8), colors: &ColorContext) -> Color32 {
+    if colors.is_light {
+        Color32::from_rgb(
+            (r as f32 * 0.75) as u8,
+            (g as f32 * 0.75) as u8,
+            (b as f32 * 0.75) as u8,
+        )
+    } else {
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+/// Fill of a node at `depth`: its base colour, darkened a little per level
+/// (and a little more for files) for visual distinction.  `detected` content
+/// types take precedence over extensions, as in the type stats.
+pub(crate) fn fill_color(
+    tree: &FileTree,
+    index: NodeIndex,
+    depth: usize,
+    colors: &ColorContext,
+    detected: &DetectedTypes,
+) -> Color32 {
+    let node = tree.node(index);
+    let base = dim(node_color(tree, index, depth, colors, detected), colors);
+    let depth_factor = 0.06 * depth as f32;
+    if node.is_dir {
+        darken(base, depth_factor)
+    } else if colors
+        .highlight
+        .is_some_and(|cat| node_category(tree, index, detected) != cat)
+    {
+        washed_out(darken(base, depth_factor + 0.10), colors.is_light)
+    } else {
+        darken(base, depth_factor + 0.10)
+    }
+}
+
+/// `color` mixed three parts to one with a neutral grey near the
+/// background, so highlighted files stand out against everything else.
+fn washed_out(color: Color32, is_light: bool) -> Color32 {
+    let grey: u16 = if is_light { 225 } else { 45 };
+    let mix = |c: u8| ((c as u16 + 3 * grey) / 4) as u8;
+    Color32::from_rgb(mix(color.r()), mix(color.g()), mix(color.b()))
+}
+
+/// Base colour of a node under the active colour mode.
+fn node_color(
+    tree: &FileTree,
+    index: NodeIndex,
+    depth: usize,
+    colors: &ColorContext,
+    detected: &DetectedTypes,
+) -> (u8, u8, u8) {
+    let node = tree.node(index);
+    match colors.mode {
+        TreemapColorMode::FileType if node.is_dir => NEUTRAL_DIR,
+        TreemapColorMode::FileType => rgb(category_color(node_category(tree, index, detected))),
+        TreemapColorMode::Age => age_color(node.newest_day.to_time(), colors.now),
+        TreemapColorMode::Depth => PALETTE[depth % PALETTE.len()],
+        TreemapColorMode::Extension if node.is_dir => NEUTRAL_DIR,
+        TreemapColorMode::Extension => extension_color(file_extension(&node.name)),
+    }
+}
+
+/// A stable, evenly spread colour for an extension (case-insensitive).
+///
+/// FNV-1a over the lowercased bytes picks the hue; saturation and value are
+/// fixed so every extension reads at the same strength.
+fn extension_color(ext: &str) -> (u8, u8, u8) {
+    if ext.is_empty() {
+        return NEUTRAL_DIR;
+    }
+    let mut hash: u32 = 0x811c_9dc5;
+    for b in ext.bytes() {
+        hash ^= b.to_ascii_lowercase() as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    let hue = (hash % 360) as f32 / 360.0;
+    rgb(egui::ecolor::Hsva::new(hue, 0.45, 0.95, 1.0).into())
+}
+
+/// Split a colour into its RGB channels.
+fn rgb(c: Color32) -> (u8, u8, u8) {
+    (c.r(), c.g(), c.b())
+}
+
+/// Colour for a node whose newest file changed at `newest`: green when
+/// recent, through yellow, to red at [`AGE_COLOR_MAX_DAYS`]; grey if unknown.
+///
+/// The scale is logarithmic so the first weeks and months stay distinct.
+fn age_color(newest: Option<SystemTime>, now: SystemTime) -> (u8, u8, u8) {
+    const RECENT: (u8, u8, u8) = (0xa6, 0xe3, 0xa1);
+    const MIDDLE: (u8, u8, u8) = (0xf9, 0xe2, 0xaf);
+    const OLD: (u8, u8, u8) = (0xf3, 0x8b, 0xa8);
+
+    let Some(newest) = newest else {
+        return (0x6c, 0x70, 0x86);
+    };
+    let days = now
+        .duration_since(newest)
+        .map_or(0.0, |d| d.as_secs_f32() / 86_400.0);
+    let t = ((1.0 + days).ln() / (1.0 + AGE_COLOR_MAX_DAYS).ln()).clamp(0.0, 1.0);
+    let mix = |a: (u8, u8, u8), b: (u8, u8, u8), t: f32| {
+        let ch = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t) as u8;
+        (ch(a.0, b.0), ch(a.1, b.1), ch(a.2, b.2))
+    };
+    if t < 0.5 {
+        mix(RECENT, MIDDLE, t * 2.0)
+    } else {
+        mix(MIDDLE, OLD, (t - 0.5) * 2.0)
+    }
+}
+
 /// Lighten a colour by blending towards white.
 #[allow(dead_code)]
 fn lighten(c: Color32, amount: f32) -> Color32 {
@@ -851,7 +1768,7 @@ fn lighten(c: Color32, amount: f32) -> Color32 {
 }
 
 /// Darken a colour by scaling towards black.
-fn darken(c: Color32, amount: f32) -> Color32 {
+pub(crate) fn darken(c: Color32, amount: f32) -> Color32 {
     let f = 1.0 - amount.clamp(0.0, 1.0);
     Color32::from_rgb(
         (c.r() as f32 * f) as u8,
@@ -7,11 +7,37 @@
 ///   - Scan lifecycle (start, progress messages, completion, cancellation)
 ///   - Treemap navigation (forward/back/up, history bounds)
 ///   - Tree-view expansion and `MAX_VISIBLE_ROWS` cap
+///   - Tree-view column sorting (order, direction, expansion preserved)
+///   - File-type category filter, donut chart panel and per-extension table
+///   - Keyboard navigation (row movement, expand/collapse, drill-in, type-ahead)
 ///   - Monitor start/stop
 ///   - Error accumulation and `MAX_SCAN_ERRORS` cap
+///   - Name search and reveal-in-tree
+///   - Trash/delete confirmation, on-disk removal and tree re-aggregation
+///   - Cleanup list totals, batch archive, and script export
+///   - Stale-file analysis, multi-select, and hand-off to the cleanup list
+///   - Compressibility estimate lifecycle
+///   - File size histogram for the scan or a folder
+///   - Usage per file owner
+///   - Stale files by access time after an extended scan
 ///
 /// The real `parallel::scan_parallel` scanner is used so no mocking is needed.
-use disksleuth_gui::state::{AppPhase, AppState};
+use disksleuth_core::analysis::FileCategory;
+use disksleuth_core::model::SortColumn;
+use disksleuth_gui::panels::cache_panel::cache_window;
+use disksleuth_gui::panels::chart_panel::chart_panel;
+use disksleuth_gui::panels::compression_panel::compression_window;
+use disksleuth_gui::panels::empty_panel::empty_window;
+use disksleuth_gui::panels::hotspot_panel::hotspot_window;
+use disksleuth_gui::panels::largest_dirs_panel::largest_dirs_window;
+use disksleuth_gui::panels::owners_panel::owners_window;
+use disksleuth_gui::panels::size_panel::size_panel;
+use disksleuth_gui::panels::slack_panel::slack_window;
+use disksleuth_gui::state::{
+    AppPhase, AppState, ExtensionColumn, MapView, TreemapColorMode, TreemapWeight,
+};
+use disksleuth_gui::widgets::sunburst::{sunburst, SunburstCache};
+use disksleuth_gui::widgets::treemap::{treemap, TreemapAction, TreemapCache};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -50,6 +76,24 @@ fn pump_until_done(state: &mut AppState) {
     }
 }
 
+/// Drain a background worker's messages until `is_running` turns false.
+fn pump(
+    state: &mut AppState,
+    is_running: impl Fn(&AppState) -> bool,
+    process: impl Fn(&mut AppState) -> bool,
+    what: &str,
+) {
+    let deadline = std::time::Instant::now() + Duration::from_secs(30);
+    while is_running(state) {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "{what} did not complete within 30 seconds"
+        );
+        process(state);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
 // ── Scan lifecycle ─────────────────────────────────────────────────────────────
 
 /// After `start_scan`, the phase must be `Scanning`.
@@ -284,6 +328,1206 @@ fn toggle_expand_collapse_removes_descendants() {
     }
 }
 
+// ── Tree-view sorting ──────────────────────────────────────────────────────────
+
+/// Names of the depth-1 rows, in display order.
+fn top_level_names(state: &AppState) -> Vec<String> {
+    let tree = state.current_tree().expect("tree");
+    state
+        .visible_rows
+        .iter()
+        .filter(|r| r.depth == 1)
+        .map(|r| tree.node(r.node_index).name.to_string())
+        .collect()
+}
+
+/// Sorting by name orders siblings A→Z; clicking again reverses; mixing
+/// folders with files drops the folders-first grouping.
+#[test]
+fn sort_by_name_toggles_direction_and_mixes_folders() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.set_tree_sort_column(SortColumn::Name);
+    assert!(
+        !state.tree_sort.descending,
+        "name sort must start ascending"
+    );
+    assert_eq!(top_level_names(&state), ["sub", "a.txt", "b.bin"]);
+
+    state.set_tree_folders_first(false);
+    assert_eq!(top_level_names(&state), ["a.txt", "b.bin", "sub"]);
+
+    state.set_tree_sort_column(SortColumn::Name);
+    assert!(state.tree_sort.descending);
+    assert_eq!(top_level_names(&state), ["sub", "b.bin", "a.txt"]);
+}
+
+/// Re-sorting rebuilds `visible_rows` without collapsing expanded folders.
+#[test]
+fn resort_keeps_expanded_directories() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let sub_row = state
+        .visible_rows
+        .iter()
+        .position(|r| {
+            state
+                .current_tree()
+                .is_some_and(|t| t.node(r.node_index).name == "sub")
+        })
+        .expect("sub row");
+    state.toggle_expand(sub_row);
+    let rows_before = state.visible_rows.len();
+
+    state.set_tree_sort_column(SortColumn::Modified);
+    assert_eq!(state.visible_rows.len(), rows_before);
+    let tree = state.current_tree().expect("tree");
+    assert!(state
+        .visible_rows
+        .iter()
+        .any(|r| r.is_expanded && tree.node(r.node_index).name == "sub"));
+}
+
+/// Filtering to a file category lists only its files and the folders
+/// holding them; clearing the filter restores every row.
+#[test]
+fn category_filter_lists_only_matching_files() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let rows_before = state.visible_rows.len();
+
+    state.set_category_filter(Some(FileCategory::Code));
+    assert_eq!(top_level_names(&state), ["sub"]);
+    let sub_row = state
+        .visible_rows
+        .iter()
+        .position(|r| r.depth == 1)
+        .expect("sub row");
+    state.toggle_expand(sub_row);
+    let tree = state.current_tree().expect("tree");
+    let names: Vec<&str> = state
+        .visible_rows
+        .iter()
+        .map(|r| tree.node(r.node_index).name.as_str())
+        .collect();
+    assert_eq!(names[1..], ["sub", "c.rs"]);
+
+    // The chart panel renders with the filter active, in both metrics.
+    let ctx = egui::Context::default();
+    for by_count in [false, true] {
+        state.chart_by_count = by_count;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| chart_panel(ui, &mut state));
+        });
+    }
+
+    state.set_category_filter(None);
+    assert_eq!(state.visible_rows.len(), rows_before + 1);
+}
+
+/// Per-extension stats are ready after a scan; picking an extension lists
+/// its largest files, and picking it again clears the list.
+#[test]
+fn extension_pick_lists_largest_files() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let stats = state.extension_stats.as_deref().expect("extension stats");
+    let rs = stats.iter().find(|e| e.extension == "rs").expect(".rs row");
+    assert_eq!((rs.file_count, rs.total_size), (1, 300));
+    assert_eq!(rs.category, FileCategory::Code);
+
+    state.select_extension(Some("rs"));
+    assert_eq!(state.extension_files.len(), 1);
+    assert!(state.extension_files[0].path.ends_with("c.rs"));
+
+    state.set_extension_sort(ExtensionColumn::Extension);
+    assert!(!state.extension_sort_descending);
+    state.chart_expanded = Some(FileCategory::Code);
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| chart_panel(ui, &mut state));
+    });
+
+    state.select_extension(Some("rs"));
+    assert!(state.selected_extension.is_none());
+    assert!(state.extension_files.is_empty());
+}
+
+/// The signature pass categorises a misnamed archive by its content, in
+/// the type stats and the category filter alike.
+#[test]
+fn signature_pass_overrides_extension_category() {
+    let tmp = make_temp_tree();
+    let mut blob = b"PK\x03\x04".to_vec();
+    blob.resize(4096, 0);
+    fs::write(tmp.path().join("backup.dat"), &blob).unwrap();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let archives = |state: &AppState| {
+        state
+            .file_type_stats
+            .as_deref()
+            .expect("type stats")
+            .iter()
+            .any(|s| s.category == Some(FileCategory::Archives))
+    };
+    assert!(!archives(&state));
+
+    state.signature_min_size = 1024;
+    state.start_signature_scan();
+    let deadline = std::time::Instant::now() + Duration::from_secs(30);
+    while state.signature_handle.is_some() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "signature pass timed out"
+        );
+        state.process_signature_messages();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let report = state.signature_report.as_ref().expect("report");
+    assert_eq!((report.detected.len(), report.mismatched), (1, 1));
+    assert!(archives(&state));
+
+    state.set_category_filter(Some(FileCategory::Archives));
+    assert_eq!(top_level_names(&state), ["backup.dat"]);
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| chart_panel(ui, &mut state));
+    });
+}
+
+// ── Keyboard navigation ────────────────────────────────────────────────────────
+
+/// Name of the selected node.
+fn selected_name(state: &AppState) -> String {
+    let tree = state.current_tree().expect("tree");
+    tree.node(state.selected_node.expect("selection"))
+        .name
+        .to_string()
+}
+
+/// Up/Down move through `visible_rows`, clamping at both ends.
+#[test]
+fn arrow_keys_move_selection_and_clamp() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.move_selection(1);
+    assert_eq!(state.selected_row(), Some(0), "first move selects row 0");
+    state.move_selection(1);
+    assert_eq!(state.selected_row(), Some(1));
+    assert!(state.scroll_tree_to_selection);
+
+    state.move_selection(-10);
+    assert_eq!(state.selected_row(), Some(0));
+    state.select_edge_row(true);
+    assert_eq!(state.selected_row(), Some(state.visible_rows.len() - 1));
+    state.move_selection(5);
+    assert_eq!(state.selected_row(), Some(state.visible_rows.len() - 1));
+}
+
+/// Right expands, Right again steps into the child; Left goes back to the
+/// parent row and then collapses it.
+#[test]
+fn left_right_expand_collapse_and_step() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    // Default order puts the "sub" folder first under the root.
+    state.select_edge_row(false);
+    state.move_selection(1);
+    assert_eq!(selected_name(&state), "sub");
+
+    state.expand_selection();
+    let sub_row = state.selected_row().unwrap();
+    assert!(state.visible_rows[sub_row].is_expanded);
+
+    state.expand_selection();
+    assert_eq!(selected_name(&state), "c.rs");
+
+    state.collapse_selection();
+    assert_eq!(selected_name(&state), "sub");
+    state.collapse_selection();
+    assert!(!state.visible_rows[sub_row].is_expanded);
+}
+
+/// Enter drills the treemap into a folder; for a file, into its parent.
+#[test]
+fn enter_drills_treemap_into_selection() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.select_edge_row(false);
+    state.move_selection(1); // "sub"
+    let sub = state.selected_node;
+    state.drill_into_selection();
+    assert_eq!(state.treemap_root, sub);
+
+    state.treemap_go_up();
+    state.select_edge_row(true); // a file directly under the root
+    let root = state.current_tree().unwrap().roots[0];
+    state.drill_into_selection();
+    assert_eq!(state.treemap_root, Some(root));
+}
+
+/// Typing a prefix jumps to the matching sibling.
+#[test]
+fn type_ahead_selects_matching_sibling() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.select_edge_row(false);
+    state.move_selection(1); // "sub"
+    state.type_ahead("B");
+    assert_eq!(selected_name(&state), "b.bin");
+    state.type_ahead(".");
+    assert_eq!(selected_name(&state), "b.bin", "refining keeps the match");
+}
+
+// ── Search ─────────────────────────────────────────────────────────────────────
+
+/// A search finds the file, and revealing it selects the node, shows its
+/// parent in the treemap, and makes it visible in the tree view.
+#[test]
+fn search_hit_reveals_node() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.search_query = "C.RS".into();
+    state.start_search();
+    pump(
+        &mut state,
+        |s| s.search_handle.is_some(),
+        AppState::process_search_messages,
+        "search",
+    );
+
+    assert_eq!(state.search_total_matches, 1);
+    let hit = state.search_results[0].index;
+    let parent = state.tree.as_ref().unwrap().node(hit).parent;
+
+    state.reveal_node(hit);
+    assert_eq!(state.selected_node, Some(hit));
+    assert_eq!(state.treemap_root, parent);
+    assert!(
+        state.visible_rows.iter().any(|r| r.node_index == hit),
+        "revealed hit must be in the visible rows"
+    );
+}
+
+/// An invalid regex surfaces an error instead of starting a search.
+#[test]
+fn invalid_regex_sets_search_error() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.search_mode = disksleuth_core::search::SearchMode::Regex;
+    state.search_query = "(unclosed".into();
+    state.start_search();
+    assert!(state.search_error.is_some());
+    assert!(state.search_handle.is_none());
+}
+
+// ── Cleanup ────────────────────────────────────────────────────────────────────
+
+/// Find a node by name in the final tree.
+fn node_named(state: &AppState, name: &str) -> disksleuth_core::model::NodeIndex {
+    let tree = state.current_tree().unwrap();
+    let i = tree.nodes.iter().position(|n| n.name == name).unwrap();
+    disksleuth_core::model::NodeIndex::new(i)
+}
+
+/// Deleting a folder asks for confirmation, removes it from disk, and drops
+/// it from the tree with sizes re-aggregated.
+#[test]
+fn delete_folder_removes_from_disk_and_tree() {
+    use disksleuth_core::cleanup::CleanupAction;
+
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let before = state.current_tree().unwrap().total_size;
+
+    let sub = node_named(&state, "sub");
+    state.request_cleanup(&[sub], CleanupAction::Delete);
+    let plan = state
+        .cleanup_confirm
+        .as_ref()
+        .expect("confirmation pending");
+    assert_eq!(plan.total_size, 300);
+    assert_eq!(plan.total_files, 1);
+    assert!(
+        tmp.path().join("sub").exists(),
+        "nothing happens before confirm"
+    );
+
+    state.confirm_cleanup();
+    pump(
+        &mut state,
+        |s| s.cleanup_handle.is_some(),
+        AppState::process_cleanup_messages,
+        "cleanup",
+    );
+
+    assert!(!tmp.path().join("sub").exists());
+    let report = state.cleanup_report.as_ref().unwrap();
+    assert!(report.finished);
+    assert_eq!((report.removed, report.failure_count), (1, 0));
+    assert_eq!(report.freed, 300);
+
+    let tree = state.current_tree().unwrap();
+    assert_eq!(tree.total_size, before - 300);
+    assert!(tree
+        .nodes
+        .iter()
+        .all(|n| n.name != "sub" && n.name != "c.rs"));
+    assert!(state
+        .visible_rows
+        .iter()
+        .all(|r| r.node_index.idx() < tree.len()));
+}
+
+/// Listing a folder and a file inside it counts the file's bytes once.
+#[test]
+fn cart_total_counts_nested_items_once() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let (sub, c, a) = (
+        node_named(&state, "sub"),
+        node_named(&state, "c.rs"),
+        node_named(&state, "a.txt"),
+    );
+    state.cart_add(c);
+    state.cart_add(sub);
+    assert_eq!((state.cart_reclaimable, state.cart_files), (300, 1));
+    assert!(state.cart_is_nested(c));
+    assert!(!state.cart_is_nested(sub));
+
+    state.cart_toggle(a);
+    assert_eq!(state.cart_reclaimable, 400);
+    state.cart_toggle(a);
+    assert_eq!(state.cart_reclaimable, 300);
+
+    // The scan root can never be listed.
+    let root = state.current_tree().unwrap().roots[0];
+    state.cart_add(root);
+    assert!(!state.is_in_cart(root));
+}
+
+/// Running the list as an archive move relocates every item, empties the
+/// list, and shrinks the tree.
+#[test]
+fn cart_archive_moves_items_and_prunes_tree() {
+    use disksleuth_core::cleanup::CleanupAction;
+
+    let tmp = make_temp_tree();
+    let archive = TempDir::new().unwrap();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    state.cart_add(node_named(&state, "a.txt"));
+    state.cart_add(node_named(&state, "sub"));
+    state.cart_action = CleanupAction::Archive;
+    assert!(state.cart_plan().is_none(), "archive needs a folder");
+    state.cart_archive_dir = archive.path().to_string_lossy().into_owned();
+
+    let script = state.cart_script().unwrap();
+    assert!(
+        script.contains("a.txt") && script.contains("sub"),
+        "{script}"
+    );
+
+    state.request_cart_cleanup();
+    assert_eq!(state.cleanup_confirm.as_ref().unwrap().total_size, 400);
+    state.confirm_cleanup();
+    pump(
+        &mut state,
+        |s| s.cleanup_handle.is_some(),
+        AppState::process_cleanup_messages,
+        "cleanup",
+    );
+
+    assert!(archive.path().join("a.txt").is_file());
+    assert!(archive.path().join("sub").join("c.rs").is_file());
+    assert!(!tmp.path().join("sub").exists());
+    assert!(state.cleanup_cart.is_empty());
+    assert_eq!(state.cart_reclaimable, 0);
+    assert_eq!(state.current_tree().unwrap().total_size, 200);
+}
+
+// ── Stale files ────────────────────────────────────────────────────────────────
+
+/// Age analysis runs after the scan; old files are listed, bucketed, and
+/// a Shift+click selection can be sent to the cleanup list.
+#[test]
+fn stale_files_listed_after_scan_and_added_to_cart() {
+    let tmp = make_temp_tree();
+    let two_years = std::time::SystemTime::now() - Duration::from_secs(2 * 365 * 86_400);
+    for name in ["a.txt", "b.bin"] {
+        fs::File::options()
+            .write(true)
+            .open(tmp.path().join(name))
+            .unwrap()
+            .set_modified(two_years)
+            .unwrap();
+    }
+
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    assert!(state.stale_handle.is_some(), "analysis starts with results");
+    pump(
+        &mut state,
+        |s| s.stale_handle.is_some(),
+        AppState::process_stale_messages,
+        "age analysis",
+    );
+
+    let report = state.stale_report.as_ref().unwrap();
+    assert_eq!((report.stale_count, report.stale_bytes), (2, 300));
+    let sizes: Vec<u64> = report.stale.iter().map(|f| f.size).collect();
+    assert_eq!(sizes, [200, 100], "largest first");
+    assert_eq!(report.histogram.bytes, [300, 0, 0, 300, 0]);
+
+    state.stale_click(0, false, false);
+    state.stale_click(1, false, true);
+    assert_eq!(state.stale_selection_totals(), (2, 300));
+    state.stale_add_selected_to_cart();
+    assert_eq!(state.cart_reclaimable, 300);
+
+    // Tightening the threshold past both files empties the list.
+    state.stale_min_age_days = 3 * 365;
+    state.start_stale_analysis();
+    pump(
+        &mut state,
+        |s| s.stale_handle.is_some(),
+        AppState::process_stale_messages,
+        "age analysis",
+    );
+    let report = state.stale_report.as_ref().unwrap();
+    assert!(report.stale.is_empty());
+    assert!(state.stale_selected.is_empty());
+}
+
+/// Measuring age by access needs an extended scan: a file modified long
+/// ago but read recently is stale by modification only, and a plain scan
+/// switches the basis back to modification.
+#[test]
+fn stale_by_access_needs_extended_scan() {
+    use disksleuth_core::analysis::AgeBasis;
+
+    let tmp = make_temp_tree();
+    let now = std::time::SystemTime::now();
+    let two_years = now - Duration::from_secs(2 * 365 * 86_400);
+    fs::File::options()
+        .write(true)
+        .open(tmp.path().join("a.txt"))
+        .unwrap()
+        .set_times(
+            fs::FileTimes::new()
+                .set_modified(two_years)
+                .set_accessed(now),
+        )
+        .unwrap();
+
+    let mut state = AppState::new();
+    state.scan_options.extended_metadata = true;
+    state.stale_basis = AgeBasis::Accessed;
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    pump(
+        &mut state,
+        |s| s.stale_handle.is_some(),
+        AppState::process_stale_messages,
+        "age analysis",
+    );
+    let report = state.stale_report.as_ref().unwrap();
+    assert_eq!(report.basis, AgeBasis::Accessed);
+    assert_eq!(report.stale_count, 0, "a.txt was read just now");
+
+    state.stale_basis = AgeBasis::Modified;
+    state.start_stale_analysis();
+    pump(
+        &mut state,
+        |s| s.stale_handle.is_some(),
+        AppState::process_stale_messages,
+        "age analysis",
+    );
+    assert_eq!(state.stale_report.as_ref().unwrap().stale_count, 1);
+
+    state.scan_options.extended_metadata = false;
+    state.stale_basis = AgeBasis::Accessed;
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    pump(
+        &mut state,
+        |s| s.stale_handle.is_some(),
+        AppState::process_stale_messages,
+        "age analysis",
+    );
+    assert_eq!(state.stale_basis, AgeBasis::Modified);
+    assert_eq!(state.stale_report.as_ref().unwrap().stale_count, 1);
+}
+
+// ── Reclaimable ────────────────────────────────────────────────────────────────
+
+/// A `target/` beside `Cargo.toml` is listed after the scan and can be
+/// cleaned up in bulk; the list follows the pruned tree.
+#[test]
+fn reclaimable_artefacts_listed_and_cleaned_up() {
+    use disksleuth_core::cleanup::CleanupAction;
+
+    let tmp = make_temp_tree();
+    let project = tmp.path().join("proj");
+    fs::create_dir_all(project.join("target").join("debug")).unwrap();
+    write_bytes(&project.join("Cargo.toml"), 10);
+    write_bytes(&project.join("target").join("debug").join("app.o"), 1_000);
+
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    assert!(
+        state.reclaimable_handle.is_some(),
+        "analysis starts with results"
+    );
+    pump(
+        &mut state,
+        |s| s.reclaimable_handle.is_some(),
+        AppState::process_reclaimable_messages,
+        "artefact analysis",
+    );
+
+    let report = state.reclaimable_report.as_ref().unwrap();
+    assert_eq!((report.total_count, report.total_bytes), (1, 1_000));
+    assert_eq!(report.artefacts[0].kind, "Cargo build output");
+
+    state.reclaimable_select_all(Some("npm packages"));
+    assert_eq!(state.reclaimable_selection_totals(), (0, 0));
+    state.reclaimable_select_all(None);
+    assert_eq!(state.reclaimable_selection_totals(), (1, 1_000));
+    state.reclaimable_cleanup(CleanupAction::Delete);
+    assert_eq!(state.cleanup_confirm.as_ref().unwrap().total_size, 1_000);
+    state.confirm_cleanup();
+    pump(
+        &mut state,
+        |s| s.cleanup_handle.is_some(),
+        AppState::process_cleanup_messages,
+        "cleanup",
+    );
+
+    assert!(!project.join("target").exists());
+    assert!(project.join("Cargo.toml").is_file());
+    let report = state.reclaimable_report.as_ref().unwrap();
+    assert!(report.artefacts.is_empty());
+    assert!(state.reclaimable_selected.is_empty());
+}
+
+/// The cache report is ready with the completed tree and dropped by a new
+/// scan; the caches window renders either way.
+#[test]
+fn cache_report_follows_scan() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let report = state.cache_report.as_ref().expect("cache report");
+    // A fresh temp dir holds no well-known location.
+    assert!(report.locations.is_empty());
+
+    state.show_caches = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        cache_window(ctx, &mut state);
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.cache_report.is_none());
+    pump_until_done(&mut state);
+}
+
+/// Empty skeletons and zero-byte files are reported after a scan; removing
+/// empty folders skips one that gained a file since the scan.
+#[test]
+fn empty_folders_reported_and_removed() {
+    let tmp = make_temp_tree();
+    let skeleton = tmp.path().join("old");
+    fs::create_dir_all(skeleton.join("a").join("b")).unwrap();
+    let busy = tmp.path().join("sub").join("cache");
+    fs::create_dir_all(&busy).unwrap();
+    write_bytes(&tmp.path().join("sub").join("empty.log"), 0);
+
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let report = state.empty_report.as_ref().expect("empty report");
+    assert_eq!(
+        (report.empty_dir_count, report.empty_dir_total),
+        (2, 4),
+        "old, old/a, old/a/b and sub/cache"
+    );
+    assert_eq!(report.zero_byte_count, 1);
+
+    state.show_empty = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        empty_window(ctx, &mut state);
+    });
+
+    write_bytes(&busy.join("new.tmp"), 5);
+    state.remove_empty_folders();
+    assert_eq!(state.cleanup_confirm.as_ref().unwrap().items.len(), 2);
+    state.confirm_cleanup();
+    pump(
+        &mut state,
+        |s| s.cleanup_handle.is_some(),
+        AppState::process_cleanup_messages,
+        "cleanup",
+    );
+
+    assert!(!skeleton.exists());
+    assert!(busy.join("new.tmp").is_file(), "non-empty folder kept");
+    let report = state.empty_report.as_ref().unwrap();
+    assert_eq!(report.empty_dir_total, 1, "only sub/cache is still listed");
+}
+
+/// Hotspots are ranked with the completed tree and re-ranked when another
+/// column is picked; the window renders and can switch the treemap weight.
+#[test]
+fn hotspots_ranked_by_file_count() {
+    use disksleuth_core::analysis::HotspotSort;
+
+    let tmp = make_temp_tree();
+    let thumbs = tmp.path().join("thumbs");
+    fs::create_dir_all(&thumbs).unwrap();
+    for i in 0..20 {
+        write_bytes(&thumbs.join(format!("t{i}.jpg")), 10);
+    }
+
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let name = |s: &AppState, i: usize| {
+        let tree = s.current_tree().unwrap();
+        tree.node(s.hotspots[i].index).name.to_string()
+    };
+    // The scan root holds every file; `thumbs` holds most of them.
+    assert_eq!(state.hotspots[0].files, 23);
+    assert_eq!(name(&state, 1), "thumbs");
+
+    state.set_hotspot_sort(HotspotSort::DirectFiles);
+    assert_eq!(name(&state, 0), "thumbs");
+    assert_eq!(state.hotspots[0].slack, 20 * 4086);
+
+    state.show_hotspots = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        hotspot_window(ctx, &mut state);
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.hotspots.is_empty());
+    pump_until_done(&mut state);
+    assert_eq!(state.hotspot_sort, HotspotSort::DirectFiles);
+    assert_eq!(name(&state, 0), "thumbs");
+}
+
+/// The slack report uses the volume's cluster size (the default where it
+/// cannot be queried) and projects the other candidates.
+#[test]
+fn slack_report_follows_scan() {
+    use disksleuth_core::analysis::DEFAULT_CLUSTER_SIZE;
+
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let cluster = state.cluster_size;
+    assert!(cluster.is_power_of_two());
+    let report = state.slack_report.as_ref().expect("slack report");
+    assert_eq!(report.cluster_size, cluster);
+    assert_eq!((report.files, report.logical), (3, 600));
+    assert_eq!(report.actual.allocated, 3 * cluster);
+    let at_64k = report.at(64 * 1024).unwrap();
+    assert_eq!(at_64k.allocated, 3 * 64 * 1024);
+    if cluster == DEFAULT_CLUSTER_SIZE {
+        assert_eq!(report.actual.slack, 3 * 4096 - 600);
+    }
+
+    state.show_slack = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        slack_window(ctx, &mut state);
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.slack_report.is_none());
+    pump_until_done(&mut state);
+}
+
+/// The compressibility estimate samples the chosen folder on a background
+/// thread and is dropped by a new scan.
+#[test]
+fn compression_estimate_samples_folder() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let root = state.tree.as_ref().unwrap().roots[0];
+    state.start_compression_estimate(root);
+    assert_eq!(state.compression_root, Some(root));
+    let deadline = std::time::Instant::now() + Duration::from_secs(30);
+    while state.compression_handle.is_some() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "compression estimate timed out"
+        );
+        state.process_compression_messages();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(state.compression_root, None);
+    let report = state.compression_report.as_ref().expect("report");
+    assert_eq!((report.root, report.total.files), (root, 3));
+    assert_eq!((report.files_sampled, report.bytes_read), (3, 600));
+    // The files are all zeros.
+    assert!(report.total.ratio() < 0.5, "{}", report.total.ratio());
+    assert_eq!(report.dirs.len(), 1);
+    assert_eq!(report.dirs[0].estimate.files, 1);
+    assert_eq!(report.extensions.len(), 3);
+
+    state.show_compression = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        compression_window(ctx, &mut state);
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.compression_report.is_none());
+    pump_until_done(&mut state);
+}
+
+/// Largest folders follow the chosen ranking and depth.
+#[test]
+fn largest_dirs_ranked_by_own_and_total() {
+    use disksleuth_core::analysis::DirRank;
+
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let root = state.tree.as_ref().unwrap().roots[0];
+    let sub = node_named(&state, "sub");
+    let ranked = |state: &AppState| {
+        state
+            .largest_dirs
+            .iter()
+            .map(|d| (d.index, d.own_bytes, d.total_bytes))
+            .collect::<Vec<_>>()
+    };
+    // Own files: the root holds 300 bytes directly, `sub` 300 too; ties go
+    // to the earlier node.
+    assert_eq!(ranked(&state), [(root, 300, 600), (sub, 300, 300)]);
+
+    state.set_largest_dirs_view(DirRank::Total, Some(1));
+    assert_eq!(ranked(&state), [(sub, 300, 300)]);
+
+    state.show_largest_dirs = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        largest_dirs_window(ctx, &mut state);
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.largest_dirs.is_empty());
+    pump_until_done(&mut state);
+    assert_eq!(state.largest_dirs_depth, Some(1));
+    assert_eq!(state.largest_dirs.len(), 1);
+}
+
+/// Owners are only totalled for a scan that recorded them.  Every file of
+/// the temp tree belongs to the test user, whose folders are listed by their
+/// own bytes; a new scan drops the report.
+#[test]
+fn owner_usage_totals_files_per_owner() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.show_owners = true;
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    assert!(!state.tree.as_ref().unwrap().owners_recorded);
+    assert!(state.owner_handle.is_none() && state.owner_report.is_none());
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        owners_window(ctx, &mut state);
+    });
+
+    state.scan_options.owners = true;
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    pump(
+        &mut state,
+        |s| s.owner_handle.is_some(),
+        AppState::process_owner_messages,
+        "owner analysis",
+    );
+
+    let root = state.tree.as_ref().unwrap().roots[0];
+    let sub = node_named(&state, "sub");
+    let report = state.owner_report.as_ref().expect("owner report");
+    assert_eq!((report.files, report.bytes), (3, 600));
+    assert_eq!(report.owners.len(), 1);
+    let usage = &report.owners[0];
+    assert_eq!((usage.files, usage.bytes), (3, 600));
+    #[cfg(unix)]
+    assert!(usage.owner.is_some(), "uid recorded by the scanner");
+    let dirs: Vec<_> = usage.dirs.iter().map(|d| (d.index, d.bytes)).collect();
+    assert_eq!(dirs, [(root, 300), (sub, 300)]);
+    #[cfg(unix)]
+    let label = usage.label();
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        owners_window(ctx, &mut state);
+    });
+
+    // Folders have owners too; the details panel names them.
+    #[cfg(unix)]
+    {
+        state.selected_node = Some(sub);
+        let texts = painted_texts(&ctx, |ui| {
+            disksleuth_gui::panels::details_panel::details_panel(ui, &state)
+        });
+        let at = texts.iter().position(|t| t == "Owner:").expect("owner row");
+        assert_eq!(texts[at + 1], label);
+    }
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.owner_report.is_none());
+    pump_until_done(&mut state);
+}
+
+/// The size histogram covers the whole scan after a scan, and one folder
+/// once it is picked.
+#[test]
+fn size_histogram_follows_scope() {
+    use disksleuth_core::analysis::size_bucket;
+
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let histogram = state.size_histogram.as_ref().expect("size histogram");
+    assert_eq!(
+        (histogram.root, histogram.files, histogram.bytes),
+        (None, 3, 600)
+    );
+    assert_eq!((histogram.p50, histogram.p99), (200, 300));
+    assert_eq!(histogram.top_percent_bytes, 300);
+    assert_eq!(histogram.buckets[size_bucket(100)].files, 1);
+
+    let sub = node_named(&state, "sub");
+    state.set_size_scope(Some(sub));
+    let histogram = state.size_histogram.as_ref().unwrap();
+    assert_eq!((histogram.root, histogram.files), (Some(sub), 1));
+
+    state.selected_node = Some(sub);
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| size_panel(ui, &mut state));
+    });
+
+    state.start_scan(tmp.path().to_path_buf());
+    assert!(state.size_histogram.is_none());
+    assert_eq!(state.size_scope, None);
+    pump_until_done(&mut state);
+}
+
+/// Folder age columns follow the chosen cutoff, and a freshly scanned tree
+/// picks up a cutoff chosen before the scan.
+#[test]
+fn old_cutoff_recomputes_old_bytes() {
+    use disksleuth_core::model::DayStamp;
+
+    let tmp = make_temp_tree();
+    let two_years = std::time::SystemTime::now() - Duration::from_secs(2 * 365 * 86_400);
+    fs::File::options()
+        .write(true)
+        .open(tmp.path().join("b.bin"))
+        .unwrap()
+        .set_modified(two_years)
+        .unwrap();
+
+    let mut state = AppState::new();
+    state.tree_age_columns = true;
+    state.set_old_cutoff_days(3 * 365);
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let root = state.current_tree().unwrap().roots[0];
+    assert_eq!(state.old_bytes.get(root), 0);
+
+    // The tree is still shared with the stale-files worker here; the
+    // cutoff change must not copy it.
+    let before = state.tree.clone().unwrap();
+    state.set_old_cutoff_days(365);
+    assert!(std::sync::Arc::ptr_eq(
+        &before,
+        state.tree.as_ref().unwrap()
+    ));
+    assert_eq!(state.old_bytes.get(root), 200);
+    let r = state.current_tree().unwrap().node(root);
+    assert_eq!(r.oldest_day, DayStamp::from_time(Some(two_years)));
+    assert!(r.newest_day > r.oldest_day);
+
+    state.set_tree_sort_column(SortColumn::OldBytes);
+    assert_eq!(top_level_names(&state)[..2], ["sub", "b.bin"]);
+}
+
+/// Old bytes are only computed while something shows or sorts by them.
+#[test]
+fn old_bytes_computed_only_when_needed() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    let tree = state.tree.clone().unwrap();
+    assert!(!state.old_bytes.is_current(&tree, state.old_cutoff_days));
+
+    state.set_tree_sort_column(SortColumn::OldBytes);
+    assert!(state.old_bytes.is_current(&tree, state.old_cutoff_days));
+}
+
+// ── Treemap rendering ──────────────────────────────────────────────────────────
+
+/// Layout caches for the headless map frames.
+#[derive(Default)]
+struct MapCaches {
+    treemap: TreemapCache,
+    sunburst: SunburstCache,
+}
+
+/// Run one headless frame of the current map view with the pointer at
+/// `pointer`, optionally clicking there.  Returns the view's action.
+fn map_frame(
+    ctx: &egui::Context,
+    state: &AppState,
+    caches: &mut MapCaches,
+    pointer: egui::Pos2,
+    click: bool,
+) -> Option<TreemapAction> {
+    let mut events = vec![egui::Event::PointerMoved(pointer)];
+    if click {
+        for pressed in [true, false] {
+            events.push(egui::Event::PointerButton {
+                pos: pointer,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            });
+        }
+    }
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(800.0, 600.0),
+        )),
+        events,
+        ..Default::default()
+    };
+    let mut action = None;
+    let _ = ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            action = match state.map_view {
+                MapView::Treemap => treemap(ui, state, &mut caches.treemap),
+                MapView::Sunburst => sunburst(ui, state, &mut caches.sunburst),
+            };
+        });
+    });
+    action
+}
+
+/// Both map views render from their cached layouts in every colour mode and
+/// weight, with cushions and the legend, and hovering over them does not
+/// panic.
+#[test]
+fn map_views_render_from_cache_in_every_mode() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let ctx = egui::Context::default();
+    let mut caches = MapCaches::default();
+    for view in [MapView::Treemap, MapView::Sunburst] {
+        state.map_view = view;
+        for cushions in [false, true] {
+            state.treemap_cushions = cushions;
+            for mode in TreemapColorMode::ALL {
+                state.treemap_color = mode;
+                for weight in TreemapWeight::ALL {
+                    state.treemap_weight = weight;
+                    for pointer in [egui::pos2(200.0, 200.0), egui::pos2(600.0, 450.0)] {
+                        map_frame(&ctx, &state, &mut caches, pointer, false);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Clicking the sunburst's centre goes up to the parent directory.
+#[test]
+fn sunburst_centre_click_goes_up() {
+    let tmp = make_temp_tree();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+
+    let sub = {
+        let tree = state.current_tree().expect("tree must exist");
+        let root = tree.roots[0];
+        tree.children(root)
+            .into_iter()
+            .find(|&c| tree.node(c).name == "sub")
+            .expect("sub directory")
+    };
+    state.treemap_navigate_to(sub);
+    state.map_view = MapView::Sunburst;
+
+    // The centre disc sits in the middle of the area below the toolbar.
+    let ctx = egui::Context::default();
+    let mut caches = MapCaches::default();
+    let centre = egui::pos2(400.0, 315.0);
+    map_frame(&ctx, &state, &mut caches, centre, false);
+    let action = map_frame(&ctx, &state, &mut caches, centre, true);
+    assert!(matches!(action, Some(TreemapAction::Up)));
+}
+
+/// A folder too thin to draw does not hide the files listed after it.
+#[test]
+fn sunburst_skips_thin_folder_but_keeps_later_files() {
+    let tmp = TempDir::new().unwrap();
+    let tiny = tmp.path().join("tiny");
+    fs::create_dir_all(&tiny).unwrap();
+    write_bytes(&tiny.join("x"), 1);
+    write_bytes(&tmp.path().join("big.iso"), 10_000);
+
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    state.map_view = MapView::Sunburst;
+    let big = node_named(&state, "big.iso");
+
+    // The file fills the innermost ring, left of the centre disc.
+    let ctx = egui::Context::default();
+    let mut caches = MapCaches::default();
+    let on_file = egui::pos2(400.0 - 65.0, 315.0);
+    map_frame(&ctx, &state, &mut caches, on_file, false);
+    let action = map_frame(&ctx, &state, &mut caches, on_file, true);
+    assert!(matches!(action, Some(TreemapAction::SelectNode(n)) if n == big));
+}
+
+/// Text painted by one headless 800×600 frame of `add_contents`.
+fn painted_texts(ctx: &egui::Context, mut add_contents: impl FnMut(&mut egui::Ui)) -> Vec<String> {
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(800.0, 600.0),
+        )),
+        ..Default::default()
+    };
+    let output = ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+    });
+    output
+        .shapes
+        .iter()
+        .filter_map(|clipped| match &clipped.shape {
+            egui::Shape::Text(text) => Some(text.galley.text().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Text painted by one headless frame of the current map view.
+fn map_texts(ctx: &egui::Context, state: &AppState, caches: &mut MapCaches) -> Vec<String> {
+    painted_texts(ctx, |ui| {
+        match state.map_view {
+            MapView::Treemap => treemap(ui, state, &mut caches.treemap),
+            MapView::Sunburst => sunburst(ui, state, &mut caches.sunburst),
+        };
+    })
+}
+
+/// Both maps colour a misnamed archive by its detected content once the
+/// signature pass completes, without waiting for the tree to change.
+#[test]
+fn map_legend_follows_detected_types() {
+    let tmp = make_temp_tree();
+    let mut blob = b"PK\x03\x04".to_vec();
+    blob.resize(4096, 0);
+    fs::write(tmp.path().join("backup.dat"), &blob).unwrap();
+    let mut state = AppState::new();
+    state.start_scan(tmp.path().to_path_buf());
+    pump_until_done(&mut state);
+    state.treemap_color = TreemapColorMode::FileType;
+
+    let ctx = egui::Context::default();
+    let mut caches = MapCaches::default();
+    let has_archives = |state: &AppState, caches: &mut MapCaches| {
+        map_texts(&ctx, state, caches)
+            .iter()
+            .any(|t| t == FileCategory::Archives.label())
+    };
+    for view in [MapView::Treemap, MapView::Sunburst] {
+        state.map_view = view;
+        assert!(!has_archives(&state, &mut caches));
+    }
+
+    state.signature_min_size = 1024;
+    state.start_signature_scan();
+    pump(
+        &mut state,
+        |s| s.signature_handle.is_some(),
+        AppState::process_signature_messages,
+        "signature pass",
+    );
+    for view in [MapView::Treemap, MapView::Sunburst] {
+        state.map_view = view;
+        assert!(has_archives(&state, &mut caches));
+    }
+}
+
 // ── Monitor ────────────────────────────────────────────────────────────────────
 
 /// Starting the monitor sets `monitor_active = true`.